@@ -358,11 +358,13 @@ impl EditorApp {
                     self.counts.sections += 1;
                     self.section_edges.push(path);
                 }
-                VariableStrokeDebugEdgeKind::JoinArc => {
+                VariableStrokeDebugEdgeKind::JoinArc | VariableStrokeDebugEdgeKind::JoinCorner => {
                     self.counts.joins += 1;
                     self.join_edges.push(path);
                 }
-                VariableStrokeDebugEdgeKind::CapArc | VariableStrokeDebugEdgeKind::CircleArc => {
+                VariableStrokeDebugEdgeKind::CapArc
+                | VariableStrokeDebugEdgeKind::CapCorner
+                | VariableStrokeDebugEdgeKind::CircleArc => {
                     self.counts.caps += 1;
                     self.cap_edges.push(path);
                 }
@@ -1,26 +1,34 @@
-use crate::build::builder::{GraphBuilder, InclusionFilterStrategy};
+use crate::build::builder::{GraphBuilder, InclusionFilterStrategy, StoreFillsHandler};
 use crate::build::sweep::{
-    EvenOddStrategy, FillStrategy, NegativeStrategy, NonZeroStrategy, PositiveStrategy,
+    EvenOddStrategy, FillHandler, FillStrategy, NegativeStrategy, NonZeroStrategy, PositiveStrategy,
+    SweepRunner, WindingAtLeastStrategy,
 };
+use crate::core::control::{OperationPhase, ProgressTracker};
 use crate::core::edge_data::OverlayEdgeData;
-use crate::core::extract::VisitState;
+use crate::core::extract::{BooleanExtractionBuffer, VisitState};
 use crate::core::fill_rule::FillRule;
-use crate::core::graph::OverlayGraph;
 use crate::core::graph::OverlayNode;
+use crate::core::graph::{OverlayGraph, WindingSource};
+use crate::core::integer::OverlayInt;
 use crate::core::link::OverlayLink;
 use crate::core::link::OverlayLinkFilter;
+use crate::core::memory::MemoryGuard;
 use crate::core::overlay::IntOverlayOptions;
-use crate::core::overlay_rule::OverlayRule;
+use crate::core::overlay_rule::{CustomRule, OverlayRule};
 use crate::core::solver::Solver;
+use crate::geom::id_point::IdPoint;
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::segment::{
-    ALL, BOTH_BOTTOM, BOTH_TOP, CLIP_BOTH, CLIP_BOTTOM, CLIP_TOP, SUBJ_BOTH, SUBJ_BOTTOM, SUBJ_TOP, Segment,
-    SegmentFill,
+    ALL, BOTH_BOTTOM, BOTH_TOP, CLIP_BOTH, CLIP_BOTTOM, CLIP_TOP, NONE, SUBJ_BOTH, SUBJ_BOTTOM, SUBJ_TOP,
+    Segment, SegmentFill,
 };
 use crate::segm::winding::WindingCount;
+use alloc::vec;
 use alloc::vec::Vec;
+use core::ops::ControlFlow;
 use i_float::int::number::int::IntNumber;
 use i_key_sort::sort::key::SortKey;
+use i_shape::int::shape::IntShapes;
 use i_tree::Expiration;
 
 impl<I, D> GraphBuilder<ShapeCountBoolean, OverlayNode, I, D>
@@ -29,16 +37,23 @@ where
     D: OverlayEdgeData,
 {
     #[inline]
-    pub(crate) fn build_boolean_all(
-        &mut self,
+    pub(crate) fn build_boolean_all<'a>(
+        &'a mut self,
         fill_rule: FillRule,
         options: IntOverlayOptions<I::WideUInt>,
         solver: &Solver,
-        segments: &[Segment<ShapeCountBoolean, I, D>],
-    ) -> OverlayGraph<'_, I, D> {
-        self.build_boolean_fills(fill_rule, solver, segments);
+        segments: &'a [Segment<ShapeCountBoolean, I, D>],
+    ) -> OverlayGraph<'a, I, D> {
+        self.build_boolean_fills(fill_rule, solver, segments, &mut ProgressTracker::none());
         self.build_links_all(segments);
-        self.boolean_graph(options, solver)
+        let mut graph = self.boolean_graph(options, solver);
+        // the windings are swept only if the graph is extracted with a custom rule
+        graph.winding_source = Some(WindingSource {
+            fill_rule,
+            solver: *solver,
+            segments,
+        });
+        graph
     }
 
     #[inline]
@@ -50,7 +65,60 @@ where
         solver: &Solver,
         segments: &[Segment<ShapeCountBoolean, I, D>],
     ) -> OverlayGraph<'_, I, D> {
-        self.build_boolean_fills(fill_rule, solver, segments);
+        self.build_boolean_fills_for(
+            overlay_rule,
+            fill_rule,
+            solver,
+            segments,
+            &mut ProgressTracker::none(),
+        );
+        self.build_boolean_links(overlay_rule, segments);
+        self.boolean_graph(options, solver)
+    }
+
+    /// Same as [`Self::build_boolean_overlay`], but grows every buffer through `memory` and polls
+    /// `progress` during the fill sweep. Returns `None` if `memory` refuses an allocation or the
+    /// operation is cancelled.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn try_build_boolean_overlay(
+        &mut self,
+        memory: &mut MemoryGuard,
+        progress: &mut ProgressTracker,
+        fill_rule: FillRule,
+        overlay_rule: OverlayRule,
+        options: IntOverlayOptions<I::WideUInt>,
+        solver: &Solver,
+        segments: &[Segment<ShapeCountBoolean, I, D>],
+    ) -> Option<OverlayGraph<'_, I, D>> {
+        self.memory = *memory;
+        self.build_boolean_fills_for(overlay_rule, fill_rule, solver, segments, progress);
+        if !(self.memory.is_failed() || progress.is_cancelled()) {
+            self.build_boolean_links(overlay_rule, segments);
+        }
+        if !(self.memory.is_failed() || progress.is_cancelled()) {
+            self.build_nodes_and_connect_links(solver);
+        }
+        *memory = core::mem::take(&mut self.memory);
+
+        if memory.is_failed() || progress.is_cancelled() {
+            return None;
+        }
+
+        Some(OverlayGraph {
+            nodes: &self.nodes,
+            links: &self.links,
+            windings: &self.windings,
+            winding_source: None,
+            options,
+        })
+    }
+
+    #[inline]
+    fn build_boolean_links(
+        &mut self,
+        overlay_rule: OverlayRule,
+        segments: &[Segment<ShapeCountBoolean, I, D>],
+    ) {
         match overlay_rule {
             OverlayRule::Subject => self.build_links_by_filter::<SubjectFilter>(segments),
             OverlayRule::Clip => self.build_links_by_filter::<ClipFilter>(segments),
@@ -59,8 +127,79 @@ where
             OverlayRule::Difference => self.build_links_by_filter::<DifferenceFilter>(segments),
             OverlayRule::InverseDifference => self.build_links_by_filter::<InverseDifferenceFilter>(segments),
             OverlayRule::Xor => self.build_links_by_filter::<XorFilter>(segments),
+            OverlayRule::Custom(rule) => self.build_custom_links(rule, segments),
         }
-        self.boolean_graph(options, solver)
+    }
+
+    /// Same as [`Self::build_links_by_filter`] for a custom rule, keeping the windings of the
+    /// included links in step with them.
+    fn build_custom_links(&mut self, rule: CustomRule, segments: &[Segment<ShapeCountBoolean, I, D>]) {
+        self.links.clear();
+        if !self.memory.reserve(&mut self.links, segments.len()) {
+            return;
+        }
+
+        let mut n = 0;
+        for (i, (segment, &fill)) in segments.iter().zip(&self.fills).enumerate() {
+            let windings = self.windings[i];
+            if !rule.is_included(fill, windings) {
+                continue;
+            }
+            self.windings[n] = windings;
+            n += 1;
+            self.links.push(OverlayLink::new_with_data(
+                IdPoint::new(0, segment.x_segment.a),
+                IdPoint::new(0, segment.x_segment.b),
+                fill,
+                segment.data,
+            ));
+        }
+        self.windings.truncate(n);
+    }
+
+    /// Builds the fills, and the windings too if `overlay_rule` is custom.
+    #[inline]
+    fn build_boolean_fills_for(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        solver: &Solver,
+        segments: &[Segment<ShapeCountBoolean, I, D>],
+        progress: &mut ProgressTracker,
+    ) {
+        if let OverlayRule::Custom(_) = overlay_rule {
+            self.build_boolean_windings(fill_rule, solver, segments, progress);
+        } else {
+            self.build_boolean_fills(fill_rule, solver, segments, progress);
+        }
+    }
+
+    /// Same as [`Self::build_boolean_fills`], also storing the top and bottom winding of every segment.
+    fn build_boolean_windings(
+        &mut self,
+        fill_rule: FillRule,
+        solver: &Solver,
+        segments: &[Segment<ShapeCountBoolean, I, D>],
+        progress: &mut ProgressTracker,
+    ) {
+        let empty = ShapeCountBoolean { subj: 0, clip: 0 };
+        if !(self.memory.resize(&mut self.fills, segments.len(), NONE)
+            && self.memory.resize(&mut self.windings, segments.len(), [empty; 2]))
+        {
+            return;
+        }
+        if !progress.report(OperationPhase::Build, 0, segments.len()) {
+            return;
+        }
+        self.sweep_runner.run_with_fill_rule(
+            fill_rule,
+            solver,
+            segments,
+            StoreRegionWindingsHandler {
+                fills: StoreFillsHandler::new(&mut self.fills, progress),
+                windings: &mut self.windings,
+            },
+        );
     }
 
     #[inline]
@@ -69,12 +208,21 @@ where
         fill_rule: FillRule,
         solver: &Solver,
         segments: &[Segment<ShapeCountBoolean, I, D>],
+        progress: &mut ProgressTracker,
     ) {
+        self.windings.clear();
         match fill_rule {
-            FillRule::EvenOdd => self.build_fills_with_strategy::<EvenOddStrategy>(solver, segments),
-            FillRule::NonZero => self.build_fills_with_strategy::<NonZeroStrategy>(solver, segments),
-            FillRule::Positive => self.build_fills_with_strategy::<PositiveStrategy>(solver, segments),
-            FillRule::Negative => self.build_fills_with_strategy::<NegativeStrategy>(solver, segments),
+            FillRule::EvenOdd => self.build_fills_with_progress(&EvenOddStrategy, solver, segments, progress),
+            FillRule::NonZero => self.build_fills_with_progress(&NonZeroStrategy, solver, segments, progress),
+            FillRule::Positive => {
+                self.build_fills_with_progress(&PositiveStrategy, solver, segments, progress)
+            }
+            FillRule::Negative => {
+                self.build_fills_with_progress(&NegativeStrategy, solver, segments, progress)
+            }
+            FillRule::WindingAtLeast(k) => {
+                self.build_fills_with_progress(&WindingAtLeastStrategy::new(k), solver, segments, progress)
+            }
         }
     }
 
@@ -88,14 +236,115 @@ where
         OverlayGraph {
             nodes: &self.nodes,
             links: &self.links,
+            windings: &self.windings,
+            winding_source: None,
             options,
         }
     }
 }
 
+impl<I, D> OverlayGraph<'_, I, D>
+where
+    I: IntNumber + Expiration + SortKey,
+    D: OverlayEdgeData,
+{
+    /// Runs `extract` on this graph, sweeping the windings of a graph view first if
+    /// `overlay_rule` is custom.
+    pub(crate) fn with_windings_for<R>(
+        &self,
+        overlay_rule: OverlayRule,
+        extract: impl FnOnce(&OverlayGraph<'_, I, D>) -> R,
+    ) -> R {
+        let source = match (overlay_rule, &self.winding_source) {
+            (OverlayRule::Custom(_), Some(source)) => source,
+            _ => return extract(self),
+        };
+
+        let empty = ShapeCountBoolean { subj: 0, clip: 0 };
+        let mut fills = vec![NONE; source.segments.len()];
+        let mut windings = vec![[empty; 2]; source.segments.len()];
+        let mut progress = ProgressTracker::none();
+        SweepRunner::new().run_with_fill_rule(
+            source.fill_rule,
+            &source.solver,
+            source.segments,
+            StoreRegionWindingsHandler {
+                fills: StoreFillsHandler::new(&mut fills, &mut progress),
+                windings: &mut windings,
+            },
+        );
+
+        extract(&OverlayGraph {
+            options: self.options,
+            nodes: self.nodes,
+            links: self.links,
+            windings: &windings,
+            winding_source: None,
+        })
+    }
+}
+
+impl<I: OverlayInt> GraphBuilder<ShapeCountBoolean, OverlayNode, I> {
+    /// Extracts the subject regions of every winding level with a single fill sweep.
+    ///
+    /// Level `k` (at index `k - 1`) holds the regions whose subject winding number is at least
+    /// `k`, the same as [`FillRule::WindingAtLeast`] with [`OverlayRule::Subject`]. Levels go
+    /// up to the highest winding number. Clip segments are ignored.
+    pub(crate) fn extract_winding_levels(
+        &mut self,
+        options: IntOverlayOptions<I::WideUInt>,
+        solver: &Solver,
+        segments: &[Segment<ShapeCountBoolean, I>],
+        buffer: &mut BooleanExtractionBuffer<I>,
+    ) -> Vec<IntShapes<I>> {
+        self.windings.clear();
+        // subject winding number right above every segment
+        let mut windings = vec![0; segments.len()];
+        self.sweep_runner.run(
+            solver,
+            segments,
+            &PositiveStrategy,
+            StoreWindingsHandler {
+                windings: &mut windings,
+            },
+        );
+
+        let max_level = segments
+            .iter()
+            .zip(&windings)
+            .filter(|(segment, _)| segment.count.subj != 0)
+            .map(|(segment, &top)| top.max(top - segment.count.subj))
+            .max()
+            .unwrap_or(0);
+
+        let mut levels = Vec::with_capacity(max_level.max(0) as usize);
+        for level in 1..=max_level {
+            self.fills.clear();
+            self.fills
+                .extend(segments.iter().zip(&windings).map(|(segment, &top)| {
+                    let bot = top - segment.count.subj;
+                    let subj_top = (top >= level) as SegmentFill;
+                    let subj_bot = (bot >= level) as SegmentFill;
+                    subj_top | (subj_bot << 1)
+                }));
+            self.build_links_by_filter::<SubjectFilter>(segments);
+            let shapes = self
+                .boolean_graph(options, solver)
+                .extract_shapes(OverlayRule::Subject, buffer);
+            levels.push(shapes);
+        }
+
+        levels
+    }
+}
+
 impl FillStrategy<ShapeCountBoolean> for EvenOddStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountBoolean, bot: ShapeCountBoolean) -> (ShapeCountBoolean, SegmentFill) {
+    fn add_and_fill(
+        &self,
+        this: ShapeCountBoolean,
+        bot: ShapeCountBoolean,
+    ) -> (ShapeCountBoolean, SegmentFill) {
         let top = bot.add(this);
         let subj_top = 1 & top.subj as SegmentFill;
         let subj_bot = 1 & bot.subj as SegmentFill;
@@ -110,7 +359,11 @@ impl FillStrategy<ShapeCountBoolean> for EvenOddStrategy {
 
 impl FillStrategy<ShapeCountBoolean> for NonZeroStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountBoolean, bot: ShapeCountBoolean) -> (ShapeCountBoolean, SegmentFill) {
+    fn add_and_fill(
+        &self,
+        this: ShapeCountBoolean,
+        bot: ShapeCountBoolean,
+    ) -> (ShapeCountBoolean, SegmentFill) {
         let top = bot.add(this);
         let subj_top = (top.subj != 0) as SegmentFill;
         let subj_bot = (bot.subj != 0) as SegmentFill;
@@ -125,7 +378,11 @@ impl FillStrategy<ShapeCountBoolean> for NonZeroStrategy {
 
 impl FillStrategy<ShapeCountBoolean> for PositiveStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountBoolean, bot: ShapeCountBoolean) -> (ShapeCountBoolean, SegmentFill) {
+    fn add_and_fill(
+        &self,
+        this: ShapeCountBoolean,
+        bot: ShapeCountBoolean,
+    ) -> (ShapeCountBoolean, SegmentFill) {
         let top = bot.add(this);
         let subj_top = (top.subj > 0) as SegmentFill;
         let subj_bot = (bot.subj > 0) as SegmentFill;
@@ -140,7 +397,11 @@ impl FillStrategy<ShapeCountBoolean> for PositiveStrategy {
 
 impl FillStrategy<ShapeCountBoolean> for NegativeStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountBoolean, bot: ShapeCountBoolean) -> (ShapeCountBoolean, SegmentFill) {
+    fn add_and_fill(
+        &self,
+        this: ShapeCountBoolean,
+        bot: ShapeCountBoolean,
+    ) -> (ShapeCountBoolean, SegmentFill) {
         let top = bot.add(this);
         let subj_top = (top.subj < 0) as SegmentFill;
         let subj_bot = (bot.subj < 0) as SegmentFill;
@@ -153,6 +414,88 @@ impl FillStrategy<ShapeCountBoolean> for NegativeStrategy {
     }
 }
 
+impl FillStrategy<ShapeCountBoolean> for WindingAtLeastStrategy {
+    #[inline(always)]
+    fn add_and_fill(
+        &self,
+        this: ShapeCountBoolean,
+        bot: ShapeCountBoolean,
+    ) -> (ShapeCountBoolean, SegmentFill) {
+        let top = bot.add(this);
+        let subj_top = (top.subj >= self.min) as SegmentFill;
+        let subj_bot = (bot.subj >= self.min) as SegmentFill;
+        let clip_top = (top.clip >= self.min) as SegmentFill;
+        let clip_bot = (bot.clip >= self.min) as SegmentFill;
+
+        let fill = subj_top | (subj_bot << 1) | (clip_top << 2) | (clip_bot << 3);
+
+        (top, fill)
+    }
+}
+
+struct StoreWindingsHandler<'a> {
+    windings: &'a mut [i32],
+}
+
+impl<I: IntNumber, D> FillHandler<ShapeCountBoolean, I, D> for StoreWindingsHandler<'_> {
+    type Output = ();
+
+    #[inline(always)]
+    fn handle(&mut self, _: usize, _: &Segment<ShapeCountBoolean, I, D>, _: SegmentFill) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    #[inline(always)]
+    fn handle_with_count(
+        &mut self,
+        index: usize,
+        _: &Segment<ShapeCountBoolean, I, D>,
+        _: SegmentFill,
+        top: ShapeCountBoolean,
+    ) -> ControlFlow<()> {
+        self.windings[index] = top.subj;
+        ControlFlow::Continue(())
+    }
+
+    #[inline(always)]
+    fn finalize(self) {}
+}
+
+/// Stores the fills like [`StoreFillsHandler`] and the top and bottom winding of every segment.
+struct StoreRegionWindingsHandler<'a, 'b> {
+    fills: StoreFillsHandler<'a, 'b>,
+    windings: &'a mut [[ShapeCountBoolean; 2]],
+}
+
+impl<I: IntNumber, D> FillHandler<ShapeCountBoolean, I, D> for StoreRegionWindingsHandler<'_, '_> {
+    type Output = ();
+
+    #[inline(always)]
+    fn handle(
+        &mut self,
+        index: usize,
+        segment: &Segment<ShapeCountBoolean, I, D>,
+        fill: SegmentFill,
+    ) -> ControlFlow<()> {
+        self.fills.handle(index, segment, fill)
+    }
+
+    #[inline(always)]
+    fn handle_with_count(
+        &mut self,
+        index: usize,
+        segment: &Segment<ShapeCountBoolean, I, D>,
+        fill: SegmentFill,
+        top: ShapeCountBoolean,
+    ) -> ControlFlow<()> {
+        self.windings[index] = [top, top.add(segment.count.invert())];
+        self.fills.handle(index, segment, fill)
+    }
+
+    #[inline(always)]
+    fn finalize(self) {}
+}
+
 struct SubjectFilter;
 struct ClipFilter;
 struct IntersectFilter;
@@ -286,7 +629,12 @@ impl BooleanFillFilter for SegmentFill {
 
 impl<I: IntNumber, D> OverlayLinkFilter for [OverlayLink<I, D>] {
     #[inline]
-    fn filter_by_overlay_into(&self, overlay_rule: OverlayRule, buffer: &mut Vec<VisitState>) {
+    fn filter_by_overlay_into(
+        &self,
+        overlay_rule: OverlayRule,
+        windings: &[[ShapeCountBoolean; 2]],
+        buffer: &mut Vec<VisitState>,
+    ) {
         match overlay_rule {
             OverlayRule::Subject => filter_subject_into(self, buffer),
             OverlayRule::Clip => filter_clip_into(self, buffer),
@@ -295,6 +643,7 @@ impl<I: IntNumber, D> OverlayLinkFilter for [OverlayLink<I, D>] {
             OverlayRule::Difference => filter_difference_into(self, buffer),
             OverlayRule::Xor => filter_xor_into(self, buffer),
             OverlayRule::InverseDifference => filter_inverse_difference_into(self, buffer),
+            OverlayRule::Custom(rule) => filter_custom_into(self, rule, windings, buffer),
         }
     }
 }
@@ -364,3 +713,18 @@ fn filter_xor_into<I: IntNumber, D>(links: &[OverlayLink<I, D>], buffer: &mut Ve
         buffer.push(VisitState::new(!link.fill.is_xor()));
     }
 }
+
+#[inline]
+fn filter_custom_into<I: IntNumber, D>(
+    links: &[OverlayLink<I, D>],
+    rule: CustomRule,
+    windings: &[[ShapeCountBoolean; 2]],
+    buffer: &mut Vec<VisitState>,
+) {
+    debug_assert_eq!(links.len(), windings.len());
+    buffer.clear();
+    buffer.reserve(links.len());
+    for (link, &windings) in links.iter().zip(windings) {
+        buffer.push(VisitState::new(!rule.is_included(link.fill, windings)));
+    }
+}
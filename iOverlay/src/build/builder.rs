@@ -1,6 +1,8 @@
 use crate::build::sweep::{FillHandler, FillStrategy, SweepRunner};
+use crate::core::control::{OperationPhase, ProgressTracker};
 use crate::core::edge_data::OverlayEdgeData;
 use crate::core::link::OverlayLink;
+use crate::core::memory::MemoryGuard;
 use crate::core::solver::Solver;
 use crate::geom::end::End;
 use crate::geom::id_point::IdPoint;
@@ -15,18 +17,19 @@ pub(super) trait InclusionFilterStrategy {
     fn is_included(fill: SegmentFill) -> bool;
 }
 
-pub(crate) struct StoreFillsHandler<'a> {
+pub(crate) struct StoreFillsHandler<'a, 'b> {
     fills: &'a mut Vec<SegmentFill>,
+    progress: &'a mut ProgressTracker<'b>,
 }
 
-impl<'a> StoreFillsHandler<'a> {
+impl<'a, 'b> StoreFillsHandler<'a, 'b> {
     #[inline]
-    pub(crate) fn new(fills: &'a mut Vec<SegmentFill>) -> Self {
-        Self { fills }
+    pub(crate) fn new(fills: &'a mut Vec<SegmentFill>, progress: &'a mut ProgressTracker<'b>) -> Self {
+        Self { fills, progress }
     }
 }
 
-impl<C, D, I: IntNumber> FillHandler<C, I, D> for StoreFillsHandler<'_> {
+impl<C, D, I: IntNumber> FillHandler<C, I, D> for StoreFillsHandler<'_, '_> {
     type Output = ();
 
     #[inline(always)]
@@ -34,23 +37,30 @@ impl<C, D, I: IntNumber> FillHandler<C, I, D> for StoreFillsHandler<'_> {
         // fills is pre-allocated to segments.len() and index is guaranteed
         // to be in range by the sweep algorithm
         unsafe { *self.fills.get_unchecked_mut(index) = fill };
-        ControlFlow::Continue(())
+        if self.progress.tick(OperationPhase::Build, index, self.fills.len()) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
     }
 
     #[inline(always)]
     fn finalize(self) {}
 }
 
-pub(crate) trait GraphNode {
-    fn with_indices(indices: &[usize]) -> Self;
+pub(crate) trait GraphNode: Sized {
+    /// Creates a node of the given links. Returns `None` if `memory` refuses the allocation.
+    fn with_indices(indices: &[usize], memory: &mut MemoryGuard) -> Option<Self>;
 }
 
 pub(crate) struct GraphBuilder<C, N, I: IntNumber + Expiration, D = ()> {
-    sweep_runner: SweepRunner<C, I>,
+    pub(super) sweep_runner: SweepRunner<C, I>,
     pub(super) links: Vec<OverlayLink<I, D>>,
     pub(super) nodes: Vec<N>,
     pub(super) fills: Vec<SegmentFill>,
+    pub(super) windings: Vec<[C; 2]>,
     pub(super) ends: Vec<End<I>>,
+    pub(crate) memory: MemoryGuard,
 }
 
 impl<C, N, I, D> GraphBuilder<C, N, I, D>
@@ -67,19 +77,33 @@ where
             links: Vec::new(),
             nodes: Vec::new(),
             fills: Vec::new(),
+            windings: Vec::new(),
             ends: Vec::new(),
+            memory: MemoryGuard::default(),
         }
     }
 
+    /// Sweeps the segments to fill them with `strategy`, polling `progress` along the way.
     #[inline]
-    pub(super) fn build_fills_with_strategy<F: FillStrategy<C>>(
+    pub(super) fn build_fills_with_progress<F: FillStrategy<C>>(
         &mut self,
+        strategy: &F,
         solver: &Solver,
         segments: &[Segment<C, I, D>],
+        progress: &mut ProgressTracker,
     ) {
-        self.fills.resize(segments.len(), NONE);
-        self.sweep_runner
-            .run::<D, F, _>(solver, segments, StoreFillsHandler::new(&mut self.fills));
+        if !self.memory.resize(&mut self.fills, segments.len(), NONE) {
+            return;
+        }
+        if !progress.report(OperationPhase::Build, 0, segments.len()) {
+            return;
+        }
+        self.sweep_runner.run(
+            solver,
+            segments,
+            strategy,
+            StoreFillsHandler::new(&mut self.fills, progress),
+        );
     }
 
     #[inline]
@@ -88,7 +112,9 @@ where
         segments: &[Segment<C, I, D>],
     ) {
         self.links.clear();
-        self.links.reserve(segments.len());
+        if !self.memory.reserve(&mut self.links, segments.len()) {
+            return;
+        }
 
         for (segment, &fill) in segments.iter().zip(&self.fills) {
             if !F::is_included(fill) {
@@ -106,7 +132,9 @@ where
     #[inline]
     pub(super) fn build_links_all(&mut self, segments: &[Segment<C, I, D>]) {
         self.links.clear();
-        self.links.reserve(segments.len());
+        if !self.memory.reserve(&mut self.links, segments.len()) {
+            return;
+        }
 
         for (segment, &fill) in segments.iter().zip(&self.fills) {
             self.links.push(OverlayLink::new_with_data(
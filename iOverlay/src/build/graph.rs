@@ -22,10 +22,14 @@ where
             return;
         }
 
-        self.build_ends(solver);
+        if !self.build_ends(solver) {
+            return;
+        }
 
         self.nodes.clear();
-        self.nodes.reserve(n);
+        if !self.memory.reserve(&mut self.nodes, n) {
+            return;
+        }
 
         let mut ai = 0;
         let mut bi = 0;
@@ -64,6 +68,9 @@ where
             };
 
             let node_id = self.nodes.len();
+            if !self.memory.reserve(&mut indices, consume_a + consume_b) {
+                return;
+            }
 
             if consume_a > 0 {
                 let start = ai;
@@ -87,22 +94,35 @@ where
             }
 
             debug_assert!(!indices.is_empty());
-            self.nodes.push(N::with_indices(indices.as_slice()));
+            let Some(node) = N::with_indices(indices.as_slice(), &mut self.memory) else {
+                return;
+            };
+            self.nodes.push(node);
             indices.clear();
         }
     }
 
     #[inline]
-    fn build_ends(&mut self, solver: &Solver) {
+    fn build_ends(&mut self, solver: &Solver) -> bool {
         self.ends.clear();
-        self.ends.reserve(self.links.len());
+        let mut buffer = Vec::new();
+        if !(self.memory.reserve(&mut self.ends, self.links.len())
+            && self.memory.preallocate(&mut buffer, self.links.len()))
+        {
+            return false;
+        }
         for (i, link) in self.links.iter().enumerate() {
             self.ends.push(End {
                 index: i,
                 point: link.b.point,
             });
         }
-        self.ends
-            .sort_by_two_keys(solver.is_parallel_sort_allowed(), |e| e.point.x, |e| e.point.y);
+        self.ends.sort_by_two_keys_and_buffer(
+            solver.is_parallel_sort_allowed(),
+            &mut buffer,
+            |e| e.point.x,
+            |e| e.point.y,
+        );
+        true
     }
 }
@@ -1,5 +1,6 @@
 use crate::build::builder::{GraphBuilder, InclusionFilterStrategy};
-use crate::build::sweep::FillStrategy;
+use crate::build::sweep::{FillStrategy, WindingAtLeastStrategy};
+use crate::core::control::ProgressTracker;
 use crate::core::fill_rule::FillRule;
 use crate::core::solver::Solver;
 use crate::segm::segment::{CLIP_BOTH, SUBJ_BOTH, Segment, SegmentFill};
@@ -22,7 +23,7 @@ where
         solver: &Solver,
         segments: &[Segment<ShapeCountString, I>],
     ) -> StringGraph<'_, I> {
-        self.build_string_fills(fill_rule, solver, segments);
+        self.build_string_fills(fill_rule, solver, segments, &mut ProgressTracker::none());
         self.build_links_all(segments);
         self.string_graph(solver)
     }
@@ -35,7 +36,31 @@ where
         solver: &Solver,
         segments: &[Segment<ShapeCountString, I>],
     ) -> StringGraph<'_, I> {
-        self.build_string_fills(fill_rule, solver, segments);
+        self.build_string_fills(fill_rule, solver, segments, &mut ProgressTracker::none());
+        self.build_string_clip_links(clip_rule, segments);
+        self.string_graph(solver)
+    }
+
+    /// Same as [`Self::build_string_clip`], but polls `progress` during the fill sweep.
+    /// Returns `None` if the operation is cancelled.
+    pub(crate) fn build_string_clip_with_progress(
+        &mut self,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: &Solver,
+        segments: &[Segment<ShapeCountString, I>],
+        progress: &mut ProgressTracker,
+    ) -> Option<StringGraph<'_, I>> {
+        self.build_string_fills(fill_rule, solver, segments, progress);
+        if progress.is_cancelled() {
+            return None;
+        }
+        self.build_string_clip_links(clip_rule, segments);
+        Some(self.string_graph(solver))
+    }
+
+    #[inline]
+    fn build_string_clip_links(&mut self, clip_rule: ClipRule, segments: &[Segment<ShapeCountString, I>]) {
         match clip_rule {
             ClipRule {
                 invert: true,
@@ -54,7 +79,6 @@ where
                 boundary_included: false,
             } => self.build_links_by_filter::<ClipInsideBoundaryExcludedFilter>(segments),
         }
-        self.string_graph(solver)
     }
 
     #[inline]
@@ -63,12 +87,20 @@ where
         fill_rule: FillRule,
         solver: &Solver,
         segments: &[Segment<ShapeCountString, I>],
+        progress: &mut ProgressTracker,
     ) {
         match fill_rule {
-            FillRule::EvenOdd => self.build_fills_with_strategy::<EvenOddStrategy>(solver, segments),
-            FillRule::NonZero => self.build_fills_with_strategy::<NonZeroStrategy>(solver, segments),
-            FillRule::Positive => self.build_fills_with_strategy::<PositiveStrategy>(solver, segments),
-            FillRule::Negative => self.build_fills_with_strategy::<NegativeStrategy>(solver, segments),
+            FillRule::EvenOdd => self.build_fills_with_progress(&EvenOddStrategy, solver, segments, progress),
+            FillRule::NonZero => self.build_fills_with_progress(&NonZeroStrategy, solver, segments, progress),
+            FillRule::Positive => {
+                self.build_fills_with_progress(&PositiveStrategy, solver, segments, progress)
+            }
+            FillRule::Negative => {
+                self.build_fills_with_progress(&NegativeStrategy, solver, segments, progress)
+            }
+            FillRule::WindingAtLeast(k) => {
+                self.build_fills_with_progress(&WindingAtLeastStrategy::new(k), solver, segments, progress)
+            }
         }
     }
 
@@ -89,7 +121,7 @@ struct NegativeStrategy;
 
 impl FillStrategy<ShapeCountString> for EvenOddStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
+    fn add_and_fill(&self, this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
         let subj = bot.subj + this.subj;
         let top = ShapeCountString { subj, clip: 0 };
 
@@ -104,7 +136,7 @@ impl FillStrategy<ShapeCountString> for EvenOddStrategy {
 
 impl FillStrategy<ShapeCountString> for NonZeroStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
+    fn add_and_fill(&self, this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
         let subj = bot.subj + this.subj;
         let top = ShapeCountString { subj, clip: 0 }; // clip not need
 
@@ -119,7 +151,7 @@ impl FillStrategy<ShapeCountString> for NonZeroStrategy {
 
 impl FillStrategy<ShapeCountString> for PositiveStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
+    fn add_and_fill(&self, this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
         let subj = bot.subj + this.subj;
         let top = ShapeCountString { subj, clip: 0 }; // clip not need
 
@@ -134,7 +166,7 @@ impl FillStrategy<ShapeCountString> for PositiveStrategy {
 
 impl FillStrategy<ShapeCountString> for NegativeStrategy {
     #[inline(always)]
-    fn add_and_fill(this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
+    fn add_and_fill(&self, this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
         let subj = bot.subj + this.subj;
         let top = ShapeCountString { subj, clip: 0 }; // clip not need
 
@@ -147,6 +179,21 @@ impl FillStrategy<ShapeCountString> for NegativeStrategy {
     }
 }
 
+impl FillStrategy<ShapeCountString> for WindingAtLeastStrategy {
+    #[inline(always)]
+    fn add_and_fill(&self, this: ShapeCountString, bot: ShapeCountString) -> (ShapeCountString, SegmentFill) {
+        let subj = bot.subj + this.subj;
+        let top = ShapeCountString { subj, clip: 0 }; // clip not need
+
+        let subj_top = (top.subj >= self.min) as SegmentFill;
+        let subj_bot = (bot.subj >= self.min) as SegmentFill;
+
+        let fill = subj_top | (subj_bot << 1) | (this.clip << 2);
+
+        (top, fill)
+    }
+}
+
 struct ClipInsideBoundaryExcludedFilter;
 struct ClipInsideBoundaryIncludedFilter;
 struct ClipOutsideBoundaryExcludedFilter;
@@ -15,7 +15,7 @@ use i_tree::key::list::KeyExpList;
 use i_tree::key::tree::KeyExpTree;
 
 pub(crate) trait FillStrategy<C> {
-    fn add_and_fill(this: C, bot: C) -> (C, SegmentFill);
+    fn add_and_fill(&self, this: C, bot: C) -> (C, SegmentFill);
 }
 
 pub(crate) trait FillHandler<C, I: IntNumber, D = ()> {
@@ -26,6 +26,19 @@ pub(crate) trait FillHandler<C, I: IntNumber, D = ()> {
         segment: &Segment<C, I, D>,
         fill: SegmentFill,
     ) -> ControlFlow<Self::Output>;
+
+    /// Same as [`Self::handle`], with the winding count `top` right above the segment.
+    #[inline(always)]
+    fn handle_with_count(
+        &mut self,
+        index: usize,
+        segment: &Segment<C, I, D>,
+        fill: SegmentFill,
+        _top: C,
+    ) -> ControlFlow<Self::Output> {
+        self.handle(index, segment, fill)
+    }
+
     fn finalize(self) -> Self::Output;
 }
 
@@ -33,6 +46,7 @@ pub(crate) trait FillHandler<C, I: IntNumber, D = ()> {
 fn sweep_with_handler<I, C, D, F, S, H>(
     scan: &mut S,
     segments: &[Segment<C, I, D>],
+    strategy: &F,
     mut handler: H,
 ) -> H::Output
 where
@@ -71,10 +85,10 @@ where
 
         for se in node.iter() {
             let sid = unsafe { segments.get_unchecked(se.index) };
-            let (new_sum, fill) = F::add_and_fill(sid.count, sum_count);
+            let (new_sum, fill) = strategy.add_and_fill(sid.count, sum_count);
             sum_count = new_sum;
 
-            if let ControlFlow::Break(result) = handler.handle(se.index, sid, fill) {
+            if let ControlFlow::Break(result) = handler.handle_with_count(se.index, sid, fill, sum_count) {
                 return result;
             }
 
@@ -108,6 +122,7 @@ impl<C: WindingCount, I: IntNumber + Expiration> SweepRunner<C, I> {
         &mut self,
         solver: &Solver,
         segments: &[Segment<C, I, D>],
+        strategy: &F,
         handler: H,
     ) -> H::Output
     where
@@ -119,13 +134,13 @@ impl<C: WindingCount, I: IntNumber + Expiration> SweepRunner<C, I> {
         if solver.is_list_fill(segments) {
             let capacity = count.log2_sqrt().max(4) * 2;
             let mut list = self.take_scan_list(capacity);
-            let result = sweep_with_handler::<I, C, D, F, _, _>(&mut list, segments, handler);
+            let result = sweep_with_handler::<I, C, D, F, _, _>(&mut list, segments, strategy, handler);
             self.list = Some(list);
             result
         } else {
             let capacity = count.log2_sqrt().max(8);
             let mut tree = self.take_scan_tree(capacity);
-            let result = sweep_with_handler::<I, C, D, F, _, _>(&mut tree, segments, handler);
+            let result = sweep_with_handler::<I, C, D, F, _, _>(&mut tree, segments, strategy, handler);
             self.tree = Some(tree);
             result
         }
@@ -146,12 +161,16 @@ impl<C: WindingCount, I: IntNumber + Expiration> SweepRunner<C, I> {
         NonZeroStrategy: FillStrategy<C>,
         PositiveStrategy: FillStrategy<C>,
         NegativeStrategy: FillStrategy<C>,
+        WindingAtLeastStrategy: FillStrategy<C>,
     {
         match fill_rule {
-            FillRule::EvenOdd => self.run::<D, EvenOddStrategy, H>(solver, segments, handler),
-            FillRule::NonZero => self.run::<D, NonZeroStrategy, H>(solver, segments, handler),
-            FillRule::Positive => self.run::<D, PositiveStrategy, H>(solver, segments, handler),
-            FillRule::Negative => self.run::<D, NegativeStrategy, H>(solver, segments, handler),
+            FillRule::EvenOdd => self.run(solver, segments, &EvenOddStrategy, handler),
+            FillRule::NonZero => self.run(solver, segments, &NonZeroStrategy, handler),
+            FillRule::Positive => self.run(solver, segments, &PositiveStrategy, handler),
+            FillRule::Negative => self.run(solver, segments, &NegativeStrategy, handler),
+            FillRule::WindingAtLeast(k) => {
+                self.run(solver, segments, &WindingAtLeastStrategy::new(k), handler)
+            }
         }
     }
 
@@ -182,3 +201,18 @@ pub(crate) struct EvenOddStrategy;
 pub(crate) struct NonZeroStrategy;
 pub(crate) struct PositiveStrategy;
 pub(crate) struct NegativeStrategy;
+
+/// Fills regions whose winding number is at least `min`, see [`FillRule::WindingAtLeast`].
+pub(crate) struct WindingAtLeastStrategy {
+    pub(crate) min: i32,
+}
+
+impl WindingAtLeastStrategy {
+    #[inline]
+    pub(crate) fn new(k: u32) -> Self {
+        // the unbounded outer region has winding 0 and is never filled
+        Self {
+            min: k.clamp(1, i32::MAX as u32) as i32,
+        }
+    }
+}
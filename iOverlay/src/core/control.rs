@@ -0,0 +1,177 @@
+//! Cooperative cancellation and progress reporting for long operations.
+//!
+//! An [`OperationControl`] passed to
+//! [`Overlay::try_overlay_with_control`](crate::core::overlay::Overlay::try_overlay_with_control) is
+//! polled from the split loops, the fill sweep of the graph build and the contour extraction. It
+//! receives the current [`OperationProgress`] and may cancel the operation, which then returns
+//! [`OverlayError::Cancelled`](crate::core::error::OverlayError::Cancelled).
+//!
+//! The same hook is taken by `FloatOverlay::try_overlay_with_control`, the
+//! `try_clip_string_lines_with_control` methods of `StringOverlay` and `FloatStringOverlay`,
+//! `StrokeOffset::try_stroke_with_control` and `OutlineOffset::try_outline_with_control`.
+
+use core::ops::ControlFlow;
+
+/// Phase of an overlay operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationPhase {
+    /// Splitting segments at their intersections. Runs in passes; progress restarts with each pass.
+    Split,
+    /// Computing segment fills and building the graph.
+    Build,
+    /// Extracting the resulting contours from the graph.
+    Extract,
+}
+
+/// Progress of the current phase.
+/// - `phase`: The running phase.
+/// - `done`: Work items processed so far in this phase or pass.
+/// - `total`: Work items of this phase or pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperationProgress {
+    pub phase: OperationPhase,
+    pub done: usize,
+    pub total: usize,
+}
+
+impl OperationProgress {
+    /// Progress of the current phase in percent, `0..=100`.
+    #[inline]
+    pub fn percent(&self) -> u8 {
+        if self.total == 0 {
+            return 100;
+        }
+        (100 * self.done.min(self.total) as u64 / self.total as u64) as u8
+    }
+}
+
+/// Hook polled during an overlay operation.
+///
+/// It is called at the start of every phase and then about every thousand work items, so it should
+/// return quickly. Closures `FnMut(OperationProgress) -> ControlFlow<()>` implement it.
+pub trait OperationControl {
+    /// Receives the current progress. Return `ControlFlow::Break(())` to cancel the operation.
+    fn on_progress(&mut self, progress: OperationProgress) -> ControlFlow<()>;
+}
+
+impl<F: FnMut(OperationProgress) -> ControlFlow<()>> OperationControl for F {
+    #[inline]
+    fn on_progress(&mut self, progress: OperationProgress) -> ControlFlow<()> {
+        self(progress)
+    }
+}
+
+/// Polls an optional [`OperationControl`] every `STRIDE` work items and remembers a cancellation.
+pub(crate) struct ProgressTracker<'a> {
+    control: Option<&'a mut dyn OperationControl>,
+    countdown: usize,
+    cancelled: bool,
+}
+
+impl<'a> ProgressTracker<'a> {
+    pub(crate) const STRIDE: usize = 1024;
+
+    #[inline]
+    pub(crate) fn new(control: &'a mut dyn OperationControl) -> Self {
+        Self {
+            control: Some(control),
+            countdown: Self::STRIDE,
+            cancelled: false,
+        }
+    }
+
+    /// A tracker without a control; it never polls and is never cancelled.
+    #[inline]
+    pub(crate) fn none() -> Self {
+        Self {
+            control: None,
+            countdown: usize::MAX,
+            cancelled: false,
+        }
+    }
+
+    /// Whether a control is polled at all.
+    #[cfg(feature = "allow_multithreading")]
+    #[inline(always)]
+    pub(crate) fn has_control(&self) -> bool {
+        self.control.is_some()
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Counts one work item and polls the control every `STRIDE` items.
+    /// Returns `false` once the operation is cancelled.
+    #[inline(always)]
+    pub(crate) fn tick(&mut self, phase: OperationPhase, done: usize, total: usize) -> bool {
+        self.tick_by(1, phase, done, total)
+    }
+
+    /// Counts `items` work items at once, see [`Self::tick`].
+    #[inline(always)]
+    pub(crate) fn tick_by(&mut self, items: usize, phase: OperationPhase, done: usize, total: usize) -> bool {
+        if self.countdown > items {
+            self.countdown -= items;
+            return true;
+        }
+        self.report(phase, done, total)
+    }
+
+    /// Polls the control right away, for example at the start of a phase.
+    /// Returns `false` once the operation is cancelled.
+    #[cold]
+    pub(crate) fn report(&mut self, phase: OperationPhase, done: usize, total: usize) -> bool {
+        if self.cancelled {
+            return false;
+        }
+        let Some(control) = self.control.as_deref_mut() else {
+            self.countdown = usize::MAX;
+            return true;
+        };
+        self.countdown = Self::STRIDE;
+        let progress = OperationProgress { phase, done, total };
+        self.cancelled = control.on_progress(progress).is_break();
+        !self.cancelled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stride() {
+        let mut calls = 0;
+        let mut control = |progress: OperationProgress| {
+            calls += 1;
+            if progress.done >= 2000 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+
+        let mut tracker = ProgressTracker::new(&mut control);
+        let mut done = 0;
+        while tracker.tick(OperationPhase::Split, done, 4096) {
+            done += 1;
+        }
+
+        assert!(tracker.is_cancelled());
+        assert_eq!(done, 2047);
+        assert!(!tracker.report(OperationPhase::Build, 0, 1));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_percent() {
+        let progress = OperationProgress {
+            phase: OperationPhase::Extract,
+            done: 1,
+            total: 3,
+        };
+        assert_eq!(progress.percent(), 33);
+    }
+}
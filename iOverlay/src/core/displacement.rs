@@ -0,0 +1,187 @@
+//! Report of the vertex displacement caused by snapping and rounding.
+//!
+//! The integer engine never moves an input vertex. Where an intersection point is not on the
+//! integer grid, or lies within the snap radius of [`Precision`](crate::core::solver::Precision)
+//! from another vertex, the split rounds or snaps it, and the edges through it are bent by a small
+//! amount. The displacement of an output vertex is the largest distance between the vertex and the
+//! exact input edges that pass through it, so every output edge stays within that distance of its
+//! input edge.
+//!
+//! A float overlay also rounds every input vertex to the integer grid. For overlays created by a
+//! `*_tracked` constructor the report includes that rounding: a moved input vertex is displaced by
+//! its own rounding, and a split point by its snapping plus the rounding of the ends of its input
+//! edge.
+
+use crate::split::source::{GridPoint, SplitSources, grid_point};
+use alloc::vec::Vec;
+use i_float::float::number::FloatNumber;
+use i_float::int::number::int::IntNumber;
+use i_float::int::point::IntPoint;
+
+/// Displacement of a single output vertex.
+/// - `shape`, `contour`, `vertex`: Position of the vertex in the output.
+/// - `point`: The output vertex.
+/// - `distance`: Displacement in output units.
+/// - `grid_distance`: Displacement in units of the integer grid. Equal to `distance` for integer
+///   overlays.
+/// - `moved_input`: The vertex is an input vertex that is not at its exact input position, because
+///   a float overlay rounded it to the grid. Never set by integer overlays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VertexDisplacement<P, T> {
+    pub shape: usize,
+    pub contour: usize,
+    pub vertex: usize,
+    pub point: P,
+    pub distance: T,
+    pub grid_distance: f64,
+    pub moved_input: bool,
+}
+
+/// Displacement of all output vertices of one operation.
+/// - `max_distance`: The largest displacement in output units, zero if nothing moved.
+/// - `max_grid_distance`: The largest displacement in units of the integer grid.
+/// - `vertices`: Every displaced output vertex, in output order. Vertices that lie exactly on their
+///   input edges, at their exact input position, are omitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplacementReport<P, T> {
+    pub max_distance: T,
+    pub max_grid_distance: f64,
+    pub vertices: Vec<VertexDisplacement<P, T>>,
+}
+
+/// Displacement report of an integer overlay; distances are in grid units.
+pub type IntDisplacementReport<I> = DisplacementReport<IntPoint<I>, f64>;
+
+impl<P, T: PartialOrd> DisplacementReport<P, T> {
+    /// Returns `true` if any vertex moved farther than `tolerance` output units.
+    #[inline]
+    pub fn exceeds(&self, tolerance: T) -> bool {
+        self.max_distance > tolerance
+    }
+
+    /// Iterates over the displaced vertices that moved farther than `tolerance` output units.
+    #[inline]
+    pub fn exceeding(&self, tolerance: T) -> impl Iterator<Item = &VertexDisplacement<P, T>>
+    where
+        T: Copy,
+    {
+        self.vertices.iter().filter(move |v| v.distance > tolerance)
+    }
+}
+
+/// Split displacement by point, built once the split is complete.
+pub(crate) struct DisplacementMap {
+    splits: Vec<(GridPoint, f64)>,
+    rounding: Vec<(GridPoint, f64)>,
+}
+
+impl DisplacementMap {
+    /// `rounding` lists the input vertices that a float overlay moved, with the distance in grid
+    /// units.
+    pub(crate) fn new(split: &SplitSources, rounding: &[(GridPoint, f64)]) -> Self {
+        let mut rounding = rounding.to_vec();
+        rounding.sort_unstable_by(|r0, r1| r0.0.cmp(&r1.0).then(r1.1.total_cmp(&r0.1)));
+        rounding.dedup_by_key(|r| r.0);
+
+        let rounded = |p: GridPoint| match rounding.binary_search_by(|r| r.0.cmp(&p)) {
+            Ok(index) => rounding[index].1,
+            Err(_) => 0.0,
+        };
+
+        // a split point is off the exact input edge by its snapping plus the rounding of the edge
+        let mut splits: Vec<(GridPoint, f64)> = split
+            .splits
+            .iter()
+            .map(|&(p, source)| {
+                let [a, b] = split.sources[source];
+                (p, distance_to_line(p, a, b) + rounded(a).max(rounded(b)))
+            })
+            .collect();
+        splits.sort_unstable_by(|s0, s1| s0.0.cmp(&s1.0).then(s1.1.total_cmp(&s0.1)));
+        splits.dedup_by_key(|s| s.0);
+        splits.retain(|s| s.1 > 0.0);
+
+        Self { splits, rounding }
+    }
+
+    /// Builds the report for the output `shapes`. `project` maps an output point to the integer grid
+    /// and `scale` a grid distance to output units.
+    pub(crate) fn report<P: Copy, T: Copy + PartialOrd, I: IntNumber>(
+        &self,
+        shapes: &[Vec<Vec<P>>],
+        project: impl Fn(&P) -> IntPoint<I>,
+        scale: impl Fn(f64) -> T,
+    ) -> DisplacementReport<P, T> {
+        let mut report = DisplacementReport {
+            max_distance: scale(0.0),
+            max_grid_distance: 0.0,
+            vertices: Vec::new(),
+        };
+        if self.splits.is_empty() && self.rounding.is_empty() {
+            return report;
+        }
+
+        let contours = shapes.iter().enumerate().flat_map(|(shape, contours)| {
+            contours
+                .iter()
+                .enumerate()
+                .map(move |(contour, points)| (shape, contour, points))
+        });
+        for (shape, contour, points) in contours {
+            for (vertex, point) in points.iter().enumerate() {
+                let p = grid_point(project(point));
+                let split = match self.splits.binary_search_by(|s| s.0.cmp(&p)) {
+                    Ok(index) => self.splits[index].1,
+                    Err(_) => 0.0,
+                };
+                let rounding = match self.rounding.binary_search_by(|r| r.0.cmp(&p)) {
+                    Ok(index) => self.rounding[index].1,
+                    Err(_) => 0.0,
+                };
+                let grid_distance = split.max(rounding);
+                if grid_distance <= 0.0 {
+                    continue;
+                }
+                let distance = scale(grid_distance);
+                if distance > report.max_distance {
+                    report.max_distance = distance;
+                }
+                report.max_grid_distance = report.max_grid_distance.max(grid_distance);
+                report.vertices.push(VertexDisplacement {
+                    shape,
+                    contour,
+                    vertex,
+                    point: *point,
+                    distance,
+                    grid_distance,
+                    moved_input: rounding > 0.0,
+                });
+            }
+        }
+
+        report
+    }
+}
+
+#[inline]
+fn distance_to_line(p: GridPoint, a: GridPoint, b: GridPoint) -> f64 {
+    let (dx, dy) = (b[0] as i128 - a[0] as i128, b[1] as i128 - a[1] as i128);
+    let cross = dx * (p[1] as i128 - a[1] as i128) - dy * (p[0] as i128 - a[0] as i128);
+    if cross == 0 {
+        return 0.0;
+    }
+    let (dx, dy) = (dx as f64, dy as f64);
+    (cross as f64).abs() / FloatNumber::sqrt(dx * dx + dy * dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_to_line() {
+        assert_eq!(distance_to_line([5, 1], [0, 0], [10, 0]), 1.0);
+        assert_eq!(distance_to_line([3, 3], [0, 0], [10, 10]), 0.0);
+        assert!((distance_to_line([0, 2], [0, 0], [2, 2]) - 2.0f64.sqrt()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,176 @@
+//! Structured errors reported by the fallible `try_*` entry points.
+//!
+//! The infallible entry points assume valid input: finite float coordinates and integer
+//! coordinates inside the safe range of the integer engine, and do not check it. The `try_*`
+//! variants check the input they are given up front and report the first offending point
+//! instead. The allocation-fallible path also reports allocator failures and exceeded memory
+//! budgets through the same type.
+
+use crate::core::overlay::ShapeType;
+use core::fmt;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::int::number::int::IntNumber;
+use i_float::int::point::IntPoint;
+
+/// Identifies the input of an operation that an [`OverlayError`] refers to.
+///
+/// Shapes of string operations and source paths of stroke and outline are reported as `Subject`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputRole {
+    Subject,
+    Clip,
+    String,
+}
+
+/// Location of an offending input point.
+/// - `role`: The input that contains the point.
+/// - `contour`: Index of the contour (or path) within that input, in insertion order.
+/// - `point`: Index of the point within the contour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointIndex {
+    pub role: InputRole,
+    pub contour: usize,
+    pub point: usize,
+}
+
+/// Error returned by the fallible `try_*` entry points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayError {
+    /// A float coordinate is NaN or infinite.
+    NonFiniteCoordinate(PointIndex),
+    /// An integer coordinate is outside the safe range of the integer engine,
+    /// `-2^(BITS - 2) < v < 2^(BITS - 2)`, or a float point is outside the rectangle of an
+    /// [`OverlaySession`](crate::float::session::OverlaySession).
+    CoordinateOutOfRange(PointIndex),
+    /// A style parameter, such as a stroke width or an outline offset, is NaN or infinite.
+    NonFiniteStyle,
+    /// The allocator could not provide memory for a working buffer.
+    OutOfMemory,
+    /// A working buffer would exceed the caller-supplied
+    /// [`MemoryBudget`](crate::core::memory::MemoryBudget).
+    MemoryBudgetExceeded,
+    /// The [`OperationControl`](crate::core::control::OperationControl) cancelled the operation.
+    Cancelled,
+}
+
+impl fmt::Display for OverlayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OverlayError::NonFiniteCoordinate(index) => write!(
+                f,
+                "non-finite coordinate at {:?} contour {} point {}",
+                index.role, index.contour, index.point
+            ),
+            OverlayError::CoordinateOutOfRange(index) => write!(
+                f,
+                "coordinate out of range at {:?} contour {} point {}",
+                index.role, index.contour, index.point
+            ),
+            OverlayError::NonFiniteStyle => write!(f, "non-finite style parameter"),
+            OverlayError::OutOfMemory => write!(f, "out of memory"),
+            OverlayError::MemoryBudgetExceeded => write!(f, "memory budget exceeded"),
+            OverlayError::Cancelled => write!(f, "operation cancelled"),
+        }
+    }
+}
+
+impl From<ShapeType> for InputRole {
+    #[inline]
+    fn from(shape_type: ShapeType) -> Self {
+        match shape_type {
+            ShapeType::Subject => InputRole::Subject,
+            ShapeType::Clip => InputRole::Clip,
+        }
+    }
+}
+
+/// Checks every contour of an integer input and returns the first point outside the safe range.
+pub(crate) fn validate_int_paths<'a, I, It>(role: InputRole, paths: It) -> Result<(), OverlayError>
+where
+    I: IntNumber + 'a,
+    It: Iterator<Item = &'a [IntPoint<I>]>,
+{
+    for (contour, path) in paths.enumerate() {
+        if let Some(point) = path.iter().position(|p| !is_int_in_range(p)) {
+            return Err(OverlayError::CoordinateOutOfRange(PointIndex {
+                role,
+                contour,
+                point,
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// Checks every path of a float source and returns the first non-finite point.
+pub(crate) fn validate_float_paths<'a, P, It>(role: InputRole, paths: It) -> Result<(), OverlayError>
+where
+    P: FloatPointCompatible + 'a,
+    It: Iterator<Item = &'a [P]>,
+{
+    for (contour, path) in paths.enumerate() {
+        if let Some(point) = first_non_finite(path) {
+            return Err(OverlayError::NonFiniteCoordinate(PointIndex {
+                role,
+                contour,
+                point,
+            }));
+        }
+    }
+    Ok(())
+}
+
+#[inline]
+pub(crate) fn is_finite_scalar<T: FloatNumber>(value: T) -> bool {
+    value.to_f64().is_finite()
+}
+
+#[inline]
+fn first_non_finite<P: FloatPointCompatible>(contour: &[P]) -> Option<usize> {
+    contour
+        .iter()
+        .position(|p| !(is_finite_scalar(p.x()) && is_finite_scalar(p.y())))
+}
+
+#[inline]
+fn is_int_in_range<I: IntNumber>(p: &IntPoint<I>) -> bool {
+    let limit = I::ONE << (I::BITS - 2);
+    let min = -limit;
+    min < p.x && p.x < limit && min < p.y && p.y < limit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int_range() {
+        let limit = 1 << 30;
+        assert!(is_int_in_range(&IntPoint::<i32>::new(limit - 1, -limit + 1)));
+        assert!(!is_int_in_range(&IntPoint::<i32>::new(limit, 0)));
+        assert!(!is_int_in_range(&IntPoint::<i32>::new(0, -limit)));
+        assert!(!is_int_in_range(&IntPoint::<i16>::new(i16::MAX, 0)));
+    }
+
+    #[test]
+    fn test_validate_int_paths() {
+        let ok = [IntPoint::<i32>::new(0, 0), IntPoint::new(1, 1)];
+        let bad = [IntPoint::<i32>::new(0, 0), IntPoint::new(i32::MAX, 0)];
+
+        let paths = [&ok[..], &ok[..], &bad[..], &bad[..]];
+        let expected = PointIndex {
+            role: InputRole::Clip,
+            contour: 2,
+            point: 1,
+        };
+        assert_eq!(
+            validate_int_paths(InputRole::Clip, paths.into_iter()),
+            Err(OverlayError::CoordinateOutOfRange(expected))
+        );
+        assert_eq!(
+            validate_int_paths(InputRole::Clip, paths[..2].iter().copied()),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,486 @@
+//! Boolean expression trees over named operand sets.
+//!
+//! An [`OverlayExpression`] describes a whole CSG tree, for example `(a ∪ b) − (c ∩ d)`, where every
+//! leaf refers to an operand by key. An [`ExpressionEvaluator`] evaluates the tree bottom-up with a
+//! single reused [`Overlay`]. Intermediate results stay in reused flat contour buffers and are never
+//! converted to nested shapes; only the root step extracts `IntShapes`.
+
+use crate::core::fill_rule::FillRule;
+use crate::core::integer::OverlayInt;
+use crate::core::overlay::{ContourDirection, IntOverlayOptions, Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::i_shape::flat::buffer::FlatContoursBuffer;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use i_float::int::number::uint::UIntNumber;
+use i_shape::int::shape::IntShapes;
+
+/// A Boolean expression tree whose leaves are keys into a set of named operands.
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::core::expression::OverlayExpression;
+///
+/// // (a ∪ b) − c
+/// let expr = OverlayExpression::operand("a")
+///     .union(OverlayExpression::operand("b"))
+///     .difference(OverlayExpression::operand("c"));
+///
+/// assert_eq!(expr.operands(), vec![&"a", &"b", &"c"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlayExpression<K> {
+    /// A leaf referring to an operand by key.
+    Operand(K),
+    /// A Boolean operation applied to the results of two sub-expressions.
+    Operation {
+        rule: OverlayRule,
+        subj: Box<OverlayExpression<K>>,
+        clip: Box<OverlayExpression<K>>,
+    },
+}
+
+impl<K> OverlayExpression<K> {
+    /// Creates a leaf referring to the operand with the given key.
+    #[inline]
+    pub fn operand(key: K) -> Self {
+        Self::Operand(key)
+    }
+
+    /// Combines `self` (as subject) and `clip` with the given overlay rule.
+    #[inline]
+    pub fn apply(self, rule: OverlayRule, clip: Self) -> Self {
+        Self::Operation {
+            rule,
+            subj: Box::new(self),
+            clip: Box::new(clip),
+        }
+    }
+
+    /// `self ∪ other`.
+    #[inline]
+    pub fn union(self, other: Self) -> Self {
+        self.apply(OverlayRule::Union, other)
+    }
+
+    /// `self ∩ other`.
+    #[inline]
+    pub fn intersect(self, other: Self) -> Self {
+        self.apply(OverlayRule::Intersect, other)
+    }
+
+    /// `self − other`.
+    #[inline]
+    pub fn difference(self, other: Self) -> Self {
+        self.apply(OverlayRule::Difference, other)
+    }
+
+    /// `self ⊕ other`.
+    #[inline]
+    pub fn xor(self, other: Self) -> Self {
+        self.apply(OverlayRule::Xor, other)
+    }
+
+    /// Returns the operand keys in left-to-right leaf order. Repeated keys are repeated.
+    pub fn operands(&self) -> Vec<&K> {
+        let mut keys = Vec::new();
+        self.collect_operands(&mut keys);
+        keys
+    }
+
+    fn collect_operands<'a>(&'a self, keys: &mut Vec<&'a K>) {
+        match self {
+            Self::Operand(key) => keys.push(key),
+            Self::Operation { subj, clip, .. } => {
+                subj.collect_operands(keys);
+                clip.collect_operands(keys);
+            }
+        }
+    }
+}
+
+/// Returned when an expression refers to a key that is missing from the operand set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownOperandError<K>(pub K);
+
+impl<K: fmt::Debug> fmt::Display for UnknownOperandError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown operand {:?}", self.0)
+    }
+}
+
+/// Evaluates [`OverlayExpression`] trees in integer space.
+///
+/// The evaluator keeps its [`Overlay`] and intermediate buffers between steps and between calls, so
+/// evaluating many trees with one evaluator amortizes allocations.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use i_float::int::point::IntPoint;
+/// use i_float::int_pnt;
+/// use i_overlay::core::expression::{ExpressionEvaluator, OverlayExpression};
+/// use i_overlay::core::fill_rule::FillRule;
+///
+/// let mut operands = BTreeMap::new();
+/// operands.insert("a", vec![vec![vec![int_pnt!(0, 0), int_pnt!(10, 0), int_pnt!(10, 10), int_pnt!(0, 10)]]]);
+/// operands.insert("b", vec![vec![vec![int_pnt!(10, 0), int_pnt!(20, 0), int_pnt!(20, 10), int_pnt!(10, 10)]]]);
+/// operands.insert("c", vec![vec![vec![int_pnt!(5, 5), int_pnt!(15, 5), int_pnt!(15, 15), int_pnt!(5, 15)]]]);
+///
+/// let expr = OverlayExpression::operand("a")
+///     .union(OverlayExpression::operand("b"))
+///     .difference(OverlayExpression::operand("c"));
+///
+/// let mut evaluator = ExpressionEvaluator::<i32>::new();
+/// let shapes = evaluator.evaluate(&expr, &operands, FillRule::NonZero).unwrap();
+///
+/// assert_eq!(shapes.len(), 1);
+/// ```
+pub struct ExpressionEvaluator<I: OverlayInt> {
+    pub solver: Solver,
+    /// Options applied to the final step. Intermediate steps keep every contour and point
+    /// required to stay exact.
+    pub options: IntOverlayOptions<I::WideUInt>,
+    overlay: Overlay<I>,
+    buffers: Vec<FlatContoursBuffer<I>>,
+}
+
+enum StepInput<'a, I: OverlayInt> {
+    Operand(&'a IntShapes<I>),
+    Buffer(FlatContoursBuffer<I>),
+}
+
+impl<I: OverlayInt> ExpressionEvaluator<I> {
+    /// Creates an evaluator with default options and solver.
+    #[inline]
+    pub fn new() -> Self {
+        Self::new_custom(Default::default(), Default::default())
+    }
+
+    /// Creates an evaluator with custom options and solver.
+    /// - `options`: Options applied to the final step.
+    /// - `solver`: Type of solver to use for every step.
+    #[inline]
+    pub fn new_custom(options: IntOverlayOptions<I::WideUInt>, solver: Solver) -> Self {
+        Self {
+            solver,
+            options,
+            overlay: Overlay::new_custom(0, options, solver),
+            buffers: Vec::new(),
+        }
+    }
+
+    /// Evaluates the expression against a set of named operands.
+    ///
+    /// Every operand is interpreted with `fill_rule`. Returns an error naming the first key that
+    /// is missing from `operands`.
+    /// - `expression`: The expression tree to evaluate.
+    /// - `operands`: The named operand shapes.
+    /// - `fill_rule`: Fill rule used to interpret the operands.
+    pub fn evaluate<K>(
+        &mut self,
+        expression: &OverlayExpression<K>,
+        operands: &BTreeMap<K, IntShapes<I>>,
+        fill_rule: FillRule,
+    ) -> Result<IntShapes<I>, UnknownOperandError<K>>
+    where
+        K: Ord + Clone,
+    {
+        if let Some(&key) = expression
+            .operands()
+            .iter()
+            .find(|key| !operands.contains_key(key))
+        {
+            return Err(UnknownOperandError(key.clone()));
+        }
+
+        self.overlay.solver = self.solver;
+
+        let rule = match expression {
+            OverlayExpression::Operand(key) => {
+                self.overlay.clear();
+                self.overlay.add_shapes(&operands[key], ShapeType::Subject);
+                OverlayRule::Subject
+            }
+            OverlayExpression::Operation { rule, subj, clip } => {
+                self.load_step(subj, clip, operands, fill_rule);
+                *rule
+            }
+        };
+
+        let step_fill_rule = match expression {
+            OverlayExpression::Operand(_) => fill_rule,
+            OverlayExpression::Operation { .. } => Self::step_fill_rule(fill_rule),
+        };
+
+        self.overlay.options = self.options;
+        Ok(self.overlay.overlay(rule, step_fill_rule))
+    }
+
+    fn step_input<'a, K: Ord>(
+        &mut self,
+        expression: &OverlayExpression<K>,
+        operands: &'a BTreeMap<K, IntShapes<I>>,
+        fill_rule: FillRule,
+    ) -> StepInput<'a, I> {
+        match expression {
+            // validated by `evaluate`
+            OverlayExpression::Operand(key) => {
+                let shapes = &operands[key];
+                if Self::step_fill_rule(fill_rule) == fill_rule {
+                    return StepInput::Operand(shapes);
+                }
+
+                // apply the rule to the operand alone, the steps then read it like any intermediate
+                self.overlay.clear();
+                self.overlay.add_shapes(shapes, ShapeType::Subject);
+
+                let mut output = self.buffers.pop().unwrap_or_default();
+                self.overlay.options = Self::intermediate_options(self.options, fill_rule);
+                self.overlay
+                    .overlay_into(OverlayRule::Subject, fill_rule, &mut output);
+
+                StepInput::Buffer(output)
+            }
+            OverlayExpression::Operation { rule, subj, clip } => {
+                self.load_step(subj, clip, operands, fill_rule);
+
+                let mut output = self.buffers.pop().unwrap_or_default();
+                self.overlay.options = Self::intermediate_options(self.options, fill_rule);
+                self.overlay
+                    .overlay_into(*rule, Self::step_fill_rule(fill_rule), &mut output);
+
+                StepInput::Buffer(output)
+            }
+        }
+    }
+
+    fn load_step<K: Ord>(
+        &mut self,
+        subj: &OverlayExpression<K>,
+        clip: &OverlayExpression<K>,
+        operands: &BTreeMap<K, IntShapes<I>>,
+        fill_rule: FillRule,
+    ) {
+        let subj = self.step_input(subj, operands, fill_rule);
+        let clip = self.step_input(clip, operands, fill_rule);
+
+        self.overlay.clear();
+        self.add_input(&subj, ShapeType::Subject);
+        self.add_input(&clip, ShapeType::Clip);
+
+        self.recycle(subj);
+        self.recycle(clip);
+    }
+
+    /// The fill rule a step reads its inputs with. Intermediate contours have a winding of one, so
+    /// `WindingAtLeast` is applied to each operand alone and the steps fill any positive winding.
+    #[inline]
+    fn step_fill_rule(fill_rule: FillRule) -> FillRule {
+        match fill_rule {
+            FillRule::WindingAtLeast(_) => FillRule::Positive,
+            _ => fill_rule,
+        }
+    }
+
+    /// Intermediate contours never overlap, so any fill rule reads them correctly as long as
+    /// outer contours wind in the direction the rule counts as filled.
+    #[inline]
+    fn intermediate_options(
+        options: IntOverlayOptions<I::WideUInt>,
+        fill_rule: FillRule,
+    ) -> IntOverlayOptions<I::WideUInt> {
+        let output_direction = match fill_rule {
+            FillRule::Negative => ContourDirection::Clockwise,
+            _ => ContourDirection::CounterClockwise,
+        };
+        IntOverlayOptions {
+            preserve_input_collinear: options.preserve_input_collinear,
+            output_direction,
+            preserve_output_collinear: options.preserve_output_collinear,
+            min_output_area: I::WideUInt::ZERO,
+            ogc: false,
+        }
+    }
+
+    #[inline]
+    fn add_input(&mut self, input: &StepInput<I>, shape_type: ShapeType) {
+        match input {
+            StepInput::Operand(shapes) => self.overlay.add_shapes(shapes, shape_type),
+            StepInput::Buffer(buffer) => self.overlay.add_flat_buffer(buffer, shape_type),
+        }
+    }
+
+    #[inline]
+    fn recycle(&mut self, input: StepInput<I>) {
+        if let StepInput::Buffer(buffer) = input {
+            self.buffers.push(buffer);
+        }
+    }
+}
+
+impl<I: OverlayInt> Default for ExpressionEvaluator<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::area::Area;
+    use i_shape::int::shape::IntShape;
+
+    fn square(x: i32, y: i32, size: i32) -> IntShapes<i32> {
+        vec![vec![vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]]]
+    }
+
+    fn area(shapes: &[IntShape<i32>]) -> i64 {
+        shapes
+            .iter()
+            .flat_map(|shape| shape.iter())
+            .map(|contour| contour.area_two())
+            .sum::<i64>()
+            .abs()
+    }
+
+    fn operands() -> BTreeMap<&'static str, IntShapes<i32>> {
+        let mut operands = BTreeMap::new();
+        operands.insert("a", square(0, 0, 10));
+        operands.insert("b", square(10, 0, 10));
+        operands.insert("c", square(5, 5, 10));
+        operands
+    }
+
+    #[test]
+    fn test_nested_expression() {
+        // (a ∪ b) − c
+        let expr = OverlayExpression::operand("a")
+            .union(OverlayExpression::operand("b"))
+            .difference(OverlayExpression::operand("c"));
+
+        let mut evaluator = ExpressionEvaluator::<i32>::new();
+        let shapes = evaluator.evaluate(&expr, &operands(), FillRule::NonZero).unwrap();
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(area(&shapes), 2 * (200 - 50));
+    }
+
+    #[test]
+    fn test_matches_step_by_step_overlay() {
+        // (a ⊕ c) ∩ (b ∪ c)
+        let expr = OverlayExpression::operand("a")
+            .xor(OverlayExpression::operand("c"))
+            .intersect(OverlayExpression::operand("b").union(OverlayExpression::operand("c")));
+
+        let ops = operands();
+        let left = Overlay::with_shapes(&ops["a"], &ops["c"]).overlay(OverlayRule::Xor, FillRule::NonZero);
+        let right = Overlay::with_shapes(&ops["b"], &ops["c"]).overlay(OverlayRule::Union, FillRule::NonZero);
+        let expected = Overlay::with_shapes(&left, &right).overlay(OverlayRule::Intersect, FillRule::NonZero);
+
+        let mut evaluator = ExpressionEvaluator::<i32>::new();
+        let shapes = evaluator.evaluate(&expr, &ops, FillRule::NonZero).unwrap();
+
+        assert_eq!(area(&shapes), area(&expected));
+        assert_eq!(shapes.len(), expected.len());
+    }
+
+    #[test]
+    fn test_every_fill_rule() {
+        let expr = OverlayExpression::operand("a")
+            .union(OverlayExpression::operand("b"))
+            .difference(OverlayExpression::operand("c"));
+
+        let mut ops = operands();
+        let mut evaluator = ExpressionEvaluator::<i32>::new();
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero, FillRule::Positive] {
+            let shapes = evaluator.evaluate(&expr, &ops, fill_rule).unwrap();
+            assert_eq!(area(&shapes), 300, "{fill_rule}");
+        }
+
+        for shapes in ops.values_mut() {
+            for contour in shapes.iter_mut().flat_map(|shape| shape.iter_mut()) {
+                contour.reverse();
+            }
+        }
+        let shapes = evaluator.evaluate(&expr, &ops, FillRule::Negative).unwrap();
+        assert_eq!(area(&shapes), 300);
+    }
+
+    #[test]
+    fn test_winding_at_least_nested() {
+        // every operand is filled where two of its contours overlap
+        let mut ops = BTreeMap::new();
+        ops.insert("a", [square(0, 0, 10), square(5, 0, 10)].concat());
+        ops.insert("b", [square(10, 0, 10), square(10, 0, 10)].concat());
+        ops.insert(
+            "c",
+            [square(30, 0, 10), square(30, 0, 10), square(0, 20, 10)].concat(),
+        );
+
+        let fill_rule = FillRule::WindingAtLeast(2);
+        let expected: i64 = ops
+            .values()
+            .map(|shapes| area(&Overlay::with_shapes(shapes, &[]).overlay(OverlayRule::Subject, fill_rule)))
+            .sum();
+        assert_eq!(expected, 2 * (50 + 100 + 100));
+
+        // (a ∪ b) ∪ c
+        let expr = OverlayExpression::operand("a")
+            .union(OverlayExpression::operand("b"))
+            .union(OverlayExpression::operand("c"));
+
+        let mut evaluator = ExpressionEvaluator::<i32>::new();
+        let shapes = evaluator.evaluate(&expr, &ops, fill_rule).unwrap();
+        assert_eq!(area(&shapes), expected);
+        assert_eq!(shapes.len(), 2);
+
+        // (a ∪ b) − c
+        let expr = OverlayExpression::operand("a")
+            .union(OverlayExpression::operand("b"))
+            .difference(OverlayExpression::operand("c"));
+        let shapes = evaluator.evaluate(&expr, &ops, fill_rule).unwrap();
+        assert_eq!(area(&shapes), 2 * 150);
+
+        let shapes = evaluator
+            .evaluate(&OverlayExpression::operand("c"), &ops, fill_rule)
+            .unwrap();
+        assert_eq!(area(&shapes), 2 * 100);
+    }
+
+    #[test]
+    fn test_single_operand() {
+        let mut ops = BTreeMap::new();
+        ops.insert(0, square(0, 0, 10));
+
+        let mut evaluator = ExpressionEvaluator::<i32>::new();
+        let shapes = evaluator
+            .evaluate(&OverlayExpression::operand(0), &ops, FillRule::NonZero)
+            .unwrap();
+
+        assert_eq!(area(&shapes), 200);
+    }
+
+    #[test]
+    fn test_unknown_operand() {
+        let expr = OverlayExpression::operand("a").union(OverlayExpression::operand("z"));
+
+        let mut evaluator = ExpressionEvaluator::<i32>::new();
+        let result = evaluator.evaluate(&expr, &operands(), FillRule::NonZero);
+
+        assert_eq!(result, Err(UnknownOperandError("z")));
+    }
+}
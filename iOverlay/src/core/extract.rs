@@ -1,11 +1,13 @@
 use super::overlay_rule::OverlayRule;
 use crate::bind::segment::{ContourIndex, IdSegment};
 use crate::bind::solver::{JoinHoles, LeftBottomSegment};
+use crate::core::control::{OperationPhase, ProgressTracker};
 use crate::core::graph::{OverlayGraph, OverlayNode};
 use crate::core::hierarchy::FlatShapeHierarchy;
 use crate::core::integer::OverlayInt;
 use crate::core::link::OverlayLink;
 use crate::core::link::OverlayLinkFilter;
+use crate::core::memory::MemoryGuard;
 use crate::core::nearest_vector::NearestVector;
 use crate::core::overlay::ContourDirection;
 use crate::i_shape::flat::buffer::FlatContoursBuffer;
@@ -67,12 +69,39 @@ where
         overlay_rule: OverlayRule,
         buffer: &mut BooleanExtractionBuffer<I>,
     ) -> IntShapes<I> {
+        // never cancelled without a control
+        self.extract_shapes_with_progress(overlay_rule, buffer, &mut ProgressTracker::none())
+            .unwrap_or_default()
+    }
+
+    /// Same as [`Self::extract_shapes`], but polls `progress` for every extracted contour.
+    /// Returns `None` if the operation is cancelled.
+    pub(crate) fn extract_shapes_with_progress(
+        &self,
+        overlay_rule: OverlayRule,
+        buffer: &mut BooleanExtractionBuffer<I>,
+        progress: &mut ProgressTracker,
+    ) -> Option<IntShapes<I>> {
+        self.with_windings_for(overlay_rule, |graph| {
+            graph.extract_shapes_by_windings(overlay_rule, buffer, progress)
+        })
+    }
+
+    fn extract_shapes_by_windings(
+        &self,
+        overlay_rule: OverlayRule,
+        buffer: &mut BooleanExtractionBuffer<I>,
+        progress: &mut ProgressTracker,
+    ) -> Option<IntShapes<I>> {
         self.links
-            .filter_by_overlay_into(overlay_rule, &mut buffer.visited);
+            .filter_by_overlay_into(overlay_rule, self.windings, &mut buffer.visited);
+        if !progress.report(OperationPhase::Extract, 0, buffer.visited.len()) {
+            return None;
+        }
         if self.options.ogc {
-            self.extract_ogc(overlay_rule, buffer)
+            self.extract_ogc(overlay_rule, buffer, progress)
         } else {
-            self.extract(overlay_rule, buffer)
+            self.extract(overlay_rule, buffer, progress)
         }
     }
 
@@ -109,16 +138,41 @@ where
         buffer: &mut BooleanExtractionBuffer<I>,
         output: &mut FlatContoursBuffer<I>,
     ) {
-        self.links
-            .filter_by_overlay_into(overlay_rule, &mut buffer.visited);
-        self.extract_contours(overlay_rule, buffer, output);
+        self.with_windings_for(overlay_rule, |graph| {
+            graph
+                .links
+                .filter_by_overlay_into(overlay_rule, graph.windings, &mut buffer.visited);
+            graph.extract_contours(overlay_rule, buffer, output, &mut MemoryGuard::default());
+        })
+    }
+
+    /// Same as [`Self::extract_contours_into`], but grows every buffer through `memory`.
+    /// Returns `false` if `memory` refuses an allocation; `output` is then incomplete.
+    pub(crate) fn try_extract_contours_into(
+        &self,
+        overlay_rule: OverlayRule,
+        buffer: &mut BooleanExtractionBuffer<I>,
+        output: &mut FlatContoursBuffer<I>,
+        memory: &mut MemoryGuard,
+    ) -> bool {
+        buffer.visited.clear();
+        if !memory.reserve(&mut buffer.visited, self.links.len()) {
+            return false;
+        }
+        self.with_windings_for(overlay_rule, |graph| {
+            graph
+                .links
+                .filter_by_overlay_into(overlay_rule, graph.windings, &mut buffer.visited);
+            graph.extract_contours(overlay_rule, buffer, output, memory)
+        })
     }
 
     pub(crate) fn extract(
         &self,
         overlay_rule: OverlayRule,
         buffer: &mut BooleanExtractionBuffer<I>,
-    ) -> IntShapes<I> {
+        progress: &mut ProgressTracker,
+    ) -> Option<IntShapes<I>> {
         let clockwise = self.options.output_direction == ContourDirection::Clockwise;
 
         let mut shapes = Vec::new();
@@ -137,6 +191,10 @@ where
                 continue;
             }
 
+            if !progress.tick(OperationPhase::Extract, link_index, buffer.visited.len()) {
+                return None;
+            }
+
             let left_top_link = unsafe {
                 // Safety: `link_index` walks 0..buffer.visited.len(), and buffer.visited.len() <= self.links.len().
                 GraphUtil::find_left_top_link(self.links, self.nodes, link_index, &buffer.visited)
@@ -147,7 +205,7 @@ where
                 // indices in 0..self.links.len(), so this lookup cannot go out of bounds.
                 self.links.get_unchecked(left_top_link)
             };
-            let is_hole = overlay_rule.is_fill_top(link.fill);
+            let is_hole = overlay_rule.is_fill_top(link.fill, self.top_winding(left_top_link));
             let visited_state = [VisitState::HullVisited, VisitState::HoleVisited][is_hole as usize];
 
             let direction = is_hole == clockwise;
@@ -199,7 +257,7 @@ where
 
         shapes.join_sorted_holes(holes, anchors, clockwise);
 
-        shapes
+        Some(shapes)
     }
 
     pub(crate) fn find_contour(
@@ -241,11 +299,19 @@ where
         overlay_rule: OverlayRule,
         buffer: &mut BooleanExtractionBuffer<I>,
         output: &mut FlatContoursBuffer<I>,
-    ) {
+        memory: &mut MemoryGuard,
+    ) -> bool {
         let clockwise = self.options.output_direction == ContourDirection::Clockwise;
         let len = buffer.visited.len();
-        buffer.points.reserve(len.saturating_sub(buffer.points.len()));
-        output.clear_and_reserve(len, 4);
+        let points_additional = len.saturating_sub(buffer.points.len());
+        output.points.clear();
+        output.ranges.clear();
+        if !(memory.reserve(&mut buffer.points, points_additional)
+            && memory.reserve(&mut output.points, len)
+            && memory.reserve(&mut output.ranges, 4))
+        {
+            return false;
+        }
 
         let mut link_index = 0;
         while link_index < len {
@@ -264,7 +330,7 @@ where
                 // indices in 0..self.links.len(), so this lookup cannot go out of bounds.
                 self.links.get_unchecked(left_top_link)
             };
-            let is_hole = overlay_rule.is_fill_top(link.fill);
+            let is_hole = overlay_rule.is_fill_top(link.fill, self.top_winding(left_top_link));
             let visited_state = [VisitState::HullVisited, VisitState::HoleVisited][is_hole as usize];
 
             let direction = is_hole == clockwise;
@@ -287,8 +353,13 @@ where
                 continue;
             }
 
+            if !memory.ensure_spare(&mut output.ranges, 1) {
+                return false;
+            }
             output.add_contour(buffer.points.as_slice());
         }
+
+        true
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::bind::segment::{ContourIndex, IdSegment};
 use crate::bind::solver::{JoinHoles, LeftBottomSegment};
+use crate::core::control::{OperationPhase, ProgressTracker};
 use crate::core::extract::{
     BooleanExtractionBuffer, GraphContour, GraphUtil, StartPathData, Visit, VisitState,
 };
@@ -20,7 +21,8 @@ where
         &self,
         overlay_rule: OverlayRule,
         buffer: &mut BooleanExtractionBuffer<I>,
-    ) -> IntShapes<I> {
+        progress: &mut ProgressTracker,
+    ) -> Option<IntShapes<I>> {
         let is_main_dir_cw = self.options.output_direction == ContourDirection::Clockwise;
 
         let mut contour_visited = if let Some(mut visited) = buffer.contour_visited.take() {
@@ -48,6 +50,11 @@ where
                 continue;
             }
 
+            if !progress.tick(OperationPhase::Extract, link_index, buffer.visited.len()) {
+                buffer.contour_visited = Some(contour_visited);
+                return None;
+            }
+
             let left_top_link = unsafe {
                 // Safety: `link_index` walks 0..buffer.visited.len(), and buffer.visited.len() <= self.links.len().
                 GraphUtil::find_left_top_link(self.links, self.nodes, link_index, &buffer.visited)
@@ -58,7 +65,7 @@ where
                 // indices in 0..self.links.len(), so this lookup cannot go out of bounds.
                 self.links.get_unchecked(left_top_link)
             };
-            let is_hole = overlay_rule.is_fill_top(link.fill);
+            let is_hole = overlay_rule.is_fill_top(link.fill, self.top_winding(left_top_link));
             let visited_state = [VisitState::HullVisited, VisitState::HoleVisited][is_hole as usize];
 
             let direction = is_hole == is_main_dir_cw;
@@ -111,6 +118,11 @@ where
                     continue;
                 }
 
+                if !progress.tick(OperationPhase::Extract, link_index, buffer.visited.len()) {
+                    buffer.contour_visited = Some(contour_visited);
+                    return None;
+                }
+
                 let left_top_link = unsafe {
                     // Safety: `link_index` walks 0..buffer.visited.len(), and buffer.visited.len() <= self.links.len().
                     GraphUtil::find_left_top_link(self.links, self.nodes, link_index, &buffer.visited)
@@ -122,7 +134,7 @@ where
                     self.links.get_unchecked(left_top_link)
                 };
 
-                debug_assert!(overlay_rule.is_fill_top(link.fill));
+                debug_assert!(overlay_rule.is_fill_top(link.fill, self.top_winding(left_top_link)));
 
                 let start_data = StartPathData::new(is_main_dir_cw, link, left_top_link);
 
@@ -171,7 +183,7 @@ where
 
         buffer.contour_visited = Some(contour_visited);
 
-        shapes
+        Some(shapes)
     }
 
     fn skip_contour(
@@ -5,6 +5,8 @@ use core::fmt;
 /// - `NonZero`: Only non-zero sub-regions are filled.
 /// - `Positive`: Fills regions where the winding number is positive.
 /// - `Negative`: Fills regions where the winding number is negative.
+/// - `WindingAtLeast(k)`: Fills regions where the winding number is at least `k`, e.g. areas covered
+///   by `k` or more positively oriented contours. `0` behaves like `1`, the outer region is never filled.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum FillRule {
     EvenOdd,
@@ -12,6 +14,7 @@ pub enum FillRule {
     NonZero,
     Positive,
     Negative,
+    WindingAtLeast(u32),
 }
 
 impl fmt::Display for FillRule {
@@ -21,6 +24,7 @@ impl fmt::Display for FillRule {
             FillRule::NonZero => "NonZero",
             FillRule::Positive => "Positive",
             FillRule::Negative => "Negative",
+            FillRule::WindingAtLeast(k) => return write!(f, "WindingAtLeast({})", k),
         };
 
         write!(f, "{}", text)
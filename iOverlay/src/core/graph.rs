@@ -4,7 +4,12 @@
 
 use super::link::OverlayLink;
 use crate::build::builder::GraphNode;
+use crate::core::fill_rule::FillRule;
+use crate::core::memory::MemoryGuard;
 use crate::core::overlay::IntOverlayOptions;
+use crate::core::solver::Solver;
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::Segment;
 use alloc::vec::Vec;
 use i_float::int::number::int::IntNumber;
 
@@ -18,6 +23,17 @@ pub struct OverlayGraph<'a, I: IntNumber, D = ()> {
     pub(crate) options: IntOverlayOptions<I::WideUInt>,
     pub(crate) nodes: &'a [OverlayNode],
     pub(crate) links: &'a [OverlayLink<I, D>],
+    /// Top and bottom winding of every link, only kept for custom overlay rules.
+    pub(crate) windings: &'a [[ShapeCountBoolean; 2]],
+    /// Input of a graph view, swept again for the windings when a custom rule is extracted.
+    pub(crate) winding_source: Option<WindingSource<'a, I, D>>,
+}
+
+/// The segments a graph view was built from, one per link.
+pub(crate) struct WindingSource<'a, I: IntNumber, D> {
+    pub(crate) fill_rule: FillRule,
+    pub(crate) solver: Solver,
+    pub(crate) segments: &'a [Segment<ShapeCountBoolean, I, D>],
 }
 
 pub(crate) enum OverlayNode {
@@ -27,16 +43,23 @@ pub(crate) enum OverlayNode {
 
 impl GraphNode for OverlayNode {
     #[inline]
-    fn with_indices(indices: &[usize]) -> Self {
+    fn with_indices(indices: &[usize], memory: &mut MemoryGuard) -> Option<Self> {
         if indices.len() == 2 {
-            Self::Bridge([indices[0], indices[1]])
-        } else {
-            Self::Cross(indices.to_vec())
+            return Some(Self::Bridge([indices[0], indices[1]]));
         }
+        memory.copy_slice(indices).map(Self::Cross)
     }
 }
 
 impl<I: IntNumber, D> OverlayGraph<'_, I, D> {
+    /// Winding of the region on the top side of a link, zero when the windings were not kept.
+    #[inline(always)]
+    pub(crate) fn top_winding(&self, link_index: usize) -> ShapeCountBoolean {
+        self.windings
+            .get(link_index)
+            .map_or(ShapeCountBoolean { subj: 0, clip: 0 }, |windings| windings[0])
+    }
+
     pub fn validate(&self) {
         for node in self.nodes.iter() {
             if let OverlayNode::Cross(indices) = node {
@@ -1,6 +1,7 @@
 use crate::core::extract::VisitState;
 use crate::core::overlay_rule::OverlayRule;
 use crate::geom::id_point::IdPoint;
+use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::segment::SegmentFill;
 use alloc::vec::Vec;
 use i_float::int::number::int::IntNumber;
@@ -36,5 +37,12 @@ impl<I: IntNumber, D> OverlayLink<I, D> {
 }
 
 pub(crate) trait OverlayLinkFilter {
-    fn filter_by_overlay_into(&self, overlay_rule: OverlayRule, buffer: &mut Vec<VisitState>);
+    /// Marks the links that are not part of the `overlay_rule` result as visited. `windings` holds the
+    /// top and bottom winding of every link, only read by [`OverlayRule::Custom`].
+    fn filter_by_overlay_into(
+        &self,
+        overlay_rule: OverlayRule,
+        windings: &[[ShapeCountBoolean; 2]],
+        buffer: &mut Vec<VisitState>,
+    );
 }
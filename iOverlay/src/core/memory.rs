@@ -0,0 +1,190 @@
+//! Memory limits for the allocation-fallible overlay path.
+//!
+//! The regular entry points grow their buffers with infallible `Vec` operations and abort the
+//! process if the allocator fails. [`Overlay::try_overlay_into`](crate::core::overlay::Overlay::try_overlay_into)
+//! instead reserves every working buffer of the split, graph-build and extract phases with
+//! `try_reserve` and reports [`OverlayError::OutOfMemory`] or
+//! [`OverlayError::MemoryBudgetExceeded`].
+
+use crate::core::error::OverlayError;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+/// Upper bound on the memory a fallible operation may allocate.
+///
+/// The budget counts the bytes of every buffer growth performed by the operation, including
+/// scratch buffers that are released before it returns, so it bounds the peak usage from above.
+/// Capacity that reusable buffers already hold from earlier calls is not counted.
+/// - `max_bytes`: The limit in bytes, or `None` for no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryBudget {
+    pub max_bytes: Option<usize>,
+}
+
+impl MemoryBudget {
+    /// No limit; only allocator failures are reported.
+    pub const UNLIMITED: Self = Self { max_bytes: None };
+
+    /// Creates a budget of `max_bytes` bytes.
+    #[inline]
+    pub fn with_max_bytes(max_bytes: usize) -> Self {
+        Self {
+            max_bytes: Some(max_bytes),
+        }
+    }
+}
+
+/// Grows buffers on behalf of the split, build and extract phases.
+///
+/// The default guard is infallible and behaves like the plain `Vec` methods. A fallible guard uses
+/// `try_reserve_exact`, charges the budget and records the first failure; once failed, every further
+/// request is refused so the phases can unwind without touching their buffers.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct MemoryGuard {
+    fallible: bool,
+    available: usize,
+    error: Option<OverlayError>,
+}
+
+impl MemoryGuard {
+    #[inline]
+    pub(crate) fn fallible(budget: MemoryBudget) -> Self {
+        Self {
+            fallible: true,
+            available: budget.max_bytes.unwrap_or(usize::MAX),
+            error: None,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_fallible(&self) -> bool {
+        self.fallible
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_failed(&self) -> bool {
+        self.error.is_some()
+    }
+
+    #[inline]
+    pub(crate) fn result(&self) -> Result<(), OverlayError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Reserves room for at least `additional` more items, like [`Vec::reserve`].
+    #[inline(always)]
+    pub(crate) fn reserve<T>(&mut self, vec: &mut Vec<T>, additional: usize) -> bool {
+        if vec.capacity() - vec.len() >= additional {
+            return true;
+        }
+        if !self.fallible {
+            vec.reserve(additional);
+            return true;
+        }
+        self.grow(vec, vec.len() + additional)
+    }
+
+    /// Makes room for `additional` more items with amortized growth. Used before pushes whose
+    /// total count is not known up front.
+    #[inline(always)]
+    pub(crate) fn ensure_spare<T>(&mut self, vec: &mut Vec<T>, additional: usize) -> bool {
+        if vec.capacity() - vec.len() >= additional {
+            return true;
+        }
+        if !self.fallible {
+            vec.reserve(additional);
+            return true;
+        }
+        let capacity = (vec.len() + additional).max(2 * vec.capacity()).max(8);
+        self.grow(vec, capacity)
+    }
+
+    /// Reserves room for `additional` more items in fallible mode only. Used for buffers that the
+    /// infallible path leaves to grow on demand, for example sort scratch.
+    #[inline(always)]
+    pub(crate) fn preallocate<T>(&mut self, vec: &mut Vec<T>, additional: usize) -> bool {
+        !self.fallible || self.reserve(vec, additional)
+    }
+
+    /// Resizes `vec` to `len` items, like [`Vec::resize`].
+    #[inline]
+    pub(crate) fn resize<T: Clone>(&mut self, vec: &mut Vec<T>, len: usize, value: T) -> bool {
+        if !self.reserve(vec, len.saturating_sub(vec.len())) {
+            return false;
+        }
+        vec.resize(len, value);
+        true
+    }
+
+    /// Copies `slice` into a new vector of exactly its length, like `<[T]>::to_vec`.
+    #[inline]
+    pub(crate) fn copy_slice<T: Clone>(&mut self, slice: &[T]) -> Option<Vec<T>> {
+        if !self.fallible {
+            return Some(slice.to_vec());
+        }
+        let mut vec = Vec::new();
+        if !self.grow(&mut vec, slice.len()) {
+            return None;
+        }
+        vec.extend_from_slice(slice);
+        Some(vec)
+    }
+
+    #[cold]
+    fn grow<T>(&mut self, vec: &mut Vec<T>, capacity: usize) -> bool {
+        if self.error.is_some() {
+            return false;
+        }
+
+        let old_capacity = vec.capacity();
+        let bytes = (capacity - old_capacity).saturating_mul(size_of::<T>());
+        if bytes > self.available {
+            self.error = Some(OverlayError::MemoryBudgetExceeded);
+            return false;
+        }
+
+        if vec.try_reserve_exact(capacity - vec.len()).is_err() {
+            self.error = Some(OverlayError::OutOfMemory);
+            return false;
+        }
+
+        let used = (vec.capacity() - old_capacity) * size_of::<T>();
+        self.available = self.available.saturating_sub(used);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget() {
+        let mut guard = MemoryGuard::fallible(MemoryBudget::with_max_bytes(64));
+        let mut vec: Vec<u64> = Vec::new();
+
+        assert!(guard.reserve(&mut vec, 6));
+        assert!(!guard.reserve(&mut vec, 10));
+        assert_eq!(guard.result(), Err(OverlayError::MemoryBudgetExceeded));
+
+        // a failed guard refuses every further growth
+        assert!(!guard.ensure_spare(&mut vec, 7));
+        assert!(guard.ensure_spare(&mut vec, 6));
+        assert_eq!(vec.capacity(), 6);
+    }
+
+    #[test]
+    fn test_infallible() {
+        let mut guard = MemoryGuard::default();
+        let mut vec: Vec<u64> = Vec::new();
+
+        assert!(guard.preallocate(&mut vec, 100));
+        assert_eq!(vec.capacity(), 0);
+        assert!(guard.resize(&mut vec, 100, 0));
+        assert_eq!(vec.len(), 100);
+        assert_eq!(guard.result(), Ok(()));
+    }
+}
@@ -1,6 +1,10 @@
+pub mod control;
+pub mod displacement;
 pub mod divide;
 pub mod edge_data;
 pub mod edge_overlay;
+pub mod error;
+pub mod expression;
 pub mod extract;
 mod extract_ogc;
 pub mod fill_rule;
@@ -8,11 +12,14 @@ pub mod graph;
 pub mod hierarchy;
 pub mod integer;
 pub(crate) mod link;
+pub mod memory;
 pub(crate) mod nearest_vector;
 pub mod overlay;
 pub mod overlay_rule;
 pub mod point_location;
 pub mod predicate;
+pub mod provenance;
 pub mod relate;
 pub mod simplify;
 pub mod solver;
+pub mod stats;
@@ -3,17 +3,24 @@
 //! manage subject and clip polygons and convert them into graphs for further operations.
 
 use crate::build::builder::GraphBuilder;
+use crate::core::control::{OperationControl, ProgressTracker};
+use crate::core::displacement::{DisplacementMap, IntDisplacementReport};
+use crate::core::error::{InputRole, OverlayError, validate_int_paths};
 use crate::core::extract::BooleanExtractionBuffer;
 use crate::core::fill_rule::FillRule;
 use crate::core::hierarchy::FlatShapeHierarchy;
 use crate::core::integer::OverlayInt;
+use crate::core::memory::{MemoryBudget, MemoryGuard};
 use crate::core::overlay_rule::OverlayRule;
+use crate::core::provenance::{InputLog, VertexSources};
 use crate::core::solver::Solver;
+use crate::core::stats::{OverlayStats, PhaseTimer, StatsClock};
 use crate::i_shape::flat::buffer::FlatContoursBuffer;
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::build::BuildSegments;
 use crate::segm::segment::Segment;
 use crate::split::solver::SplitSolver;
+use crate::split::source::SplitSources;
 use crate::vector::edge::{DataVectorEdge, VectorShape};
 use alloc::vec::Vec;
 use i_float::int::number::uint::UIntNumber;
@@ -71,6 +78,7 @@ pub struct Overlay<I: OverlayInt> {
     pub(crate) segments: Vec<Segment<ShapeCountBoolean, I>>,
     pub(crate) split_solver: SplitSolver<I>,
     pub(crate) graph_builder: GraphBuilder<ShapeCountBoolean, OverlayNode, I>,
+    pub(crate) input_log: Option<InputLog>,
 }
 
 impl<I> Overlay<I>
@@ -88,6 +96,7 @@ where
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
             graph_builder: GraphBuilder::<ShapeCountBoolean, OverlayNode, I>::new(),
+            input_log: None,
         }
     }
 
@@ -104,9 +113,20 @@ where
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
             graph_builder: GraphBuilder::<ShapeCountBoolean, OverlayNode, I>::new(),
+            input_log: None,
         }
     }
 
+    /// Same as [`Self::new`], but the overlay logs every contour added to it, so that
+    /// [`Self::overlay_with_vertex_sources`] can trace the output vertices back to the input.
+    /// Logging keeps a copy of every added point.
+    /// - `capacity`: The initial capacity for storing edge data.
+    pub fn new_tracked(capacity: usize) -> Self {
+        let mut overlay = Self::new(capacity);
+        overlay.input_log = Some(InputLog::default());
+        overlay
+    }
+
     /// Creates a new `Overlay` instance and initializes it with subject and clip contours.
     /// - `subj`: An array of contours that together define the subject.
     /// - `clip`: An array of contours that together define the clip.
@@ -188,6 +208,51 @@ where
         overlay
     }
 
+    /// Same as [`Self::with_contours`], but logs the input as [`Self::new_tracked`] does.
+    /// - `subj`: An array of contours that together define the subject shape.
+    /// - `clip`: An array of contours that together define the clip shape.
+    pub fn with_contours_tracked(subj: &[IntContour<I>], clip: &[IntContour<I>]) -> Self {
+        let mut overlay = Self::new_tracked(subj.points_count() + clip.points_count());
+        overlay.add_contours(subj, ShapeType::Subject);
+        overlay.add_contours(clip, ShapeType::Clip);
+        overlay
+    }
+
+    /// Same as [`Self::with_shapes`], but logs the input as [`Self::new_tracked`] does.
+    /// - `subj`: An array of shapes to be used as the subject in the overlay operation.
+    /// - `clip`: An array of shapes to be used as the clip in the overlay operation.
+    pub fn with_shapes_tracked(subj: &[IntShape<I>], clip: &[IntShape<I>]) -> Self {
+        let mut overlay = Self::new_tracked(subj.points_count() + clip.points_count());
+        overlay.add_shapes(subj, ShapeType::Subject);
+        overlay.add_shapes(clip, ShapeType::Clip);
+        overlay
+    }
+
+    /// Same as [`Self::with_contours`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::CoordinateOutOfRange`] naming the first point whose coordinates are
+    /// outside the safe range of the integer engine.
+    /// - `subj`: An array of contours that together define the subject shape.
+    /// - `clip`: An array of contours that together define the clip shape.
+    pub fn try_with_contours(subj: &[IntContour<I>], clip: &[IntContour<I>]) -> Result<Self, OverlayError> {
+        validate_int_paths(InputRole::Subject, subj.iter().map(|c| c.as_slice()))?;
+        validate_int_paths(InputRole::Clip, clip.iter().map(|c| c.as_slice()))?;
+        Ok(Self::with_contours(subj, clip))
+    }
+
+    /// Same as [`Self::with_shapes`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::CoordinateOutOfRange`] naming the first point whose coordinates are
+    /// outside the safe range of the integer engine. Contours are counted across all shapes of
+    /// an input, in insertion order.
+    /// - `subj`: An array of shapes to be used as the subject in the overlay operation.
+    /// - `clip`: An array of shapes to be used as the clip in the overlay operation.
+    pub fn try_with_shapes(subj: &[IntShape<I>], clip: &[IntShape<I>]) -> Result<Self, OverlayError> {
+        validate_int_paths(InputRole::Subject, subj.iter().flatten().map(|c| c.as_slice()))?;
+        validate_int_paths(InputRole::Clip, clip.iter().flatten().map(|c| c.as_slice()))?;
+        Ok(Self::with_shapes(subj, clip))
+    }
+
     /// Adds a path to the overlay using an iterator, allowing for more flexible path input.
     /// This function is particularly useful when working with dynamically generated paths or
     /// when paths are not directly stored in a collection.
@@ -195,6 +260,16 @@ where
     /// - `shape_type`: Specifies the role of the added path in the overlay operation, either as `Subject` or `Clip`.
     #[inline]
     pub fn add_path_iter<It: Iterator<Item = IntPoint<I>>>(&mut self, iter: It, shape_type: ShapeType) {
+        if let Some(log) = self.input_log.as_mut() {
+            let points: Vec<_> = iter.collect();
+            log.add_contour(shape_type.into(), &points);
+            self.segments.append_path_iter(
+                points.into_iter(),
+                shape_type,
+                self.options.preserve_input_collinear,
+            );
+            return;
+        }
         self.segments
             .append_path_iter(iter, shape_type, self.options.preserve_input_collinear);
     }
@@ -204,6 +279,9 @@ where
     /// - `shape_type`: Specifies the role of the added path in the overlay operation, either as `Subject` or `Clip`.
     #[inline]
     pub fn add_contour(&mut self, contour: &[IntPoint<I>], shape_type: ShapeType) {
+        if let Some(log) = self.input_log.as_mut() {
+            log.add_contour(shape_type.into(), contour);
+        }
         self.segments.append_path_iter(
             contour.iter().copied(),
             shape_type,
@@ -242,6 +320,9 @@ where
     #[inline]
     pub fn clear(&mut self) {
         self.segments.clear();
+        if let Some(log) = self.input_log.as_mut() {
+            log.clear();
+        }
     }
 
     /// Adds multiple flat-shape to the overlay as either subject or clip shapes.
@@ -353,7 +434,173 @@ where
     /// particularly for complex or resource-intensive geometries.
     #[inline]
     pub fn overlay(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> IntShapes<I> {
+        self.split_solver.split_segments(&mut self.segments, &self.solver);
+        self.build_and_extract_shapes(overlay_rule, fill_rule)
+    }
+
+    /// Extracts every winding level of the subject as a separate set of shapes, from a single
+    /// split and fill pass.
+    ///
+    /// The result at index `k - 1` holds the regions covered with a winding number of at least `k`,
+    /// exactly what [`Self::overlay`] returns for [`OverlayRule::Subject`] with
+    /// [`FillRule::WindingAtLeast(k)`](FillRule::WindingAtLeast). Levels go up to the highest
+    /// winding number, so the last level is never empty. Contours in the output direction
+    /// (counterclockwise by default) count as `+1`, opposite contours as `-1`. Clip input is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::core::overlay::{Overlay, ShapeType};
+    ///
+    /// let square = |x: i32| {
+    ///     vec![IntPoint::new(x, 0), IntPoint::new(x + 10, 0), IntPoint::new(x + 10, 10), IntPoint::new(x, 10)]
+    /// };
+    ///
+    /// // three overlapping squares
+    /// let mut overlay = Overlay::new(12);
+    /// for x in [0, 5, 8] {
+    ///     overlay.add_contour(&square(x), ShapeType::Subject);
+    /// }
+    ///
+    /// let levels = overlay.extract_winding_levels();
+    ///
+    /// assert_eq!(levels.len(), 3);
+    /// // the area covered by all three squares
+    /// assert_eq!(levels[2], vec![vec![vec![
+    ///     IntPoint::new(8, 10),
+    ///     IntPoint::new(8, 0),
+    ///     IntPoint::new(10, 0),
+    ///     IntPoint::new(10, 10),
+    /// ]]]);
+    /// ```
+    pub fn extract_winding_levels(&mut self) -> Vec<IntShapes<I>> {
         self.split_solver.split_segments(&mut self.segments, &self.solver);
+        if self.segments.is_empty() {
+            return Vec::new();
+        }
+        let mut buffer = self.boolean_buffer.take().unwrap_or_default();
+        let levels = self.graph_builder.extract_winding_levels(
+            self.options,
+            &self.solver,
+            &self.segments,
+            &mut buffer,
+        );
+        self.boolean_buffer = Some(buffer);
+        levels
+    }
+
+    /// Same as [`Self::overlay`], but also reports how far snapping displaced the output vertices
+    /// from the exact input edges.
+    ///
+    /// Every split point is measured against the input edge it splits, see
+    /// [`displacement`](crate::core::displacement). Tracking the input edges through the split
+    /// costs an extra copy of the segments; the result is identical to [`Self::overlay`].
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule used to determine interior regions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::core::fill_rule::FillRule;
+    /// use i_overlay::core::overlay::Overlay;
+    /// use i_overlay::core::overlay_rule::OverlayRule;
+    ///
+    /// // the diagonals cross at (1.5, 1.5), which is rounded to the grid
+    /// let subj = [IntPoint::new(0, 0), IntPoint::new(3, 3), IntPoint::new(3, 0)];
+    /// let clip = [IntPoint::new(0, 3), IntPoint::new(3, 0), IntPoint::new(0, 0)];
+    /// let mut overlay = Overlay::with_contour(&subj, &clip);
+    ///
+    /// let (shapes, report) = overlay.overlay_with_displacement(OverlayRule::Intersect, FillRule::NonZero);
+    ///
+    /// assert_eq!(shapes.len(), 1);
+    /// assert!(report.max_distance > 0.0 && report.max_distance < 1.0);
+    /// assert!(!report.exceeds(1.0));
+    /// ```
+    pub fn overlay_with_displacement(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> (IntShapes<I>, IntDisplacementReport<I>) {
+        let (shapes, split) = self.overlay_tracking_splits(overlay_rule, fill_rule);
+        let report = DisplacementMap::new(&split, &[]).report(&shapes, |p| *p, |distance| distance);
+        (shapes, report)
+    }
+
+    /// Same as [`Self::overlay`], but also returns the origin of every output vertex.
+    ///
+    /// The sources are parallel to the output: `sources[shape][contour][vertex]` tells whether the
+    /// vertex is an original input vertex, with its contour and index, or an intersection point,
+    /// with the input edges that produced it. Returns `None` for the sources unless the overlay was
+    /// created by [`Self::new_tracked`] or another `*_tracked` constructor.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule used to determine interior regions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::core::error::{InputRole, PointIndex};
+    /// use i_overlay::core::fill_rule::FillRule;
+    /// use i_overlay::core::overlay::{Overlay, ShapeType};
+    /// use i_overlay::core::overlay_rule::OverlayRule;
+    /// use i_overlay::core::provenance::VertexSource;
+    ///
+    /// let subj = [IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10), IntPoint::new(0, 10)];
+    /// let clip = [IntPoint::new(5, 5), IntPoint::new(15, 5), IntPoint::new(15, 15), IntPoint::new(5, 15)];
+    ///
+    /// let mut overlay = Overlay::new_tracked(8);
+    /// overlay.add_contour(&subj, ShapeType::Subject);
+    /// overlay.add_contour(&clip, ShapeType::Clip);
+    ///
+    /// let (shapes, sources) = overlay.overlay_with_vertex_sources(OverlayRule::Intersect, FillRule::NonZero);
+    /// let sources = sources.unwrap();
+    ///
+    /// // the intersection is the square (5, 5) - (10, 10)
+    /// let vertex = shapes[0][0].iter().position(|p| *p == IntPoint::new(10, 5)).unwrap();
+    /// let edge = |role, point| PointIndex { role, contour: 0, point };
+    /// assert_eq!(
+    ///     sources[0][0][vertex],
+    ///     VertexSource::Intersection(vec![edge(InputRole::Subject, 1), edge(InputRole::Clip, 0)])
+    /// );
+    /// ```
+    pub fn overlay_with_vertex_sources(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> (IntShapes<I>, Option<VertexSources>) {
+        if self.input_log.is_none() {
+            return (self.overlay(overlay_rule, fill_rule), None);
+        }
+        let (shapes, split) = self.overlay_tracking_splits(overlay_rule, fill_rule);
+        let sources = self
+            .input_log
+            .as_ref()
+            .map(|log| log.sources(&shapes, &split, |p| *p));
+        (shapes, sources)
+    }
+
+    /// Runs [`Self::overlay`] while recording every split point and the input segment it splits.
+    pub(crate) fn overlay_tracking_splits(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> (IntShapes<I>, SplitSources) {
+        let mut store = SplitSources::default();
+        let mut segments = store.track(&self.segments);
+        self.split_solver
+            .split_segments_with_store(&mut segments, &self.solver, &mut store);
+
+        self.segments.clear();
+        self.segments.extend(segments.iter().map(Segment::without_data));
+
+        let shapes = self.build_and_extract_shapes(overlay_rule, fill_rule);
+        (shapes, store)
+    }
+
+    #[inline]
+    fn build_and_extract_shapes(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> IntShapes<I> {
         if self.segments.is_empty() {
             return Vec::new();
         }
@@ -372,6 +619,74 @@ where
         shapes
     }
 
+    /// Same as [`Self::overlay`], but also returns [`OverlayStats`] of the operation.
+    ///
+    /// The counters cost next to nothing; the phase timings read `clock` at each phase boundary.
+    /// Pass [`NoClock`](crate::core::stats::NoClock) to skip timing.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule used to determine interior regions.
+    /// - `clock`: Time source for the phase timings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::core::fill_rule::FillRule;
+    /// use i_overlay::core::overlay::Overlay;
+    /// use i_overlay::core::overlay_rule::OverlayRule;
+    ///
+    /// let subj = [IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10), IntPoint::new(0, 10)];
+    /// let clip = [IntPoint::new(5, 5), IntPoint::new(15, 5), IntPoint::new(15, 15), IntPoint::new(5, 15)];
+    /// let mut overlay = Overlay::with_contour(&subj, &clip);
+    ///
+    /// let start = Instant::now();
+    /// let (shapes, stats) = overlay.overlay_with_stats(OverlayRule::Union, FillRule::NonZero, &mut || start.elapsed());
+    ///
+    /// assert_eq!(shapes.len(), 1);
+    /// assert_eq!(stats.input_edges, 8);
+    /// assert_eq!(stats.intersections, 4);
+    /// assert_eq!(stats.output_edges, 8);
+    /// ```
+    pub fn overlay_with_stats(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        clock: &mut dyn StatsClock,
+    ) -> (IntShapes<I>, OverlayStats) {
+        let mut stats = OverlayStats {
+            input_edges: self.segments.len(),
+            ..Default::default()
+        };
+        let mut timer = PhaseTimer::start(clock);
+
+        self.split_solver.split_segments(&mut self.segments, &self.solver);
+        stats.set_split(self.split_solver.stats, self.segments.len());
+        stats.split_time = timer.lap();
+        if self.segments.is_empty() {
+            return (Vec::new(), stats);
+        }
+
+        let mut buffer = self.boolean_buffer.take().unwrap_or_default();
+        let graph = self.graph_builder.build_boolean_overlay(
+            fill_rule,
+            overlay_rule,
+            self.options,
+            &self.solver,
+            &self.segments,
+        );
+        stats.nodes = graph.nodes.len();
+        stats.links = graph.links.len();
+        stats.build_time = timer.lap();
+
+        let shapes = graph.extract_shapes(overlay_rule, &mut buffer);
+        self.boolean_buffer = Some(buffer);
+        stats.output_edges = shapes.points_count();
+        stats.extract_time = timer.lap();
+
+        (shapes, stats)
+    }
+
     /// Executes a Boolean operation and returns flat shapes together with their
     /// immediate nesting relationships.
     ///
@@ -435,6 +750,187 @@ where
             .extract_contours_into(overlay_rule, &mut buffer, output);
         self.boolean_buffer = Some(buffer);
     }
+
+    /// Allocation-fallible variant of [`Self::overlay_into`] for targets where an allocation
+    /// failure must not abort the process.
+    ///
+    /// The working buffers of the split, graph-build and extract phases, as well as `output`, are
+    /// grown with `try_reserve` and charged against `budget`. The first refused allocation stops
+    /// the operation and is reported as [`OverlayError::OutOfMemory`] or
+    /// [`OverlayError::MemoryBudgetExceeded`]. The input is not checked here; create the overlay
+    /// with a `try_with_*` constructor to validate it.
+    ///
+    /// The operation runs single-threaded. Not covered are the search trees of the sweep line and
+    /// of the tree split strategy and the small bucket tables of the radix sort; their size grows
+    /// with the number of segments but stays well below the covered buffers.
+    ///
+    /// After an error the overlay keeps its segments in a valid state, so the call can be repeated
+    /// with a larger budget.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule used to determine interior regions.
+    /// - `budget`: Limit on the memory the operation may allocate.
+    /// - `output`: Destination [`FlatContoursBuffer<I>`] that receives resulting contours.
+    ///   Existing buffer contents are replaced; on error its contents are unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::core::error::OverlayError;
+    /// use i_overlay::core::fill_rule::FillRule;
+    /// use i_overlay::core::memory::MemoryBudget;
+    /// use i_overlay::core::overlay::Overlay;
+    /// use i_overlay::core::overlay_rule::OverlayRule;
+    /// use i_overlay::i_shape::flat::buffer::FlatContoursBuffer;
+    ///
+    /// let subj = [IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10), IntPoint::new(0, 10)];
+    /// let clip = [IntPoint::new(5, 5), IntPoint::new(15, 5), IntPoint::new(15, 15), IntPoint::new(5, 15)];
+    ///
+    /// let mut overlay = Overlay::with_contour(&subj, &clip);
+    /// let mut output = FlatContoursBuffer::default();
+    ///
+    /// let result = overlay.try_overlay_into(
+    ///     OverlayRule::Union,
+    ///     FillRule::NonZero,
+    ///     MemoryBudget::with_max_bytes(16),
+    ///     &mut output,
+    /// );
+    /// assert_eq!(result, Err(OverlayError::MemoryBudgetExceeded));
+    ///
+    /// overlay
+    ///     .try_overlay_into(OverlayRule::Union, FillRule::NonZero, MemoryBudget::UNLIMITED, &mut output)
+    ///     .unwrap();
+    /// assert_eq!(output.ranges.len(), 1);
+    /// ```
+    pub fn try_overlay_into(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        budget: MemoryBudget,
+        output: &mut FlatContoursBuffer<I>,
+    ) -> Result<(), OverlayError> {
+        let solver = Solver {
+            multithreading: None,
+            ..self.solver
+        };
+
+        self.split_solver.memory = MemoryGuard::fallible(budget);
+        self.split_solver.split_segments(&mut self.segments, &solver);
+        let mut memory = core::mem::take(&mut self.split_solver.memory);
+        memory.result()?;
+
+        if self.segments.is_empty() {
+            output.points.clear();
+            output.ranges.clear();
+            return Ok(());
+        }
+
+        let mut buffer = self.boolean_buffer.take().unwrap_or_default();
+        let is_extracted = self
+            .graph_builder
+            .try_build_boolean_overlay(
+                &mut memory,
+                &mut ProgressTracker::none(),
+                fill_rule,
+                overlay_rule,
+                self.options,
+                &solver,
+                &self.segments,
+            )
+            .is_some_and(|graph| {
+                graph.try_extract_contours_into(overlay_rule, &mut buffer, output, &mut memory)
+            });
+        self.boolean_buffer = Some(buffer);
+
+        debug_assert_eq!(is_extracted, !memory.is_failed());
+        memory.result()
+    }
+
+    /// Same as [`Self::overlay`], but reports progress to `control` and lets it cancel the
+    /// operation.
+    ///
+    /// `control` is polled at the start of every phase and periodically in the split loops, the
+    /// fill sweep of the graph build and the contour extraction. A cancelled operation returns
+    /// [`OverlayError::Cancelled`]; the overlay keeps its segments in a valid state, so the call
+    /// can be repeated. With multithreading enabled, the fragment split is polled between its
+    /// passes only.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule used to determine interior regions.
+    /// - `control`: Receives [`OperationProgress`](crate::core::control::OperationProgress)
+    ///   updates and may cancel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::ops::ControlFlow;
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::core::control::{OperationPhase, OperationProgress};
+    /// use i_overlay::core::error::OverlayError;
+    /// use i_overlay::core::fill_rule::FillRule;
+    /// use i_overlay::core::overlay::Overlay;
+    /// use i_overlay::core::overlay_rule::OverlayRule;
+    ///
+    /// let subj = [IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10), IntPoint::new(0, 10)];
+    /// let clip = [IntPoint::new(5, 5), IntPoint::new(15, 5), IntPoint::new(15, 15), IntPoint::new(5, 15)];
+    /// let mut overlay = Overlay::with_contour(&subj, &clip);
+    ///
+    /// // cancel as soon as the graph build starts
+    /// let mut cancel = |progress: OperationProgress| match progress.phase {
+    ///     OperationPhase::Split => ControlFlow::Continue(()),
+    ///     _ => ControlFlow::Break(()),
+    /// };
+    /// let result = overlay.try_overlay_with_control(OverlayRule::Union, FillRule::NonZero, &mut cancel);
+    /// assert_eq!(result, Err(OverlayError::Cancelled));
+    ///
+    /// let mut phases = Vec::new();
+    /// let mut record = |progress: OperationProgress| {
+    ///     phases.push(progress.phase);
+    ///     ControlFlow::Continue(())
+    /// };
+    /// let shapes = overlay
+    ///     .try_overlay_with_control(OverlayRule::Union, FillRule::NonZero, &mut record)
+    ///     .unwrap();
+    /// assert_eq!(shapes.len(), 1);
+    /// assert_eq!(phases.last(), Some(&OperationPhase::Extract));
+    /// ```
+    pub fn try_overlay_with_control(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        control: &mut dyn OperationControl,
+    ) -> Result<IntShapes<I>, OverlayError> {
+        let mut progress = ProgressTracker::new(control);
+        self.split_solver.split_segments_with_progress(
+            &mut self.segments,
+            &self.solver,
+            &mut (),
+            &mut progress,
+        );
+        if progress.is_cancelled() {
+            return Err(OverlayError::Cancelled);
+        }
+        if self.segments.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut memory = MemoryGuard::default();
+        let mut buffer = self.boolean_buffer.take().unwrap_or_default();
+        let shapes = self
+            .graph_builder
+            .try_build_boolean_overlay(
+                &mut memory,
+                &mut progress,
+                fill_rule,
+                overlay_rule,
+                self.options,
+                &self.solver,
+                &self.segments,
+            )
+            .and_then(|graph| graph.extract_shapes_with_progress(overlay_rule, &mut buffer, &mut progress));
+        self.boolean_buffer = Some(buffer);
+
+        shapes.ok_or(OverlayError::Cancelled)
+    }
 }
 
 impl<U: UIntNumber> Default for IntOverlayOptions<U> {
@@ -1,4 +1,7 @@
-use crate::segm::segment::{BOTH_BOTTOM, BOTH_TOP, CLIP_TOP, NONE, SUBJ_TOP, SegmentFill};
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::{
+    BOTH_BOTTOM, BOTH_TOP, CLIP_BOTTOM, CLIP_TOP, NONE, SUBJ_BOTTOM, SUBJ_TOP, SegmentFill,
+};
 use core::fmt;
 
 /// Defines the types of overlay/boolean operations that can be applied to shapes. For a visual description, see [Overlay Rules](https://ishape-rust.github.io/iShape-js/overlay/overlay_rules/overlay_rules.html).
@@ -9,6 +12,7 @@ use core::fmt;
 /// - `Difference`: Subtracts the area of the clip shape from the subject shape, removing the clip shape's area from the subject.
 /// - `InverseDifference`: Subtracts the area of the subject shape from the clip shape, removing the subject shape's area from the clip.
 /// - `Xor`: Produces a shape consisting of areas unique to each shape, excluding any parts where the subject and clip overlap.
+/// - `Custom`: Keeps the regions accepted by a user predicate, see [`CustomRule`].
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OverlayRule {
     Subject,
@@ -18,11 +22,132 @@ pub enum OverlayRule {
     Difference,
     InverseDifference,
     Xor,
+    Custom(CustomRule),
+}
+
+/// State of a region on one side of an edge, as seen by a [`CustomRule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionFill {
+    /// The region is inside the subject by the fill rule.
+    pub subj: bool,
+    /// The region is inside the clip by the fill rule.
+    pub clip: bool,
+    /// Subject winding number of the region, before the fill rule is applied.
+    pub subj_winding: i32,
+    /// Clip winding number of the region, before the fill rule is applied.
+    pub clip_winding: i32,
+}
+
+impl RegionFill {
+    /// The unbounded region around all the input.
+    pub const OUTSIDE: Self = Self {
+        subj: false,
+        clip: false,
+        subj_winding: 0,
+        clip_winding: 0,
+    };
+
+    #[inline(always)]
+    fn top(fill: SegmentFill, top: ShapeCountBoolean) -> Self {
+        Self {
+            subj: fill & SUBJ_TOP != 0,
+            clip: fill & CLIP_TOP != 0,
+            subj_winding: top.subj,
+            clip_winding: top.clip,
+        }
+    }
+
+    #[inline(always)]
+    fn bottom(fill: SegmentFill, bottom: ShapeCountBoolean) -> Self {
+        Self {
+            subj: fill & SUBJ_BOTTOM != 0,
+            clip: fill & CLIP_BOTTOM != 0,
+            subj_winding: bottom.subj,
+            clip_winding: bottom.clip,
+        }
+    }
+}
+
+/// User predicate for [`OverlayRule::Custom`], deciding whether a region belongs to the result.
+///
+/// The predicate is evaluated for the regions on the top and the bottom side of every edge; the edge
+/// is part of the result boundary when exactly one side is accepted. The region carries both the fill
+/// state given by the fill rule and the raw winding numbers, so a rule can tell a negative-wound
+/// region from a positive-wound one whatever the fill rule is.
+///
+/// A predicate accepting [`RegionFill::OUTSIDE`] would keep the unbounded region, which has no
+/// contour, and is rejected by [`CustomRule::new`].
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay_rule::{CustomRule, OverlayRule, RegionFill};
+/// use i_overlay::float::single::SingleFloatOverlay;
+///
+/// // subject minus clip, but the negative-wound parts of the clip do not cut
+/// let rule = CustomRule::new(|fill: RegionFill| fill.subj && fill.clip_winding <= 0).unwrap();
+///
+/// let subj = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+/// let clip = [[2.0, 0.0], [6.0, 0.0], [6.0, 4.0], [2.0, 4.0]];
+///
+/// let custom = subj.overlay(&clip, OverlayRule::Custom(rule), FillRule::NonZero);
+/// let difference = subj.overlay(&clip, OverlayRule::Difference, FillRule::NonZero);
+/// assert_eq!(custom, difference);
+/// ```
+#[derive(Clone, Copy)]
+pub struct CustomRule {
+    predicate: fn(RegionFill) -> bool,
+}
+
+impl CustomRule {
+    /// Creates a rule that keeps the regions for which `predicate` returns `true`.
+    ///
+    /// Returns `None` if `predicate` accepts [`RegionFill::OUTSIDE`]. Any closure without captures
+    /// coerces to the `fn` pointer.
+    #[inline]
+    pub fn new(predicate: fn(RegionFill) -> bool) -> Option<Self> {
+        if predicate(RegionFill::OUTSIDE) {
+            return None;
+        }
+        Some(Self { predicate })
+    }
+
+    /// Whether the region on the top side of an edge with `fill` and the top winding `top` is accepted.
+    #[inline(always)]
+    pub(crate) fn is_fill_top(&self, fill: SegmentFill, top: ShapeCountBoolean) -> bool {
+        (self.predicate)(RegionFill::top(fill, top))
+    }
+
+    /// Whether an edge separates an accepted region from a rejected one, with `windings` holding
+    /// the top and the bottom winding of the edge.
+    #[inline(always)]
+    pub(crate) fn is_included(&self, fill: SegmentFill, windings: [ShapeCountBoolean; 2]) -> bool {
+        let top = self.is_fill_top(fill, windings[0]);
+        let bottom = (self.predicate)(RegionFill::bottom(fill, windings[1]));
+        top != bottom
+    }
+}
+
+/// Two rules are equal when they refer to the same predicate.
+impl PartialEq for CustomRule {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::fn_addr_eq(self.predicate, other.predicate)
+    }
+}
+
+impl fmt::Debug for CustomRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CustomRule")
+    }
 }
 
 impl OverlayRule {
+    /// Whether the region on the top side of an edge is part of the result; `top` is the winding
+    /// of that region, only used by [`OverlayRule::Custom`].
     #[inline(always)]
-    pub(crate) fn is_fill_top(&self, fill: SegmentFill) -> bool {
+    pub(crate) fn is_fill_top(&self, fill: SegmentFill, top: ShapeCountBoolean) -> bool {
         match self {
             OverlayRule::Subject => fill & SUBJ_TOP == SUBJ_TOP,
             OverlayRule::Clip => fill & CLIP_TOP == CLIP_TOP,
@@ -35,6 +160,7 @@ impl OverlayRule {
                 let is_clip = fill & BOTH_TOP == CLIP_TOP;
                 is_subject || is_clip
             }
+            OverlayRule::Custom(rule) => rule.is_fill_top(fill, top),
         }
     }
 }
@@ -49,6 +175,7 @@ impl fmt::Display for OverlayRule {
             OverlayRule::Difference => "Difference",
             OverlayRule::InverseDifference => "InverseDifference",
             OverlayRule::Xor => "Xor",
+            OverlayRule::Custom(_) => "Custom",
         };
 
         write!(f, "{}", text)
@@ -0,0 +1,421 @@
+//! Provenance of output vertices.
+//!
+//! An overlay created by [`Overlay::new_tracked`](crate::core::overlay::Overlay::new_tracked) or
+//! another `*_tracked` constructor logs the vertices of every added contour, and
+//! [`Overlay::overlay_with_vertex_sources`](crate::core::overlay::Overlay::overlay_with_vertex_sources)
+//! returns a [`VertexSource`] for every output vertex, in the same nesting as the output shapes.
+//!
+//! The same log carries per-vertex attributes such as elevation or measure through an operation,
+//! see [`FloatOverlay::overlay_with_attributes`](crate::float::overlay::FloatOverlay::overlay_with_attributes).
+
+use crate::core::error::{InputRole, PointIndex};
+use crate::split::source::{GridPoint, SplitSources, grid_point};
+use alloc::vec::Vec;
+use core::ops::Range;
+use i_float::float::number::FloatNumber;
+use i_float::int::number::int::IntNumber;
+use i_float::int::point::IntPoint;
+
+/// Origin of an output vertex.
+///
+/// Input edges are identified by the [`PointIndex`] of their first point; edge `i` of a contour
+/// runs from point `i` to point `i + 1`, the last one back to the first point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VertexSource {
+    /// An input vertex, kept in place. If several input vertices share the position, the first one
+    /// in input order.
+    Input(PointIndex),
+    /// A point created where input edges intersect. Lists every input edge through the point,
+    /// sorted and without duplicates. A point that snapping placed near an input edge counts as on
+    /// that edge.
+    Intersection(Vec<PointIndex>),
+}
+
+/// Vertex sources parallel to the output shapes: `sources[shape][contour][vertex]`.
+pub type VertexSources = Vec<Vec<Vec<VertexSource>>>;
+
+/// Per-vertex attributes parallel to the output shapes: `attributes[shape][contour][vertex]`.
+pub type ShapeAttributes<T, const N: usize> = Vec<Vec<Vec<[T; N]>>>;
+
+/// Per-vertex attributes parallel to the output paths: `attributes[path][vertex]`.
+pub type PathAttributes<T, const N: usize> = Vec<Vec<[T; N]>>;
+
+#[derive(Debug, Clone, Copy)]
+struct LoggedEdge {
+    a: GridPoint,
+    b: GridPoint,
+    start: PointIndex,
+    end: PointIndex,
+    // `a` is the position of `start`
+    forward: bool,
+}
+
+/// Vertices and edges of the added contours.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InputLog {
+    vertices: Vec<(GridPoint, PointIndex)>,
+    edges: Vec<LoggedEdge>,
+    contours: [usize; 3],
+    // input vertices moved by the float to grid rounding, with the distance in grid units
+    rounding: Vec<(GridPoint, f64)>,
+}
+
+impl InputLog {
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+        self.contours = [0; 3];
+        self.rounding.clear();
+    }
+
+    /// Logs an input vertex that rounding to the grid moved by `distance` grid units.
+    #[inline]
+    pub(crate) fn add_rounding<I: IntNumber>(&mut self, p: IntPoint<I>, distance: f64) {
+        self.rounding.push((grid_point(p), distance));
+    }
+
+    #[inline]
+    pub(crate) fn rounding(&self) -> &[(GridPoint, f64)] {
+        &self.rounding
+    }
+
+    #[inline]
+    pub(crate) fn add_contour<I: IntNumber>(&mut self, role: InputRole, points: &[IntPoint<I>]) {
+        self.add_path(role, points, true);
+    }
+
+    /// Logs a path; a `closed` path also gets the edge from its last point back to the first.
+    pub(crate) fn add_path<I: IntNumber>(&mut self, role: InputRole, points: &[IntPoint<I>], closed: bool) {
+        let contour = self.contours[role as usize];
+        self.contours[role as usize] = contour + 1;
+        let start = self.vertices.len();
+        for (point, p) in points.iter().enumerate() {
+            let index = PointIndex { role, contour, point };
+            self.vertices.push((grid_point(*p), index));
+        }
+
+        let vertices = &self.vertices[start..];
+        let count = if closed {
+            vertices.len()
+        } else {
+            vertices.len().saturating_sub(1)
+        };
+        for i in 0..count {
+            let (a, start) = vertices[i];
+            let (b, end) = vertices[(i + 1) % vertices.len()];
+            if a == b {
+                continue;
+            }
+            let forward = a < b;
+            let (a, b) = if forward { (a, b) } else { (b, a) };
+            self.edges.push(LoggedEdge {
+                a,
+                b,
+                start,
+                end,
+                forward,
+            });
+        }
+    }
+
+    /// Resolves the sources of every vertex of `shapes`. `project` maps an output point to the
+    /// integer grid.
+    pub(crate) fn sources<P, I: IntNumber>(
+        &self,
+        shapes: &[Vec<Vec<P>>],
+        split: &SplitSources,
+        project: impl Fn(&P) -> IntPoint<I>,
+    ) -> VertexSources {
+        let lookup = SourceLookup::new(self, split);
+        shapes
+            .iter()
+            .map(|shape| {
+                shape
+                    .iter()
+                    .map(|contour| {
+                        contour
+                            .iter()
+                            .map(|point| lookup.source(grid_point(project(point))))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Interpolates the attributes of every vertex of `shapes`, see [`Self::path_attributes`].
+    pub(crate) fn shape_attributes<P, I: IntNumber, T: FloatNumber, const N: usize>(
+        &self,
+        shapes: &[Vec<Vec<P>>],
+        split: &SplitSources,
+        project: impl Fn(&P) -> IntPoint<I>,
+        attributes: impl Fn(PointIndex) -> [T; N],
+    ) -> ShapeAttributes<T, N> {
+        let lookup = SourceLookup::new(self, split);
+        shapes
+            .iter()
+            .map(|shape| {
+                shape
+                    .iter()
+                    .map(|contour| {
+                        contour
+                            .iter()
+                            .map(|point| lookup.attributes(grid_point(project(point)), &attributes))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Interpolates the attributes of every vertex of `paths`. An input vertex keeps its attributes,
+    /// averaged if several input vertices share the position. A new vertex is interpolated along
+    /// every input edge through it and the results are averaged.
+    pub(crate) fn path_attributes<P, I: IntNumber, T: FloatNumber, const N: usize>(
+        &self,
+        paths: &[Vec<P>],
+        split: &SplitSources,
+        project: impl Fn(&P) -> IntPoint<I>,
+        attributes: impl Fn(PointIndex) -> [T; N],
+    ) -> PathAttributes<T, N> {
+        let lookup = SourceLookup::new(self, split);
+        paths
+            .iter()
+            .map(|path| {
+                path.iter()
+                    .map(|point| lookup.attributes(grid_point(project(point)), &attributes))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The log and the split points of one operation, sorted for lookup by position.
+struct SourceLookup<'a> {
+    vertices: Vec<(GridPoint, PointIndex)>,
+    edges: Vec<LoggedEdge>,
+    splits: Vec<(GridPoint, usize)>,
+    sources: &'a [[GridPoint; 2]],
+}
+
+impl<'a> SourceLookup<'a> {
+    fn new(log: &InputLog, split: &'a SplitSources) -> Self {
+        let mut vertices = log.vertices.clone();
+        vertices.sort_unstable_by(|v0, v1| v0.0.cmp(&v1.0).then(order(&v0.1).cmp(&order(&v1.1))));
+
+        let mut edges = log.edges.clone();
+        edges.sort_unstable_by_key(|e| e.a);
+
+        let mut splits = split.splits.clone();
+        splits.sort_unstable();
+        splits.dedup();
+
+        Self {
+            vertices,
+            edges,
+            splits,
+            sources: &split.sources,
+        }
+    }
+
+    /// Input vertices at `p`, in input order.
+    #[inline]
+    fn inputs(&self, p: GridPoint) -> &[(GridPoint, PointIndex)] {
+        let start = self.vertices.partition_point(|v| v.0 < p);
+        let end = start + self.vertices[start..].partition_point(|v| v.0 == p);
+        &self.vertices[start..end]
+    }
+
+    /// Input edges through `p`, sorted by their first point and without duplicates.
+    fn edges_through(&self, p: GridPoint) -> Vec<LoggedEdge> {
+        let mut result = Vec::new();
+        for &(_, source) in &self.splits[equal_range(&self.splits, p)] {
+            collect_edges(&self.edges, self.sources[source], p, &mut result);
+        }
+        result.sort_unstable_by_key(|e| order(&e.start));
+        result.dedup_by_key(|e| e.start);
+        result
+    }
+
+    fn source(&self, p: GridPoint) -> VertexSource {
+        if let Some(&(_, index)) = self.inputs(p).first() {
+            return VertexSource::Input(index);
+        }
+        let edges = self.edges_through(p);
+        VertexSource::Intersection(edges.iter().map(|e| e.start).collect())
+    }
+
+    fn attributes<T: FloatNumber, const N: usize>(
+        &self,
+        p: GridPoint,
+        attributes: &impl Fn(PointIndex) -> [T; N],
+    ) -> [T; N] {
+        let mut sum = [0.0; N];
+        let mut count = 0;
+
+        for &(_, index) in self.inputs(p) {
+            add(&mut sum, attributes(index), 1.0);
+            count += 1;
+        }
+
+        if count == 0 {
+            for edge in self.edges_through(p) {
+                let (s, e) = if edge.forward {
+                    (edge.a, edge.b)
+                } else {
+                    (edge.b, edge.a)
+                };
+                let dir = sub(e, s);
+                let t = (dot(sub(p, s), dir) as f64 / dot(dir, dir) as f64).clamp(0.0, 1.0);
+                add(&mut sum, attributes(edge.start), 1.0 - t);
+                add(&mut sum, attributes(edge.end), t);
+                count += 1;
+            }
+        }
+
+        if count == 0 {
+            // not expected, but keep the output complete: use the closest input vertex
+            let closest = self.vertices.iter().min_by_key(|v| {
+                let d = sub(v.0, p);
+                dot(d, d)
+            });
+            if let Some(&(_, index)) = closest {
+                add(&mut sum, attributes(index), 1.0);
+                count = 1;
+            }
+        }
+
+        let inv = if count > 0 { 1.0 / count as f64 } else { 0.0 };
+        sum.map(|s| T::from_float(s * inv))
+    }
+}
+
+#[inline]
+fn order(index: &PointIndex) -> (usize, usize, usize) {
+    (index.role as usize, index.contour, index.point)
+}
+
+#[inline]
+fn add<T: FloatNumber, const N: usize>(sum: &mut [f64; N], values: [T; N], weight: f64) {
+    for (s, v) in sum.iter_mut().zip(values) {
+        *s += v.to_f64() * weight;
+    }
+}
+
+#[inline]
+fn equal_range(splits: &[(GridPoint, usize)], p: GridPoint) -> Range<usize> {
+    let start = splits.partition_point(|s| s.0 < p);
+    let end = start + splits[start..].partition_point(|s| s.0 == p);
+    start..end
+}
+
+/// Adds the logged edges that lie on the input segment `[a, b]` and whose extent along it
+/// contains the projection of `p`.
+fn collect_edges(edges: &[LoggedEdge], [a, b]: [GridPoint; 2], p: GridPoint, result: &mut Vec<LoggedEdge>) {
+    // collinear edges within the segment start between its ends
+    let start = edges.partition_point(|e| e.a < a);
+    let end = edges.partition_point(|e| e.a < b);
+
+    let dir = sub(b, a);
+    let t = dot(sub(p, a), dir);
+    for edge in edges[start..end].iter() {
+        if cross(dir, sub(edge.a, a)) != 0 || cross(dir, sub(edge.b, a)) != 0 {
+            continue;
+        }
+        let ta = dot(sub(edge.a, a), dir);
+        let tb = dot(sub(edge.b, a), dir);
+        if ta <= t && t <= tb {
+            result.push(*edge);
+        }
+    }
+}
+
+#[inline]
+fn sub(p0: GridPoint, p1: GridPoint) -> [i128; 2] {
+    [p0[0] as i128 - p1[0] as i128, p0[1] as i128 - p1[1] as i128]
+}
+
+#[inline]
+fn dot(v0: [i128; 2], v1: [i128; 2]) -> i128 {
+    v0[0] * v1[0] + v0[1] * v1[1]
+}
+
+#[inline]
+fn cross(v0: [i128; 2], v1: [i128; 2]) -> i128 {
+    v0[0] * v1[1] - v0[1] * v1[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_edges() {
+        let mut log = InputLog::default();
+        let contour = [
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+        ];
+        log.add_contour(InputRole::Clip, &contour);
+
+        assert_eq!(log.vertices.len(), 4);
+        let starts: Vec<usize> = log.edges.iter().map(|e| e.start.point).collect();
+        assert_eq!(starts, [0, 2, 3]);
+        assert_eq!(log.edges[1].b, [10, 10]);
+        assert_eq!(log.edges[2].a, [0, 0]);
+        assert!(!log.edges[2].forward);
+        assert_eq!(log.edges[2].end.point, 0);
+    }
+
+    #[test]
+    fn test_log_open_path() {
+        let mut log = InputLog::default();
+        let path = [IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10)];
+        log.add_path(InputRole::String, &path, false);
+
+        assert_eq!(log.edges.len(), 2);
+        assert_eq!(log.edges[1].end.point, 2);
+
+        log.add_path(InputRole::String, &path, false);
+        assert_eq!(log.edges[3].start.contour, 1);
+    }
+
+    #[test]
+    fn test_collect_collinear() {
+        let mut log = InputLog::default();
+        let contour = [
+            IntPoint::new(0, 0),
+            IntPoint::new(4, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(5, 5),
+        ];
+        log.add_contour(InputRole::Subject, &contour);
+        log.edges.sort_unstable_by_key(|e| e.a);
+
+        // the merged input segment (0, 0) - (10, 0) covers the first two edges
+        let mut result = Vec::new();
+        collect_edges(&log.edges, [[0, 0], [10, 0]], [7, 1], &mut result);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].start.point, 1);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let mut log = InputLog::default();
+        let path = [IntPoint::new(10, 0), IntPoint::new(0, 0)];
+        log.add_path(InputRole::String, &path, false);
+
+        let split = SplitSources {
+            sources: alloc::vec![[[0, 0], [10, 0]]],
+            splits: alloc::vec![([4, 0], 0)],
+        };
+        let lookup = SourceLookup::new(&log, &split);
+        let z = |index: PointIndex| [index.point as f64 * 10.0];
+
+        assert_eq!(lookup.attributes([4, 0], &z), [6.0]);
+        assert_eq!(lookup.attributes([0, 0], &z), [10.0]);
+    }
+}
@@ -158,6 +158,14 @@ where
                     ContourFillDirection::Empty
                 }
             }
+            FillRule::WindingAtLeast(k) => {
+                // a single contour never winds more than once
+                if k <= 1 && contour_clockwise == output_clockwise {
+                    ContourFillDirection::Correct
+                } else {
+                    ContourFillDirection::Empty
+                }
+            }
         }
     }
 
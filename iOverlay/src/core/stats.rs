@@ -0,0 +1,134 @@
+//! Opt-in statistics of a single overlay operation.
+//!
+//! [`Overlay::overlay_with_stats`](crate::core::overlay::Overlay::overlay_with_stats) and its float
+//! and string counterparts return an [`OverlayStats`] next to the result. The counters are cheap and
+//! collected on every run; the per-phase timing reads a caller-provided [`StatsClock`] because the
+//! crate itself has no access to a system clock.
+
+use crate::core::solver::Strategy;
+use core::time::Duration;
+
+/// Counters and timings of one overlay operation.
+/// - `input_edges`: Edges added to the overlay. Collinear input points are dropped when added unless
+///   `preserve_input_collinear` is set.
+/// - `split_edges`: Edges after splitting at all intersections.
+/// - `output_edges`: Edges of the resulting contours or paths.
+/// - `intersections`: Split points found on edges, summed over all split passes. A proper crossing of
+///   two edges splits both of them and counts twice.
+/// - `snap_escalations`: Split passes that ran with an enlarged snap radius, see [`Precision`](crate::core::solver::Precision).
+/// - `split_strategy`: Strategy of the last split pass; `Strategy::Auto` resolves to one of `List`,
+///   `Tree` or `Frag`. `None` if no split pass was needed.
+/// - `nodes`: Nodes of the built graph.
+/// - `links`: Links of the built graph.
+/// - `split_time`, `build_time`, `extract_time`: Time spent in each phase, measured with the
+///   [`StatsClock`] of the call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OverlayStats {
+    pub input_edges: usize,
+    pub split_edges: usize,
+    pub output_edges: usize,
+    pub intersections: usize,
+    pub snap_escalations: usize,
+    pub split_strategy: Option<Strategy>,
+    pub nodes: usize,
+    pub links: usize,
+    pub split_time: Duration,
+    pub build_time: Duration,
+    pub extract_time: Duration,
+}
+
+impl OverlayStats {
+    /// Time spent in all phases.
+    #[inline]
+    pub fn total_time(&self) -> Duration {
+        self.split_time + self.build_time + self.extract_time
+    }
+
+    #[inline]
+    pub(crate) fn set_split(&mut self, split: SplitStats, split_edges: usize) {
+        self.split_edges = split_edges;
+        self.intersections = split.intersections;
+        self.snap_escalations = split.snap_escalations;
+        self.split_strategy = split.strategy;
+    }
+}
+
+/// Time source for the phase timings of [`OverlayStats`].
+///
+/// Returns the time elapsed since an arbitrary but fixed origin. Closures `FnMut() -> Duration`
+/// implement it, so with `std` a clock is `|| start.elapsed()` for some `start: Instant`.
+/// Use [`NoClock`] to skip timing.
+pub trait StatsClock {
+    fn now(&mut self) -> Duration;
+}
+
+impl<F: FnMut() -> Duration> StatsClock for F {
+    #[inline]
+    fn now(&mut self) -> Duration {
+        self()
+    }
+}
+
+/// A clock that always reads zero; all phase timings stay zero.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoClock;
+
+impl StatsClock for NoClock {
+    #[inline]
+    fn now(&mut self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// Split counters kept by the split solver. Reset at the start of every split.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SplitStats {
+    pub(crate) intersections: usize,
+    pub(crate) snap_escalations: usize,
+    pub(crate) strategy: Option<Strategy>,
+}
+
+/// Reads a [`StatsClock`] at phase boundaries.
+pub(crate) struct PhaseTimer<'a> {
+    clock: &'a mut dyn StatsClock,
+    last: Duration,
+}
+
+impl<'a> PhaseTimer<'a> {
+    #[inline]
+    pub(crate) fn start(clock: &'a mut dyn StatsClock) -> Self {
+        let last = clock.now();
+        Self { clock, last }
+    }
+
+    /// Returns the time since the previous lap or the start.
+    #[inline]
+    pub(crate) fn lap(&mut self) -> Duration {
+        let now = self.clock.now();
+        let elapsed = now.saturating_sub(self.last);
+        self.last = now;
+        elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_timer() {
+        let mut ticks = 0;
+        let mut clock = || {
+            ticks += 5;
+            Duration::from_millis(ticks)
+        };
+
+        let mut timer = PhaseTimer::start(&mut clock);
+        assert_eq!(timer.lap(), Duration::from_millis(5));
+        assert_eq!(timer.lap(), Duration::from_millis(5));
+
+        let mut no_clock = NoClock;
+        let mut timer = PhaseTimer::start(&mut no_clock);
+        assert_eq!(timer.lap(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,585 @@
+//! Automatic integer-engine selection for the float API.
+//!
+//! The float adapters map input coordinates onto an integer grid whose step depends on the input
+//! bounds and on the integer engine (`i16`, `i32` or `i64`). A wider engine gives a finer grid but
+//! costs more memory and time. The `*_auto` methods inspect the input and pick the smallest engine
+//! whose grid satisfies the requested [`AutoScaleOptions`], then report the choice as an
+//! [`EngineSelection`].
+//!
+//! # Precision guarantee
+//!
+//! The selected grid step is `1 / scale`. Every input vertex is stored on that grid, so it moves by
+//! at most [`EngineSelection::max_error`] (`0.5 / scale`) along each axis. Vertices created by
+//! intersections may be snapped further by the solver [`Precision`](crate::core::solver::Precision).
+
+use crate::core::fill_rule::FillRule;
+use crate::core::integer::OverlayInt;
+use crate::core::overlay_rule::OverlayRule;
+use crate::float::overlay::FloatOverlay;
+use crate::float::relate::FloatPredicateOverlay;
+use crate::float::string_overlay::FloatStringOverlay;
+use crate::string::clip::ClipRule;
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+use i_float::int::number::int::IntNumber;
+use i_shape::base::data::{Paths, Shapes};
+use i_shape::source::resource::ShapeResource;
+
+/// Integer engine used to run an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IntEngine {
+    I16,
+    I32,
+    I64,
+}
+
+impl IntEngine {
+    /// All engines ordered from the smallest to the widest.
+    pub const ALL: [IntEngine; 3] = [IntEngine::I16, IntEngine::I32, IntEngine::I64];
+
+    /// Bit width of the engine integer type.
+    #[inline]
+    pub fn bits(&self) -> u32 {
+        match self {
+            IntEngine::I16 => i16::BITS,
+            IntEngine::I32 => i32::BITS,
+            IntEngine::I64 => i64::BITS,
+        }
+    }
+}
+
+/// Requirements used to select an integer engine automatically.
+#[derive(Debug, Clone, Copy)]
+pub struct AutoScaleOptions<F: FloatNumber> {
+    /// Required absolute precision in input units. When set, the selected engine must place every
+    /// input vertex within this distance along each axis.
+    pub precision: Option<F>,
+
+    /// Minimum number of grid steps a representative small feature must span.
+    pub feature_resolution: F,
+
+    /// Quantile of the non-zero edge length distribution used as the representative small
+    /// feature size. `0.0` uses the shortest edge, `0.5` the median edge.
+    pub feature_quantile: F,
+}
+
+/// The integer engine and scale chosen by an automatic operation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineSelection<F: FloatNumber> {
+    /// Selected integer engine.
+    pub engine: IntEngine,
+
+    /// Float-to-integer scale: `x_int = (x_float - offset_x) * scale`.
+    pub scale: F,
+
+    /// Maximum distance an input vertex moves along each axis, `0.5 / scale`.
+    pub max_error: F,
+
+    /// Representative small feature size measured on the input, if it has any non-zero edge.
+    pub feature_size: Option<F>,
+
+    /// `false` if even the widest engine could not satisfy the requested options.
+    /// The widest engine is selected in that case.
+    pub is_satisfied: bool,
+}
+
+/// The result of an automatic operation together with the engine it ran on.
+#[derive(Debug, Clone)]
+pub struct AutoOutput<T, F: FloatNumber> {
+    pub result: T,
+    pub selection: EngineSelection<F>,
+}
+
+impl<F: FloatNumber> Default for AutoScaleOptions<F> {
+    fn default() -> Self {
+        Self {
+            precision: None,
+            feature_resolution: F::from_float(1024.0),
+            feature_quantile: F::from_float(0.05),
+        }
+    }
+}
+
+impl<F: FloatNumber> AutoScaleOptions<F> {
+    /// Creates options that require the given absolute precision.
+    #[inline]
+    pub fn with_precision(precision: F) -> Self {
+        Self {
+            precision: Some(precision),
+            ..Default::default()
+        }
+    }
+
+    /// Measures the representative small feature size of the given paths.
+    ///
+    /// - `paths`: Input paths.
+    /// - `is_closed`: Whether the last point of each path connects back to the first one.
+    pub fn feature_size<'a, P, It>(&self, paths: It, is_closed: bool) -> Option<F>
+    where
+        P: FloatPointCompatible<Scalar = F> + 'a,
+        It: Iterator<Item = &'a [P]>,
+    {
+        let mut lengths = Vec::new();
+        for path in paths {
+            if path.len() < 2 {
+                continue;
+            }
+            for w in path.windows(2) {
+                push_length(&mut lengths, &w[0], &w[1]);
+            }
+            if is_closed && path.len() > 2 {
+                push_length(&mut lengths, &path[path.len() - 1], &path[0]);
+            }
+        }
+
+        if lengths.is_empty() {
+            return None;
+        }
+
+        let q = self.feature_quantile.to_f64().clamp(0.0, 1.0);
+        let index = ((lengths.len() - 1) as f64 * q) as usize;
+        let (_, value, _) = lengths.select_nth_unstable_by(index, |a, b| a.total_cmp(b));
+
+        Some(F::from_float(*value))
+    }
+
+    #[inline]
+    fn is_satisfied_by(&self, scale: f64, feature_size: Option<F>) -> bool {
+        let is_precise = self
+            .precision
+            .is_none_or(|precision| 0.5 / scale <= precision.to_f64());
+        let is_resolved =
+            feature_size.is_none_or(|size| size.to_f64() * scale >= self.feature_resolution.to_f64());
+        is_precise && is_resolved
+    }
+}
+
+#[inline]
+fn push_length<P: FloatPointCompatible>(lengths: &mut Vec<f64>, a: &P, b: &P) {
+    let dx = (b.x() - a.x()).to_f64();
+    let dy = (b.y() - a.y()).to_f64();
+    let len = (dx * dx + dy * dy).sqrt();
+    if len > 0.0 && len.is_finite() {
+        lengths.push(len);
+    }
+}
+
+impl<F: FloatNumber> EngineSelection<F> {
+    /// Selects the smallest integer engine whose grid over `rect` satisfies `options`.
+    ///
+    /// - `rect`: Bounds of the data the adapter has to cover.
+    /// - `feature_size`: Representative small feature size, see [`AutoScaleOptions::feature_size`].
+    /// - `options`: Precision requirements.
+    pub fn with_rect(rect: FloatRect<F>, feature_size: Option<F>, options: &AutoScaleOptions<F>) -> Self {
+        let mut selection = Self::for_engine(IntEngine::I64, rect, feature_size);
+        selection.is_satisfied = false;
+
+        for engine in IntEngine::ALL {
+            let candidate = Self::for_engine(engine, rect, feature_size);
+            if options.is_satisfied_by(candidate.scale.to_f64(), feature_size) {
+                selection = candidate;
+                break;
+            }
+        }
+
+        selection
+    }
+
+    /// Selects an integer engine for a Boolean operation between closed contours or shapes.
+    ///
+    /// - `subj`: A `ShapeResource` that define the subject.
+    /// - `clip`: A `ShapeResource` that define the clip.
+    /// - `options`: Precision requirements.
+    pub fn with_subj_and_clip<P, R0, R1>(subj: &R0, clip: &R1, options: &AutoScaleOptions<F>) -> Self
+    where
+        P: FloatPointCompatible<Scalar = F>,
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        let iter = subj.iter_paths().chain(clip.iter_paths()).flatten();
+        let rect = FloatRect::with_iter(iter).unwrap_or(FloatRect::zero());
+        let feature_size = options.feature_size(subj.iter_paths().chain(clip.iter_paths()), true);
+        Self::with_rect(rect, feature_size, options)
+    }
+
+    fn for_engine(engine: IntEngine, rect: FloatRect<F>, feature_size: Option<F>) -> Self {
+        let scale = match engine {
+            IntEngine::I16 => Self::adapter_scale::<i16>(rect),
+            IntEngine::I32 => Self::adapter_scale::<i32>(rect),
+            IntEngine::I64 => Self::adapter_scale::<i64>(rect),
+        };
+
+        Self {
+            engine,
+            scale,
+            max_error: F::HALF / scale,
+            feature_size,
+            is_satisfied: true,
+        }
+    }
+
+    #[inline]
+    fn adapter_scale<I: IntNumber>(rect: FloatRect<F>) -> F {
+        FloatPointAdapter::<[F; 2], I>::new(rect).dir_scale()
+    }
+}
+
+/// Trait `AutoFloatOverlay` runs Boolean operations on an integer engine selected from the input.
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::float::auto::{AutoFloatOverlay, AutoScaleOptions};
+///
+/// let subj = vec![[0.0, 0.0], [0.0, 5.0], [5.0, 5.0], [5.0, 0.0]];
+/// let clip = vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]];
+///
+/// let output = subj.overlay_auto(
+///     &clip,
+///     OverlayRule::Difference,
+///     FillRule::EvenOdd,
+///     AutoScaleOptions::with_precision(1e-6),
+/// );
+///
+/// assert_eq!(output.result.len(), 1);
+/// assert!(output.selection.max_error <= 1e-6);
+/// ```
+pub trait AutoFloatOverlay<R0, R1, P>
+where
+    R0: ShapeResource<P>,
+    R1: ShapeResource<P>,
+    P: FloatPointCompatible,
+{
+    /// Same as [`SingleFloatOverlay::overlay`](crate::float::single::SingleFloatOverlay::overlay),
+    /// but the integer engine is selected automatically.
+    fn overlay_auto(
+        &self,
+        source: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Shapes<P>, P::Scalar>;
+}
+
+impl<R0, R1, P> AutoFloatOverlay<R0, R1, P> for R0
+where
+    R0: ShapeResource<P>,
+    R1: ShapeResource<P>,
+    P: FloatPointCompatible,
+{
+    fn overlay_auto(
+        &self,
+        source: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Shapes<P>, P::Scalar> {
+        let selection = EngineSelection::with_subj_and_clip(self, source, &options);
+        let result = match selection.engine {
+            IntEngine::I16 => {
+                FloatOverlay::<P, i16>::from_subj_and_clip(self, source).overlay(overlay_rule, fill_rule)
+            }
+            IntEngine::I32 => {
+                FloatOverlay::<P, i32>::from_subj_and_clip(self, source).overlay(overlay_rule, fill_rule)
+            }
+            IntEngine::I64 => {
+                FloatOverlay::<P, i64>::from_subj_and_clip(self, source).overlay(overlay_rule, fill_rule)
+            }
+        };
+
+        AutoOutput { result, selection }
+    }
+}
+
+/// Trait `AutoFloatClip` clips float string paths by float shapes on an integer engine selected
+/// from the input.
+pub trait AutoFloatClip<R, P>
+where
+    R: ShapeResource<P>,
+    P: FloatPointCompatible,
+{
+    /// Same as [`FloatClip::clip_by`](crate::float::clip::FloatClip::clip_by), but the integer
+    /// engine is selected automatically.
+    fn clip_by_auto(
+        &self,
+        source: &R,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Paths<P>, P::Scalar>;
+}
+
+impl<R0, R1, P> AutoFloatClip<R0, P> for R1
+where
+    R0: ShapeResource<P>,
+    R1: ShapeResource<P>,
+    P: FloatPointCompatible,
+{
+    fn clip_by_auto(
+        &self,
+        resource: &R0,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Paths<P>, P::Scalar> {
+        let iter = resource.iter_paths().chain(self.iter_paths()).flatten();
+        let rect = FloatRect::with_iter(iter).unwrap_or(FloatRect::zero());
+        let shape_feature = options.feature_size(resource.iter_paths(), true);
+        let string_feature = options.feature_size(self.iter_paths(), false);
+        let feature_size = match (shape_feature, string_feature) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        let selection = EngineSelection::with_rect(rect, feature_size, &options);
+        let result = match selection.engine {
+            IntEngine::I16 => FloatStringOverlay::<P, i16>::from_shape_and_string(resource, self)
+                .clip_string_lines_with_solver(fill_rule, clip_rule, Default::default()),
+            IntEngine::I32 => FloatStringOverlay::<P, i32>::from_shape_and_string(resource, self)
+                .clip_string_lines_with_solver(fill_rule, clip_rule, Default::default()),
+            IntEngine::I64 => FloatStringOverlay::<P, i64>::from_shape_and_string(resource, self)
+                .clip_string_lines_with_solver(fill_rule, clip_rule, Default::default()),
+        };
+
+        AutoOutput { result, selection }
+    }
+}
+
+/// Spatial predicate evaluated by [`AutoFloatRelate::relate_auto`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialPredicate {
+    Intersects,
+    InteriorsIntersect,
+    Touches,
+    PointIntersects,
+    Within,
+}
+
+impl SpatialPredicate {
+    #[inline]
+    fn evaluate<P: FloatPointCompatible, I: OverlayInt>(
+        self,
+        overlay: &mut FloatPredicateOverlay<P, I>,
+    ) -> bool {
+        match self {
+            SpatialPredicate::Intersects => overlay.intersects(),
+            SpatialPredicate::InteriorsIntersect => overlay.interiors_intersect(),
+            SpatialPredicate::Touches => overlay.touches(),
+            SpatialPredicate::PointIntersects => overlay.point_intersects(),
+            SpatialPredicate::Within => overlay.within(),
+        }
+    }
+}
+
+/// Trait `AutoFloatRelate` evaluates spatial predicates on an integer engine selected from the input.
+pub trait AutoFloatRelate<R1, P>
+where
+    R1: ShapeResource<P> + ?Sized,
+    P: FloatPointCompatible,
+{
+    /// Same as the [`FloatRelate`](crate::float::relate::FloatRelate) predicates, but the integer
+    /// engine is selected automatically.
+    fn relate_auto(
+        &self,
+        other: &R1,
+        predicate: SpatialPredicate,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<bool, P::Scalar>;
+}
+
+impl<R0, R1, P> AutoFloatRelate<R1, P> for R0
+where
+    R0: ShapeResource<P> + ?Sized,
+    R1: ShapeResource<P> + ?Sized,
+    P: FloatPointCompatible,
+{
+    fn relate_auto(
+        &self,
+        other: &R1,
+        predicate: SpatialPredicate,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<bool, P::Scalar> {
+        let selection = EngineSelection::with_subj_and_clip(self, other, &options);
+        let result = match selection.engine {
+            IntEngine::I16 => predicate.evaluate(&mut FloatPredicateOverlay::<P, i16>::from_subj_and_clip(
+                self, other,
+            )),
+            IntEngine::I32 => predicate.evaluate(&mut FloatPredicateOverlay::<P, i32>::from_subj_and_clip(
+                self, other,
+            )),
+            IntEngine::I64 => predicate.evaluate(&mut FloatPredicateOverlay::<P, i64>::from_subj_and_clip(
+                self, other,
+            )),
+        };
+
+        AutoOutput { result, selection }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::auto::{
+        AutoFloatClip, AutoFloatOverlay, AutoFloatRelate, AutoScaleOptions, EngineSelection, IntEngine,
+        SpatialPredicate,
+    };
+    use crate::mesh::outline::offset::OutlineOffset;
+    use crate::mesh::stroke::offset::StrokeOffset;
+    use crate::mesh::style::{LineCap, LineJoin, OutlineStyle, StrokeStyle};
+    use crate::string::clip::ClipRule;
+    use alloc::vec;
+    use i_shape::float::area::Area;
+
+    #[test]
+    fn test_coarse_input_selects_i16() {
+        let subj = vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let clip = vec![[0.5, 0.5], [0.5, 1.5], [1.5, 1.5], [1.5, 0.5]];
+
+        let output = subj.overlay_auto(&clip, OverlayRule::Union, FillRule::EvenOdd, Default::default());
+
+        assert_eq!(output.selection.engine, IntEngine::I16);
+        assert!(output.selection.is_satisfied);
+        assert_eq!(output.result.len(), 1);
+        assert_eq!(output.result[0][0].len(), 8);
+    }
+
+    #[test]
+    fn test_precision_selects_wider_engine() {
+        let subj = vec![[0.0, 0.0], [0.0, 5.0], [5.0, 5.0], [5.0, 0.0]];
+        let clip = vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]];
+
+        let output = subj.overlay_auto(
+            &clip,
+            OverlayRule::Difference,
+            FillRule::EvenOdd,
+            AutoScaleOptions::with_precision(1e-6),
+        );
+
+        assert_eq!(output.selection.engine, IntEngine::I32);
+        assert!(output.selection.max_error <= 1e-6);
+        assert_eq!(output.result.len(), 1);
+    }
+
+    #[test]
+    fn test_small_features_select_i64() {
+        let subj = vec![
+            [0.0, 0.0],
+            [1_000_000.0, 0.0],
+            [1_000_000.0, 0.001],
+            [1_000_000.0, 1_000_000.0],
+            [0.0, 1_000_000.0],
+        ];
+        let options = AutoScaleOptions {
+            feature_quantile: 0.0,
+            ..Default::default()
+        };
+
+        let selection = EngineSelection::with_subj_and_clip(&subj, &[[0.0f64; 2]; 0], &options);
+
+        assert_eq!(selection.engine, IntEngine::I64);
+        assert_eq!(selection.feature_size, Some(0.001));
+        assert!(selection.is_satisfied);
+    }
+
+    #[test]
+    fn test_unreachable_precision_falls_back_to_i64() {
+        let subj = vec![[0.0, 0.0], [0.0, 5.0], [5.0, 5.0], [5.0, 0.0]];
+
+        let selection = EngineSelection::with_subj_and_clip(
+            &subj,
+            &[[0.0f64; 2]; 0],
+            &AutoScaleOptions::with_precision(1e-30),
+        );
+
+        assert_eq!(selection.engine, IntEngine::I64);
+        assert!(!selection.is_satisfied);
+    }
+
+    #[test]
+    fn test_clip_auto() {
+        let shape = vec![[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+        let string = vec![[-1.0, 1.0], [3.0, 1.0]];
+        let clip_rule = ClipRule {
+            invert: false,
+            boundary_included: false,
+        };
+
+        let output = string.clip_by_auto(&shape, FillRule::EvenOdd, clip_rule, Default::default());
+
+        assert_eq!(output.selection.engine, IntEngine::I16);
+        assert_eq!(output.result, vec![vec![[0.0, 1.0], [2.0, 1.0]]]);
+    }
+
+    #[test]
+    fn test_relate_auto() {
+        let square = vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let inner = vec![[2.0, 2.0], [2.0, 8.0], [8.0, 8.0], [8.0, 2.0]];
+        let options = AutoScaleOptions::with_precision(1e-9);
+
+        let within = inner.relate_auto(&square, SpatialPredicate::Within, options);
+        let touches = inner.relate_auto(&square, SpatialPredicate::Touches, options);
+
+        assert!(within.result);
+        assert!(!touches.result);
+        assert_eq!(within.selection.engine, IntEngine::I64);
+    }
+
+    #[test]
+    fn test_stroke_auto_accounts_for_width() {
+        let path = vec![[0.0, 0.0], [1000.0, 0.0]];
+
+        let wide = path.stroke_auto(StrokeStyle::new(100.0), false, Default::default());
+        let thin = path.stroke_auto(StrokeStyle::new(0.01), false, Default::default());
+
+        assert_eq!(wide.selection.engine, IntEngine::I16);
+        assert_eq!(thin.selection.engine, IntEngine::I32);
+        assert_eq!(wide.result.len(), 1);
+        assert_eq!(thin.result.len(), 1);
+    }
+
+    #[test]
+    fn test_stroke_auto_zero_width() {
+        let path = vec![[0.0, 0.0], [1000.0, 0.0]];
+
+        for width in [0.0, -1.0] {
+            let output = path.stroke_auto(StrokeStyle::new(width), false, Default::default());
+
+            assert!(output.result.is_empty());
+            assert!(output.selection.is_satisfied);
+            assert_eq!(output.selection.feature_size, None);
+            assert_eq!(output.selection.engine, IntEngine::I16);
+        }
+    }
+
+    #[test]
+    fn test_stroke_auto_covers_caps_and_joins() {
+        let path = vec![[0.0, 0.0], [10.0, 0.0], [10.0, 1.0]];
+        let style = StrokeStyle::new(2.0)
+            .start_cap(LineCap::Round(0.1))
+            .end_cap(LineCap::Square)
+            .line_join(LineJoin::Miter(0.05));
+
+        let output = path.stroke_auto(style.clone(), false, Default::default());
+        let expected = path.stroke_as::<i32>(style, false);
+
+        assert_eq!(output.result.len(), 1);
+        let delta: f64 = output.result.area() - expected.area();
+        assert!(delta.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_outline_auto() {
+        let path = vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+
+        let output = path.outline_auto(&OutlineStyle::new(0.001), Default::default());
+
+        assert_eq!(output.selection.engine, IntEngine::I32);
+        assert_eq!(output.result.len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::error::OverlayError;
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
 use crate::core::solver::Solver;
@@ -51,6 +52,28 @@ where
     where
         I: OverlayInt;
 
+    /// Same as [`Self::clip_by`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate. Clipping shape contours are reported as
+    /// [`InputRole::Subject`](crate::core::error::InputRole::Subject).
+    fn try_clip_by(
+        &self,
+        source: &R,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Paths<P>, OverlayError>;
+
+    /// Same as [`Self::try_clip_by`], but with an explicit integer engine.
+    fn try_clip_by_as<I>(
+        &self,
+        source: &R,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Paths<P>, OverlayError>
+    where
+        I: OverlayInt;
+
     /// Clips paths according to the specified build and clip rules using a fixed float-to-integer scale.
     /// - `resource`: A clipping shape.
     ///   `ShapeResource` can be one of the following:
@@ -230,6 +253,32 @@ where
         self.clip_by_with_solver_as::<I>(resource, fill_rule, clip_rule, Default::default())
     }
 
+    #[inline]
+    fn try_clip_by(
+        &self,
+        resource: &R0,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Paths<P>, OverlayError> {
+        self.try_clip_by_as::<i32>(resource, fill_rule, clip_rule)
+    }
+
+    #[inline]
+    fn try_clip_by_as<I>(
+        &self,
+        resource: &R0,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Paths<P>, OverlayError>
+    where
+        I: OverlayInt,
+    {
+        Ok(
+            FloatStringOverlay::<P, I>::try_from_shape_and_string(resource, self)?
+                .clip_string_lines_with_solver(fill_rule, clip_rule, Default::default()),
+        )
+    }
+
     #[inline]
     fn clip_by_with_solver(
         &self,
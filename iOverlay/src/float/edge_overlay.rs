@@ -0,0 +1,263 @@
+//! Float counterpart of [`EdgeOverlay`]: Boolean operations on float edges that carry user data.
+//! Edges are converted to integer space with a `FloatPointAdapter`, and the resulting vector edges
+//! are converted back, so the attached data survives the round trip.
+
+use crate::core::edge_data::OverlayEdgeData;
+use crate::core::edge_overlay::{EdgeOverlay, InputEdge};
+use crate::core::fill_rule::FillRule;
+use crate::core::integer::OverlayInt;
+use crate::core::overlay::ShapeType;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::float::scale::FixedScaleOverlayError;
+use crate::vector::edge::{DataVectorEdge, SideFill};
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+
+/// A float input edge from `a` to `b` carrying user `data`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatInputEdge<P, D> {
+    pub a: P,
+    pub b: P,
+    pub data: D,
+}
+
+/// A float output edge, the counterpart of [`DataVectorEdge`]. `fill` tells which sides of the edge
+/// are covered by the subject and the clip, `data` is the merged user data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatDataVectorEdge<P, D = ()> {
+    pub a: P,
+    pub b: P,
+    pub fill: SideFill,
+    pub data: D,
+}
+
+pub type FloatDataVectorPath<P, D = ()> = Vec<FloatDataVectorEdge<P, D>>;
+pub type FloatDataVectorShape<P, D = ()> = Vec<FloatDataVectorPath<P, D>>;
+
+/// Builder for Boolean operations on float edges with user data.
+///
+/// The float-to-integer conversion is controlled by the `FloatPointAdapter` scale:
+/// `x_int = (x_float - offset_x) * scale`. Split and merge callbacks of `D` run in integer space;
+/// use [`Self::adapter`] to map their points back to floats.
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::core::edge_data::{EdgeDataMerge, OverlayEdgeData};
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay::ShapeType;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::float::edge_overlay::{FloatEdgeOverlay, FloatInputEdge};
+/// use i_overlay::segm::boolean::ShapeCountBoolean;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// struct Layer(u32);
+///
+/// impl OverlayEdgeData for Layer {
+///     type Store = ();
+///
+///     fn merge(ctx: EdgeDataMerge<ShapeCountBoolean, Self>, _: &mut Self::Store) -> Self {
+///         Layer(ctx.lhs_data.0.min(ctx.rhs_data.0))
+///     }
+/// }
+///
+/// fn square(x: f64, size: f64, layer: u32) -> Vec<FloatInputEdge<[f64; 2], Layer>> {
+///     let p = [[x, 0.0], [x + size, 0.0], [x + size, size], [x, size]];
+///     (0..4)
+///         .map(|i| FloatInputEdge { a: p[i], b: p[(i + 1) % 4], data: Layer(layer) })
+///         .collect()
+/// }
+///
+/// let mut overlay = FloatEdgeOverlay::with_edges(&square(0.0, 1.0, 1), &square(0.5, 1.0, 2));
+/// let shapes = overlay.build_vector_shapes(OverlayRule::Union, FillRule::NonZero);
+///
+/// assert_eq!(shapes.len(), 1);
+/// let bottom = shapes[0][0].iter().find(|e| e.a == [1.5, 0.0] || e.b == [1.5, 0.0]).unwrap();
+/// assert_eq!(bottom.data, Layer(2));
+/// ```
+pub struct FloatEdgeOverlay<P: FloatPointCompatible, D: OverlayEdgeData, I: OverlayInt = i32> {
+    pub(super) overlay: EdgeOverlay<I, D>,
+    pub(super) adapter: FloatPointAdapter<P, I>,
+}
+
+impl<P, D, I> FloatEdgeOverlay<P, D, I>
+where
+    P: FloatPointCompatible,
+    D: OverlayEdgeData,
+    I: OverlayInt,
+{
+    /// Constructs a new `FloatEdgeOverlay` using a pre-configured adapter.
+    ///
+    /// - `adapter`: A `FloatPointAdapter` instance responsible for coordinate conversion between
+    ///   float and integer values. Every added point must lie inside its rectangle.
+    /// - `capacity`: Initial capacity for storing segments, ideally matching the total number of
+    ///   edges for efficient memory allocation.
+    #[inline]
+    pub fn with_adapter(adapter: FloatPointAdapter<P, I>, capacity: usize) -> Self {
+        Self {
+            overlay: EdgeOverlay::new(capacity),
+            adapter,
+        }
+    }
+
+    /// Constructs a new `FloatEdgeOverlay` using a pre-configured adapter and solver.
+    ///
+    /// - `adapter`: A `FloatPointAdapter` instance responsible for coordinate conversion.
+    /// - `solver`: Type of solver to use.
+    /// - `capacity`: Initial capacity for storing segments.
+    #[inline]
+    pub fn with_adapter_custom(adapter: FloatPointAdapter<P, I>, solver: Solver, capacity: usize) -> Self {
+        let mut overlay = EdgeOverlay::new(capacity);
+        overlay.solver = solver;
+        Self { overlay, adapter }
+    }
+
+    /// Creates a new `FloatEdgeOverlay` and adds the subject and clip edges.
+    ///
+    /// This variant uses automatic scaling based on the combined bounds of `subj` and `clip`.
+    /// - `subj`: Edges added as `Subject`.
+    /// - `clip`: Edges added as `Clip`.
+    pub fn from_edges(subj: &[FloatInputEdge<P, D>], clip: &[FloatInputEdge<P, D>]) -> Self {
+        let iter = subj.iter().chain(clip.iter()).flat_map(|e| [&e.a, &e.b]);
+        let adapter = FloatPointAdapter::with_iter(iter);
+        let mut overlay = Self::with_adapter(adapter, subj.len() + clip.len());
+        overlay.add_edges(subj.iter().copied(), ShapeType::Subject);
+        overlay.add_edges(clip.iter().copied(), ShapeType::Clip);
+        overlay
+    }
+
+    /// Creates a new `FloatEdgeOverlay` with a fixed float-to-integer scale and adds the subject
+    /// and clip edges.
+    ///
+    /// This variant validates that the requested scale is finite, positive, and fits the
+    /// input bounds. Use `scale = 1.0 / grid_size` if you want a grid-size style parameter.
+    pub fn from_edges_fixed_scale(
+        subj: &[FloatInputEdge<P, D>],
+        clip: &[FloatInputEdge<P, D>],
+        scale: P::Scalar,
+    ) -> Result<Self, FixedScaleOverlayError> {
+        let iter = subj.iter().chain(clip.iter()).flat_map(|e| [&e.a, &e.b]);
+        let adapter = FloatPointAdapter::with_iter_and_scale_checked(iter, scale)?;
+        let mut overlay = Self::with_adapter(adapter, subj.len() + clip.len());
+        overlay.add_edges(subj.iter().copied(), ShapeType::Subject);
+        overlay.add_edges(clip.iter().copied(), ShapeType::Clip);
+        Ok(overlay)
+    }
+
+    /// Adds a single edge. Both ends must lie inside the adapter rectangle.
+    /// Edges that collapse to a point in integer space are skipped.
+    #[inline]
+    pub fn add_edge(&mut self, edge: FloatInputEdge<P, D>, shape_type: ShapeType) {
+        let edge = InputEdge {
+            a: self.adapter.float_to_int(&edge.a),
+            b: self.adapter.float_to_int(&edge.b),
+            data: edge.data,
+        };
+        self.overlay.add_edge(edge, shape_type);
+    }
+
+    /// Adds edges, see [`Self::add_edge`].
+    pub fn add_edges<It>(&mut self, edges: It, shape_type: ShapeType)
+    where
+        It: IntoIterator<Item = FloatInputEdge<P, D>>,
+    {
+        for edge in edges {
+            self.add_edge(edge, shape_type);
+        }
+    }
+
+    /// The adapter used to convert between float and integer space.
+    #[inline]
+    pub fn adapter(&self) -> &FloatPointAdapter<P, I> {
+        &self.adapter
+    }
+
+    #[inline]
+    pub fn data_store(&self) -> &D::Store {
+        self.overlay.data_store()
+    }
+
+    #[inline]
+    pub fn data_store_mut(&mut self) -> &mut D::Store {
+        self.overlay.data_store_mut()
+    }
+
+    #[inline]
+    pub fn into_data_store(self) -> D::Store {
+        self.overlay.into_data_store()
+    }
+
+    /// Executes the Boolean operation and returns the boundary edges of the result, unordered.
+    /// See [`EdgeOverlay::build_vectors`].
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn build_vectors(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Vec<FloatDataVectorEdge<P, D>> {
+        let vectors = self.overlay.build_vectors(overlay_rule, fill_rule);
+        vectors.iter().map(|e| self.edge_to_float(e)).collect()
+    }
+
+    /// Executes the Boolean operation and returns the boundary edges of the result grouped as
+    /// shapes, contours and edges. See [`EdgeOverlay::build_vector_shapes`].
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn build_vector_shapes(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Vec<FloatDataVectorShape<P, D>> {
+        let shapes = self.overlay.build_vector_shapes(overlay_rule, fill_rule);
+        shapes
+            .iter()
+            .map(|shape| {
+                shape
+                    .iter()
+                    .map(|path| path.iter().map(|e| self.edge_to_float(e)).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Input edges as currently stored in integer space, converted back to floats.
+    #[inline]
+    pub fn edges(&self) -> impl Iterator<Item = [P; 2]> + '_ {
+        self.overlay
+            .edges()
+            .map(|[a, b]| [self.adapter.int_to_float(&a), self.adapter.int_to_float(&b)])
+    }
+
+    #[inline]
+    fn edge_to_float(&self, edge: &DataVectorEdge<I, D>) -> FloatDataVectorEdge<P, D> {
+        FloatDataVectorEdge {
+            a: self.adapter.int_to_float(&edge.a),
+            b: self.adapter.int_to_float(&edge.b),
+            fill: edge.fill,
+            data: edge.data,
+        }
+    }
+}
+
+impl<P: FloatPointCompatible, D: OverlayEdgeData> FloatEdgeOverlay<P, D> {
+    /// Creates a new `FloatEdgeOverlay` with subject and clip edges.
+    /// Uses the default integer engine (`i32`).
+    #[inline]
+    pub fn with_edges(subj: &[FloatInputEdge<P, D>], clip: &[FloatInputEdge<P, D>]) -> Self {
+        Self::from_edges(subj, clip)
+    }
+
+    /// Creates a new `FloatEdgeOverlay` with a fixed float-to-integer scale.
+    /// Uses the default integer engine (`i32`).
+    #[inline]
+    pub fn with_edges_fixed_scale(
+        subj: &[FloatInputEdge<P, D>],
+        clip: &[FloatInputEdge<P, D>],
+        scale: P::Scalar,
+    ) -> Result<Self, FixedScaleOverlayError> {
+        Self::from_edges_fixed_scale(subj, clip, scale)
+    }
+}
@@ -0,0 +1,282 @@
+//! Float front end for [`OverlayExpression`] trees.
+//!
+//! All operands referenced by an expression are converted to integer space with one shared
+//! [`FloatPointAdapter`], the whole tree is evaluated there by an [`ExpressionEvaluator`], and only
+//! the final result is converted back to float. Intermediate results never pass through float
+//! coordinates, so no rounding accumulates between steps.
+
+use crate::core::expression::{ExpressionEvaluator, OverlayExpression, UnknownOperandError};
+use crate::core::fill_rule::FillRule;
+use crate::core::integer::OverlayInt;
+use crate::core::solver::Solver;
+use crate::float::overlay::OverlayOptions;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_shape::base::data::Shapes;
+use i_shape::float::adapter::ShapesToFloat;
+use i_shape::float::despike::DeSpikeContour;
+use i_shape::float::simple::SimplifyContour;
+use i_shape::int::shape::IntShapes;
+use i_shape::source::resource::ShapeResource;
+
+/// Evaluates [`OverlayExpression`] trees over named float operands.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use i_overlay::core::expression::OverlayExpression;
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::float::expression::FloatExpressionEvaluator;
+///
+/// let mut operands = BTreeMap::new();
+/// operands.insert("a", vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+/// operands.insert("b", vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0]]);
+/// operands.insert("c", vec![[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]]);
+///
+/// // (a ∪ b) − c
+/// let expr = OverlayExpression::operand("a")
+///     .union(OverlayExpression::operand("b"))
+///     .difference(OverlayExpression::operand("c"));
+///
+/// let mut evaluator = FloatExpressionEvaluator::<[f64; 2]>::new();
+/// let shapes = evaluator.evaluate(&expr, &operands, FillRule::NonZero).unwrap();
+///
+/// assert_eq!(shapes.len(), 1);
+/// ```
+pub struct FloatExpressionEvaluator<P: FloatPointCompatible, I: OverlayInt = i32> {
+    pub options: OverlayOptions<P::Scalar, I>,
+    pub solver: Solver,
+    evaluator: ExpressionEvaluator<I>,
+}
+
+impl<P, I> FloatExpressionEvaluator<P, I>
+where
+    P: FloatPointCompatible,
+    I: OverlayInt,
+{
+    /// Creates an evaluator with default options and solver.
+    #[inline]
+    pub fn new() -> Self {
+        Self::new_custom(Default::default(), Default::default())
+    }
+
+    /// Creates an evaluator with custom options and solver.
+    /// - `options`: Options applied to the final result.
+    /// - `solver`: Type of solver to use for every step.
+    #[inline]
+    pub fn new_custom(options: OverlayOptions<P::Scalar, I>, solver: Solver) -> Self {
+        Self {
+            options,
+            solver,
+            evaluator: ExpressionEvaluator::new(),
+        }
+    }
+
+    /// Evaluates the expression with an adapter fitted to the bounds of every referenced operand.
+    /// - `expression`: The expression tree to evaluate.
+    /// - `operands`: The named operands, each a `ShapeResource`.
+    /// - `fill_rule`: Fill rule used to interpret the operands.
+    pub fn evaluate<K, R>(
+        &mut self,
+        expression: &OverlayExpression<K>,
+        operands: &BTreeMap<K, R>,
+        fill_rule: FillRule,
+    ) -> Result<Shapes<P>, UnknownOperandError<K>>
+    where
+        K: Ord + Clone,
+        R: ShapeResource<P>,
+    {
+        let resources = Self::referenced(expression, operands)?;
+        let iter = resources
+            .iter()
+            .flat_map(|(_, resource)| resource.iter_paths())
+            .flatten();
+        let adapter = FloatPointAdapter::with_iter(iter);
+
+        Ok(self.evaluate_referenced(expression, &resources, fill_rule, &adapter))
+    }
+
+    /// Evaluates the expression with a pre-configured adapter, for example the one of an
+    /// [`OverlaySession`](crate::float::session::OverlaySession).
+    ///
+    /// Every referenced operand must lie inside the adapter rectangle.
+    /// - `expression`: The expression tree to evaluate.
+    /// - `operands`: The named operands, each a `ShapeResource`.
+    /// - `fill_rule`: Fill rule used to interpret the operands.
+    /// - `adapter`: The adapter used to convert operands and the result.
+    pub fn evaluate_with_adapter<K, R>(
+        &mut self,
+        expression: &OverlayExpression<K>,
+        operands: &BTreeMap<K, R>,
+        fill_rule: FillRule,
+        adapter: &FloatPointAdapter<P, I>,
+    ) -> Result<Shapes<P>, UnknownOperandError<K>>
+    where
+        K: Ord + Clone,
+        R: ShapeResource<P>,
+    {
+        let resources = Self::referenced(expression, operands)?;
+        Ok(self.evaluate_referenced(expression, &resources, fill_rule, adapter))
+    }
+
+    fn referenced<'a, K, R>(
+        expression: &OverlayExpression<K>,
+        operands: &'a BTreeMap<K, R>,
+    ) -> Result<Vec<(K, &'a R)>, UnknownOperandError<K>>
+    where
+        K: Ord + Clone,
+    {
+        let mut keys = expression.operands();
+        keys.sort_unstable();
+        keys.dedup();
+
+        keys.into_iter()
+            .map(|key| match operands.get(key) {
+                Some(resource) => Ok((key.clone(), resource)),
+                None => Err(UnknownOperandError(key.clone())),
+            })
+            .collect()
+    }
+
+    fn evaluate_referenced<K, R>(
+        &mut self,
+        expression: &OverlayExpression<K>,
+        resources: &[(K, &R)],
+        fill_rule: FillRule,
+        adapter: &FloatPointAdapter<P, I>,
+    ) -> Shapes<P>
+    where
+        K: Ord + Clone,
+        R: ShapeResource<P>,
+    {
+        let int_operands: BTreeMap<K, IntShapes<I>> = resources
+            .iter()
+            .map(|(key, resource)| {
+                let contours = resource
+                    .iter_paths()
+                    .map(|path| path.iter().map(|p| adapter.float_to_int(p)).collect())
+                    .collect();
+                (key.clone(), alloc::vec![contours])
+            })
+            .collect();
+
+        self.evaluator.solver = self.solver;
+        self.evaluator.options = self.options.int_with_adapter(adapter);
+
+        // every referenced key is present
+        let shapes = self
+            .evaluator
+            .evaluate(expression, &int_operands, fill_rule)
+            .unwrap_or_default();
+
+        let mut float = shapes.to_float(adapter);
+        if self.options.clean_result {
+            if self.options.preserve_output_collinear {
+                float.despike_contour(adapter);
+            } else {
+                float.simplify_contour(adapter);
+            }
+        }
+
+        float
+    }
+}
+
+impl<P, I> Default for FloatExpressionEvaluator<P, I>
+where
+    P: FloatPointCompatible,
+    I: OverlayInt,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::expression::{OverlayExpression, UnknownOperandError};
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::expression::FloatExpressionEvaluator;
+    use crate::float::session::OverlaySession;
+    use crate::float::single::SingleFloatOverlay;
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::float::rect::FloatRect;
+    use i_shape::float::area::Area;
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<[f64; 2]> {
+        vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]
+    }
+
+    fn area(shapes: &[Vec<Vec<[f64; 2]>>]) -> f64 {
+        shapes.iter().map(|shape| shape.area()).sum::<f64>().abs()
+    }
+
+    fn operands() -> BTreeMap<&'static str, Vec<[f64; 2]>> {
+        let mut operands = BTreeMap::new();
+        operands.insert("a", square(0.0, 0.0, 1.0));
+        operands.insert("b", square(1.0, 0.0, 1.0));
+        operands.insert("c", square(0.5, 0.5, 1.0));
+        operands.insert("unused", square(-100.0, -100.0, 1.0));
+        operands
+    }
+
+    #[test]
+    fn test_nested_expression() {
+        // (a ∪ b) − c
+        let expr = OverlayExpression::operand("a")
+            .union(OverlayExpression::operand("b"))
+            .difference(OverlayExpression::operand("c"));
+
+        let mut evaluator = FloatExpressionEvaluator::<[f64; 2]>::new();
+        let shapes = evaluator.evaluate(&expr, &operands(), FillRule::NonZero).unwrap();
+
+        assert_eq!(shapes.len(), 1);
+        assert!((area(&shapes) - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_matches_pairwise_overlay() {
+        // a ∩ c
+        let expr = OverlayExpression::operand("a").intersect(OverlayExpression::operand("c"));
+        let ops = operands();
+
+        let mut evaluator = FloatExpressionEvaluator::<[f64; 2]>::new();
+        let shapes = evaluator.evaluate(&expr, &ops, FillRule::NonZero).unwrap();
+        let expected = ops["a"].overlay(&ops["c"], OverlayRule::Intersect, FillRule::NonZero);
+
+        assert_eq!(shapes, expected);
+    }
+
+    #[test]
+    fn test_session_adapter() {
+        let session = OverlaySession::<[f64; 2]>::with_rect(FloatRect::new(-200.0, 200.0, -200.0, 200.0));
+        let expr = OverlayExpression::operand("b").xor(OverlayExpression::operand("c"));
+
+        let mut evaluator = FloatExpressionEvaluator::<[f64; 2]>::new();
+        let shapes = evaluator
+            .evaluate_with_adapter(&expr, &operands(), FillRule::NonZero, session.adapter())
+            .unwrap();
+
+        assert!((area(&shapes) - 1.5).abs() < 1e-6);
+        for p in shapes.iter().flatten().flatten() {
+            assert_eq!(session.snap(p), *p);
+        }
+    }
+
+    #[test]
+    fn test_unknown_operand() {
+        let expr = OverlayExpression::operand("a").union(OverlayExpression::operand("z"));
+
+        let mut evaluator = FloatExpressionEvaluator::<[f64; 2]>::new();
+        let result = evaluator.evaluate(&expr, &operands(), FillRule::NonZero);
+
+        assert_eq!(result, Err(UnknownOperandError("z")));
+    }
+}
@@ -218,6 +218,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::single_range_in_vec_init)]
     fn clean_result_preserves_valid_collinear_mode_contour() {
         let int_hierarchy = FlatShapeHierarchy {
             shapes: FlatShapesBuffer {
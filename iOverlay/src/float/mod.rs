@@ -1,9 +1,13 @@
+pub mod auto;
 pub mod clip;
+pub mod edge_overlay;
+pub mod expression;
 pub mod graph;
 pub mod hierarchy;
 pub mod overlay;
 pub mod relate;
 pub mod scale;
+pub mod session;
 pub mod simplify;
 pub mod single;
 pub mod slice;
@@ -2,14 +2,20 @@
 //! boolean operations (union, intersection, etc.) on polygons. It provides structures and methods to
 //! manage subject and clip polygons and convert them into graphs for further operations.
 
+use crate::core::control::OperationControl;
+use crate::core::displacement::{DisplacementMap, DisplacementReport};
+use crate::core::error::{InputRole, OverlayError, PointIndex, validate_float_paths};
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
 use crate::core::overlay::{ContourDirection, IntOverlayOptions, Overlay, ShapeType};
 use crate::core::overlay_rule::OverlayRule;
+use crate::core::provenance::{ShapeAttributes, VertexSources};
 use crate::core::solver::Solver;
+use crate::core::stats::{OverlayStats, PhaseTimer, StatsClock};
 use crate::float::graph::FloatOverlayGraph;
 use crate::float::hierarchy::FloatFlatShapeHierarchy;
 use crate::i_shape::source::resource::ShapeResource;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
@@ -24,6 +30,7 @@ use i_shape::flat::float::FloatFlatContoursBuffer;
 use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::despike::DeSpikeContour;
 use i_shape::float::simple::SimplifyContour;
+use i_shape::int::shape::IntShapes;
 
 /// Options for float overlay extraction.
 ///
@@ -78,6 +85,16 @@ where
         Self::new_custom(adapter, Default::default(), Default::default(), capacity)
     }
 
+    /// Same as [`Self::with_adapter`], but the overlay logs every contour added to it, for
+    /// [`Self::overlay_with_vertex_sources`] and [`Self::overlay_with_attributes`]. The log is kept
+    /// by `clear` and the `reinit_*` methods. Logging keeps a copy of every added point.
+    #[inline]
+    pub fn with_adapter_tracked(adapter: FloatPointAdapter<P, I>, capacity: usize) -> Self {
+        let mut overlay = Self::with_adapter(adapter, capacity);
+        overlay.overlay.input_log = Some(Default::default());
+        overlay
+    }
+
     /// Constructs a new `FloatOverlay`, a builder for overlaying geometric shapes
     /// by converting float-based geometry to integer space, using a pre-configured adapter.
     ///
@@ -159,6 +176,23 @@ where
             .unsafe_add_source(clip, ShapeType::Clip)
     }
 
+    /// Same as [`Self::from_subj_and_clip`], but logs the input as [`Self::with_adapter_tracked`]
+    /// does.
+    pub fn from_subj_and_clip_tracked<R0, R1>(subj: &R0, clip: &R1) -> Self
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        let iter = subj.iter_paths().chain(clip.iter_paths()).flatten();
+        let adapter = FloatPointAdapter::with_iter(iter);
+        let subj_capacity = subj.iter_paths().fold(0, |s, c| s + c.len());
+        let clip_capacity = clip.iter_paths().fold(0, |s, c| s + c.len());
+
+        Self::with_adapter_tracked(adapter, subj_capacity + clip_capacity)
+            .unsafe_add_source(subj, ShapeType::Subject)
+            .unsafe_add_source(clip, ShapeType::Clip)
+    }
+
     /// Creates a new `FloatOverlay` instance and initializes it with subject and clip shapes.
     /// - `subj`: A `ShapeResource` that define the subject.
     /// - `clip`: A `ShapeResource` that define the clip.
@@ -188,6 +222,39 @@ where
             .unsafe_add_source(clip, ShapeType::Clip)
     }
 
+    /// Same as [`Self::from_subj_and_clip`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate.
+    pub fn try_from_subj_and_clip<R0, R1>(subj: &R0, clip: &R1) -> Result<Self, OverlayError>
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        validate_float_paths(InputRole::Subject, subj.iter_paths())?;
+        validate_float_paths(InputRole::Clip, clip.iter_paths())?;
+        Ok(Self::from_subj_and_clip(subj, clip))
+    }
+
+    /// Same as [`Self::from_subj_and_clip_custom`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate.
+    pub fn try_from_subj_and_clip_custom<R0, R1>(
+        subj: &R0,
+        clip: &R1,
+        options: OverlayOptions<P::Scalar, I>,
+        solver: Solver,
+    ) -> Result<Self, OverlayError>
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        validate_float_paths(InputRole::Subject, subj.iter_paths())?;
+        validate_float_paths(InputRole::Clip, clip.iter_paths())?;
+        Ok(Self::from_subj_and_clip_custom(subj, clip, options, solver))
+    }
+
     /// Creates a new `FloatOverlay` instance and initializes it with subject and clip shapes.
     /// - `subj`: A `ShapeResource` that define the subject.
     ///   `ShapeResource` can be one of the following:
@@ -247,8 +314,7 @@ where
     /// - **Safety**: Marked `unsafe` because it assumes the path is fully contained within the bounding box.
     #[inline]
     pub fn unsafe_add_contour(mut self, contour: &[P], shape_type: ShapeType) -> Self {
-        self.overlay
-            .add_path_iter(contour.iter().map(|p| self.adapter.float_to_int(p)), shape_type);
+        self.add_contour(contour, shape_type);
         self
     }
 
@@ -260,11 +326,29 @@ where
     #[inline]
     fn add_source<R: ShapeResource<P> + ?Sized>(&mut self, resource: &R, shape_type: ShapeType) {
         for contour in resource.iter_paths() {
-            self.overlay
-                .add_path_iter(contour.iter().map(|p| self.adapter.float_to_int(p)), shape_type);
+            self.add_contour(contour, shape_type);
         }
     }
 
+    #[inline]
+    fn add_contour(&mut self, contour: &[P], shape_type: ShapeType) {
+        if let Some(log) = self.overlay.input_log.as_mut() {
+            let dir_scale = self.adapter.dir_scale().to_f64();
+            for q in contour.iter() {
+                let p = self.adapter.float_to_int(q);
+                let r = self.adapter.int_to_float(&p);
+                let dx = (q.x() - r.x()).to_f64();
+                let dy = (q.y() - r.y()).to_f64();
+                let distance = FloatNumber::sqrt(dx * dx + dy * dy) * dir_scale;
+                if distance > 0.0 {
+                    log.add_rounding(p, distance);
+                }
+            }
+        }
+        self.overlay
+            .add_path_iter(contour.iter().map(|p| self.adapter.float_to_int(p)), shape_type);
+    }
+
     /// Reinit `FloatOverlay` instance and initializes it with subject and clip shapes.
     /// - `subj`: A `ShapeResource` that define the subject.
     /// - `clip`: A `ShapeResource` that define the clip.
@@ -353,12 +437,187 @@ where
     /// particularly for complex or resource-intensive geometries.
     #[inline]
     pub fn overlay(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Shapes<P> {
-        let preserve_output_collinear = self.overlay.options.preserve_output_collinear;
         let shapes = self.overlay.overlay(overlay_rule, fill_rule);
+        self.shapes_to_float(shapes)
+    }
+
+    /// Extracts every winding level of the subject as a separate set of shapes, from a single
+    /// overlay pass. See [`Overlay::extract_winding_levels`].
+    ///
+    /// The result at index `k - 1` holds the regions covered with a winding number of at least `k`.
+    #[inline]
+    pub fn extract_winding_levels(&mut self) -> Vec<Shapes<P>> {
+        let levels = self.overlay.extract_winding_levels();
+        levels
+            .into_iter()
+            .map(|shapes| self.shapes_to_float(shapes))
+            .collect()
+    }
+
+    /// Same as [`Self::overlay`], but also reports how far snapping displaced the output vertices
+    /// from the exact input edges.
+    ///
+    /// See [`Overlay::overlay_with_displacement`]. Distances are given in float units and, as
+    /// `grid_distance`, in units of the integer grid of the adapter; the snapped points are those of
+    /// the cleaned float result.
+    ///
+    /// The rounding of the input vertices to the grid is only known to an overlay created by a
+    /// `*_tracked` constructor. Such an overlay flags every input vertex that rounding moved as
+    /// `moved_input` and adds the rounding to the distances, see
+    /// [`displacement`](crate::core::displacement); other overlays report the snapping alone.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn overlay_with_displacement(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> (Shapes<P>, DisplacementReport<P, P::Scalar>) {
+        let (shapes, split) = self.overlay.overlay_tracking_splits(overlay_rule, fill_rule);
+        let shapes = self.shapes_to_float(shapes);
+
+        let inv_scale = self.adapter.inv_scale().to_f64();
+        let rounding = self
+            .overlay
+            .input_log
+            .as_ref()
+            .map_or(&[][..], |log| log.rounding());
+        let report = DisplacementMap::new(&split, rounding).report(
+            &shapes,
+            |p| self.adapter.float_to_int(p),
+            |distance| P::Scalar::from_float(distance * inv_scale),
+        );
+        (shapes, report)
+    }
+
+    /// Same as [`Self::overlay`], but also returns the origin of every output vertex,
+    /// see [`Overlay::overlay_with_vertex_sources`].
+    ///
+    /// Returns `None` for the sources unless the overlay was created by a `*_tracked` constructor,
+    /// such as [`Self::with_adapter_tracked`].
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn overlay_with_vertex_sources(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> (Shapes<P>, Option<VertexSources>) {
+        if self.overlay.input_log.is_none() {
+            return (self.overlay(overlay_rule, fill_rule), None);
+        }
+        let (shapes, split) = self.overlay.overlay_tracking_splits(overlay_rule, fill_rule);
+        let shapes = self.shapes_to_float(shapes);
+        let sources = self
+            .overlay
+            .input_log
+            .as_ref()
+            .map(|log| log.sources(&shapes, &split, |p| self.adapter.float_to_int(p)));
+        (shapes, sources)
+    }
+
+    /// Same as [`Self::overlay`], but also carries per-vertex attributes, such as elevation (Z) or
+    /// measure (M), to the output.
+    ///
+    /// `attributes` returns the attributes of an input vertex by its [`PointIndex`]. An output vertex
+    /// that is an input vertex keeps its attributes, averaged if several input vertices share the
+    /// position. An intersection vertex is linearly interpolated along every input edge through it,
+    /// and the results are averaged, so coincident and crossing edges contribute equally.
+    ///
+    /// The attributes are parallel to the output: `attributes[shape][contour][vertex]`. Returns `None`
+    /// for them unless the overlay was created by a `*_tracked` constructor, such as
+    /// [`Self::with_adapter_tracked`].
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `attributes`: Attributes of an input vertex.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use i_overlay::core::error::{InputRole, PointIndex};
+    /// use i_overlay::core::fill_rule::FillRule;
+    /// use i_overlay::core::overlay_rule::OverlayRule;
+    /// use i_overlay::float::overlay::FloatOverlay;
+    ///
+    /// let subj = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+    /// let clip = [[2.0, -2.0], [6.0, -2.0], [6.0, 2.0], [2.0, 2.0]];
+    /// // elevation of every input vertex
+    /// let subj_z = [0.0, 4.0, 4.0, 0.0];
+    /// let clip_z = [10.0, 10.0, 10.0, 10.0];
+    ///
+    /// let mut overlay = FloatOverlay::with_subj_and_clip_tracked(&subj, &clip);
+    ///
+    /// let z = |index: PointIndex| match index.role {
+    ///     InputRole::Subject => [subj_z[index.point]],
+    ///     _ => [clip_z[index.point]],
+    /// };
+    /// let (shapes, attributes) = overlay.overlay_with_attributes(OverlayRule::Difference, FillRule::NonZero, z);
+    /// let attributes = attributes.unwrap();
+    ///
+    /// // (2, 0) is on the subject edge (0, 0) - (4, 0) and the clip edge (2, 2) - (2, -2)
+    /// let vertex = shapes[0][0].iter().position(|p| *p == [2.0, 0.0]).unwrap();
+    /// assert_eq!(attributes[0][0][vertex], [6.0]);
+    /// ```
+    pub fn overlay_with_attributes<const N: usize>(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        attributes: impl Fn(PointIndex) -> [P::Scalar; N],
+    ) -> (Shapes<P>, Option<ShapeAttributes<P::Scalar, N>>) {
+        if self.overlay.input_log.is_none() {
+            return (self.overlay(overlay_rule, fill_rule), None);
+        }
+        let (shapes, split) = self.overlay.overlay_tracking_splits(overlay_rule, fill_rule);
+        let shapes = self.shapes_to_float(shapes);
+        let result =
+            self.overlay.input_log.as_ref().map(|log| {
+                log.shape_attributes(&shapes, &split, |p| self.adapter.float_to_int(p), attributes)
+            });
+        (shapes, result)
+    }
+
+    /// Same as [`Self::overlay`], but also returns [`OverlayStats`] of the operation.
+    /// See [`Overlay::overlay_with_stats`]; the float conversion counts towards `extract_time` and
+    /// `output_edges` refers to the cleaned float contours.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clock`: Time source for the phase timings.
+    pub fn overlay_with_stats(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        clock: &mut dyn StatsClock,
+    ) -> (Shapes<P>, OverlayStats) {
+        let (shapes, mut stats) = self.overlay.overlay_with_stats(overlay_rule, fill_rule, clock);
+        let mut timer = PhaseTimer::start(clock);
+        let shapes = self.shapes_to_float(shapes);
+        stats.extract_time += timer.lap();
+        stats.output_edges = shapes.iter().flatten().map(|contour| contour.len()).sum();
+        (shapes, stats)
+    }
+
+    /// Same as [`Self::overlay`], but reports progress to `control` and lets it cancel the
+    /// operation with [`OverlayError::Cancelled`].
+    /// See [`Overlay::try_overlay_with_control`] for when `control` is polled.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `control`: Receives progress updates and may cancel.
+    pub fn try_overlay_with_control(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        control: &mut dyn OperationControl,
+    ) -> Result<Shapes<P>, OverlayError> {
+        let shapes = self
+            .overlay
+            .try_overlay_with_control(overlay_rule, fill_rule, control)?;
+        Ok(self.shapes_to_float(shapes))
+    }
+
+    #[inline]
+    fn shapes_to_float(&self, shapes: IntShapes<I>) -> Shapes<P> {
         let mut float = shapes.to_float(&self.adapter);
 
         if self.clean_result {
-            if preserve_output_collinear {
+            if self.overlay.options.preserve_output_collinear {
                 float.despike_contour(&self.adapter);
             } else {
                 float.simplify_contour(&self.adapter);
@@ -447,6 +706,49 @@ impl<P: FloatPointCompatible> FloatOverlay<P> {
         Self::from_subj_and_clip_custom(subj, clip, options, solver)
     }
 
+    /// Same as [`Self::with_subj_and_clip`], but logs the input for
+    /// [`Self::overlay_with_vertex_sources`] and [`Self::overlay_with_attributes`].
+    /// Uses the default integer engine (`i32`).
+    #[inline]
+    pub fn with_subj_and_clip_tracked<R0, R1>(subj: &R0, clip: &R1) -> Self
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        Self::from_subj_and_clip_tracked(subj, clip)
+    }
+
+    /// Same as [`Self::with_subj_and_clip`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate.
+    #[inline]
+    pub fn try_with_subj_and_clip<R0, R1>(subj: &R0, clip: &R1) -> Result<Self, OverlayError>
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        Self::try_from_subj_and_clip(subj, clip)
+    }
+
+    /// Same as [`Self::with_subj_and_clip_custom`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate.
+    #[inline]
+    pub fn try_with_subj_and_clip_custom<R0, R1>(
+        subj: &R0,
+        clip: &R1,
+        options: OverlayOptions<P::Scalar, i32>,
+        solver: Solver,
+    ) -> Result<Self, OverlayError>
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        Self::try_from_subj_and_clip_custom(subj, clip, options, solver)
+    }
+
     /// Creates a new `FloatOverlay` instance and initializes it with subject.
     /// Uses the default integer engine (`i32`).
     #[inline]
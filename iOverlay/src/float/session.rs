@@ -0,0 +1,473 @@
+//! A shared float-to-integer adapter for running several operations on one integer lattice.
+//!
+//! Every float operation normally computes its own [`FloatPointAdapter`] from the bounds of its
+//! input, so two operations on neighbouring data snap shared vertices to different grids. An
+//! [`OverlaySession`] fixes the adapter once, over a declared world rectangle or with a fixed
+//! scale, and runs every operation through it. All outputs therefore lie on one common lattice and
+//! shared vertices match exactly.
+
+use crate::core::error::{InputRole, OverlayError, PointIndex, validate_float_paths};
+use crate::core::fill_rule::FillRule;
+use crate::core::integer::OverlayInt;
+use crate::core::overlay::ShapeType;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::float::overlay::{FloatOverlay, OverlayOptions};
+use crate::float::relate::FloatPredicateOverlay;
+use crate::float::scale::FixedScaleOverlayError;
+use crate::float::string_overlay::FloatStringOverlay;
+use crate::mesh::outline::offset::{outline_extent, outline_with_adapter};
+use crate::mesh::stroke::offset::{stroke_extent, stroke_with_adapter};
+use crate::mesh::style::{OutlineStyle, StrokeStyle};
+use crate::string::clip::ClipRule;
+use crate::string::rule::StringRule;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+use i_shape::base::data::{Paths, Shapes};
+use i_shape::source::resource::ShapeResource;
+
+/// Runs float operations through one shared [`FloatPointAdapter`].
+///
+/// The input of every operation must lie inside the session rectangle, and stroke and outline
+/// input must leave room for the width, caps and joins too. Every operation checks its input up
+/// front and returns [`OverlayError::CoordinateOutOfRange`] for the first point outside, or
+/// [`OverlayError::NonFiniteCoordinate`] for a NaN or infinite one.
+///
+/// # Example
+///
+/// ```
+/// use i_float::float::rect::FloatRect;
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::float::session::OverlaySession;
+///
+/// let session = OverlaySession::<[f64; 2]>::with_rect(FloatRect::new(0.0, 100.0, 0.0, 100.0));
+///
+/// let left = vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+/// let right = vec![[10.0, 0.0], [10.0, 10.0], [20.0, 10.0], [20.0, 0.0]];
+/// let cut = vec![[5.0, 5.0], [5.0, 15.0], [15.0, 15.0], [15.0, 5.0]];
+///
+/// let a = session.overlay(&left, &cut, OverlayRule::Difference, FillRule::EvenOdd).unwrap();
+/// let b = session.overlay(&right, &cut, OverlayRule::Difference, FillRule::EvenOdd).unwrap();
+///
+/// assert_eq!(a.len(), 1);
+/// assert_eq!(b.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct OverlaySession<P: FloatPointCompatible, I: OverlayInt = i32> {
+    adapter: FloatPointAdapter<P, I>,
+    pub options: OverlayOptions<P::Scalar, I>,
+    pub solver: Solver,
+}
+
+impl<P, I> OverlaySession<P, I>
+where
+    P: FloatPointCompatible,
+    I: OverlayInt,
+{
+    /// Creates a session whose adapter covers the given world rectangle with the finest safe scale.
+    /// - `rect`: The world rectangle every operation input must lie in.
+    #[inline]
+    pub fn with_rect(rect: FloatRect<P::Scalar>) -> Self {
+        Self::with_adapter(FloatPointAdapter::new(rect))
+    }
+
+    /// Creates a session with a fixed float-to-integer scale over the given world rectangle.
+    ///
+    /// Returns an error if the scale is not finite, not positive or too large for the rectangle.
+    /// - `rect`: The world rectangle every operation input must lie in.
+    /// - `scale`: Fixed float-to-integer scale. Use `scale = 1.0 / grid_size` if you prefer grid size semantics.
+    #[inline]
+    pub fn with_rect_and_scale(
+        rect: FloatRect<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Self, FixedScaleOverlayError> {
+        Ok(Self::with_adapter(FloatPointAdapter::try_with_scale(
+            rect, scale,
+        )?))
+    }
+
+    /// Creates a session from a pre-configured adapter.
+    #[inline]
+    pub fn with_adapter(adapter: FloatPointAdapter<P, I>) -> Self {
+        Self {
+            adapter,
+            options: Default::default(),
+            solver: Default::default(),
+        }
+    }
+
+    /// The shared adapter.
+    #[inline]
+    pub fn adapter(&self) -> &FloatPointAdapter<P, I> {
+        &self.adapter
+    }
+
+    /// Returns `true` if every point of the resource lies inside the session rectangle.
+    #[inline]
+    pub fn contains<R: ShapeResource<P> + ?Sized>(&self, resource: &R) -> bool {
+        let rect = self.adapter.rect();
+        resource.iter_paths().flatten().all(|p| rect.contains(p))
+    }
+
+    /// Checks that every point of `resource` is finite and lies at least `extent` inside the
+    /// session rectangle, as input outside it may overflow the integer lattice.
+    fn check<R: ShapeResource<P> + ?Sized>(
+        &self,
+        role: InputRole,
+        resource: &R,
+        extent: P::Scalar,
+    ) -> Result<(), OverlayError> {
+        validate_float_paths(role, resource.iter_paths())?;
+        let rect = self.adapter.rect();
+        for (contour, path) in resource.iter_paths().enumerate() {
+            let outside = path.iter().position(|p| {
+                p.x() - extent < rect.min_x
+                    || p.x() + extent > rect.max_x
+                    || p.y() - extent < rect.min_y
+                    || p.y() + extent > rect.max_y
+            });
+            if let Some(point) = outside {
+                return Err(OverlayError::CoordinateOutOfRange(PointIndex {
+                    role,
+                    contour,
+                    point,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Snaps a point to the session lattice.
+    #[inline]
+    pub fn snap(&self, point: &P) -> P {
+        self.adapter.snap_to_grid(point)
+    }
+
+    /// Executes a Boolean operation between subject and clip shapes.
+    /// - `subj`: A `ShapeResource` that define the subject.
+    /// - `clip`: A `ShapeResource` that define the clip.
+    /// - `overlay_rule`: The Boolean operation to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn overlay<R0, R1>(
+        &self,
+        subj: &R0,
+        clip: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Result<Shapes<P>, OverlayError>
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        self.check(InputRole::Subject, subj, P::Scalar::ZERO)?;
+        self.check(InputRole::Clip, clip, P::Scalar::ZERO)?;
+        let capacity = points_count(subj) + points_count(clip);
+        Ok(
+            FloatOverlay::new_custom(self.adapter.clone(), self.options, self.solver, capacity)
+                .unsafe_add_source(subj, ShapeType::Subject)
+                .unsafe_add_source(clip, ShapeType::Clip)
+                .overlay(overlay_rule, fill_rule),
+        )
+    }
+
+    /// Simplifies shapes by resolving self-intersections and removing degenerate parts.
+    /// - `subj`: A `ShapeResource` that define the shapes.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn simplify<R>(&self, subj: &R, fill_rule: FillRule) -> Result<Shapes<P>, OverlayError>
+    where
+        R: ShapeResource<P> + ?Sized,
+    {
+        self.check(InputRole::Subject, subj, P::Scalar::ZERO)?;
+        Ok(FloatOverlay::new_custom(
+            self.adapter.clone(),
+            self.options,
+            self.solver,
+            points_count(subj),
+        )
+        .unsafe_add_source(subj, ShapeType::Subject)
+        .overlay(OverlayRule::Subject, fill_rule))
+    }
+
+    /// Slices shapes by string lines.
+    /// - `shape`: A `ShapeResource` that define the shapes.
+    /// - `string`: A `ShapeResource` that define the string lines.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn slice<R0, R1>(
+        &self,
+        shape: &R0,
+        string: &R1,
+        fill_rule: FillRule,
+    ) -> Result<Shapes<P>, OverlayError>
+    where
+        R0: ShapeResource<P>,
+        R1: ShapeResource<P>,
+    {
+        self.check(InputRole::Subject, shape, P::Scalar::ZERO)?;
+        self.check(InputRole::String, string, P::Scalar::ZERO)?;
+        let capacity = points_count(shape) + points_count(string);
+        Ok(FloatStringOverlay::with_adapter(self.adapter.clone(), capacity)
+            .unsafe_add_shapes(shape)
+            .unsafe_add_string_lines(string)
+            .build_graph_view_with_solver(fill_rule, self.solver)
+            .map(|graph| graph.extract_shapes_custom(StringRule::Slice, self.options))
+            .unwrap_or_default())
+    }
+
+    /// Clips string lines by shapes.
+    /// - `string`: A `ShapeResource` that define the string lines.
+    /// - `shape`: A `ShapeResource` that define the clipping shapes.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how boundary and inversion settings affect the result.
+    pub fn clip<R0, R1>(
+        &self,
+        string: &R0,
+        shape: &R1,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Paths<P>, OverlayError>
+    where
+        R0: ShapeResource<P>,
+        R1: ShapeResource<P>,
+    {
+        self.check(InputRole::String, string, P::Scalar::ZERO)?;
+        self.check(InputRole::Subject, shape, P::Scalar::ZERO)?;
+        let capacity = points_count(shape) + points_count(string);
+        Ok(FloatStringOverlay::with_adapter(self.adapter.clone(), capacity)
+            .unsafe_add_shapes(shape)
+            .unsafe_add_string_lines(string)
+            .clip_string_lines_with_solver(fill_rule, clip_rule, self.solver))
+    }
+
+    /// Prepares a spatial predicate evaluation between subject and clip shapes.
+    /// - `subj`: A `ShapeResource` that define the subject.
+    /// - `clip`: A `ShapeResource` that define the clip.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn relate<R0, R1>(
+        &self,
+        subj: &R0,
+        clip: &R1,
+        fill_rule: FillRule,
+    ) -> Result<FloatPredicateOverlay<P, I>, OverlayError>
+    where
+        R0: ShapeResource<P> + ?Sized,
+        R1: ShapeResource<P> + ?Sized,
+    {
+        self.check(InputRole::Subject, subj, P::Scalar::ZERO)?;
+        self.check(InputRole::Clip, clip, P::Scalar::ZERO)?;
+        let capacity = points_count(subj) + points_count(clip);
+        let mut overlay = FloatPredicateOverlay::with_adapter_custom(
+            self.adapter.clone(),
+            fill_rule,
+            self.solver,
+            capacity,
+        );
+        overlay.add_source(subj, ShapeType::Subject);
+        overlay.add_source(clip, ShapeType::Clip);
+        Ok(overlay)
+    }
+}
+
+impl<P, I> OverlaySession<P, I>
+where
+    P: FloatPointCompatible + 'static,
+    I: OverlayInt + 'static,
+{
+    /// Generates stroke shapes for paths, contours, or shapes.
+    /// - `source`: A `ShapeResource` that define the paths.
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    ///
+    /// Returns [`OverlayError::NonFiniteStyle`] if a style parameter is NaN or infinite.
+    pub fn stroke<R>(
+        &self,
+        source: &R,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+    ) -> Result<Shapes<P>, OverlayError>
+    where
+        R: ShapeResource<P>,
+    {
+        if !style.is_finite() {
+            return Err(OverlayError::NonFiniteStyle);
+        }
+        let extent = stroke_extent(&style, is_closed_path);
+        self.check(InputRole::Subject, source, extent)?;
+        Ok(stroke_with_adapter(
+            source,
+            style,
+            is_closed_path,
+            self.options,
+            self.adapter.clone(),
+        ))
+    }
+
+    /// Generates outline shapes for contours, or shapes.
+    /// - `source`: A `ShapeResource` that define the contours.
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    ///
+    /// Returns [`OverlayError::NonFiniteStyle`] if a style parameter is NaN or infinite.
+    pub fn outline<R>(&self, source: &R, style: &OutlineStyle<P::Scalar>) -> Result<Shapes<P>, OverlayError>
+    where
+        R: ShapeResource<P>,
+    {
+        if !style.is_finite() {
+            return Err(OverlayError::NonFiniteStyle);
+        }
+        self.check(InputRole::Subject, source, outline_extent::<P>(style))?;
+        Ok(outline_with_adapter(
+            source,
+            style,
+            self.options,
+            self.adapter.clone(),
+        ))
+    }
+}
+
+#[inline]
+fn points_count<P: FloatPointCompatible, R: ShapeResource<P> + ?Sized>(resource: &R) -> usize {
+    resource.iter_paths().fold(0, |s, c| s + c.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::error::{InputRole, OverlayError, PointIndex};
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::session::OverlaySession;
+    use crate::mesh::style::{OutlineStyle, StrokeStyle};
+    use crate::string::clip::ClipRule;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::float::rect::FloatRect;
+
+    fn contains_point(shapes: &[Vec<Vec<[f64; 2]>>], p: [f64; 2]) -> bool {
+        shapes.iter().flatten().flatten().any(|q| *q == p)
+    }
+
+    #[test]
+    fn test_shared_vertices_match() {
+        let rect = FloatRect::new(0.0, 30.0, 0.0, 30.0);
+        let session = OverlaySession::<[f64; 2]>::with_rect(rect);
+
+        let left = vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let right = vec![[10.0, 0.0], [10.0, 10.0], [20.0, 10.0], [20.0, 0.0]];
+        let cut = vec![
+            [0.0, 1.0 / 3.0],
+            [20.0, 1.0 / 3.0],
+            [20.0, 2.0 / 3.0],
+            [0.0, 2.0 / 3.0],
+        ];
+
+        let a = session
+            .overlay(&left, &cut, OverlayRule::Difference, FillRule::EvenOdd)
+            .unwrap();
+        let b = session
+            .overlay(&right, &cut, OverlayRule::Difference, FillRule::EvenOdd)
+            .unwrap();
+
+        let shared = session.snap(&[10.0, 1.0 / 3.0]);
+        assert!(contains_point(&a, shared));
+        assert!(contains_point(&b, shared));
+
+        for p in a.iter().chain(b.iter()).flatten().flatten() {
+            assert_eq!(*p, session.snap(p));
+        }
+    }
+
+    #[test]
+    fn test_fixed_scale() {
+        let rect = FloatRect::new(-10.0, 10.0, -10.0, 10.0);
+        let session = OverlaySession::<[f64; 2]>::with_rect_and_scale(rect, 8.0).unwrap();
+
+        let square = vec![[0.1, 0.1], [0.1, 2.3], [2.3, 2.3], [2.3, 0.1]];
+        let shapes = session.simplify(&square, FillRule::NonZero).unwrap();
+
+        assert_eq!(shapes.len(), 1);
+        for p in shapes.iter().flatten().flatten() {
+            assert_eq!(p[0] * 8.0, (p[0] * 8.0).round());
+            assert_eq!(p[1] * 8.0, (p[1] * 8.0).round());
+        }
+
+        assert!(OverlaySession::<[f64; 2]>::with_rect_and_scale(rect, 0.0).is_err());
+        assert!(OverlaySession::<[f64; 2]>::with_rect_and_scale(rect, 1e30).is_err());
+    }
+
+    #[test]
+    fn test_input_outside_rect() {
+        let session = OverlaySession::<[f64; 2]>::with_rect(FloatRect::new(0.0, 10.0, 0.0, 10.0));
+        let inside = vec![[1.0, 1.0], [1.0, 9.0], [9.0, 9.0], [9.0, 1.0]];
+        let square = vec![[0.0, 0.0], [0.0, 20.0], [20.0, 20.0], [20.0, 0.0]];
+        let line = vec![[2.0, 5.0], [8.0, 5.0]];
+
+        let index = |role, point| PointIndex {
+            role,
+            contour: 0,
+            point,
+        };
+        assert_eq!(
+            session.simplify(&square, FillRule::NonZero),
+            Err(OverlayError::CoordinateOutOfRange(index(InputRole::Subject, 1)))
+        );
+        assert_eq!(
+            session.overlay(&inside, &square, OverlayRule::Union, FillRule::NonZero),
+            Err(OverlayError::CoordinateOutOfRange(index(InputRole::Clip, 1)))
+        );
+        assert_eq!(
+            session.simplify(&[[0.0, f64::NAN]], FillRule::NonZero),
+            Err(OverlayError::NonFiniteCoordinate(index(InputRole::Subject, 0)))
+        );
+
+        // the stroke and the outline need room for their width too
+        assert!(session.stroke(&line, StrokeStyle::new(2.0), false).is_ok());
+        assert_eq!(
+            session.stroke(&line, StrokeStyle::new(6.0), false),
+            Err(OverlayError::CoordinateOutOfRange(index(InputRole::Subject, 0)))
+        );
+        assert!(session.outline(&inside, &OutlineStyle::new(0.4)).is_ok());
+        assert_eq!(
+            session.outline(&inside, &OutlineStyle::new(2.0)),
+            Err(OverlayError::CoordinateOutOfRange(index(InputRole::Subject, 0)))
+        );
+        assert_eq!(
+            session.stroke(&line, StrokeStyle::new(f64::NAN), false),
+            Err(OverlayError::NonFiniteStyle)
+        );
+    }
+
+    #[test]
+    fn test_slice_clip_relate_stroke() {
+        let rect = FloatRect::new(-10.0, 10.0, -10.0, 10.0);
+        let session = OverlaySession::<[f64; 2], i64>::with_rect(rect);
+
+        let square = vec![[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [4.0, 0.0]];
+        let line = vec![[-1.0, 2.0], [5.0, 2.0]];
+        let clip_rule = ClipRule {
+            invert: false,
+            boundary_included: false,
+        };
+
+        assert!(session.contains(&square));
+        assert!(!session.contains(&[[20.0, 0.0]]));
+
+        assert_eq!(session.slice(&square, &line, FillRule::NonZero).unwrap().len(), 2);
+        assert_eq!(
+            session.clip(&line, &square, FillRule::NonZero, clip_rule),
+            Ok(vec![vec![[0.0, 2.0], [4.0, 2.0]]])
+        );
+        let other = vec![[2.0, 2.0], [2.0, 6.0], [6.0, 6.0], [6.0, 2.0]];
+        let mut relate = session.relate(&other, &square, FillRule::NonZero).unwrap();
+        assert!(relate.intersects());
+
+        let stroke = session.stroke(&line, StrokeStyle::new(2.0), false).unwrap();
+        assert_eq!(stroke.len(), 1);
+        let hull = vec![[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+        let outline = session.outline(&hull, &OutlineStyle::new(1.0)).unwrap();
+        assert_eq!(outline.len(), 1);
+        for p in stroke.iter().chain(outline.iter()).flatten().flatten() {
+            assert_eq!(*p, session.snap(p));
+        }
+    }
+}
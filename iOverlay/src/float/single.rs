@@ -1,3 +1,4 @@
+use crate::core::error::OverlayError;
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
 use crate::core::overlay_rule::OverlayRule;
@@ -48,6 +49,27 @@ where
     fn overlay_as<I>(&self, source: &R1, overlay_rule: OverlayRule, fill_rule: FillRule) -> Shapes<P>
     where
         I: OverlayInt;
+
+    /// Same as [`Self::overlay`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate.
+    fn try_overlay(
+        &self,
+        source: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Result<Shapes<P>, OverlayError>;
+
+    /// Same as [`Self::try_overlay`], but with an explicit integer engine.
+    fn try_overlay_as<I>(
+        &self,
+        source: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Result<Shapes<P>, OverlayError>
+    where
+        I: OverlayInt;
 }
 
 impl<R0, R1, P> SingleFloatOverlay<R0, R1, P> for R0
@@ -68,6 +90,29 @@ where
     {
         FloatOverlay::<P, I>::from_subj_and_clip(self, resource).overlay(overlay_rule, fill_rule)
     }
+
+    #[inline]
+    fn try_overlay(
+        &self,
+        resource: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Result<Shapes<P>, OverlayError> {
+        self.try_overlay_as::<i32>(resource, overlay_rule, fill_rule)
+    }
+
+    #[inline]
+    fn try_overlay_as<I>(
+        &self,
+        resource: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Result<Shapes<P>, OverlayError>
+    where
+        I: OverlayInt,
+    {
+        Ok(FloatOverlay::<P, I>::try_from_subj_and_clip(self, resource)?.overlay(overlay_rule, fill_rule))
+    }
 }
 
 #[cfg(test)]
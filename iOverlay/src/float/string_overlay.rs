@@ -1,10 +1,14 @@
+use crate::core::control::OperationControl;
+use crate::core::error::{InputRole, OverlayError, PointIndex, validate_float_paths};
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
+use crate::core::provenance::PathAttributes;
 use crate::core::solver::Solver;
 use crate::float::scale::FixedScaleOverlayError;
 use crate::float::string_graph::FloatStringGraph;
 use crate::string::clip::ClipRule;
 use crate::string::overlay::StringOverlay;
+use alloc::vec::Vec;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_shape::base::data::Paths;
@@ -44,6 +48,16 @@ where
         }
     }
 
+    /// Same as [`Self::with_adapter`], but the overlay logs the string lines added to it, for
+    /// [`Self::clip_string_lines_with_attributes`]. Logging keeps a copy of every added point.
+    #[inline]
+    pub fn with_adapter_tracked(adapter: FloatPointAdapter<P, I>, capacity: usize) -> Self {
+        Self {
+            overlay: StringOverlay::new_tracked(capacity),
+            adapter,
+        }
+    }
+
     /// Creates a new `FloatOverlay` instance and initializes it with subject and clip shapes.
     ///
     /// This variant uses automatic scaling based on the combined bounds of `shape` and `string`.
@@ -72,6 +86,37 @@ where
             .unsafe_add_string_lines(string)
     }
 
+    /// Same as [`Self::from_shape_and_string`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate. Shape contours are reported as [`InputRole::Subject`].
+    pub fn try_from_shape_and_string<R0, R1>(shape: &R0, string: &R1) -> Result<Self, OverlayError>
+    where
+        R0: ShapeResource<P>,
+        R1: ShapeResource<P>,
+    {
+        validate_float_paths(InputRole::Subject, shape.iter_paths())?;
+        validate_float_paths(InputRole::String, string.iter_paths())?;
+        Ok(Self::from_shape_and_string(shape, string))
+    }
+
+    /// Same as [`Self::from_shape_and_string`], but logs the string lines as
+    /// [`Self::with_adapter_tracked`] does.
+    pub fn from_shape_and_string_tracked<R0, R1>(shape: &R0, string: &R1) -> Self
+    where
+        R0: ShapeResource<P>,
+        R1: ShapeResource<P>,
+    {
+        let iter = shape.iter_paths().chain(string.iter_paths()).flatten();
+        let adapter = FloatPointAdapter::with_iter(iter);
+        let shape_capacity = shape.iter_paths().fold(0, |s, c| s + c.len());
+        let string_capacity = string.iter_paths().fold(0, |s, c| s + c.len());
+
+        Self::with_adapter_tracked(adapter, shape_capacity + string_capacity)
+            .unsafe_add_shapes(shape)
+            .unsafe_add_string_lines(string)
+    }
+
     /// Creates a new `FloatStringOverlay` instance with a fixed float-to-integer scale.
     ///
     /// This variant validates that the requested scale is finite, positive, and fits the
@@ -140,10 +185,14 @@ where
     /// - **Safety**: Marked `unsafe` because it assumes each path is fully contained within the bounding box.
     #[inline]
     pub fn unsafe_add_string_line(mut self, path: &[P]) -> Self {
+        if let Some(log) = self.overlay.input_log.as_mut() {
+            let points: Vec<_> = path.iter().map(|p| self.adapter.float_to_int(p)).collect();
+            log.add_path(InputRole::String, &points, false);
+        }
         for w in path.windows(2) {
             let a = self.adapter.float_to_int(&w[0]);
             let b = self.adapter.float_to_int(&w[1]);
-            self.overlay.add_string_line([a, b]);
+            self.overlay.push_string_line([a, b]);
         }
 
         self
@@ -195,6 +244,89 @@ where
             .clip_string_lines_with_solver(fill_rule, clip_rule, solver);
         paths.to_float(&self.adapter)
     }
+
+    /// Same as [`Self::clip_string_lines_with_solver`], but reports progress to `control` and lets it
+    /// cancel the operation with [`OverlayError::Cancelled`].
+    /// See [`StringOverlay::try_clip_string_lines_with_control`] for when `control` is polled.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how the boundary and inversion settings affect the result.
+    /// - `solver`: Type of solver to use.
+    /// - `control`: Receives progress updates and may cancel.
+    pub fn try_clip_string_lines_with_control(
+        self,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+        control: &mut dyn OperationControl,
+    ) -> Result<Paths<P>, OverlayError> {
+        let paths = self
+            .overlay
+            .try_clip_string_lines_with_control(fill_rule, clip_rule, solver, control)?;
+        Ok(paths.to_float(&self.adapter))
+    }
+
+    /// Same as [`Self::clip_string_lines_with_solver`], but also carries per-vertex attributes of the
+    /// string lines, such as elevation (Z) or measure (M), to the clipped paths.
+    ///
+    /// `attributes` returns the attributes of a string line vertex by its [`PointIndex`], with
+    /// `role` [`InputRole::String`]. A vertex of a clipped path that is an input vertex keeps its
+    /// attributes; a new vertex is linearly interpolated along the string edges through it, averaged
+    /// if several coincide. Shape edges do not contribute.
+    ///
+    /// The attributes are parallel to the output: `attributes[path][vertex]`. Returns `None` for them
+    /// unless the overlay was created by a `*_tracked` constructor, such as
+    /// [`Self::with_adapter_tracked`].
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how the boundary and inversion settings affect the result.
+    /// - `solver`: Type of solver to use.
+    /// - `attributes`: Attributes of a string line vertex.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use i_float::adapter::FloatPointAdapter;
+    /// use i_float::float::rect::FloatRect;
+    /// use i_overlay::core::error::PointIndex;
+    /// use i_overlay::core::fill_rule::FillRule;
+    /// use i_overlay::float::string_overlay::FloatStringOverlay;
+    /// use i_overlay::string::clip::ClipRule;
+    ///
+    /// let square = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+    /// let line = [[-4.0, 2.0], [8.0, 2.0]];
+    /// // linear measure along the line
+    /// let m = [0.0, 12.0];
+    ///
+    /// let adapter = FloatPointAdapter::<[f64; 2], i32>::new(FloatRect::new(-4.0, 8.0, 0.0, 4.0));
+    /// let (paths, attributes) = FloatStringOverlay::with_adapter_tracked(adapter, 8)
+    ///     .unsafe_add_shape_contour(&square)
+    ///     .unsafe_add_string_line(&line)
+    ///     .clip_string_lines_with_attributes(
+    ///         FillRule::NonZero,
+    ///         ClipRule { invert: false, boundary_included: false },
+    ///         Default::default(),
+    ///         |index: PointIndex| [m[index.point]],
+    ///     );
+    ///
+    /// assert_eq!(paths.len(), 1);
+    /// let mut measures: Vec<f64> = attributes.unwrap()[0].iter().map(|a| a[0]).collect();
+    /// measures.sort_by(f64::total_cmp);
+    /// assert_eq!(measures, [4.0, 8.0]);
+    /// ```
+    pub fn clip_string_lines_with_attributes<const N: usize>(
+        self,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+        attributes: impl Fn(PointIndex) -> [P::Scalar; N],
+    ) -> (Paths<P>, Option<PathAttributes<P::Scalar, N>>) {
+        let (paths, split, log) = self
+            .overlay
+            .clip_string_lines_tracking_splits(fill_rule, clip_rule, solver);
+        let paths = paths.to_float(&self.adapter);
+        let result =
+            log.map(|log| log.path_attributes(&paths, &split, |p| self.adapter.float_to_int(p), attributes));
+        (paths, result)
+    }
 }
 
 impl<P: FloatPointCompatible> FloatStringOverlay<P> {
@@ -209,6 +341,31 @@ impl<P: FloatPointCompatible> FloatStringOverlay<P> {
         Self::from_shape_and_string(shape, string)
     }
 
+    /// Same as [`Self::with_shape_and_string`], but logs the string lines for
+    /// [`Self::clip_string_lines_with_attributes`].
+    /// Uses the default integer engine (`i32`).
+    #[inline]
+    pub fn with_shape_and_string_tracked<R0, R1>(shape: &R0, string: &R1) -> Self
+    where
+        R0: ShapeResource<P>,
+        R1: ShapeResource<P>,
+    {
+        Self::from_shape_and_string_tracked(shape, string)
+    }
+
+    /// Same as [`Self::with_shape_and_string`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first point with a NaN or infinite
+    /// coordinate. Shape contours are reported as [`InputRole::Subject`].
+    #[inline]
+    pub fn try_with_shape_and_string<R0, R1>(shape: &R0, string: &R1) -> Result<Self, OverlayError>
+    where
+        R0: ShapeResource<P>,
+        R1: ShapeResource<P>,
+    {
+        Self::try_from_shape_and_string(shape, string)
+    }
+
     /// Creates a new `FloatStringOverlay` instance with a fixed float-to-integer scale.
     /// Uses the default integer engine (`i32`).
     #[inline]
@@ -3,7 +3,10 @@ mod miter;
 pub mod outline;
 mod overlay;
 mod rotator;
+pub mod source;
 pub mod stroke;
 pub mod style;
 mod subject;
+pub mod triangulation;
+pub mod variable_outline;
 pub mod variable_stroke;
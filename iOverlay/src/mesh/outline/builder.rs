@@ -3,6 +3,7 @@ use crate::mesh::outline::builder_join::JoinBuilder;
 use crate::mesh::outline::builder_join::{BevelJoinBuilder, MiterJoinBuilder, RoundJoinBuilder};
 use crate::mesh::outline::section::OffsetSection;
 use crate::mesh::outline::uniq_iter::{UniqueSegment, UniqueSegmentsIter};
+use crate::mesh::source::{MarkSources, OffsetEdgeKind, SourceTrack};
 use crate::mesh::style::LineJoin;
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::segment::Segment;
@@ -24,8 +25,15 @@ trait OutlineBuild<P: FloatPointCompatible, I: IntNumber> {
         segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
     );
 
+    fn build_with_sources(
+        &self,
+        path: &[P],
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        track: &mut SourceTrack,
+    );
+
     fn capacity(&self, points_count: usize) -> usize;
-    fn additional_offset(&self, radius: P::Scalar) -> P::Scalar;
 }
 
 pub(super) struct OutlineBuilder<P: FloatPointCompatible, I: IntNumber> {
@@ -78,14 +86,35 @@ impl<P: FloatPointCompatible + 'static, I: IntNumber + 'static> OutlineBuilder<P
         self.builder.build(path, adapter, segments);
     }
 
+    /// Same as [`Self::build`], and records the source of every added segment.
+    #[inline]
+    pub(super) fn build_with_sources(
+        &self,
+        path: &[P],
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        track: &mut SourceTrack,
+    ) {
+        self.builder.build_with_sources(path, adapter, segments, track);
+    }
+
     #[inline]
     pub(super) fn capacity(&self, points_count: usize) -> usize {
         self.builder.capacity(points_count)
     }
 
-    #[inline]
-    pub(super) fn additional_offset(&self, radius: P::Scalar) -> P::Scalar {
-        self.builder.additional_offset(radius)
+    /// How far the joins of a builder for `join` reach past the offset, found without creating it.
+    pub(super) fn additional_offset(radius: P::Scalar, join: &LineJoin<P::Scalar>) -> P::Scalar {
+        match join {
+            LineJoin::Miter(ratio) => {
+                let join_builder = MiterJoinBuilder::new(*ratio, radius);
+                JoinBuilder::<P, I>::additional_offset(&join_builder, radius)
+            }
+            // a round join stays within the radius as a bevel does
+            LineJoin::Round(_) | LineJoin::Bevel => {
+                JoinBuilder::<P, I>::additional_offset(&BevelJoinBuilder {}, radius)
+            }
+        }
     }
 }
 
@@ -101,17 +130,27 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> OutlineBuild<P
             return;
         }
 
-        self.build(path, adapter, segments);
+        self.build(path, adapter, segments, None);
     }
 
     #[inline]
-    fn capacity(&self, points_count: usize) -> usize {
-        self.join_builder.capacity() * points_count
+    fn build_with_sources(
+        &self,
+        path: &[P],
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        track: &mut SourceTrack,
+    ) {
+        if path.len() < 2 {
+            return;
+        }
+
+        self.build(path, adapter, segments, Some(track));
     }
 
     #[inline]
-    fn additional_offset(&self, radius: P::Scalar) -> P::Scalar {
-        self.join_builder.additional_offset(radius)
+    fn capacity(&self, points_count: usize) -> usize {
+        self.join_builder.capacity() * points_count
     }
 }
 
@@ -121,6 +160,7 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> Builder<J, P,
         path: &[P],
         adapter: &FloatPointAdapter<P, I>,
         segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        mut track: Option<&mut SourceTrack>,
     ) {
         let iter = path.iter().map(|p| adapter.float_to_int(p));
         let mut uniq_segments = if let Some(iter) = UniqueSegmentsIter::new(iter) {
@@ -139,14 +179,18 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> Builder<J, P,
         let mut sk = s0.clone();
 
         segments.push_some(sk.top_segment());
+        track.mark(segments.len(), OffsetEdgeKind::Side, us0.index);
 
         for usi in uniq_segments {
             let si = OffsetSection::new(self.radius, &usi, adapter);
             segments.push_some(si.top_segment());
+            track.mark(segments.len(), OffsetEdgeKind::Side, usi.index);
             self.feed_join(&sk, &si, adapter, segments);
+            track.mark(segments.len(), OffsetEdgeKind::Join, usi.index);
             sk = si;
         }
         self.feed_join(&sk, &s0, adapter, segments);
+        track.mark(segments.len(), OffsetEdgeKind::Join, us0.index);
     }
 
     #[inline]
@@ -1,13 +1,21 @@
+use crate::core::control::{OperationControl, OperationPhase, ProgressTracker};
+use crate::core::edge_overlay::EdgeOverlay;
+use crate::core::error::{InputRole, OverlayError, validate_float_paths};
 use crate::core::extract::BooleanExtractionBuffer;
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
 use crate::core::overlay::ShapeType::Subject;
 use crate::core::overlay::{ContourDirection, Overlay};
 use crate::core::overlay_rule::OverlayRule;
+use crate::float::auto::{AutoOutput, AutoScaleOptions, EngineSelection, IntEngine};
+use crate::float::edge_overlay::FloatDataVectorShape;
 use crate::float::overlay::OverlayOptions;
 use crate::float::scale::FixedScaleOverlayError;
 use crate::mesh::outline::builder::OutlineBuilder;
-use crate::mesh::style::OutlineStyle;
+use crate::mesh::source::{
+    OffsetEdgeSource, SourceTrack, add_segments_with_sources, add_vector_shapes, shapes_to_float,
+};
+use crate::mesh::style::{LineJoin, OutlineStyle};
 use alloc::vec;
 use alloc::vec::Vec;
 use i_float::adapter::FloatPointAdapter;
@@ -24,6 +32,7 @@ use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::despike::DeSpikeContour;
 use i_shape::float::int_area::IntArea;
 use i_shape::float::simple::SimplifyContour;
+use i_shape::int::shape::IntShapes;
 use i_shape::source::resource::ShapeResource;
 
 /// Trait for offsetting float contours and shapes.
@@ -209,6 +218,58 @@ pub trait OutlineOffset<P: FloatPointCompatible> {
     ) -> Result<(), FixedScaleOverlayError>
     where
         I: OverlayInt + 'static;
+
+    /// Generates outline shapes as edges that tell where they come from.
+    ///
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    ///
+    /// # Returns
+    /// The region of [`Self::outline`] as shapes of contours of edges. Every edge carries the
+    /// [`OffsetEdgeSource`] naming the source contour, the segment, and whether it is a side or a
+    /// join edge. A side along a run of collinear segments reports the first segment of the run.
+    fn outline_with_sources(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>>;
+
+    /// Same as [`Self::outline_with_sources`], but with an explicit integer engine.
+    fn outline_with_sources_as<I>(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>>
+    where
+        I: OverlayInt + 'static;
+
+    /// Same as [`Self::outline`], but the integer engine is selected automatically.
+    ///
+    /// The selection takes the outline bounds, the input edges and the offsets into account.
+    /// See [`crate::float::auto`] for the precision guarantee.
+    fn outline_auto(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Shapes<P>, P::Scalar>;
+
+    /// Same as [`Self::outline`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first contour point with a NaN or
+    /// infinite coordinate, or [`OverlayError::NonFiniteStyle`] if an offset or join parameter is
+    /// not finite.
+    ///
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    fn try_outline(&self, style: &OutlineStyle<P::Scalar>) -> Result<Shapes<P>, OverlayError>;
+
+    /// Same as [`Self::try_outline`], but reports progress to `control` and lets it cancel the
+    /// operation with [`OverlayError::Cancelled`]. The offsets of the single contours are reported
+    /// as [`OperationPhase::Build`], then the union of them goes through all phases.
+    ///
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    /// - `control`: Receives progress updates and may cancel.
+    fn try_outline_with_control(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+        control: &mut dyn OperationControl,
+    ) -> Result<Shapes<P>, OverlayError>;
 }
 
 impl<S, P> OutlineOffset<P> for S
@@ -404,6 +465,103 @@ where
         solver.build_into(self, options, output);
         Ok(())
     }
+
+    fn outline_with_sources(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>> {
+        self.outline_with_sources_as::<i32>(style)
+    }
+
+    fn outline_with_sources_as<I>(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>>
+    where
+        I: OverlayInt + 'static,
+    {
+        match OutlineSolver::<P, I>::prepare(self, style) {
+            Some(solver) => solver.build_with_sources(self),
+            None => vec![],
+        }
+    }
+
+    fn outline_auto(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Shapes<P>, P::Scalar> {
+        let join = style.join.normalize();
+        let rect = OutlineSolver::<P, i32>::rect(self, style.outer_offset, style.inner_offset, &join);
+
+        let mut feature_size = options.feature_size(self.iter_paths(), true);
+        for offset in [style.outer_offset.abs(), style.inner_offset.abs()] {
+            if offset > P::Scalar::ZERO {
+                feature_size = Some(feature_size.map_or(offset, |size| size.min(offset)));
+            }
+        }
+
+        let selection = EngineSelection::with_rect(rect, feature_size, &options);
+        let result = match selection.engine {
+            IntEngine::I16 => self.outline_as::<i16>(style),
+            IntEngine::I32 => self.outline_as::<i32>(style),
+            IntEngine::I64 => self.outline_as::<i64>(style),
+        };
+
+        AutoOutput { result, selection }
+    }
+
+    fn try_outline(&self, style: &OutlineStyle<P::Scalar>) -> Result<Shapes<P>, OverlayError> {
+        validate_float_paths(InputRole::Subject, self.iter_paths())?;
+        if !style.is_finite() {
+            return Err(OverlayError::NonFiniteStyle);
+        }
+        Ok(self.outline(style))
+    }
+
+    fn try_outline_with_control(
+        &self,
+        style: &OutlineStyle<P::Scalar>,
+        control: &mut dyn OperationControl,
+    ) -> Result<Shapes<P>, OverlayError> {
+        validate_float_paths(InputRole::Subject, self.iter_paths())?;
+        if !style.is_finite() {
+            return Err(OverlayError::NonFiniteStyle);
+        }
+        match OutlineSolver::<P, i32>::prepare(self, style) {
+            Some(solver) => solver.try_build_with_control(self, Default::default(), control),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+/// Generates outline shapes on a pre-configured adapter grid.
+///
+/// The caller is responsible for the adapter covering the outline, including its joins.
+pub(crate) fn outline_with_adapter<S, P, I>(
+    source: &S,
+    style: &OutlineStyle<P::Scalar>,
+    options: OverlayOptions<P::Scalar, I>,
+    adapter: FloatPointAdapter<P, I>,
+) -> Shapes<P>
+where
+    S: ShapeResource<P>,
+    P: FloatPointCompatible + 'static,
+    I: OverlayInt + 'static,
+{
+    match OutlineSolver::with_adapter(source, style, adapter) {
+        Some(solver) => solver.build(source, options),
+        None => vec![],
+    }
+}
+
+/// An upper bound of how far the outline of `style` reaches past its source contours.
+pub(crate) fn outline_extent<P>(style: &OutlineStyle<P::Scalar>) -> P::Scalar
+where
+    P: FloatPointCompatible + 'static,
+{
+    let join = style.join.normalize();
+    OutlineSolver::<P, i32>::extent(style.outer_offset, style.inner_offset, &join)
 }
 
 struct OutlineSolver<P: FloatPointCompatible, I: IntNumber> {
@@ -419,6 +577,16 @@ where
     I: OverlayInt + 'static,
 {
     fn prepare<S: ShapeResource<P>>(source: &S, style: &OutlineStyle<P::Scalar>) -> Option<Self> {
+        let join = style.join.normalize();
+        let rect = Self::rect(source, style.outer_offset, style.inner_offset, &join);
+        Self::with_adapter(source, style, FloatPointAdapter::new(rect))
+    }
+
+    fn with_adapter<S: ShapeResource<P>>(
+        source: &S,
+        style: &OutlineStyle<P::Scalar>,
+        adapter: FloatPointAdapter<P, I>,
+    ) -> Option<Self> {
         let (points_count, paths_count) = {
             let mut points_count = 0;
             let mut paths_count = 0;
@@ -433,23 +601,10 @@ where
             return None;
         }
 
-        let join = style.join.clone().normalize();
+        let join = style.join.normalize();
         let outer_builder: OutlineBuilder<P, I> = OutlineBuilder::new(-style.outer_offset, &join);
         let inner_builder: OutlineBuilder<P, I> = OutlineBuilder::new(-style.inner_offset, &join);
 
-        let outer_radius = style.outer_offset;
-        let inner_radius = style.inner_offset;
-
-        let outer_additional_offset = outer_builder.additional_offset(outer_radius);
-        let inner_additional_offset = inner_builder.additional_offset(inner_radius);
-
-        let additional_offset = outer_additional_offset.abs() + inner_additional_offset.abs();
-
-        let mut rect = FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
-        rect.add_offset(additional_offset);
-
-        let adapter = FloatPointAdapter::<P, I>::new(rect);
-
         Some(Self {
             outer_builder,
             inner_builder,
@@ -458,6 +613,26 @@ where
         })
     }
 
+    /// The bounds of the outline, grown by the offsets and joins of both contour kinds.
+    fn rect<S: ShapeResource<P>>(
+        source: &S,
+        outer_radius: P::Scalar,
+        inner_radius: P::Scalar,
+        join: &LineJoin<P::Scalar>,
+    ) -> FloatRect<P::Scalar> {
+        let mut rect = FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
+        rect.add_offset(Self::extent(outer_radius, inner_radius, join));
+        rect
+    }
+
+    /// How far the outline reaches past its source contours.
+    fn extent(outer_radius: P::Scalar, inner_radius: P::Scalar, join: &LineJoin<P::Scalar>) -> P::Scalar {
+        let outer_additional_offset = OutlineBuilder::<P, I>::additional_offset(outer_radius, join);
+        let inner_additional_offset = OutlineBuilder::<P, I>::additional_offset(inner_radius, join);
+
+        outer_additional_offset.abs() + inner_additional_offset.abs()
+    }
+
     fn apply_scale(&mut self, scale: f64) -> Result<(), FixedScaleOverlayError> {
         let s = P::Scalar::from_float(scale);
         self.adapter = FloatPointAdapter::try_with_scale(*self.adapter.rect(), s)?;
@@ -468,6 +643,7 @@ where
         &self,
         source: &S,
         options: OverlayOptions<P::Scalar, I>,
+        progress: &mut ProgressTracker,
     ) -> Overlay<I> {
         let total_capacity = self.outer_builder.capacity(self.points_count);
         let mut overlay = Overlay::new_custom(
@@ -483,7 +659,13 @@ where
         let mut bool_buffer = BooleanExtractionBuffer::default();
         let mut flat_buffer = FlatContoursBuffer::<I>::with_capacity(0);
 
+        let mut done = 0;
         for path in source.iter_paths() {
+            if !progress.tick_by(path.len(), OperationPhase::Build, done, self.points_count) {
+                break;
+            }
+            done += path.len();
+
             let area = path.unsafe_int_area(&self.adapter);
             if area.unsigned_abs() <= <I::WideUInt as UIntNumber>::from_u64(1) {
                 // ignore degenerate paths
@@ -518,12 +700,89 @@ where
         overlay
     }
 
+    fn build_with_sources<S: ShapeResource<P>>(
+        self,
+        source: &S,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>> {
+        let total_capacity = self.outer_builder.capacity(self.points_count);
+        let mut overlay = EdgeOverlay::new(total_capacity);
+
+        let mut segments = Vec::new();
+        let mut sources = Vec::new();
+
+        for (path_index, path) in source.iter_paths().enumerate() {
+            let area = path.unsafe_int_area(&self.adapter);
+            if area.unsigned_abs() <= <I::WideUInt as UIntNumber>::from_u64(1) {
+                // ignore degenerate paths
+                continue;
+            }
+
+            segments.clear();
+            sources.clear();
+            let mut track = SourceTrack {
+                sources: &mut sources,
+                path_index,
+                segments: None,
+            };
+
+            let mut offset_overlay = EdgeOverlay::new(16);
+            let contour_fill_rule = if area > I::Wide::ZERO {
+                offset_overlay.options.output_direction = ContourDirection::CounterClockwise;
+                self.outer_builder
+                    .build_with_sources(path, &self.adapter, &mut segments, &mut track);
+                FillRule::Positive
+            } else {
+                offset_overlay.options.output_direction = ContourDirection::Clockwise;
+                self.inner_builder
+                    .build_with_sources(path, &self.adapter, &mut segments, &mut track);
+                FillRule::Negative
+            };
+
+            add_segments_with_sources(&mut offset_overlay, &segments, &sources);
+            let shapes = offset_overlay.build_vector_shapes(OverlayRule::Subject, contour_fill_rule);
+            add_vector_shapes(&mut overlay, &shapes, Subject);
+        }
+
+        let shapes = overlay.build_vector_shapes(OverlayRule::Subject, FillRule::Positive);
+        shapes_to_float(&shapes, &self.adapter)
+    }
+
     fn build<S: ShapeResource<P>>(self, source: &S, options: OverlayOptions<P::Scalar, I>) -> Shapes<P> {
         let preserve_output_collinear = options.preserve_output_collinear;
         let clean_result = options.clean_result;
-        let mut overlay = self.build_overlay(source, options);
+        let mut overlay = self.build_overlay(source, options, &mut ProgressTracker::none());
         let shapes = overlay.overlay(OverlayRule::Subject, FillRule::Positive);
 
+        self.to_clean_float(&shapes, clean_result, preserve_output_collinear)
+    }
+
+    fn try_build_with_control<S: ShapeResource<P>>(
+        self,
+        source: &S,
+        options: OverlayOptions<P::Scalar, I>,
+        control: &mut dyn OperationControl,
+    ) -> Result<Shapes<P>, OverlayError> {
+        let preserve_output_collinear = options.preserve_output_collinear;
+        let clean_result = options.clean_result;
+        let mut progress = ProgressTracker::new(control);
+        if !progress.report(OperationPhase::Build, 0, self.points_count) {
+            return Err(OverlayError::Cancelled);
+        }
+        let mut overlay = self.build_overlay(source, options, &mut progress);
+        if progress.is_cancelled() {
+            return Err(OverlayError::Cancelled);
+        }
+        let shapes = overlay.try_overlay_with_control(OverlayRule::Subject, FillRule::Positive, control)?;
+
+        Ok(self.to_clean_float(&shapes, clean_result, preserve_output_collinear))
+    }
+
+    fn to_clean_float(
+        &self,
+        shapes: &IntShapes<I>,
+        clean_result: bool,
+        preserve_output_collinear: bool,
+    ) -> Shapes<P> {
         if clean_result {
             let mut float = shapes.to_float(&self.adapter);
             if preserve_output_collinear {
@@ -545,7 +804,7 @@ where
     ) {
         let preserve_output_collinear = options.preserve_output_collinear;
         let clean_result = options.clean_result;
-        let mut overlay = self.build_overlay(source, options);
+        let mut overlay = self.build_overlay(source, options, &mut ProgressTracker::none());
 
         let mut int_output = FlatContoursBuffer::<I>::with_capacity(0);
         overlay.overlay_into(OverlayRule::Subject, FillRule::Positive, &mut int_output);
@@ -565,8 +824,10 @@ where
 #[cfg(test)]
 mod tests {
     use crate::core::fill_rule::FillRule;
+    use crate::float::edge_overlay::FloatDataVectorShape;
     use crate::float::simplify::SimplifyShape;
     use crate::mesh::outline::offset::OutlineOffset;
+    use crate::mesh::source::{OffsetEdgeKind, OffsetEdgeSource};
     use crate::mesh::style::{LineJoin, OutlineStyle};
     use alloc::vec;
     use alloc::vec::Vec;
@@ -1535,4 +1796,77 @@ mod tests {
 
         points
     }
+
+    fn near(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    fn edge_sources<F: Fn([f64; 2], [f64; 2]) -> bool>(
+        shapes: &[FloatDataVectorShape<[f64; 2], OffsetEdgeSource>],
+        select: F,
+    ) -> Vec<(usize, usize, OffsetEdgeKind)> {
+        let mut sources: Vec<_> = shapes
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|e| select(e.a, e.b))
+            .map(|e| (e.data.path_index, e.data.segment_index, e.data.kind))
+            .collect();
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    #[test]
+    fn test_sources_square() {
+        let path = [[0.0, 0.0], [5.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let style = OutlineStyle::new(1.0).line_join(LineJoin::Bevel);
+        let shapes = path.outline_with_sources(&style);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0][0].len(), 8);
+
+        // the bottom side runs along the collinear segments 0 and 1
+        let bottom = edge_sources(&shapes, |a, b| near(a[1], -1.0) && near(b[1], -1.0));
+        let right = edge_sources(&shapes, |a, b| near(a[0], 11.0) && near(b[0], 11.0));
+        let top = edge_sources(&shapes, |a, b| near(a[1], 11.0) && near(b[1], 11.0));
+        let left = edge_sources(&shapes, |a, b| near(a[0], -1.0) && near(b[0], -1.0));
+        let joins = edge_sources(&shapes, |a, b| !near(a[0], b[0]) && !near(a[1], b[1]));
+        assert_eq!(bottom, vec![(0, 0, OffsetEdgeKind::Side)]);
+        assert_eq!(right, vec![(0, 2, OffsetEdgeKind::Side)]);
+        assert_eq!(top, vec![(0, 3, OffsetEdgeKind::Side)]);
+        assert_eq!(left, vec![(0, 4, OffsetEdgeKind::Side)]);
+        assert_eq!(
+            joins,
+            vec![
+                (0, 0, OffsetEdgeKind::Join),
+                (0, 2, OffsetEdgeKind::Join),
+                (0, 3, OffsetEdgeKind::Join),
+                (0, 4, OffsetEdgeKind::Join),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sources_hole() {
+        let shape = vec![
+            vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            vec![[3.0, 3.0], [3.0, 7.0], [7.0, 7.0], [7.0, 3.0]],
+        ];
+        let style = OutlineStyle::new(1.0).line_join(LineJoin::Round(0.1));
+        let shapes = shape.outline_with_sources_as::<i64>(&style);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+
+        // the hole shrinks to a 2 x 2 square, its sides keep the segments of the hole
+        let hole = edge_sources(&shapes, |a, b| a[0].min(b[0]) > 3.0 && a[0].max(b[0]) < 7.0);
+        let inner_left = edge_sources(&shapes, |a, b| near(a[0], 4.0) && near(b[0], 4.0));
+        assert_eq!(inner_left, vec![(1, 0, OffsetEdgeKind::Side)]);
+        assert!(hole.iter().all(|s| s.0 == 1 && s.2 == OffsetEdgeKind::Side));
+
+        let round = edge_sources(&shapes, |a, b| !near(a[0], b[0]) && !near(a[1], b[1]));
+        assert!(!round.is_empty());
+        assert!(round.iter().all(|s| s.0 == 0 && s.2 == OffsetEdgeKind::Join));
+    }
 }
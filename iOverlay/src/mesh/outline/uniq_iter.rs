@@ -1,4 +1,4 @@
-use core::iter::Chain;
+use core::iter::{Chain, Enumerate};
 use i_float::int::number::int::IntNumber;
 use i_float::int::number::wide_int::WideIntNumber;
 use i_float::int::point::IntPoint;
@@ -6,16 +6,20 @@ use i_float::int::point::IntPoint;
 pub(super) struct UniqueSegment<I: IntNumber> {
     pub(super) a: IntPoint<I>,
     pub(super) b: IntPoint<I>,
+    /// Index of the last input point at `a`, the segment leaving it is the first one of the run.
+    pub(super) index: usize,
 }
 
+type IndexedPoint<I> = (usize, IntPoint<I>);
+
 pub(super) struct UniqueSegmentsIter<It, I>
 where
     It: Iterator<Item = IntPoint<I>>,
     I: IntNumber,
 {
-    iter: Chain<It, core::array::IntoIter<IntPoint<I>, 2>>,
-    p0: IntPoint<I>,
-    p1: IntPoint<I>,
+    iter: Chain<Enumerate<It>, core::array::IntoIter<IndexedPoint<I>, 2>>,
+    p0: IndexedPoint<I>,
+    p1: IndexedPoint<I>,
 }
 
 impl<It, I> UniqueSegmentsIter<It, I>
@@ -25,15 +29,16 @@ where
 {
     #[inline]
     pub(super) fn new(iter: It) -> Option<Self> {
-        let mut iter = iter;
+        let mut iter = iter.enumerate();
 
-        let mut p0 = iter.next()?;
-        let mut p1 = iter.find(|p| p0.ne(p))?;
+        let (_, p) = iter.next()?;
+        let mut p1 = iter.find(|(_, pi)| p.ne(pi))?;
+        let mut p0 = (p1.0 - 1, p);
 
         let q0 = p0;
 
         for p2 in &mut iter {
-            if include_point(p0, p1, p2) {
+            if include_point(p0.1, p1.1, p2.1) {
                 p0 = p1;
                 p1 = p2;
                 break;
@@ -51,6 +56,15 @@ where
             p1,
         })
     }
+
+    #[inline]
+    fn segment(&self) -> UniqueSegment<I> {
+        UniqueSegment {
+            a: self.p0.1,
+            b: self.p1.1,
+            index: self.p0.0,
+        }
+    }
 }
 
 impl<It, I> Iterator for UniqueSegmentsIter<It, I>
@@ -62,14 +76,11 @@ where
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         for p2 in &mut self.iter {
-            if !include_point(self.p0, self.p1, p2) {
+            if !include_point(self.p0.1, self.p1.1, p2.1) {
                 self.p1 = p2;
                 continue;
             }
-            let s = UniqueSegment {
-                a: self.p0,
-                b: self.p1,
-            };
+            let s = self.segment();
 
             self.p0 = self.p1;
             self.p1 = p2;
@@ -77,12 +88,9 @@ where
             return Some(s);
         }
 
-        let add_last = self.p1 != self.p0;
+        let add_last = self.p1.1 != self.p0.1;
         if add_last {
-            let s = UniqueSegment {
-                a: self.p0,
-                b: self.p1,
-            };
+            let s = self.segment();
             self.p1 = self.p0;
             Some(s)
         } else {
@@ -174,15 +182,36 @@ mod tests {
         validate_case_all_rotations(&path, 4);
     }
 
+    #[test]
+    fn test_square_repeated_points() {
+        #[rustfmt::skip]
+        let path = int_path![[0, 0], [0, 0], [10, 0], [10, 5], [10, 10], [10, 10], [0, 10]];
+        validate_case_all_rotations(&path, 4);
+    }
+
     fn validate_case_all_rotations(path: &[IntPoint<i32>], expected_segments_count: usize) {
         assert!(!path.is_empty(), "path must not be empty");
 
-        for shift in 0..path.len() {
-            let uniq_iter =
-                UniqueSegmentsIter::new(path[shift..].iter().chain(path[..shift].iter()).copied()).unwrap();
+        let n = path.len();
+        for shift in 0..n {
+            let rotated: Vec<_> = path[shift..]
+                .iter()
+                .chain(path[..shift].iter())
+                .copied()
+                .collect();
+            let uniq_iter = UniqueSegmentsIter::new(rotated.iter().copied()).unwrap();
 
             let segments: Vec<_> = uniq_iter.collect();
 
+            for s in segments.iter() {
+                assert_eq!(rotated[s.index], s.a, "segment index does not point to its start");
+                assert_ne!(
+                    rotated[(s.index + 1) % n],
+                    s.a,
+                    "segment index is not the last point at start"
+                );
+            }
+
             assert_eq!(
                 segments.len(),
                 expected_segments_count,
@@ -22,6 +22,7 @@ impl<I: OverlayInt> Overlay<I> {
             segments,
             split_solver: SplitSolver::new(),
             graph_builder: GraphBuilder::<ShapeCountBoolean, OverlayNode, I>::new(),
+            input_log: None,
         }
     }
 }
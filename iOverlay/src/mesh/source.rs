@@ -0,0 +1,160 @@
+use crate::core::edge_data::{EdgeDataMerge, OverlayEdgeData};
+use crate::core::edge_overlay::{EdgeOverlay, InputEdge};
+use crate::core::integer::OverlayInt;
+use crate::core::overlay::ShapeType;
+use crate::float::edge_overlay::{FloatDataVectorEdge, FloatDataVectorShape};
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::Segment;
+use crate::vector::edge::DataVectorShape;
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+
+/// The part of a stroke or outline construction an output edge comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OffsetEdgeKind {
+    /// Runs along a source segment, at the offset distance or, where an aligned stroke is cut
+    /// by its path, on the segment itself.
+    Side,
+    /// Part of a join around a source vertex.
+    Join,
+    /// Part of the start or end cap of an open path.
+    Cap,
+}
+
+/// The source of an output edge of a stroke or outline.
+///
+/// Where edges of different sources overlap, the smallest source is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OffsetEdgeSource {
+    /// Index of the source path, in the order the source yields its paths.
+    pub path_index: usize,
+    /// Index of the source segment, segment `i` runs from point `i` to the next point of the
+    /// path. A join reports the segment leaving its corner, a cap the first or the last segment.
+    pub segment_index: usize,
+    pub kind: OffsetEdgeKind,
+}
+
+impl OverlayEdgeData for OffsetEdgeSource {
+    type Store = ();
+
+    #[inline]
+    fn merge(ctx: EdgeDataMerge<ShapeCountBoolean, Self>, _: &mut Self::Store) -> Self {
+        ctx.lhs_data.min(ctx.rhs_data)
+    }
+}
+
+/// Records the source of every segment a builder adds, `sources` runs parallel to the segments.
+pub(crate) struct SourceTrack<'a> {
+    pub(crate) sources: &'a mut Vec<OffsetEdgeSource>,
+    pub(crate) path_index: usize,
+    /// For every point of the built path, the source segment of the edge ending at it. `None`
+    /// when the built path is the source path itself.
+    pub(crate) segments: Option<&'a [usize]>,
+}
+
+impl SourceTrack<'_> {
+    /// The source segment of the built edge ending at point `end` of a path of `n` points.
+    #[inline]
+    pub(crate) fn segment_ending_at(&self, end: usize, n: usize) -> usize {
+        match self.segments {
+            Some(segments) => segments[end],
+            None => (end + n - 1) % n,
+        }
+    }
+
+    /// Tags the segments added since the last mark.
+    #[inline]
+    pub(crate) fn mark(&mut self, len: usize, kind: OffsetEdgeKind, segment_index: usize) {
+        let source = OffsetEdgeSource {
+            path_index: self.path_index,
+            segment_index,
+            kind,
+        };
+        self.sources.resize(len, source);
+    }
+}
+
+pub(crate) trait MarkSources {
+    fn segment_ending_at(&self, end: usize, n: usize) -> usize;
+    fn mark(&mut self, len: usize, kind: OffsetEdgeKind, segment_index: usize);
+}
+
+impl MarkSources for Option<&mut SourceTrack<'_>> {
+    #[inline]
+    fn segment_ending_at(&self, end: usize, n: usize) -> usize {
+        match self {
+            Some(track) => track.segment_ending_at(end, n),
+            None => 0,
+        }
+    }
+
+    #[inline]
+    fn mark(&mut self, len: usize, kind: OffsetEdgeKind, segment_index: usize) {
+        if let Some(track) = self {
+            track.mark(len, kind, segment_index);
+        }
+    }
+}
+
+/// Adds subject segments with their sources to an edge overlay.
+pub(crate) fn add_segments_with_sources<I: OverlayInt>(
+    overlay: &mut EdgeOverlay<I, OffsetEdgeSource>,
+    segments: &[Segment<ShapeCountBoolean, I>],
+    sources: &[OffsetEdgeSource],
+) {
+    for (s, &data) in segments.iter().zip(sources.iter()) {
+        let (a, b) = if s.count.subj > 0 {
+            (s.x_segment.a, s.x_segment.b)
+        } else {
+            (s.x_segment.b, s.x_segment.a)
+        };
+        overlay.add_edge(InputEdge { a, b, data }, ShapeType::Subject);
+    }
+}
+
+/// Adds the edges of vector shapes as subject edges.
+pub(crate) fn add_vector_shapes<I: OverlayInt>(
+    overlay: &mut EdgeOverlay<I, OffsetEdgeSource>,
+    shapes: &[DataVectorShape<I, OffsetEdgeSource>],
+    shape_type: ShapeType,
+) {
+    for e in shapes.iter().flatten().flatten() {
+        overlay.add_edge(
+            InputEdge {
+                a: e.a,
+                b: e.b,
+                data: e.data,
+            },
+            shape_type,
+        );
+    }
+}
+
+pub(crate) fn shapes_to_float<P, I>(
+    shapes: &[DataVectorShape<I, OffsetEdgeSource>],
+    adapter: &FloatPointAdapter<P, I>,
+) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>>
+where
+    P: FloatPointCompatible,
+    I: OverlayInt,
+{
+    shapes
+        .iter()
+        .map(|shape| {
+            shape
+                .iter()
+                .map(|path| {
+                    path.iter()
+                        .map(|e| FloatDataVectorEdge {
+                            a: adapter.int_to_float(&e.a),
+                            b: adapter.int_to_float(&e.b),
+                            fill: e.fill,
+                            data: e.data,
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
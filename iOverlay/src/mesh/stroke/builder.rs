@@ -1,16 +1,21 @@
+use crate::mesh::source::{MarkSources, OffsetEdgeKind, SourceTrack};
 use crate::mesh::stroke::builder_cap::CapBuilder;
-use crate::mesh::stroke::builder_join::{BevelJoinBuilder, JoinBuilder, MiterJoinBuilder, RoundJoinBuilder};
+use crate::mesh::stroke::builder_join::{
+    BevelJoinBuilder, JoinBuilder, MiterJoinBuilder, RoundJoinBuilder, SvgMiterJoinBuilder,
+};
 use crate::mesh::stroke::section::{Section, SectionToSegment};
-use crate::mesh::style::{LineJoin, StrokeStyle};
+use crate::mesh::style::{LineCap, LineJoin, StrokeSide, StrokeStyle, SvgLineJoin, SvgStroke};
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::segment::Segment;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::f64::consts::PI;
 use core::marker::PhantomData;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
 use i_float::int::number::int::IntNumber;
+use i_float::int::point::IntPoint;
 
 trait StrokeBuild<P: FloatPointCompatible, I: IntNumber> {
     fn build(
@@ -21,16 +26,56 @@ trait StrokeBuild<P: FloatPointCompatible, I: IntNumber> {
         segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
     );
 
+    fn build_with_sources(
+        &self,
+        path: &[P],
+        is_closed_path: bool,
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        track: &mut SourceTrack,
+    );
+
+    fn build_curve(
+        &self,
+        path: &[P],
+        adapter: &FloatPointAdapter<P, I>,
+        sides: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        joins: &mut CurveJoins<I>,
+    );
+
     fn capacity(&self, paths_count: usize, points_count: usize, is_closed_path: bool) -> usize;
     fn additional_offset(&self, radius: P::Scalar) -> P::Scalar;
 }
 
+/// The join edges of an offset curve, with the corner of the path every edge belongs to.
+pub(super) struct CurveJoins<I: IntNumber> {
+    pub(super) segments: Vec<Segment<ShapeCountBoolean, I>>,
+    pub(super) corners: Vec<IntPoint<I>>,
+}
+
+impl<I: IntNumber> CurveJoins<I> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            corners: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub(super) fn clear(&mut self) {
+        self.segments.clear();
+        self.corners.clear();
+    }
+}
+
 pub(super) struct StrokeBuilder<P: FloatPointCompatible, I: IntNumber> {
     builder: Box<dyn StrokeBuild<P, I>>,
 }
 
 struct Builder<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> {
     radius: P::Scalar,
+    side: StrokeSide,
     join_builder: J,
     start_cap_builder: CapBuilder<P>,
     end_cap_builder: CapBuilder<P>,
@@ -41,36 +86,113 @@ impl<P: FloatPointCompatible + 'static, I: IntNumber + 'static> StrokeBuilder<P,
     pub(super) fn new(style: StrokeStyle<P>) -> StrokeBuilder<P, I> {
         let radius = P::Scalar::from_float(0.5 * style.width.to_f64().max(0.0));
 
-        let start_cap_builder = CapBuilder::new(style.start_cap.normalize(), radius);
-        let end_cap_builder = CapBuilder::new(style.end_cap.normalize(), radius);
+        let side = style.side;
+
+        if let Some(svg) = style.svg {
+            return Self::with_svg(svg, style.start_cap, style.end_cap, radius, side);
+        }
+
+        let start_cap_builder = CapBuilder::with_side(style.start_cap.normalize(), radius, side, true);
+        let end_cap_builder = CapBuilder::with_side(style.end_cap.normalize(), radius, side, false);
 
-        let builder: Box<dyn StrokeBuild<P, I>> = match style.join.normalize() {
-            LineJoin::Miter(ratio) => Box::new(Builder {
+        let builder = match style.join.normalize() {
+            LineJoin::Miter(ratio) => Self::boxed(
+                MiterJoinBuilder::new(ratio, radius),
                 radius,
-                join_builder: MiterJoinBuilder::new(ratio, radius),
+                side,
                 start_cap_builder,
                 end_cap_builder,
-                _phantom: Default::default(),
-            }),
-            LineJoin::Round(ratio) => Box::new(Builder {
+            ),
+            LineJoin::Round(ratio) => Self::boxed(
+                RoundJoinBuilder::new(ratio, radius),
                 radius,
-                join_builder: RoundJoinBuilder::new(ratio, radius),
+                side,
                 start_cap_builder,
                 end_cap_builder,
-                _phantom: Default::default(),
-            }),
-            LineJoin::Bevel => Box::new(Builder {
+            ),
+            LineJoin::Bevel => Self::boxed(
+                BevelJoinBuilder {},
                 radius,
-                join_builder: BevelJoinBuilder {},
+                side,
                 start_cap_builder,
                 end_cap_builder,
-                _phantom: Default::default(),
-            }),
+            ),
+        };
+
+        Self { builder }
+    }
+
+    fn with_svg(
+        svg: SvgStroke<P::Scalar>,
+        start_cap: LineCap<P>,
+        end_cap: LineCap<P>,
+        radius: P::Scalar,
+        side: StrokeSide,
+    ) -> StrokeBuilder<P, I> {
+        let angle = svg.round_angle(radius);
+        // a cap takes a whole number of steps, round it up so no step exceeds the angle
+        let steps = PI / angle.to_f64();
+        let n = steps as usize + usize::from((steps as usize as f64) < steps);
+        let cap_angle = P::Scalar::from_float(PI / (n as f64 + 0.5));
+        let svg_cap = |cap: LineCap<P>| match cap {
+            LineCap::Round(_) => LineCap::Round(cap_angle),
+            cap => cap,
+        };
+        let start_cap_builder = CapBuilder::with_side(svg_cap(start_cap), radius, side, true);
+        let end_cap_builder = CapBuilder::with_side(svg_cap(end_cap), radius, side, false);
+
+        let builder = match svg.join {
+            SvgLineJoin::Miter => Self::boxed(
+                SvgMiterJoinBuilder::new(svg.miter_limit, radius, false),
+                radius,
+                side,
+                start_cap_builder,
+                end_cap_builder,
+            ),
+            SvgLineJoin::MiterClip | SvgLineJoin::Arcs => Self::boxed(
+                SvgMiterJoinBuilder::new(svg.miter_limit, radius, true),
+                radius,
+                side,
+                start_cap_builder,
+                end_cap_builder,
+            ),
+            SvgLineJoin::Round => Self::boxed(
+                RoundJoinBuilder::with_max_angle(angle, radius),
+                radius,
+                side,
+                start_cap_builder,
+                end_cap_builder,
+            ),
+            SvgLineJoin::Bevel => Self::boxed(
+                BevelJoinBuilder {},
+                radius,
+                side,
+                start_cap_builder,
+                end_cap_builder,
+            ),
         };
 
         Self { builder }
     }
 
+    #[inline]
+    fn boxed<J: JoinBuilder<P, I> + 'static>(
+        join_builder: J,
+        radius: P::Scalar,
+        side: StrokeSide,
+        start_cap_builder: CapBuilder<P>,
+        end_cap_builder: CapBuilder<P>,
+    ) -> Box<dyn StrokeBuild<P, I>> {
+        Box::new(Builder {
+            radius,
+            side,
+            join_builder,
+            start_cap_builder,
+            end_cap_builder,
+            _phantom: Default::default(),
+        })
+    }
+
     #[inline]
     pub(super) fn build(
         &self,
@@ -82,6 +204,34 @@ impl<P: FloatPointCompatible + 'static, I: IntNumber + 'static> StrokeBuilder<P,
         self.builder.build(path, is_closed_path, adapter, segments);
     }
 
+    /// Same as [`Self::build`], and records the source of every added segment.
+    #[inline]
+    pub(super) fn build_with_sources(
+        &self,
+        path: &[P],
+        is_closed_path: bool,
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        track: &mut SourceTrack,
+    ) {
+        self.builder
+            .build_with_sources(path, is_closed_path, adapter, segments, track);
+    }
+
+    /// Builds the offset edges on the side of the style of an open path into `sides` and the
+    /// edges of its joins into `joins`, without caps. The edges run against the path on the left
+    /// and along it on the right.
+    #[inline]
+    pub(super) fn build_curve(
+        &self,
+        path: &[P],
+        adapter: &FloatPointAdapter<P, I>,
+        sides: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        joins: &mut CurveJoins<I>,
+    ) {
+        self.builder.build_curve(path, adapter, sides, joins);
+    }
+
     #[inline]
     pub(super) fn capacity(&self, paths_count: usize, points_count: usize, is_closed_path: bool) -> usize {
         self.builder.capacity(paths_count, points_count, is_closed_path)
@@ -91,6 +241,36 @@ impl<P: FloatPointCompatible + 'static, I: IntNumber + 'static> StrokeBuilder<P,
     pub(super) fn additional_offset(&self, radius: P::Scalar) -> P::Scalar {
         self.builder.additional_offset(radius)
     }
+
+    /// An upper bound of how far the stroke of `style` reaches past its path, found without
+    /// building the stroke.
+    pub(super) fn max_offset(style: &StrokeStyle<P>) -> P::Scalar {
+        let radius = P::Scalar::from_float(0.5 * style.width.to_f64().max(0.0));
+        let start_cap = CapBuilder::max_offset(&style.start_cap, radius);
+        let end_cap = CapBuilder::max_offset(&style.end_cap, radius);
+
+        let join = match (&style.svg, &style.join) {
+            (Some(svg), _) => match svg.join {
+                SvgLineJoin::Miter | SvgLineJoin::MiterClip | SvgLineJoin::Arcs => {
+                    let join_builder = SvgMiterJoinBuilder::new(svg.miter_limit, radius, false);
+                    JoinBuilder::<P, I>::additional_offset(&join_builder, radius)
+                }
+                SvgLineJoin::Round | SvgLineJoin::Bevel => {
+                    JoinBuilder::<P, I>::additional_offset(&BevelJoinBuilder {}, radius)
+                }
+            },
+            (None, LineJoin::Miter(ratio)) => {
+                let join_builder = MiterJoinBuilder::new(*ratio, radius);
+                JoinBuilder::<P, I>::additional_offset(&join_builder, radius)
+            }
+            // a round join stays within the radius as a bevel does
+            (None, LineJoin::Round(_) | LineJoin::Bevel) => {
+                JoinBuilder::<P, I>::additional_offset(&BevelJoinBuilder {}, radius)
+            }
+        };
+
+        join.max(start_cap.max(end_cap))
+    }
 }
 
 impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> StrokeBuild<P, I> for Builder<J, P, I> {
@@ -103,12 +283,39 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> StrokeBuild<P,
         segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
     ) {
         if is_closed_path {
-            self.closed_segments(path, adapter, segments);
+            self.closed_segments(path, adapter, segments, None);
+        } else {
+            self.open_segments(path, adapter, segments, None, None);
+        }
+    }
+
+    #[inline]
+    fn build_with_sources(
+        &self,
+        path: &[P],
+        is_closed_path: bool,
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        track: &mut SourceTrack,
+    ) {
+        if is_closed_path {
+            self.closed_segments(path, adapter, segments, Some(track));
         } else {
-            self.open_segments(path, adapter, segments);
+            self.open_segments(path, adapter, segments, None, Some(track));
         }
     }
 
+    #[inline]
+    fn build_curve(
+        &self,
+        path: &[P],
+        adapter: &FloatPointAdapter<P, I>,
+        sides: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        joins: &mut CurveJoins<I>,
+    ) {
+        self.open_segments(path, adapter, sides, Some(joins), None);
+    }
+
     #[inline]
     fn capacity(&self, paths_count: usize, points_count: usize, is_closed_path: bool) -> usize {
         if is_closed_path {
@@ -129,11 +336,15 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> StrokeBuild<P,
 }
 
 impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> Builder<J, P, I> {
+    /// With `curve_joins` only the offset edges are built into `segments`, and the joins go to
+    /// `curve_joins`.
     fn open_segments(
         &self,
         path: &[P],
         adapter: &FloatPointAdapter<P, I>,
         segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        mut curve_joins: Option<&mut CurveJoins<I>>,
+        mut track: Option<&mut SourceTrack>,
     ) {
         // build segments only from points which are not equal in int space
 
@@ -153,11 +364,17 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> Builder<J, P,
             ip = adapter.float_to_int(&path[j]);
         }
 
-        let mut s0 = Section::new(self.radius, &path[0], &path[j]);
-
-        self.start_cap_builder.add_to_start(&s0, adapter, segments);
+        let mut s0 = Section::with_side(self.radius, self.side, &path[0], &path[j]);
+        let mut index = track.segment_ending_at(j, n);
 
-        segments.add_section(&s0, adapter);
+        if curve_joins.is_some() {
+            segments.add_side(&s0, self.side, adapter);
+        } else {
+            self.start_cap_builder.add_to_start(&s0, adapter, segments);
+            track.mark(segments.len(), OffsetEdgeKind::Cap, index);
+            segments.add_section(&s0, adapter);
+            track.mark(segments.len(), OffsetEdgeKind::Side, index);
+        }
 
         ip0 = ip;
         j += 1;
@@ -172,14 +389,26 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> Builder<J, P,
                 p = &path[j];
                 ip = adapter.float_to_int(p);
             }
-            let s1 = Section::new(self.radius, &s0.b, p);
-            self.join_builder.add_join(&s0, &s1, adapter, segments);
-            segments.add_section(&s1, adapter);
+            let s1 = Section::with_side(self.radius, self.side, &s0.b, p);
+            index = track.segment_ending_at(j, n);
+            if let Some(joins) = curve_joins.as_deref_mut() {
+                self.join_builder.add_join(&s0, &s1, adapter, &mut joins.segments);
+                joins.corners.resize(joins.segments.len(), ip0);
+                segments.add_side(&s1, self.side, adapter);
+            } else {
+                self.join_builder.add_join(&s0, &s1, adapter, segments);
+                track.mark(segments.len(), OffsetEdgeKind::Join, index);
+                segments.add_section(&s1, adapter);
+                track.mark(segments.len(), OffsetEdgeKind::Side, index);
+            }
             s0 = s1;
             ip0 = ip;
         }
 
-        self.end_cap_builder.add_to_end(&s0, adapter, segments);
+        if curve_joins.is_none() {
+            self.end_cap_builder.add_to_end(&s0, adapter, segments);
+            track.mark(segments.len(), OffsetEdgeKind::Cap, index);
+        }
     }
 
     fn closed_segments(
@@ -187,34 +416,42 @@ impl<J: JoinBuilder<P, I>, P: FloatPointCompatible, I: IntNumber> Builder<J, P,
         path: &[P],
         adapter: &FloatPointAdapter<P, I>,
         segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        mut track: Option<&mut SourceTrack>,
     ) {
-        if path.len() < 2 {
+        let n = path.len();
+        if n < 2 {
             return;
         }
 
         // build segments only from points which are not equal in int space
-        let i0 = path.len() - 1;
+        let i0 = n - 1;
         let i1 = Self::next_unique_point(i0, 0, path, adapter);
         if i1 == usize::MAX {
             return;
         }
 
-        let start = Section::new(self.radius, &path[i0], &path[i1]);
+        let start = Section::with_side(self.radius, self.side, &path[i0], &path[i1]);
+        let start_index = track.segment_ending_at(i1, n);
         let mut s0 = start.clone();
         segments.add_section(&s0, adapter);
+        track.mark(segments.len(), OffsetEdgeKind::Side, start_index);
 
         let mut i = i1;
         i = Self::next_unique_point(i, i + 1, path, adapter);
         while i != usize::MAX {
-            let si = Section::new(self.radius, &s0.b, &path[i]);
+            let si = Section::with_side(self.radius, self.side, &s0.b, &path[i]);
+            let index = track.segment_ending_at(i, n);
             self.join_builder.add_join(&s0, &si, adapter, segments);
+            track.mark(segments.len(), OffsetEdgeKind::Join, index);
             segments.add_section(&si, adapter);
+            track.mark(segments.len(), OffsetEdgeKind::Side, index);
 
             i = Self::next_unique_point(i, i + 1, path, adapter);
             s0 = si;
         }
 
         self.join_builder.add_join(&s0, &start, adapter, segments);
+        track.mark(segments.len(), OffsetEdgeKind::Join, start_index);
     }
 
     #[inline]
@@ -1,11 +1,12 @@
 use crate::mesh::rotator::Rotator;
 use crate::mesh::stroke::section::Section;
-use crate::mesh::style::LineCap;
+use crate::mesh::style::{LineCap, StrokeSide};
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::segment::Segment;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::f64::consts::PI;
+use core::iter::once;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -29,6 +30,55 @@ impl<P: FloatPointCompatible> CapBuilder<P> {
         Self { points }
     }
 
+    /// Same as [`Self::new`], but only the half of the cap on the stroked `side` is kept.
+    pub(super) fn with_side(cap: LineCap<P>, radius: P::Scalar, side: StrokeSide, is_start: bool) -> Self {
+        let builder = Self::new(cap, radius);
+        // a template runs from y = -r to y = r, and y < 0 is on the left at the start
+        // and on the right at the end
+        let is_lower = match side {
+            StrokeSide::Both => return builder,
+            StrokeSide::Left => is_start,
+            StrokeSide::Right => !is_start,
+        };
+        let points = builder
+            .points
+            .map(|points| Self::half_points(points, radius, is_lower));
+
+        Self { points }
+    }
+
+    fn half_points(points: Vec<P>, r: P::Scalar, is_lower: bool) -> Vec<P> {
+        let zero = P::Scalar::from_float(0.0);
+        let end = P::from_xy(zero, r);
+
+        // split the template where it crosses the path line
+        let mut prev = P::from_xy(zero, -r);
+        for (i, p) in points.iter().chain(once(&end)).enumerate() {
+            if p.y() < zero {
+                prev = *p;
+                continue;
+            }
+            let c = if p.y() > prev.y() {
+                let t = -prev.y() / (p.y() - prev.y());
+                P::from_xy(prev.x() + t * (p.x() - prev.x()), zero)
+            } else {
+                *p
+            };
+
+            let mut half = Vec::with_capacity(points.len() + 1);
+            if is_lower {
+                half.extend_from_slice(&points[..i]);
+                half.push(c);
+            } else {
+                half.push(c);
+                half.extend_from_slice(&points[i.min(points.len())..]);
+            }
+            return half;
+        }
+
+        points
+    }
+
     pub(super) fn round_points(angle: P::Scalar, r: P::Scalar) -> Vec<P> {
         let angle_f64 = angle.to_f64();
         let n = if angle_f64 > 0.0 {
@@ -80,12 +130,16 @@ impl<P: FloatPointCompatible> CapBuilder<P> {
                 let r = rotator.rotate(p);
                 let q = FloatPointMath::add(&r, &section.a);
                 let b = adapter.float_to_int(&q);
-                segments.push(Segment::subject(a, b));
-                a = b;
+                if a != b {
+                    segments.push(Segment::subject(a, b));
+                    a = b;
+                }
             }
         }
         let last = adapter.float_to_int(&section.a_bot);
-        segments.push(Segment::subject(a, last));
+        if a != last {
+            segments.push(Segment::subject(a, last));
+        }
     }
 
     pub(super) fn add_to_end<I: IntNumber>(
@@ -101,12 +155,16 @@ impl<P: FloatPointCompatible> CapBuilder<P> {
                 let r = rotator.rotate(p);
                 let q = FloatPointMath::add(&r, &section.b);
                 let b = adapter.float_to_int(&q);
-                segments.push(Segment::subject(a, b));
-                a = b;
+                if a != b {
+                    segments.push(Segment::subject(a, b));
+                    a = b;
+                }
             }
         }
         let last = adapter.float_to_int(&section.b_top);
-        segments.push(Segment::subject(a, last));
+        if a != last {
+            segments.push(Segment::subject(a, last));
+        }
     }
 
     #[inline]
@@ -118,6 +176,19 @@ impl<P: FloatPointCompatible> CapBuilder<P> {
         }
     }
 
+    /// An upper bound of [`Self::additional_offset`] for `cap`, found without building its template.
+    pub(super) fn max_offset(cap: &LineCap<P>, radius: P::Scalar) -> P::Scalar {
+        match cap {
+            LineCap::Butt => P::Scalar::from_float(0.0),
+            // both span `r` along the path and `2r` across it
+            LineCap::Round(_) | LineCap::Square => P::Scalar::from_float(3.0) * radius,
+            LineCap::Custom(points) => match FloatRect::with_iter(points.iter()) {
+                Some(rect) => (rect.width() + rect.height()) * radius,
+                None => P::Scalar::from_float(0.0),
+            },
+        }
+    }
+
     #[inline]
     pub(super) fn additional_offset(&self) -> P::Scalar {
         if let Some(points) = &self.points {
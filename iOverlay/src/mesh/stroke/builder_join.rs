@@ -217,11 +217,126 @@ impl<P: FloatPointCompatible, I: IntNumber> JoinBuilder<P, I> for MiterJoinBuild
     }
 }
 
+/// Miter join with SVG `stroke-miterlimit` semantics: past the limit the corner is beveled,
+/// or clipped perpendicular to the bisector at `limit * radius` from the vertex.
+pub(super) struct SvgMiterJoinBuilder<T> {
+    limit_dot_product: T,
+    clip_distance: T,
+    is_clip: bool,
+}
+
+impl<T: FloatNumber> SvgMiterJoinBuilder<T> {
+    pub(super) fn new(miter_limit: T, radius: T, is_clip: bool) -> Self {
+        // the miter ratio is 1 / cos(a / 2), where a is the turn angle
+        let limit = miter_limit.max(T::from_float(1.0));
+        let limit_dot_product = T::from_float(2.0) / (limit * limit) - T::from_float(1.0);
+        Self {
+            limit_dot_product,
+            clip_distance: limit * radius,
+            is_clip,
+        }
+    }
+}
+
+impl<P: FloatPointCompatible, I: IntNumber> JoinBuilder<P, I> for SvgMiterJoinBuilder<P::Scalar> {
+    fn add_join(
+        &self,
+        s0: &Section<P>,
+        s1: &Section<P>,
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+    ) {
+        let dot_product = FloatPointMath::dot_product(&s0.dir, &s1.dir);
+        let cross_product = FloatPointMath::cross_product(&s0.dir, &s1.dir);
+        let is_limited = self.limit_dot_product > dot_product;
+        let is_straight = cross_product.abs() < P::Scalar::from_float(0.0001);
+
+        // a reversal is still clipped
+        let is_bevel = if is_limited { !self.is_clip } else { is_straight };
+        if is_bevel {
+            BevelJoinBuilder::join_top(s0, s1, adapter, segments);
+            BevelJoinBuilder::join_bot(s0, s1, adapter, segments);
+            return;
+        }
+
+        let turn = cross_product >= P::Scalar::from_float(0.0);
+        let (pa, pb, va, vb) = if turn {
+            BevelJoinBuilder::join_top(s0, s1, adapter, segments);
+            (s1.a_bot, s0.b_bot, s1.dir, s0.dir)
+        } else {
+            BevelJoinBuilder::join_bot(s0, s1, adapter, segments);
+            (s0.b_top, s1.a_top, s0.dir, s1.dir)
+        };
+
+        if !is_limited {
+            match Miter::sharp(pa, pb, va, vb, adapter) {
+                SharpMiter::AB(a, b) => segments.push(Segment::subject(b, a)),
+                SharpMiter::AcB(a, c, b) => {
+                    segments.push(Segment::subject(c, a));
+                    segments.push(Segment::subject(b, c));
+                }
+                SharpMiter::Degenerate => {}
+            }
+            return;
+        }
+
+        // the miter tip lies along the bisector s0.dir - s1.dir
+        let m = FloatPointMath::normalize(&FloatPointMath::sub(&s0.dir, &s1.dir));
+        let center = s0.b;
+        let offset = FloatPointMath::dot_product(&FloatPointMath::sub(&pa, &center), &m);
+        let speed = FloatPointMath::dot_product(&s0.dir, &m);
+        let t = (self.clip_distance - offset) / speed;
+
+        let (ac, bc) = if turn {
+            (
+                FloatPointMath::sub(&pa, &FloatPointMath::scale(&va, t)),
+                FloatPointMath::add(&pb, &FloatPointMath::scale(&vb, t)),
+            )
+        } else {
+            (
+                FloatPointMath::add(&pa, &FloatPointMath::scale(&va, t)),
+                FloatPointMath::sub(&pb, &FloatPointMath::scale(&vb, t)),
+            )
+        };
+
+        let ia = adapter.float_to_int(&pa);
+        let ib = adapter.float_to_int(&pb);
+        if ia == ib {
+            return;
+        }
+
+        let iac = adapter.float_to_int(&ac);
+        let ibc = adapter.float_to_int(&bc);
+
+        if ia != iac {
+            segments.push(Segment::subject(iac, ia));
+        }
+        if iac != ibc {
+            segments.push(Segment::subject(ibc, iac));
+        }
+        if ibc != ib {
+            segments.push(Segment::subject(ib, ibc));
+        }
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        4
+    }
+
+    #[inline]
+    fn additional_offset(&self, radius: P::Scalar) -> P::Scalar {
+        // add extra 10% to avoid problems with floating point precision.
+        P::Scalar::from_float(1.1) * self.clip_distance.max(radius)
+    }
+}
+
 pub(super) struct RoundJoinBuilder<T> {
     inv_ratio: T,
     average_count: usize,
     radius: T,
     limit_dot_product: T,
+    round_up: bool,
 }
 
 impl<T: FloatNumber> RoundJoinBuilder<T> {
@@ -235,6 +350,15 @@ impl<T: FloatNumber> RoundJoinBuilder<T> {
             average_count,
             radius,
             limit_dot_product,
+            round_up: false,
+        }
+    }
+
+    /// Same as [`Self::new`], but `angle` is never exceeded by an arc step.
+    pub(super) fn with_max_angle(angle: T, radius: T) -> Self {
+        Self {
+            round_up: true,
+            ..Self::new(angle, radius)
         }
     }
 }
@@ -254,7 +378,11 @@ impl<P: FloatPointCompatible, I: IntNumber> JoinBuilder<P, I> for RoundJoinBuild
         }
 
         let angle = dot_product.acos();
-        let n = (angle * self.inv_ratio).to_usize();
+        let steps = angle * self.inv_ratio;
+        let mut n = steps.to_usize();
+        if self.round_up && P::Scalar::from_usize(n) < steps {
+            n += 1;
+        }
         let delta_angle = angle / P::Scalar::from_usize(n);
 
         let cross_product = FloatPointMath::cross_product(&s0.dir, &s1.dir);
@@ -267,11 +395,17 @@ impl<P: FloatPointCompatible, I: IntNumber> JoinBuilder<P, I> for RoundJoinBuild
             let ortho = P::from_xy(-s0.dir.y(), s0.dir.x());
             (s0.b_top, s1.a_top, ortho)
         };
+        let mut a = adapter.float_to_int(&start);
+        let last = adapter.float_to_int(&end);
+        if a == last {
+            // the outer side lies on the path
+            return;
+        }
+
         let rotator = Rotator::<P::Scalar>::with_angle(-delta_angle);
 
         let center = s0.b;
         let mut v = dir;
-        let mut a = adapter.float_to_int(&start);
         for _ in 1..n {
             v = rotator.rotate(&v);
             let p = FloatPointMath::add(&center, &FloatPointMath::scale(&v, self.radius));
@@ -283,9 +417,8 @@ impl<P: FloatPointCompatible, I: IntNumber> JoinBuilder<P, I> for RoundJoinBuild
             }
         }
 
-        let b = adapter.float_to_int(&end);
-        if a != b {
-            segments.push(Segment::subject(b, a));
+        if a != last {
+            segments.push(Segment::subject(last, a));
         }
     }
 
@@ -303,7 +436,7 @@ impl<P: FloatPointCompatible, I: IntNumber> JoinBuilder<P, I> for RoundJoinBuild
 
 #[cfg(test)]
 mod tests {
-    use super::{BevelJoinBuilder, JoinBuilder, MiterJoinBuilder, RoundJoinBuilder};
+    use super::{BevelJoinBuilder, JoinBuilder, MiterJoinBuilder, RoundJoinBuilder, SvgMiterJoinBuilder};
     use crate::mesh::stroke::section::Section;
     use crate::segm::boolean::ShapeCountBoolean;
     use crate::segm::segment::Segment;
@@ -387,4 +520,74 @@ mod tests {
         assert!(miter.is_empty());
         assert!(round.is_empty());
     }
+
+    #[test]
+    fn svg_miter_follows_miter_limit() {
+        let radius = 1.0;
+        let scale = 1_000.0;
+
+        // a right angle has the miter ratio sqrt(2)
+        let (a, b, c) = ([-10.0, 0.0], [0.0, 0.0], [0.0, 10.0]);
+        let sharp = build_join(&MiterJoinBuilder::new(PI / 6.0, radius), radius, a, b, c, scale);
+        let svg = build_join(
+            &SvgMiterJoinBuilder::new(4.0, radius, false),
+            radius,
+            a,
+            b,
+            c,
+            scale,
+        );
+        let clip = build_join(
+            &SvgMiterJoinBuilder::new(4.0, radius, true),
+            radius,
+            a,
+            b,
+            c,
+            scale,
+        );
+        assert_eq!(svg, sharp);
+        assert_eq!(clip, sharp);
+
+        let bevel = build_join(&BevelJoinBuilder, radius, a, b, c, scale);
+        let svg = build_join(
+            &SvgMiterJoinBuilder::new(1.4, radius, false),
+            radius,
+            a,
+            b,
+            c,
+            scale,
+        );
+        let clip = build_join(
+            &SvgMiterJoinBuilder::new(1.4, radius, true),
+            radius,
+            a,
+            b,
+            c,
+            scale,
+        );
+        assert_eq!(svg, bevel);
+        assert_eq!(clip.len(), 4);
+    }
+
+    #[test]
+    fn svg_miter_clip_reversal() {
+        let radius = 1.0;
+        let (a, b, c) = ([-10.0, 0.0], [0.0, 0.0], [-10.0, 0.0]);
+
+        let clip = build_join(
+            &SvgMiterJoinBuilder::new(2.0, radius, true),
+            radius,
+            a,
+            b,
+            c,
+            1_000.0,
+        );
+
+        // the clip line is at x = 2
+        assert_eq!(clip.len(), 4);
+        assert!(
+            clip.iter()
+                .any(|s| s.x_segment.a.x == 2_000 && s.x_segment.b.x == 2_000)
+        );
+    }
 }
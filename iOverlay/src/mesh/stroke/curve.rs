@@ -0,0 +1,718 @@
+use crate::core::error::{InputRole, OverlayError, validate_float_paths};
+use crate::core::fill_rule::FillRule;
+use crate::core::integer::OverlayInt;
+use crate::float::scale::FixedScaleOverlayError;
+use crate::i_shape::source::resource::ShapeResource;
+use crate::mesh::stroke::builder::{CurveJoins, StrokeBuilder};
+use crate::mesh::style::{LineCap, LineJoin, OffsetCurveStyle, StrokeSide, StrokeStyle};
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::Segment;
+use crate::string::clip::ClipRule;
+use crate::string::line::IntLine;
+use crate::string::overlay::StringOverlay;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+use i_float::int::number::int::IntNumber;
+use i_float::int::number::uint::UIntNumber;
+use i_float::int::number::wide_int::WideIntNumber;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::Paths;
+use i_shape::float::adapter::ShapeToFloat;
+use i_shape::int::path::IntPath;
+
+/// Trait for generating parallel offset curves of open float paths.
+///
+/// Default methods use the `i32` integer engine. Use the `*_as::<I>` methods when you need to
+/// select `i16`, `i32`, or `i64` explicitly.
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::mesh::stroke::curve::OffsetCurve;
+/// use i_overlay::mesh::style::{OffsetCurveStyle, StrokeSide};
+///
+/// let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+/// let style = OffsetCurveStyle::new(1.0).side(StrokeSide::Right);
+///
+/// let curves = path.offset_curve(style);
+///
+/// assert_eq!(curves.len(), 1);
+/// ```
+pub trait OffsetCurve<P: FloatPointCompatible> {
+    /// Generates offset curves for paths, contours, or shapes, every path is taken as open.
+    ///
+    /// - `style`: Defines the curve properties, including distance, side, and joins.
+    ///
+    /// # Returns
+    /// A collection of `Paths<P>` running in the direction of their source paths. The parts of a
+    /// curve closer to its path than the distance, such as the loops at inner corners, are
+    /// trimmed, so one curve can break into several paths. Only a join, such as a bevel, may
+    /// come closer to the corner it is built at. With [`StrokeSide::Both`] the left curves of a
+    /// path come before its right curves.
+    fn offset_curve(&self, style: OffsetCurveStyle<P::Scalar>) -> Paths<P>;
+
+    /// Generates offset curves for paths, contours, or shapes with a fixed float-to-integer scale.
+    ///
+    /// - `style`: Defines the curve properties, including distance, side, and joins.
+    /// - `scale`: Fixed float-to-integer scale. Use `scale = 1.0 / grid_size` if you prefer grid size semantics.
+    fn offset_curve_fixed_scale(
+        &self,
+        style: OffsetCurveStyle<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Paths<P>, FixedScaleOverlayError>;
+
+    /// Same as [`Self::offset_curve`], but with an explicit integer engine.
+    fn offset_curve_as<I>(&self, style: OffsetCurveStyle<P::Scalar>) -> Paths<P>
+    where
+        I: OverlayInt + 'static;
+
+    /// Same as [`Self::offset_curve_fixed_scale`], but with an explicit integer engine.
+    fn offset_curve_fixed_scale_as<I>(
+        &self,
+        style: OffsetCurveStyle<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Paths<P>, FixedScaleOverlayError>
+    where
+        I: OverlayInt + 'static;
+
+    /// Same as [`Self::offset_curve`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first path point with a NaN or
+    /// infinite coordinate, or [`OverlayError::NonFiniteStyle`] if a style parameter is not finite.
+    ///
+    /// - `style`: Defines the curve properties, including distance, side, and joins.
+    fn try_offset_curve(&self, style: OffsetCurveStyle<P::Scalar>) -> Result<Paths<P>, OverlayError>;
+}
+
+impl<S, P> OffsetCurve<P> for S
+where
+    S: ShapeResource<P>,
+    P: FloatPointCompatible + 'static,
+{
+    fn offset_curve(&self, style: OffsetCurveStyle<P::Scalar>) -> Paths<P> {
+        self.offset_curve_as::<i32>(style)
+    }
+
+    fn offset_curve_fixed_scale(
+        &self,
+        style: OffsetCurveStyle<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Paths<P>, FixedScaleOverlayError> {
+        self.offset_curve_fixed_scale_as::<i32>(style, scale)
+    }
+
+    fn offset_curve_as<I>(&self, style: OffsetCurveStyle<P::Scalar>) -> Paths<P>
+    where
+        I: OverlayInt + 'static,
+    {
+        match OffsetCurveSolver::<P, I>::prepare(self, style) {
+            Some(solver) => solver.build(self),
+            None => Vec::new(),
+        }
+    }
+
+    fn offset_curve_fixed_scale_as<I>(
+        &self,
+        style: OffsetCurveStyle<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Paths<P>, FixedScaleOverlayError>
+    where
+        I: OverlayInt + 'static,
+    {
+        let mut solver = match OffsetCurveSolver::<P, I>::prepare(self, style) {
+            Some(solver) => solver,
+            None => return Ok(Vec::new()),
+        };
+        solver.adapter = FloatPointAdapter::try_with_scale(*solver.adapter.rect(), scale)?;
+        Ok(solver.build(self))
+    }
+
+    fn try_offset_curve(&self, style: OffsetCurveStyle<P::Scalar>) -> Result<Paths<P>, OverlayError> {
+        validate_float_paths(InputRole::Subject, self.iter_paths())?;
+        if !style.is_finite() {
+            return Err(OverlayError::NonFiniteStyle);
+        }
+        Ok(self.offset_curve(style))
+    }
+}
+
+struct OffsetCurveSolver<P: FloatPointCompatible, I: IntNumber> {
+    r: P::Scalar,
+    /// The area within the distance of the path, the offset edges of a curve are trimmed where
+    /// they enter it.
+    band_builder: StrokeBuilder<P, I>,
+    curves: Vec<CurveBuilder<P, I>>,
+    adapter: FloatPointAdapter<P, I>,
+}
+
+/// Builds the curve on one side of a path and the band its joins are trimmed with.
+struct CurveBuilder<P: FloatPointCompatible, I: IntNumber> {
+    side: StrokeSide,
+    curve: StrokeBuilder<P, I>,
+    /// The halves of the join band: the round one on the other side and the one with the joins
+    /// of the curve, so they run along the band boundary.
+    join_band: [StrokeBuilder<P, I>; 2],
+}
+
+impl<P, I> OffsetCurveSolver<P, I>
+where
+    P: 'static + FloatPointCompatible,
+    I: OverlayInt + 'static,
+{
+    /// The step of the round caps and joins of the bands.
+    const ROUND_ANGLE: f64 = 0.02 * PI;
+
+    fn prepare<S: ShapeResource<P>>(source: &S, style: OffsetCurveStyle<P::Scalar>) -> Option<Self> {
+        let mut rect = FloatRect::with_iter(source.iter_paths().flatten())?;
+
+        let r = P::Scalar::from_float(style.distance.to_f64().max(0.0));
+        let round_angle = P::Scalar::from_float(Self::ROUND_ANGLE);
+        let round_cap = LineCap::Round(round_angle);
+        let band_style = StrokeStyle::new(r + r)
+            .start_cap(round_cap.clone())
+            .end_cap(round_cap)
+            .line_join(LineJoin::Round(round_angle));
+        let curve_style = band_style.clone().line_join(style.join);
+
+        let sides: &[StrokeSide] = match style.side {
+            StrokeSide::Both => &[StrokeSide::Left, StrokeSide::Right],
+            StrokeSide::Left => &[StrokeSide::Left],
+            StrokeSide::Right => &[StrokeSide::Right],
+        };
+        let curves: Vec<_> = sides
+            .iter()
+            .map(|&side| {
+                let other = match side {
+                    StrokeSide::Left => StrokeSide::Right,
+                    _ => StrokeSide::Left,
+                };
+                CurveBuilder {
+                    side,
+                    curve: StrokeBuilder::new(curve_style.clone().side(side)),
+                    join_band: [
+                        StrokeBuilder::new(band_style.clone().side(other)),
+                        StrokeBuilder::new(curve_style.clone().side(side)),
+                    ],
+                }
+            })
+            .collect();
+        let band_builder = StrokeBuilder::<P, I>::new(band_style);
+
+        let offset = curves
+            .iter()
+            .flat_map(|curve| curve.join_band.iter())
+            .map(|band| band.additional_offset(r))
+            .fold(
+                band_builder.additional_offset(r),
+                |a, b| if b > a { b } else { a },
+            );
+        rect.add_offset(offset);
+        let adapter = FloatPointAdapter::new(rect);
+
+        Some(Self {
+            r,
+            band_builder,
+            curves,
+            adapter,
+        })
+    }
+
+    fn build<S: ShapeResource<P>>(self, source: &S) -> Paths<P> {
+        let ir = self.adapter.round_len_to_int(self.r).to_wide().unsigned_abs();
+        if ir <= I::WideUInt::ONE {
+            // offset is too small
+            return Vec::new();
+        }
+
+        let radius = self.r.to_f64() * self.adapter.dir_scale().to_f64();
+
+        let mut band = Vec::new();
+        let mut join_band = Vec::new();
+        let mut sides = Vec::new();
+        let mut joins = CurveJoins::new();
+        let mut lines = Vec::new();
+        let mut paths = Vec::new();
+        for path in source.iter_paths() {
+            band.clear();
+            self.band_builder.build(path, false, &self.adapter, &mut band);
+            let corners = CornerGrid::new(path.iter().map(|p| self.adapter.float_to_int(p)), radius);
+
+            for builder in self.curves.iter() {
+                sides.clear();
+                joins.clear();
+                builder
+                    .curve
+                    .build_curve(path, &self.adapter, &mut sides, &mut joins);
+                if sides.is_empty() {
+                    continue;
+                }
+
+                lines.clear();
+                lines.extend(sides.iter().map(|s| Self::directed_line(s, builder.side)));
+                let mut curves = Self::clip(&band, &lines);
+
+                if !joins.segments.is_empty() {
+                    // A join is within the distance of its own corner, so it is trimmed with a band
+                    // that has the joins of the curve, and with the round corners of the others.
+                    lines.clear();
+                    for (segment, corner) in joins.segments.iter().zip(joins.corners.iter()) {
+                        let line = Self::directed_line(segment, builder.side);
+                        corners.cut_line(line, *corner, &mut lines);
+                    }
+                    join_band.clear();
+                    for half in builder.join_band.iter() {
+                        half.build(path, false, &self.adapter, &mut join_band);
+                    }
+                    let join_curves = Self::clip(&join_band, &lines);
+                    curves = Self::stitch(curves, join_curves, &joins.segments);
+                }
+
+                curves.iter_mut().for_each(Self::remove_collinear);
+                paths.extend(curves.to_float(&self.adapter));
+            }
+        }
+
+        paths
+    }
+
+    /// The parts of the curve lines outside the band.
+    fn clip(band: &[Segment<ShapeCountBoolean, I>], lines: &[IntLine<I>]) -> Vec<IntPath<I>> {
+        // a curve is kept where it runs on the boundary of the band, inside it is a loop.
+        // An outside clip without the boundary in the clip area keeps the boundary lines.
+        let clip_rule = ClipRule {
+            invert: true,
+            boundary_included: false,
+        };
+
+        let mut overlay = StringOverlay::<I>::new(band.len() + lines.len());
+        overlay.push_shape_segments(band);
+        for line in lines.iter() {
+            overlay.push_string_line(*line);
+        }
+
+        overlay.clip_string_lines(FillRule::Positive, clip_rule)
+    }
+
+    /// Joins the trimmed joins to the trimmed offset edges, where exactly one line ends and one
+    /// starts at an end of a join edge.
+    fn stitch(
+        sides: Vec<IntPath<I>>,
+        joins: Vec<IntPath<I>>,
+        join_edges: &[Segment<ShapeCountBoolean, I>],
+    ) -> Vec<IntPath<I>> {
+        let mut ends: Vec<IntPoint<I>> = join_edges
+            .iter()
+            .flat_map(|s| [s.x_segment.a, s.x_segment.b])
+            .collect();
+        ends.sort_unstable();
+        ends.dedup();
+
+        let lines: Vec<IntPath<I>> = sides.into_iter().chain(joins).filter(|l| l.len() > 1).collect();
+
+        // (point, line) sorted by point, for the first and the last point of every line
+        let mut starts: Vec<(IntPoint<I>, usize)> =
+            lines.iter().enumerate().map(|(i, l)| (l[0], i)).collect();
+        let mut finishes: Vec<(IntPoint<I>, usize)> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (l[l.len() - 1], i))
+            .collect();
+        starts.sort_unstable();
+        finishes.sort_unstable();
+
+        let single = |list: &[(IntPoint<I>, usize)], p: IntPoint<I>| {
+            let i = list.partition_point(|e| e.0 < p);
+            let is_single = i < list.len() && list[i].0 == p && (i + 1 == list.len() || list[i + 1].0 != p);
+            is_single.then(|| list[i].1)
+        };
+
+        let mut next = vec![usize::MAX; lines.len()];
+        let mut has_prev = vec![false; lines.len()];
+        for &(p, i) in finishes.iter() {
+            if ends.binary_search(&p).is_err() {
+                continue;
+            }
+            if let (Some(a), Some(b)) = (single(&finishes, p), single(&starts, p))
+                && a == i
+                && b != i
+            {
+                next[i] = b;
+                has_prev[b] = true;
+            }
+        }
+
+        let mut result = Vec::with_capacity(lines.len());
+        let mut visited = vec![false; lines.len()];
+        // open chains first start at a line without a predecessor, what is left are loops
+        for is_loop in [false, true] {
+            for first in 0..lines.len() {
+                if visited[first] || (!is_loop && has_prev[first]) {
+                    continue;
+                }
+                let mut chain = lines[first].clone();
+                visited[first] = true;
+                let mut i = next[first];
+                while i != usize::MAX && !visited[i] {
+                    visited[i] = true;
+                    chain.extend_from_slice(&lines[i][1..]);
+                    i = next[i];
+                }
+                result.push(chain);
+            }
+        }
+
+        result
+    }
+
+    /// Removes the points inside straight runs, where the band split a curve edge.
+    fn remove_collinear(path: &mut IntPath<I>) {
+        let mut n = 0;
+        for i in 0..path.len() {
+            let p = path[i];
+            if n >= 2 {
+                let ab = path[n - 1] - path[n - 2];
+                let bp = p - path[n - 1];
+                if ab.cross_product(bp) == I::Wide::ZERO && ab.dot_product(bp) > I::Wide::ZERO {
+                    n -= 1;
+                }
+            }
+            path[n] = p;
+            n += 1;
+        }
+        path.truncate(n);
+    }
+
+    /// The curve edge in the direction of the path.
+    #[inline]
+    fn directed_line(segment: &Segment<ShapeCountBoolean, I>, side: StrokeSide) -> IntLine<I> {
+        // left edges are built against the path
+        let a = segment.x_segment.a;
+        let b = segment.x_segment.b;
+        if (segment.count.subj > 0) == (side == StrokeSide::Right) {
+            [a, b]
+        } else {
+            [b, a]
+        }
+    }
+}
+
+/// The corners of a path bucketed in square cells, to cut the join edges of a curve where they
+/// come within the distance of another corner.
+struct CornerGrid<I: IntNumber> {
+    origin: [f64; 2],
+    cell: f64,
+    radius: f64,
+    /// The corners with their cells, sorted by cell.
+    corners: Vec<((usize, usize), IntPoint<I>)>,
+}
+
+impl<I: IntNumber> CornerGrid<I> {
+    fn new(points: impl Iterator<Item = IntPoint<I>>, radius: f64) -> Self {
+        let mut grid = Self {
+            origin: [f64::MAX; 2],
+            cell: 2.0 * radius.max(1.0),
+            radius,
+            corners: points.map(|p| ((0, 0), p)).collect(),
+        };
+        for (_, p) in grid.corners.iter() {
+            grid.origin = [grid.origin[0].min(p.x.to_f64()), grid.origin[1].min(p.y.to_f64())];
+        }
+        // the cells start a band width below the corners, so every edge end has a cell
+        grid.origin = [grid.origin[0] - grid.cell, grid.origin[1] - grid.cell];
+        for i in 0..grid.corners.len() {
+            let p = grid.corners[i].1;
+            grid.corners[i].0 = grid.cell_of(p.x.to_f64(), p.y.to_f64());
+        }
+        grid.corners.sort_unstable_by_key(|(cell, p)| (*cell, *p));
+        grid.corners.dedup();
+        grid
+    }
+
+    #[inline]
+    fn cell_of(&self, x: f64, y: f64) -> (usize, usize) {
+        (
+            ((x - self.origin[0]) / self.cell) as usize,
+            ((y - self.origin[1]) / self.cell) as usize,
+        )
+    }
+
+    /// Adds the parts of `line` farther than the distance from every corner but `own`.
+    fn cut_line(&self, line: IntLine<I>, own: IntPoint<I>, lines: &mut Vec<IntLine<I>>) {
+        let [a, b] = line;
+        let (ax, ay) = (a.x.to_f64(), a.y.to_f64());
+        let (dx, dy) = (b.x.to_f64() - ax, b.y.to_f64() - ay);
+        let sqr_len = dx * dx + dy * dy;
+        if sqr_len == 0.0 {
+            return;
+        }
+
+        // the parameter ranges of the line inside the circles
+        let mut cuts: Vec<[f64; 2]> = Vec::new();
+        let r = self.radius;
+        let min = self.cell_of(ax.min(ax + dx) - r, ay.min(ay + dy) - r);
+        let max = self.cell_of(ax.max(ax + dx) + r, ay.max(ay + dy) + r);
+        for cx in min.0..=max.0 {
+            let start = self.corners.partition_point(|(cell, _)| *cell < (cx, min.1));
+            for &(cell, c) in self.corners[start..].iter() {
+                if cell > (cx, max.1) {
+                    break;
+                }
+                if c == own {
+                    continue;
+                }
+                let (ox, oy) = (ax - c.x.to_f64(), ay - c.y.to_f64());
+                let half_b = dx * ox + dy * oy;
+                let d = half_b * half_b - sqr_len * (ox * ox + oy * oy - r * r);
+                if d <= 0.0 {
+                    continue;
+                }
+                let sq = d.sqrt();
+                let t0 = ((-half_b - sq) / sqr_len).max(0.0);
+                let t1 = ((-half_b + sq) / sqr_len).min(1.0);
+                if t0 < t1 {
+                    cuts.push([t0, t1]);
+                }
+            }
+        }
+
+        if cuts.is_empty() {
+            lines.push(line);
+            return;
+        }
+
+        cuts.sort_unstable_by(|c0, c1| c0[0].total_cmp(&c1[0]));
+        let point = |t: f64| {
+            if t <= 0.0 {
+                a
+            } else if t >= 1.0 {
+                b
+            } else {
+                IntPoint::new(
+                    I::from_rounded_float(ax + t * dx),
+                    I::from_rounded_float(ay + t * dy),
+                )
+            }
+        };
+        let mut t = 0.0;
+        for cut in cuts.iter().chain(core::iter::once(&[1.0, 1.0])) {
+            if cut[0] > t {
+                let (p0, p1) = (point(t), point(cut[0]));
+                if p0 != p1 {
+                    lines.push([p0, p1]);
+                }
+            }
+            t = t.max(cut[1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::error::OverlayError;
+    use crate::mesh::stroke::curve::OffsetCurve;
+    use crate::mesh::style::{LineJoin, OffsetCurveStyle, StrokeSide};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::f64::consts::PI;
+
+    fn assert_path(path: &[[f64; 2]], expected: &[[f64; 2]]) {
+        assert_eq!(path.len(), expected.len(), "{:?}", path);
+        for (a, b) in path.iter().zip(expected) {
+            assert!(
+                (a[0] - b[0]).abs() < 1e-6 && (a[1] - b[1]).abs() < 1e-6,
+                "{:?}",
+                path
+            );
+        }
+    }
+
+    fn distance_to_path(p: [f64; 2], path: &[[f64; 2]]) -> f64 {
+        path.windows(2)
+            .map(|w| {
+                let (a, b) = (w[0], w[1]);
+                let (vx, vy) = (b[0] - a[0], b[1] - a[1]);
+                let t = ((p[0] - a[0]) * vx + (p[1] - a[1]) * vy) / (vx * vx + vy * vy);
+                let t = t.clamp(0.0, 1.0);
+                let (dx, dy) = (a[0] + t * vx - p[0], a[1] + t * vy - p[1]);
+                (dx * dx + dy * dy).sqrt()
+            })
+            .fold(f64::MAX, f64::min)
+    }
+
+    #[test]
+    fn test_line() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+        let curves = path.offset_curve(OffsetCurveStyle::new(1.0));
+
+        assert_eq!(curves.len(), 2);
+        assert_path(&curves[0], &[[0.0, 1.0], [10.0, 1.0]]);
+        assert_path(&curves[1], &[[0.0, -1.0], [10.0, -1.0]]);
+    }
+
+    #[test]
+    fn test_corner() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let style = OffsetCurveStyle::new(1.0).line_join(LineJoin::Miter(0.1 * PI));
+
+        // the inner curve is trimmed where its edges cross
+        let left = path.offset_curve(style.clone().side(StrokeSide::Left));
+        assert_eq!(left.len(), 1);
+        assert_path(&left[0], &[[0.0, 1.0], [9.0, 1.0], [9.0, 10.0]]);
+
+        let right = path.offset_curve(style.side(StrokeSide::Right));
+        assert_eq!(right.len(), 1);
+        assert_path(&right[0], &[[0.0, -1.0], [11.0, -1.0], [11.0, 10.0]]);
+
+        let bevel = path.offset_curve(OffsetCurveStyle::new(1.0).side(StrokeSide::Right));
+        assert_eq!(bevel.len(), 1);
+        assert_path(&bevel[0], &[[0.0, -1.0], [10.0, -1.0], [11.0, 0.0], [11.0, 10.0]]);
+    }
+
+    #[test]
+    fn test_reversed_path() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let reversed: Vec<[f64; 2]> = path.iter().rev().copied().collect();
+
+        let right = path.offset_curve(OffsetCurveStyle::new(1.0).side(StrokeSide::Right));
+        let mut left = reversed.offset_curve(OffsetCurveStyle::new(1.0).side(StrokeSide::Left));
+        assert_eq!(left.len(), 1);
+        left[0].reverse();
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn test_round_distance() {
+        let path = [
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [12.0, 6.0],
+            [6.0, 5.0],
+            [8.0, 12.0],
+            [20.0, 14.0],
+        ];
+        let style = OffsetCurveStyle::new(1.5).line_join(LineJoin::Round(0.1));
+
+        let curves = path.offset_curve(style);
+        assert!(curves.len() >= 2);
+        for p in curves.iter().flatten() {
+            let d = distance_to_path(*p, &path);
+            assert!((d - 1.5).abs() < 0.001, "{:?} {}", p, d);
+        }
+    }
+
+    #[test]
+    fn test_trimmed_at_distance() {
+        // the curve passes the start and the outer corner closer than the distance
+        let path = [[50.0, 77.0], [42.0, 26.0], [42.0, 50.0]];
+        let curves = path.offset_curve(OffsetCurveStyle::new(4.0).side(StrokeSide::Right));
+        assert!(!curves.is_empty());
+        for p in curves.iter().flatten() {
+            let d = distance_to_path(*p, &path);
+            assert!(d > 4.0 - 0.01, "{:?} {}", p, d);
+        }
+
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        let joins = [LineJoin::Round(0.1), LineJoin::Miter(0.1 * PI)];
+        for i in 0..600 {
+            let n = 3 + i % 4;
+            let path: Vec<[f64; 2]> = (0..n).map(|_| [100.0 * random(), 100.0 * random()]).collect();
+            let distance = 1.0 + 10.0 * random();
+            let style = OffsetCurveStyle::new(distance).line_join(joins[i % 2]);
+
+            for p in path.offset_curve(style.clone()).iter().flatten() {
+                let d = distance_to_path(*p, &path);
+                assert!(
+                    d > distance * 0.998,
+                    "{:?} {:?} {} {} {:?} {:?}",
+                    path,
+                    p,
+                    distance,
+                    d,
+                    joins[i % 3],
+                    path.offset_curve(style)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_narrow_turn() {
+        // the inner curves of a turn narrower than twice the distance vanish
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 1.0], [0.0, 1.0]];
+
+        let left = path.offset_curve(OffsetCurveStyle::new(1.0).side(StrokeSide::Left));
+        assert!(left.is_empty(), "{:?}", left);
+
+        let right = path.offset_curve(OffsetCurveStyle::new(1.0).side(StrokeSide::Right));
+        assert_eq!(right.len(), 1);
+        assert_path(
+            &right[0],
+            &[
+                [0.0, -1.0],
+                [10.0, -1.0],
+                [11.0, 0.0],
+                [11.0, 1.0],
+                [10.0, 2.0],
+                [0.0, 2.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_shapes_and_empty() {
+        let paths = vec![
+            vec![[0.0, 0.0], [10.0, 0.0]],
+            vec![[0.0, 5.0], [10.0, 5.0]],
+            vec![[3.0, 3.0]],
+        ];
+        let curves = paths.offset_curve(OffsetCurveStyle::new(1.0).side(StrokeSide::Left));
+        assert_eq!(curves.len(), 2);
+        assert_path(&curves[0], &[[0.0, 1.0], [10.0, 1.0]]);
+        assert_path(&curves[1], &[[0.0, 6.0], [10.0, 6.0]]);
+
+        let empty: [[f64; 2]; 0] = [];
+        assert!(empty.offset_curve(OffsetCurveStyle::new(1.0)).is_empty());
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+        assert!(path.offset_curve(OffsetCurveStyle::new(0.0)).is_empty());
+    }
+
+    #[test]
+    fn test_fixed_scale_and_engines() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let style = OffsetCurveStyle::new(1.0);
+
+        let curves = path.offset_curve(style.clone());
+        let wide = path.offset_curve_as::<i64>(style.clone());
+        assert_eq!(wide.len(), curves.len());
+        for (a, b) in wide.iter().zip(curves.iter()) {
+            assert_path(a, b);
+        }
+        assert_eq!(
+            path.offset_curve_fixed_scale(style.clone(), 1024.0).unwrap(),
+            curves
+        );
+        assert!(path.offset_curve_fixed_scale(style.clone(), f64::NAN).is_err());
+
+        let invalid = [[0.0, 0.0], [f64::NAN, 1.0]];
+        assert!(matches!(
+            invalid.try_offset_curve(style.clone()),
+            Err(OverlayError::NonFiniteCoordinate { .. })
+        ));
+        assert!(matches!(
+            path.try_offset_curve(style.distance(f64::INFINITY)),
+            Err(OverlayError::NonFiniteStyle)
+        ));
+    }
+}
@@ -0,0 +1,345 @@
+use alloc::vec::Vec;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+
+/// A normalized dash pattern: an even number of alternating dash and gap lengths.
+pub(super) struct DashPattern {
+    lengths: Vec<f64>,
+    /// Index of the element the pattern starts in, after applying the offset.
+    start_index: usize,
+    /// What is left of the start element after applying the offset.
+    start_remain: f64,
+}
+
+/// Walks a path and cuts it into dashes.
+struct DashCursor<'a, P> {
+    pattern: &'a DashPattern,
+    index: usize,
+    remain: f64,
+    dash: Vec<P>,
+    /// For every dash point, the path segment of the edge ending at it, if requested.
+    dash_segments: Option<Vec<usize>>,
+    dash_len: f64,
+    dir: [f64; 2],
+}
+
+impl DashPattern {
+    /// Builds a pattern the way SVG `stroke-dasharray` does: an odd list is repeated to make it
+    /// even. Returns `None` for a solid line, that is an empty list, a negative or non-finite
+    /// length, or a pattern with zero total length.
+    pub(super) fn new<T: FloatNumber>(array: &[T], offset: T) -> Option<Self> {
+        if array.is_empty() {
+            return None;
+        }
+
+        let mut lengths: Vec<f64> = array.iter().map(|v| v.to_f64()).collect();
+        if lengths.iter().any(|v| !v.is_finite() || *v < 0.0) {
+            return None;
+        }
+        if lengths.len() % 2 == 1 {
+            lengths.extend_from_within(..);
+        }
+
+        let total: f64 = lengths.iter().sum();
+        let offset = offset.to_f64();
+        if total <= 0.0 || !offset.is_finite() {
+            return None;
+        }
+
+        let mut phase = offset % total;
+        if phase < 0.0 {
+            phase += total;
+        }
+        let mut start_index = 0;
+        // a zero length dash right at the phase is kept
+        while phase > lengths[start_index] || (phase == lengths[start_index] && phase > 0.0) {
+            phase -= lengths[start_index];
+            start_index = (start_index + 1) % lengths.len();
+        }
+
+        Some(Self {
+            start_remain: lengths[start_index] - phase,
+            lengths,
+            start_index,
+        })
+    }
+
+    /// Cuts `path` into dashes and appends them to `dashes` as open paths. The pattern phase
+    /// runs continuously through the path vertices, so a dash can turn a corner.
+    /// On a closed path, a dash crossing the start point is merged into one.
+    ///
+    /// Dashes shorter than `min_len` are extended to it along the path direction, so zero length
+    /// dashes still get their caps.
+    ///
+    /// Returns `false` if no gap falls on the path; the path must then be stroked as is.
+    pub(super) fn split<P: FloatPointCompatible>(
+        &self,
+        path: &[P],
+        is_closed_path: bool,
+        min_len: f64,
+        dashes: &mut Vec<Vec<P>>,
+    ) -> bool {
+        self.split_tracked(path, is_closed_path, min_len, dashes, None)
+    }
+
+    /// Same as [`Self::split`], and appends for every dash the path segment of the edge ending at
+    /// each of its points.
+    pub(super) fn split_with_segments<P: FloatPointCompatible>(
+        &self,
+        path: &[P],
+        is_closed_path: bool,
+        min_len: f64,
+        dashes: &mut Vec<Vec<P>>,
+        segments: &mut Vec<Vec<usize>>,
+    ) -> bool {
+        self.split_tracked(path, is_closed_path, min_len, dashes, Some(segments))
+    }
+
+    fn split_tracked<P: FloatPointCompatible>(
+        &self,
+        path: &[P],
+        is_closed_path: bool,
+        min_len: f64,
+        dashes: &mut Vec<Vec<P>>,
+        mut segments: Option<&mut Vec<Vec<usize>>>,
+    ) -> bool {
+        let first_dash = dashes.len();
+        let mut cursor = DashCursor {
+            pattern: self,
+            index: self.start_index,
+            remain: self.start_remain,
+            dash: Vec::new(),
+            dash_segments: segments.as_ref().map(|_| Vec::new()),
+            dash_len: 0.0,
+            dir: [1.0, 0.0],
+        };
+
+        let starts_on = cursor.is_on();
+        let mut has_gap = false;
+
+        let closing = if is_closed_path { path.first() } else { None };
+        let mut iter = path.iter().chain(closing);
+        let Some(mut a) = iter.next() else {
+            return false;
+        };
+        if starts_on {
+            cursor.push(*a, 0);
+        }
+
+        for (segment, b) in iter.enumerate() {
+            let (ax, ay) = (a.x().to_f64(), a.y().to_f64());
+            let (dx, dy) = (b.x().to_f64() - ax, b.y().to_f64() - ay);
+            let len = FloatNumber::sqrt(dx * dx + dy * dy);
+            if len == 0.0 {
+                continue;
+            }
+            cursor.dir = [dx / len, dy / len];
+
+            let mut t = 0.0;
+            while len - t >= cursor.remain {
+                // every switch of the pattern on the path borders a gap, even a trailing one
+                has_gap = true;
+                t += cursor.remain;
+                let s = t / len;
+                let p = P::from_xy(
+                    P::Scalar::from_float(ax + s * dx),
+                    P::Scalar::from_float(ay + s * dy),
+                );
+                if cursor.is_on() {
+                    cursor.dash_len += cursor.remain;
+                    cursor.push(p, segment);
+                    cursor.flush(min_len, dashes, segments.as_deref_mut());
+                } else {
+                    cursor.push(p, segment);
+                }
+                cursor.next();
+            }
+
+            cursor.remain -= len - t;
+            if cursor.is_on() {
+                cursor.dash_len += len - t;
+                cursor.push(*b, segment);
+            }
+            a = b;
+        }
+
+        let ends_on = cursor.is_on();
+        if !has_gap && starts_on {
+            // the pattern never turns off on this path
+            dashes.truncate(first_dash);
+            if let Some(segments) = segments {
+                segments.truncate(first_dash);
+            }
+            return false;
+        }
+
+        if is_closed_path && starts_on && ends_on && dashes.len() > first_dash {
+            // join the last dash with the first one over the start point
+            let mut last = core::mem::take(&mut cursor.dash);
+            let first = &mut dashes[first_dash];
+            last.extend_from_slice(&first[1..]);
+            *first = last;
+            if let (Some(segments), Some(mut last)) = (segments, cursor.dash_segments.take()) {
+                let first = &mut segments[first_dash];
+                last.extend_from_slice(&first[1..]);
+                *first = last;
+            }
+        } else if ends_on && cursor.dash.len() > 1 {
+            cursor.flush(min_len, dashes, segments);
+        }
+
+        true
+    }
+}
+
+impl<P: FloatPointCompatible> DashCursor<'_, P> {
+    #[inline]
+    fn is_on(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+
+    #[inline]
+    fn next(&mut self) {
+        self.index = (self.index + 1) % self.pattern.lengths.len();
+        self.remain = self.pattern.lengths[self.index];
+    }
+
+    #[inline]
+    fn push(&mut self, p: P, segment: usize) {
+        self.dash.push(p);
+        if let Some(segments) = &mut self.dash_segments {
+            segments.push(segment);
+        }
+    }
+
+    fn flush(&mut self, min_len: f64, dashes: &mut Vec<Vec<P>>, segments: Option<&mut Vec<Vec<usize>>>) {
+        let mut dash = core::mem::take(&mut self.dash);
+        let mut dash_segments = self.dash_segments.as_mut().map(core::mem::take);
+        if self.dash_len < min_len {
+            // a dot, keep its direction for the caps
+            let a = dash[0];
+            let x = a.x().to_f64() + min_len * self.dir[0];
+            let y = a.y().to_f64() + min_len * self.dir[1];
+            dash.truncate(1);
+            dash.push(P::from_xy(P::Scalar::from_float(x), P::Scalar::from_float(y)));
+            if let Some(dash_segments) = &mut dash_segments {
+                let last = dash_segments[dash_segments.len() - 1];
+                dash_segments.truncate(1);
+                dash_segments.push(last);
+            }
+        }
+        dashes.push(dash);
+        if let (Some(segments), Some(dash_segments)) = (segments, dash_segments) {
+            segments.push(dash_segments);
+        }
+        self.dash_len = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mesh::stroke::dash::DashPattern;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn split(array: &[f64], offset: f64, path: &[[f64; 2]], closed: bool) -> Vec<Vec<[f64; 2]>> {
+        let mut dashes = Vec::new();
+        let pattern = DashPattern::new(array, offset).unwrap();
+        assert!(pattern.split(path, closed, 0.01, &mut dashes));
+        dashes
+    }
+
+    #[test]
+    fn test_solid() {
+        assert!(DashPattern::new::<f64>(&[], 0.0).is_none());
+        assert!(DashPattern::new(&[0.0, 0.0], 0.0).is_none());
+        assert!(DashPattern::new(&[1.0, -1.0], 0.0).is_none());
+        assert!(DashPattern::new(&[1.0, f64::NAN], 0.0).is_none());
+
+        let pattern = DashPattern::new(&[20.0, 1.0], 0.0).unwrap();
+        let mut dashes = Vec::new();
+        assert!(!pattern.split(&[[0.0, 0.0], [10.0, 0.0]], false, 0.01, &mut dashes));
+        assert!(dashes.is_empty());
+    }
+
+    #[test]
+    fn test_line() {
+        let dashes = split(&[2.0, 1.0], 0.0, &[[0.0, 0.0], [7.0, 0.0]], false);
+        assert_eq!(
+            dashes,
+            vec![
+                vec![[0.0, 0.0], [2.0, 0.0]],
+                vec![[3.0, 0.0], [5.0, 0.0]],
+                vec![[6.0, 0.0], [7.0, 0.0]],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_odd_array_and_offset() {
+        // [2] is [2, 2], the offset shifts the pattern forward
+        let dashes = split(&[2.0], 1.0, &[[0.0, 0.0], [8.0, 0.0]], false);
+        assert_eq!(
+            dashes,
+            vec![
+                vec![[0.0, 0.0], [1.0, 0.0]],
+                vec![[3.0, 0.0], [5.0, 0.0]],
+                vec![[7.0, 0.0], [8.0, 0.0]],
+            ]
+        );
+
+        let negative = split(&[2.0], -3.0, &[[0.0, 0.0], [8.0, 0.0]], false);
+        assert_eq!(dashes, negative);
+    }
+
+    #[test]
+    fn test_corner() {
+        let dashes = split(&[4.0, 2.0], 0.0, &[[0.0, 0.0], [2.0, 0.0], [2.0, 5.0]], false);
+        assert_eq!(
+            dashes,
+            vec![
+                vec![[0.0, 0.0], [2.0, 0.0], [2.0, 2.0]],
+                vec![[2.0, 4.0], [2.0, 5.0]]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_closed_seam() {
+        // perimeter 16, the last dash runs over the start point into the first one
+        let square = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+        let dashes = split(&[3.0, 2.0], 0.0, &square, true);
+        assert_eq!(dashes.len(), 3);
+        assert_eq!(dashes[0], vec![[0.0, 1.0], [0.0, 0.0], [3.0, 0.0]]);
+        assert_eq!(dashes[1], vec![[4.0, 1.0], [4.0, 4.0]]);
+        assert_eq!(dashes[2], vec![[2.0, 4.0], [0.0, 4.0], [0.0, 3.0]]);
+    }
+
+    #[test]
+    fn test_dots() {
+        let dashes = split(&[0.0, 2.0], 0.0, &[[0.0, 0.0], [0.0, 5.0]], false);
+        assert_eq!(dashes.len(), 3);
+        for (i, dash) in dashes.iter().enumerate() {
+            let y = 2.0 * i as f64;
+            assert_eq!(dash, &vec![[0.0, y], [0.0, y + 0.01]]);
+        }
+    }
+
+    #[test]
+    fn test_trailing_gap() {
+        // the last dash ends before the path end and the gap runs past it
+        let dashes = split(&[2.0, 2.0], 0.0, &[[0.0, 0.0], [3.0, 0.0]], false);
+        assert_eq!(dashes, vec![vec![[0.0, 0.0], [2.0, 0.0]]]);
+
+        let dashes = split(&[3.0, 100.0], 0.0, &[[0.0, 0.0], [10.0, 0.0]], false);
+        assert_eq!(dashes, vec![vec![[0.0, 0.0], [3.0, 0.0]]]);
+
+        // perimeter 12, the only gap covers the end of the closing edge
+        let square = [[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0]];
+        let dashes = split(&[10.0, 100.0], 0.0, &square, true);
+        assert_eq!(
+            dashes,
+            vec![vec![[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0], [0.0, 2.0]]]
+        );
+    }
+}
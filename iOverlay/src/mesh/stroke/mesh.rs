@@ -0,0 +1,413 @@
+use crate::core::integer::OverlayInt;
+use crate::i_shape::source::resource::ShapeResource;
+use crate::mesh::stroke::offset::StrokeOffset;
+use crate::mesh::style::StrokeStyle;
+use crate::mesh::triangulation::triangulate::Triangulate;
+use crate::mesh::triangulation::{MeshOptions, TriangleMesh};
+use alloc::vec::Vec;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+
+/// Trait for generating stroke geometry as an indexed triangle mesh.
+///
+/// Default methods use the `i32` integer engine. Use the `*_as::<I>` methods when you need to
+/// select `i16`, `i32`, or `i64` explicitly.
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::mesh::stroke::mesh::StrokeMesh;
+/// use i_overlay::mesh::style::StrokeStyle;
+/// use i_overlay::mesh::triangulation::MeshOptions;
+///
+/// let path = [[0.0, 0.0], [10.0, 0.0]];
+/// let style = StrokeStyle::new(2.0);
+///
+/// let mesh = path.stroke_mesh(style, false, MeshOptions::default());
+///
+/// assert_eq!(mesh.triangles_count(), 2);
+/// assert_eq!(mesh.distance.len(), mesh.vertices.len());
+/// ```
+pub trait StrokeMesh<P: FloatPointCompatible> {
+    /// Generates a stroke mesh for paths, contours, or shapes.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `options`: Adjust the mesh, such as the antialiasing fringe.
+    ///
+    /// # Returns
+    /// A `TriangleMesh<P>` of the stroke, the same region as [`StrokeOffset::stroke`] returns.
+    /// Its `distance` holds, for every vertex, the arc length along the nearest source path,
+    /// measured from the start of that path, for example to map dashes or gradients.
+    fn stroke_mesh(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        options: MeshOptions<P::Scalar>,
+    ) -> TriangleMesh<P>;
+
+    /// Same as [`Self::stroke_mesh`], but with an explicit integer engine.
+    fn stroke_mesh_as<I>(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        options: MeshOptions<P::Scalar>,
+    ) -> TriangleMesh<P>
+    where
+        I: OverlayInt + 'static;
+}
+
+impl<S, P> StrokeMesh<P> for S
+where
+    S: ShapeResource<P>,
+    P: FloatPointCompatible + 'static,
+{
+    fn stroke_mesh(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        options: MeshOptions<P::Scalar>,
+    ) -> TriangleMesh<P> {
+        self.stroke_mesh_as::<i32>(style, is_closed_path, options)
+    }
+
+    fn stroke_mesh_as<I>(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        options: MeshOptions<P::Scalar>,
+    ) -> TriangleMesh<P>
+    where
+        I: OverlayInt + 'static,
+    {
+        let shapes = self.stroke_as::<I>(style, is_closed_path);
+        let mut mesh = shapes.triangulate_as::<I>(options);
+        let grid = EdgeGrid::new(self, is_closed_path, &mesh.vertices);
+        mesh.distance = mesh
+            .vertices
+            .iter()
+            .map(|p| P::Scalar::from_float(grid.distance([p.x().to_f64(), p.y().to_f64()])))
+            .collect();
+
+        mesh
+    }
+}
+
+/// A source path edge with the arc length at its start.
+struct SourceEdge {
+    a: [f64; 2],
+    e: [f64; 2],
+    sqr_len: f64,
+    len: f64,
+    start: f64,
+}
+
+/// The source path edges bucketed in a uniform grid, so finding the nearest edge of a point
+/// only visits the cells around it instead of every edge.
+struct EdgeGrid {
+    edges: Vec<SourceEdge>,
+    min: [f64; 2],
+    cell: f64,
+    cols: usize,
+    rows: usize,
+    /// The edges of cell `i` are `items[offsets[i]..offsets[i + 1]]`.
+    offsets: Vec<usize>,
+    items: Vec<usize>,
+}
+
+impl EdgeGrid {
+    /// Builds the grid of the `source` paths, covering the query `points` too.
+    fn new<S, P>(source: &S, is_closed_path: bool, points: &[P]) -> Self
+    where
+        S: ShapeResource<P>,
+        P: FloatPointCompatible,
+    {
+        let mut edges = Vec::new();
+        for path in source.iter_paths() {
+            let xy = |p: &P| [p.x().to_f64(), p.y().to_f64()];
+            let mut start = 0.0;
+            let mut add = |a: [f64; 2], b: [f64; 2]| {
+                let e = [b[0] - a[0], b[1] - a[1]];
+                let sqr_len = e[0] * e[0] + e[1] * e[1];
+                let len = sqr_len.sqrt();
+                edges.push(SourceEdge {
+                    a,
+                    e,
+                    sqr_len,
+                    len,
+                    start,
+                });
+                start += len;
+            };
+            match path {
+                [] => {}
+                // a single point is an edge of zero length
+                [a] => add(xy(a), xy(a)),
+                _ => {
+                    for w in path.windows(2) {
+                        add(xy(&w[0]), xy(&w[1]));
+                    }
+                    if is_closed_path && path.len() > 2 {
+                        add(xy(&path[path.len() - 1]), xy(&path[0]));
+                    }
+                }
+            }
+        }
+
+        let mut min = [f64::MAX; 2];
+        let mut max = [f64::MIN; 2];
+        let ends = edges
+            .iter()
+            .flat_map(|e| [e.a, [e.a[0] + e.e[0], e.a[1] + e.e[1]]]);
+        for p in ends.chain(points.iter().map(|p| [p.x().to_f64(), p.y().to_f64()])) {
+            min = [min[0].min(p[0]), min[1].min(p[1])];
+            max = [max[0].max(p[0]), max[1].max(p[1])];
+        }
+
+        let mut grid = Self {
+            edges,
+            min,
+            cell: 1.0,
+            cols: 1,
+            rows: 1,
+            offsets: Vec::new(),
+            items: Vec::new(),
+        };
+        if grid.edges.is_empty() {
+            return grid;
+        }
+
+        // about one cell per edge, and never more cells along a side than edges
+        let n = grid.edges.len() as f64;
+        let (w, h) = (max[0] - min[0], max[1] - min[1]);
+        let cell = (w * h / n).sqrt().max(w.max(h) / n);
+        if cell > 0.0 {
+            grid.cell = cell;
+        }
+        grid.cols = (w / grid.cell) as usize + 1;
+        grid.rows = (h / grid.cell) as usize + 1;
+
+        let mut counts = alloc::vec![0; grid.cols * grid.rows + 1];
+        for edge in grid.edges.iter() {
+            grid.for_each_cell(edge, |i| counts[i + 1] += 1);
+        }
+        for i in 1..counts.len() {
+            counts[i] += counts[i - 1];
+        }
+        let mut items = alloc::vec![0; counts[counts.len() - 1]];
+        let mut next = counts.clone();
+        for (index, edge) in grid.edges.iter().enumerate() {
+            grid.for_each_cell(edge, |i| {
+                items[next[i]] = index;
+                next[i] += 1;
+            });
+        }
+        grid.offsets = counts;
+        grid.items = items;
+
+        grid
+    }
+
+    #[inline]
+    fn col(&self, x: f64) -> usize {
+        (((x - self.min[0]) / self.cell).max(0.0) as usize).min(self.cols - 1)
+    }
+
+    #[inline]
+    fn row(&self, y: f64) -> usize {
+        (((y - self.min[1]) / self.cell).max(0.0) as usize).min(self.rows - 1)
+    }
+
+    /// Calls `f` with every cell the edge crosses, column by column.
+    fn for_each_cell(&self, edge: &SourceEdge, mut f: impl FnMut(usize)) {
+        let [ax, ay] = edge.a;
+        let (bx, by) = (ax + edge.e[0], ay + edge.e[1]);
+        let (x_min, x_max) = (ax.min(bx), ax.max(bx));
+        for col in self.col(x_min)..=self.col(x_max) {
+            // the part of the edge inside the column
+            let x0 = (self.min[0] + col as f64 * self.cell).max(x_min);
+            let x1 = (self.min[0] + (col + 1) as f64 * self.cell).min(x_max);
+            let (y0, y1) = if edge.e[0] == 0.0 {
+                (ay, by)
+            } else {
+                let k = edge.e[1] / edge.e[0];
+                (ay + (x0 - ax) * k, ay + (x1 - ax) * k)
+            };
+            for row in self.row(y0.min(y1))..=self.row(y0.max(y1)) {
+                f(row * self.cols + col);
+            }
+        }
+    }
+
+    /// The arc length at the point of the source paths nearest to `p`. Of equally near edges,
+    /// the first one in path order wins.
+    fn distance(&self, p: [f64; 2]) -> f64 {
+        if self.edges.is_empty() {
+            return 0.0;
+        }
+        let [px, py] = p;
+        let (pc, pr) = (self.col(px) as isize, self.row(py) as isize);
+        let (cols, rows) = (self.cols as isize, self.rows as isize);
+
+        let mut best_sqr_dist = f64::MAX;
+        let mut best_index = usize::MAX;
+        let mut best = 0.0;
+        for ring in 0..cols.max(rows) {
+            // the cells `ring` steps away from the cell of `p`
+            for r in (pr - ring).max(0)..=(pr + ring).min(rows - 1) {
+                let step = if (r - pr).abs() == ring { 1 } else { 2 * ring };
+                let mut c = pc - ring;
+                while c <= pc + ring {
+                    if c >= 0 && c < cols {
+                        let i = (r * cols + c) as usize;
+                        for &index in &self.items[self.offsets[i]..self.offsets[i + 1]] {
+                            let edge = &self.edges[index];
+                            let [ax, ay] = edge.a;
+                            let [ex, ey] = edge.e;
+                            let t = if edge.sqr_len > 0.0 {
+                                (((px - ax) * ex + (py - ay) * ey) / edge.sqr_len).clamp(0.0, 1.0)
+                            } else {
+                                0.0
+                            };
+                            let (dx, dy) = (px - ax - t * ex, py - ay - t * ey);
+                            let sqr_dist = dx * dx + dy * dy;
+                            if sqr_dist < best_sqr_dist || (sqr_dist == best_sqr_dist && index < best_index) {
+                                best_sqr_dist = sqr_dist;
+                                best_index = index;
+                                best = edge.start + t * edge.len;
+                            }
+                        }
+                    }
+                    c += step.max(1);
+                }
+            }
+
+            // every cell further away is at least `ring` cells from `p`
+            let reach = ring as f64 * self.cell;
+            if best_sqr_dist < reach * reach {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mesh::stroke::mesh::{EdgeGrid, StrokeMesh};
+    use crate::mesh::style::{LineCap, LineJoin, StrokeStyle};
+    use crate::mesh::triangulation::{MeshOptions, TriangleMesh};
+
+    fn area(mesh: &TriangleMesh<[f64; 2]>) -> f64 {
+        let mut area = 0.0;
+        for t in mesh.indices.chunks(3) {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| mesh.vertices[i as usize]);
+            let s = 0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]));
+            assert!(s > 0.0);
+            area += s;
+        }
+        area
+    }
+
+    #[test]
+    fn test_line() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+        let mesh = path.stroke_mesh(StrokeStyle::new(2.0), false, MeshOptions::default());
+
+        assert_eq!(mesh.vertices.len(), 4);
+        assert!((area(&mesh) - 20.0).abs() < 1e-6);
+        for (p, d) in mesh.vertices.iter().zip(mesh.distance.iter()) {
+            assert!((p[0] - d).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_corner_distance() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let style = StrokeStyle::new(2.0)
+            .start_cap(LineCap::Square)
+            .end_cap(LineCap::Square);
+        let mesh = path.stroke_mesh(style, false, MeshOptions::default());
+
+        assert_eq!(mesh.distance.len(), mesh.vertices.len());
+        let max = mesh.distance.iter().cloned().fold(f64::MIN, f64::max);
+        let min = mesh.distance.iter().cloned().fold(f64::MAX, f64::min);
+        assert!(min.abs() < 1e-6);
+        assert!((max - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_closed_with_fringe() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let options = MeshOptions::default().fringe_width(0.5);
+        let style = StrokeStyle::new(2.0).line_join(LineJoin::Miter(0.1));
+        let mesh = path.stroke_mesh(style, true, options);
+
+        // a frame from 12 x 12 down to 8 x 8, grown by the fringe on both sides
+        assert_eq!(mesh.coverage.len(), mesh.vertices.len());
+        assert_eq!(mesh.distance.len(), mesh.vertices.len());
+        assert!((area(&mesh) - (13.0 * 13.0 - 7.0 * 7.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_empty() {
+        let path: [[f64; 2]; 0] = [];
+        let mesh = path.stroke_mesh(StrokeStyle::new(2.0), false, MeshOptions::default());
+
+        assert!(mesh.is_empty());
+        assert!(mesh.vertices.is_empty());
+    }
+
+    #[test]
+    fn test_grid_matches_brute_force() {
+        // a spiral with some repeated and collinear points
+        let mut path = alloc::vec::Vec::new();
+        for i in 0..400 {
+            let a = 0.1 * i as f64;
+            let r = 1.0 + 0.05 * i as f64;
+            path.push([
+                (r * a.cos() * 8.0).round() / 8.0,
+                (r * a.sin() * 8.0).round() / 8.0,
+            ]);
+        }
+        let points: alloc::vec::Vec<[f64; 2]> = (0..2000)
+            .map(|i| {
+                let a = 0.37 * i as f64;
+                let r = 0.013 * i as f64;
+                [r * a.cos(), r * a.sin()]
+            })
+            .collect();
+
+        for is_closed in [false, true] {
+            let grid = EdgeGrid::new(&path, is_closed, &points);
+            let mut edges = alloc::vec::Vec::new();
+            let mut start = 0.0;
+            let n = if is_closed { path.len() } else { path.len() - 1 };
+            for i in 0..n {
+                let (a, b) = (path[i], path[(i + 1) % path.len()]);
+                let len = ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+                edges.push((a, b, start, len));
+                start += len;
+            }
+            for p in points.iter() {
+                let mut best = (f64::MAX, 0.0);
+                for &(a, b, start, len) in edges.iter() {
+                    let e = [b[0] - a[0], b[1] - a[1]];
+                    let sqr_len = e[0] * e[0] + e[1] * e[1];
+                    let t = if sqr_len > 0.0 {
+                        (((p[0] - a[0]) * e[0] + (p[1] - a[1]) * e[1]) / sqr_len).clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    };
+                    let (dx, dy) = (p[0] - a[0] - t * e[0], p[1] - a[1] - t * e[1]);
+                    let sqr_dist = dx * dx + dy * dy;
+                    if sqr_dist < best.0 {
+                        best = (sqr_dist, start + t * len);
+                    }
+                }
+                assert_eq!(grid.distance(*p), best.1);
+            }
+        }
+    }
+}
@@ -1,5 +1,8 @@
 mod builder;
 mod builder_cap;
 mod builder_join;
+pub mod curve;
+mod dash;
+pub mod mesh;
 pub mod offset;
 mod section;
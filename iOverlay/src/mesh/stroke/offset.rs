@@ -1,13 +1,25 @@
+use crate::core::control::OperationControl;
+use crate::core::edge_overlay::{EdgeOverlay, InputEdge};
+use crate::core::error::{InputRole, OverlayError, validate_float_paths};
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
-use crate::core::overlay::Overlay;
+use crate::core::overlay::{Overlay, ShapeType};
 use crate::core::overlay_rule::OverlayRule;
+use crate::float::auto::{AutoOutput, AutoScaleOptions, EngineSelection, IntEngine};
+use crate::float::edge_overlay::FloatDataVectorShape;
 use crate::float::overlay::OverlayOptions;
 use crate::float::scale::FixedScaleOverlayError;
 use crate::i_shape::source::resource::ShapeResource;
+use crate::mesh::source::{
+    OffsetEdgeKind, OffsetEdgeSource, SourceTrack, add_segments_with_sources, add_vector_shapes,
+    shapes_to_float,
+};
 use crate::mesh::stroke::builder::StrokeBuilder;
+use crate::mesh::stroke::dash::DashPattern;
 use crate::mesh::stroke::offset::vec::Vec;
-use crate::mesh::style::StrokeStyle;
+use crate::mesh::style::{StrokeAlignment, StrokeSide, StrokeStyle};
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::Segment;
 use alloc::vec;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
@@ -22,6 +34,7 @@ use i_shape::flat::float::FloatFlatContoursBuffer;
 use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::despike::DeSpikeContour;
 use i_shape::float::simple::SimplifyContour;
+use i_shape::int::shape::IntShapes;
 
 /// Trait for generating stroke outlines from float paths.
 ///
@@ -239,6 +252,65 @@ pub trait StrokeOffset<P: FloatPointCompatible> {
     ) -> Result<(), FixedScaleOverlayError>
     where
         I: OverlayInt + 'static;
+
+    /// Generates stroke shapes as edges that tell where they come from.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    ///
+    /// # Returns
+    /// The region of [`Self::stroke`] as shapes of contours of edges. Every edge carries the
+    /// [`OffsetEdgeSource`] naming the source path, the segment, and whether it is a side, join,
+    /// or cap edge. Collinear output points are kept, so no edge spans two sources.
+    fn stroke_with_sources(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>>;
+
+    /// Same as [`Self::stroke_with_sources`], but with an explicit integer engine.
+    fn stroke_with_sources_as<I>(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>>
+    where
+        I: OverlayInt + 'static;
+
+    /// Same as [`Self::stroke`], but the integer engine is selected automatically.
+    ///
+    /// The selection takes the stroke bounds, the input edges and the stroke width into account.
+    /// A width that is not positive strokes nothing, the result is empty and no feature is
+    /// required to be resolved. See [`crate::float::auto`] for the precision guarantee.
+    fn stroke_auto(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Shapes<P>, P::Scalar>;
+
+    /// Same as [`Self::stroke`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::NonFiniteCoordinate`] naming the first path point with a NaN or
+    /// infinite coordinate, or [`OverlayError::NonFiniteStyle`] if a style parameter is not finite.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    fn try_stroke(&self, style: StrokeStyle<P>, is_closed_path: bool) -> Result<Shapes<P>, OverlayError>;
+
+    /// Same as [`Self::try_stroke`], but reports progress to `control` and lets it cancel the
+    /// operation with [`OverlayError::Cancelled`]. `control` is polled by every overlay the stroke
+    /// runs, so an aligned stroke goes through the phases twice.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `control`: Receives progress updates and may cancel.
+    fn try_stroke_with_control(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        control: &mut dyn OperationControl,
+    ) -> Result<Shapes<P>, OverlayError>;
 }
 
 impl<S, P> StrokeOffset<P> for S
@@ -284,7 +356,7 @@ where
         is_closed_path: bool,
         options: OverlayOptions<P::Scalar>,
     ) -> Shapes<P> {
-        match StrokeSolver::<P, i32>::prepare(self, style) {
+        match StrokeSolver::<P, i32>::prepare(self, style, is_closed_path) {
             Some(solver) => solver.build(self, is_closed_path, options),
             None => vec![],
         }
@@ -297,7 +369,7 @@ where
         options: OverlayOptions<P::Scalar>,
         output: &mut FloatFlatContoursBuffer<P>,
     ) {
-        match StrokeSolver::<P, i32>::prepare(self, style) {
+        match StrokeSolver::<P, i32>::prepare(self, style, is_closed_path) {
             Some(solver) => solver.build_into(self, is_closed_path, options, output),
             None => output.clear_and_reserve(0, 0),
         }
@@ -310,7 +382,7 @@ where
         options: OverlayOptions<P::Scalar>,
         scale: P::Scalar,
     ) -> Result<Shapes<P>, FixedScaleOverlayError> {
-        let mut solver = match StrokeSolver::<P, i32>::prepare(self, style) {
+        let mut solver = match StrokeSolver::<P, i32>::prepare(self, style, is_closed_path) {
             Some(solver) => solver,
             None => return Ok(vec![]),
         };
@@ -326,7 +398,7 @@ where
         scale: P::Scalar,
         output: &mut FloatFlatContoursBuffer<P>,
     ) -> Result<(), FixedScaleOverlayError> {
-        let mut solver = match StrokeSolver::<P, i32>::prepare(self, style) {
+        let mut solver = match StrokeSolver::<P, i32>::prepare(self, style, is_closed_path) {
             Some(solver) => solver,
             None => {
                 output.clear_and_reserve(0, 0);
@@ -365,7 +437,7 @@ where
     where
         I: OverlayInt + 'static,
     {
-        match StrokeSolver::<P, I>::prepare(self, style) {
+        match StrokeSolver::<P, I>::prepare(self, style, is_closed_path) {
             Some(solver) => solver.build(self, is_closed_path, options),
             None => vec![],
         }
@@ -380,7 +452,7 @@ where
     ) where
         I: OverlayInt + 'static,
     {
-        match StrokeSolver::<P, I>::prepare(self, style) {
+        match StrokeSolver::<P, I>::prepare(self, style, is_closed_path) {
             Some(solver) => solver.build_into(self, is_closed_path, options, output),
             None => output.clear_and_reserve(0, 0),
         }
@@ -421,7 +493,7 @@ where
     where
         I: OverlayInt + 'static,
     {
-        let mut solver = match StrokeSolver::<P, I>::prepare(self, style) {
+        let mut solver = match StrokeSolver::<P, I>::prepare(self, style, is_closed_path) {
             Some(solver) => solver,
             None => return Ok(vec![]),
         };
@@ -440,7 +512,7 @@ where
     where
         I: OverlayInt + 'static,
     {
-        let mut solver = match StrokeSolver::<P, I>::prepare(self, style) {
+        let mut solver = match StrokeSolver::<P, I>::prepare(self, style, is_closed_path) {
             Some(solver) => solver,
             None => {
                 output.clear_and_reserve(0, 0);
@@ -451,14 +523,167 @@ where
         solver.build_into(self, is_closed_path, options, output);
         Ok(())
     }
+
+    fn stroke_with_sources(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>> {
+        self.stroke_with_sources_as::<i32>(style, is_closed_path)
+    }
+
+    fn stroke_with_sources_as<I>(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>>
+    where
+        I: OverlayInt + 'static,
+    {
+        match StrokeSolver::<P, I>::prepare(self, style, is_closed_path) {
+            Some(solver) => solver.build_with_sources(self, is_closed_path),
+            None => vec![],
+        }
+    }
+
+    fn stroke_auto(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        options: AutoScaleOptions<P::Scalar>,
+    ) -> AutoOutput<Shapes<P>, P::Scalar> {
+        let rect = match FloatRect::with_iter(self.iter_paths().flatten()) {
+            Some(mut rect) => {
+                rect.add_offset(stroke_extent(&style, is_closed_path));
+                rect
+            }
+            None => FloatRect::zero(),
+        };
+
+        let width = style.width;
+        if width <= P::Scalar::ZERO {
+            // nothing to stroke, so no feature has to be resolved
+            let selection = EngineSelection::with_rect(rect, None, &options);
+            return AutoOutput {
+                result: vec![],
+                selection,
+            };
+        }
+
+        let feature_size = match options.feature_size(self.iter_paths(), is_closed_path) {
+            Some(size) => Some(size.min(width)),
+            None => Some(width),
+        };
+
+        let selection = EngineSelection::with_rect(rect, feature_size, &options);
+        let result = match selection.engine {
+            IntEngine::I16 => {
+                let adapter = FloatPointAdapter::<P, i16>::new(rect);
+                stroke_with_adapter(self, style, is_closed_path, Default::default(), adapter)
+            }
+            IntEngine::I32 => {
+                let adapter = FloatPointAdapter::<P, i32>::new(rect);
+                stroke_with_adapter(self, style, is_closed_path, Default::default(), adapter)
+            }
+            IntEngine::I64 => {
+                let adapter = FloatPointAdapter::<P, i64>::new(rect);
+                stroke_with_adapter(self, style, is_closed_path, Default::default(), adapter)
+            }
+        };
+
+        AutoOutput { result, selection }
+    }
+
+    fn try_stroke(&self, style: StrokeStyle<P>, is_closed_path: bool) -> Result<Shapes<P>, OverlayError> {
+        validate_float_paths(InputRole::Subject, self.iter_paths())?;
+        if !style.is_finite() {
+            return Err(OverlayError::NonFiniteStyle);
+        }
+        Ok(self.stroke(style, is_closed_path))
+    }
+
+    fn try_stroke_with_control(
+        &self,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        control: &mut dyn OperationControl,
+    ) -> Result<Shapes<P>, OverlayError> {
+        validate_float_paths(InputRole::Subject, self.iter_paths())?;
+        if !style.is_finite() {
+            return Err(OverlayError::NonFiniteStyle);
+        }
+        match StrokeSolver::<P, i32>::prepare(self, style, is_closed_path) {
+            Some(solver) => solver.try_build_with_control(self, is_closed_path, Default::default(), control),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+/// Generates stroke shapes on a pre-configured adapter grid.
+///
+/// The caller is responsible for the adapter covering the stroke, including its caps and joins.
+pub(crate) fn stroke_with_adapter<S, P, I>(
+    source: &S,
+    style: StrokeStyle<P>,
+    is_closed_path: bool,
+    options: OverlayOptions<P::Scalar, I>,
+    adapter: FloatPointAdapter<P, I>,
+) -> Shapes<P>
+where
+    S: ShapeResource<P>,
+    P: FloatPointCompatible + 'static,
+    I: OverlayInt + 'static,
+{
+    match StrokeSolver::with_adapter(source, style, is_closed_path, adapter) {
+        Some(solver) => solver.build(source, is_closed_path, options),
+        None => vec![],
+    }
+}
+
+/// An upper bound of how far the stroke of `style` reaches past its source paths.
+pub(crate) fn stroke_extent<P>(style: &StrokeStyle<P>, is_closed_path: bool) -> P::Scalar
+where
+    P: FloatPointCompatible + 'static,
+{
+    let (builder_style, _) = builder_style(style.clone(), is_closed_path);
+    StrokeBuilder::<P, i32>::max_offset(&builder_style)
+}
+
+/// The style the builder runs with, and where the stroke lies.
+///
+/// A closed path is stroked on both sides. An aligned or one-sided stroke is built twice as wide,
+/// then clipped or cut down to the requested width.
+fn builder_style<P: FloatPointCompatible>(
+    mut style: StrokeStyle<P>,
+    is_closed_path: bool,
+) -> (StrokeStyle<P>, StrokeAlignment) {
+    let alignment = if is_closed_path {
+        style.side = StrokeSide::Both;
+        style.alignment
+    } else {
+        StrokeAlignment::Center
+    };
+    if alignment != StrokeAlignment::Center {
+        // a centered band twice as wide, one half is clipped off
+        style.width = style.width + style.width;
+    }
+    if style.side != StrokeSide::Both {
+        // the builder offsets one side only by half of the width
+        style.width = style.width + style.width;
+    }
+
+    (style, alignment)
 }
 
 struct StrokeSolver<P: FloatPointCompatible, I: IntNumber> {
     r: P::Scalar,
     builder: StrokeBuilder<P, I>,
+    dash: Option<DashPattern>,
     adapter: FloatPointAdapter<P, I>,
     paths_count: usize,
     points_count: usize,
+    /// Where the stroke lies, always `Center` for open paths.
+    alignment: StrokeAlignment,
 }
 
 impl<P, I> StrokeSolver<P, I>
@@ -466,7 +691,24 @@ where
     P: 'static + FloatPointCompatible,
     I: OverlayInt + 'static,
 {
-    fn prepare<S: ShapeResource<P>>(source: &S, style: StrokeStyle<P>) -> Option<Self> {
+    fn prepare<S: ShapeResource<P>>(source: &S, style: StrokeStyle<P>, is_closed_path: bool) -> Option<Self> {
+        let zero_adapter = FloatPointAdapter::new(FloatRect::zero());
+        let mut solver = Self::with_adapter(source, style, is_closed_path, zero_adapter)?;
+
+        let a = solver.builder.additional_offset(solver.r);
+        let mut rect = FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
+        rect.add_offset(a);
+        solver.adapter = FloatPointAdapter::<P, I>::new(rect);
+
+        Some(solver)
+    }
+
+    fn with_adapter<S: ShapeResource<P>>(
+        source: &S,
+        style: StrokeStyle<P>,
+        is_closed_path: bool,
+        adapter: FloatPointAdapter<P, I>,
+    ) -> Option<Self> {
         let mut paths_count = 0;
         let mut points_count = 0;
         for path in source.iter_paths() {
@@ -478,20 +720,19 @@ where
             return None;
         }
 
+        let (style, alignment) = builder_style(style, is_closed_path);
         let r = P::Scalar::from_float(0.5 * style.width.to_f64());
+        let dash = DashPattern::new(&style.dash_array, style.dash_offset);
         let builder = StrokeBuilder::<P, I>::new(style);
-        let a = builder.additional_offset(r);
-
-        let mut rect = FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
-        rect.add_offset(a);
-        let adapter = FloatPointAdapter::<P, I>::new(rect);
 
         Some(Self {
             r,
             builder,
+            dash,
             adapter,
             paths_count,
             points_count,
+            alignment,
         })
     }
 
@@ -500,6 +741,145 @@ where
         Ok(())
     }
 
+    fn add_paths<S: ShapeResource<P>>(
+        &self,
+        source: &S,
+        is_closed_path: bool,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+    ) {
+        let Some(dash) = &self.dash else {
+            for path in source.iter_paths() {
+                self.builder.build(path, is_closed_path, &self.adapter, segments);
+            }
+            return;
+        };
+
+        // two grid cells, so even a dot keeps a direction for its caps
+        let min_len = 2.0 * self.adapter.inv_scale().to_f64();
+        let mut dashes = Vec::new();
+        for path in source.iter_paths() {
+            dashes.clear();
+            if dash.split(path, is_closed_path, min_len, &mut dashes) {
+                for item in dashes.iter() {
+                    self.builder.build(item, false, &self.adapter, segments);
+                }
+            } else {
+                self.builder.build(path, is_closed_path, &self.adapter, segments);
+            }
+        }
+    }
+
+    fn add_paths_with_sources<S: ShapeResource<P>>(
+        &self,
+        source: &S,
+        is_closed_path: bool,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+        sources: &mut Vec<OffsetEdgeSource>,
+    ) {
+        let min_len = 2.0 * self.adapter.inv_scale().to_f64();
+        let mut dashes = Vec::new();
+        let mut dash_segments = Vec::new();
+        for (path_index, path) in source.iter_paths().enumerate() {
+            dashes.clear();
+            dash_segments.clear();
+            let is_dashed = self.dash.as_ref().is_some_and(|dash| {
+                dash.split_with_segments(path, is_closed_path, min_len, &mut dashes, &mut dash_segments)
+            });
+            if !is_dashed {
+                let mut track = SourceTrack {
+                    sources: &mut *sources,
+                    path_index,
+                    segments: None,
+                };
+                self.builder
+                    .build_with_sources(path, is_closed_path, &self.adapter, segments, &mut track);
+                continue;
+            }
+
+            for (item, item_segments) in dashes.iter().zip(dash_segments.iter()) {
+                let mut track = SourceTrack {
+                    sources: &mut *sources,
+                    path_index,
+                    segments: Some(item_segments),
+                };
+                self.builder
+                    .build_with_sources(item, false, &self.adapter, segments, &mut track);
+            }
+        }
+    }
+
+    /// The overlay of the stroke segments, with the rules that extract the stroke from it.
+    fn build_overlay<S: ShapeResource<P>>(
+        &self,
+        source: &S,
+        is_closed_path: bool,
+        options: OverlayOptions<P::Scalar, I>,
+    ) -> (Overlay<I>, OverlayRule, FillRule) {
+        let mut overlay = self.band_overlay(source, is_closed_path, options);
+        let Some(overlay_rule) = self.side_rule() else {
+            return (overlay, OverlayRule::Subject, FillRule::Positive);
+        };
+
+        // keep the half of the wide band that is inside or outside the filled region
+        let mut band = FlatContoursBuffer::<I>::with_capacity(0);
+        overlay.overlay_into(OverlayRule::Subject, FillRule::Positive, &mut band);
+
+        (
+            self.side_overlay(source, &band, &overlay),
+            overlay_rule,
+            FillRule::NonZero,
+        )
+    }
+
+    /// The overlay of the stroke segments, its `Subject` with `Positive` fill is the wide band.
+    fn band_overlay<S: ShapeResource<P>>(
+        &self,
+        source: &S,
+        is_closed_path: bool,
+        options: OverlayOptions<P::Scalar, I>,
+    ) -> Overlay<I> {
+        let capacity = self
+            .builder
+            .capacity(self.paths_count, self.points_count, is_closed_path);
+        let mut segments = Vec::with_capacity(capacity);
+
+        self.add_paths(source, is_closed_path, &mut segments);
+
+        let mut overlay = Overlay::with_segments(segments);
+        overlay.options = options.int_with_adapter(&self.adapter);
+        overlay
+    }
+
+    /// The rule that cuts the band by the filled region, `None` for a centered stroke.
+    #[inline]
+    fn side_rule(&self) -> Option<OverlayRule> {
+        match self.alignment {
+            StrokeAlignment::Center => None,
+            StrokeAlignment::Inside => Some(OverlayRule::Intersect),
+            StrokeAlignment::Outside => Some(OverlayRule::Difference),
+        }
+    }
+
+    /// The overlay of the band as `Subject` and the source paths as `Clip`.
+    fn side_overlay<S: ShapeResource<P>>(
+        &self,
+        source: &S,
+        band: &FlatContoursBuffer<I>,
+        band_overlay: &Overlay<I>,
+    ) -> Overlay<I> {
+        let mut clip_overlay = Overlay::new_custom(
+            band.points.len() + self.points_count,
+            band_overlay.options,
+            Default::default(),
+        );
+        clip_overlay.add_flat_buffer(band, ShapeType::Subject);
+        for path in source.iter_paths() {
+            let iter = path.iter().map(|p| self.adapter.float_to_int(p));
+            clip_overlay.add_path_iter(iter, ShapeType::Clip);
+        }
+        clip_overlay
+    }
+
     fn build<S: ShapeResource<P>>(
         self,
         source: &S,
@@ -512,21 +892,41 @@ where
             return vec![];
         }
 
-        let capacity = self
-            .builder
-            .capacity(self.paths_count, self.points_count, is_closed_path);
-        let mut segments = Vec::with_capacity(capacity);
+        let (mut overlay, overlay_rule, fill_rule) = self.build_overlay(source, is_closed_path, options);
+        let shapes = overlay.overlay(overlay_rule, fill_rule);
 
-        for path in source.iter_paths() {
-            self.builder
-                .build(path, is_closed_path, &self.adapter, &mut segments);
+        self.to_clean_float(&shapes, options)
+    }
+
+    fn try_build_with_control<S: ShapeResource<P>>(
+        self,
+        source: &S,
+        is_closed_path: bool,
+        options: OverlayOptions<P::Scalar, I>,
+        control: &mut dyn OperationControl,
+    ) -> Result<Shapes<P>, OverlayError> {
+        let ir = self.adapter.round_len_to_int(self.r).to_wide().unsigned_abs();
+        if ir <= I::WideUInt::ONE {
+            // offset is too small
+            return Ok(vec![]);
         }
 
-        let mut overlay = Overlay::with_segments(segments);
-        overlay.options = options.int_with_adapter(&self.adapter);
+        let mut overlay = self.band_overlay(source, is_closed_path, options);
+        let band = overlay.try_overlay_with_control(OverlayRule::Subject, FillRule::Positive, control)?;
+        let shapes = match self.side_rule() {
+            None => band,
+            Some(overlay_rule) => {
+                let mut buffer = FlatContoursBuffer::<I>::with_capacity(0);
+                buffer.set_with_shapes(&band);
+                self.side_overlay(source, &buffer, &overlay)
+                    .try_overlay_with_control(overlay_rule, FillRule::NonZero, control)?
+            }
+        };
 
-        let shapes = overlay.overlay(OverlayRule::Subject, FillRule::Positive);
+        Ok(self.to_clean_float(&shapes, options))
+    }
 
+    fn to_clean_float(&self, shapes: &IntShapes<I>, options: OverlayOptions<P::Scalar, I>) -> Shapes<P> {
         let mut float = shapes.to_float(&self.adapter);
 
         if options.clean_result {
@@ -540,35 +940,78 @@ where
         float
     }
 
-    fn build_into<S: ShapeResource<P>>(
+    fn build_with_sources<S: ShapeResource<P>>(
         self,
         source: &S,
         is_closed_path: bool,
-        options: OverlayOptions<P::Scalar, I>,
-        output: &mut FloatFlatContoursBuffer<P>,
-    ) {
+    ) -> Vec<FloatDataVectorShape<P, OffsetEdgeSource>> {
         let ir = self.adapter.round_len_to_int(self.r).to_wide().unsigned_abs();
         if ir <= I::WideUInt::ONE {
             // offset is too small
-            output.clear_and_reserve(0, 0);
-            return;
+            return vec![];
         }
 
         let capacity = self
             .builder
             .capacity(self.paths_count, self.points_count, is_closed_path);
         let mut segments = Vec::with_capacity(capacity);
+        let mut sources = Vec::with_capacity(capacity);
+        self.add_paths_with_sources(source, is_closed_path, &mut segments, &mut sources);
 
-        for path in source.iter_paths() {
-            self.builder
-                .build(path, is_closed_path, &self.adapter, &mut segments);
+        let mut overlay = EdgeOverlay::new(segments.len());
+        add_segments_with_sources(&mut overlay, &segments, &sources);
+
+        let overlay_rule = match self.alignment {
+            StrokeAlignment::Center => {
+                let shapes = overlay.build_vector_shapes(OverlayRule::Subject, FillRule::Positive);
+                return shapes_to_float(&shapes, &self.adapter);
+            }
+            StrokeAlignment::Inside => OverlayRule::Intersect,
+            StrokeAlignment::Outside => OverlayRule::Difference,
+        };
+
+        // the edges along the path cut the band, they come from its segments
+        let band = overlay.build_vector_shapes(OverlayRule::Subject, FillRule::Positive);
+        let band_count = band.iter().flatten().map(|contour| contour.len()).sum::<usize>();
+        let mut clip_overlay = EdgeOverlay::new(band_count + self.points_count);
+        add_vector_shapes(&mut clip_overlay, &band, ShapeType::Subject);
+        for (path_index, path) in source.iter_paths().enumerate() {
+            let n = path.len();
+            for (i, a) in path.iter().enumerate() {
+                let edge = InputEdge {
+                    a: self.adapter.float_to_int(a),
+                    b: self.adapter.float_to_int(&path[(i + 1) % n]),
+                    data: OffsetEdgeSource {
+                        path_index,
+                        segment_index: i,
+                        kind: OffsetEdgeKind::Side,
+                    },
+                };
+                clip_overlay.add_edge(edge, ShapeType::Clip);
+            }
         }
 
-        let mut overlay = Overlay::with_segments(segments);
-        overlay.options = options.int_with_adapter(&self.adapter);
+        let shapes = clip_overlay.build_vector_shapes(overlay_rule, FillRule::NonZero);
+        shapes_to_float(&shapes, &self.adapter)
+    }
 
+    fn build_into<S: ShapeResource<P>>(
+        self,
+        source: &S,
+        is_closed_path: bool,
+        options: OverlayOptions<P::Scalar, I>,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) {
+        let ir = self.adapter.round_len_to_int(self.r).to_wide().unsigned_abs();
+        if ir <= I::WideUInt::ONE {
+            // offset is too small
+            output.clear_and_reserve(0, 0);
+            return;
+        }
+
+        let (mut overlay, overlay_rule, fill_rule) = self.build_overlay(source, is_closed_path, options);
         let mut int_output = FlatContoursBuffer::<I>::with_capacity(0);
-        overlay.overlay_into(OverlayRule::Subject, FillRule::Positive, &mut int_output);
+        overlay.overlay_into(overlay_rule, fill_rule, &mut int_output);
 
         let iter = int_output.points.iter().map(|p| self.adapter.int_to_float(p));
         output.set_with_iter(iter, &int_output.ranges);
@@ -585,8 +1028,15 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::edge_overlay::FloatDataVectorShape;
+    use crate::float::single::SingleFloatOverlay;
+    use crate::mesh::source::{OffsetEdgeKind, OffsetEdgeSource};
     use crate::mesh::stroke::offset::StrokeOffset;
-    use crate::mesh::style::{LineCap, LineJoin, StrokeStyle};
+    use crate::mesh::style::{
+        LineCap, LineJoin, StrokeAlignment, StrokeSide, StrokeStyle, SvgLineJoin, SvgStroke,
+    };
     use alloc::vec;
     use alloc::vec::Vec;
     use core::f32::consts::PI;
@@ -867,4 +1317,495 @@ mod tests {
                 .is_err()
         );
     }
+
+    #[test]
+    fn test_dash_line() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+
+        let style = StrokeStyle::new(2.0).dash(&[2.0, 2.0], 0.0);
+        let shapes = path.stroke(style, false);
+
+        // dashes at 0..2, 4..6, 8..10
+        assert_eq!(shapes.len(), 3);
+        for shape in shapes.iter() {
+            assert_eq!(shape.len(), 1);
+            assert_eq!(shape[0].len(), 4);
+        }
+    }
+
+    #[test]
+    fn test_dash_trailing_gap() {
+        let style = StrokeStyle::new(2.0).dash(&[2.0, 2.0], 0.0);
+
+        // the gap after the only dash runs past the path end
+        let shapes = [[0.0, 0.0], [3.0, 0.0]].stroke(style.clone(), false);
+        assert_eq!(shapes.len(), 1);
+        let xs = shapes[0][0].iter().map(|p| p[0]);
+        let (min, max) = xs.fold((f64::MAX, f64::MIN), |(a, b), x| (a.min(x), b.max(x)));
+        assert!(min.abs() < 1e-9 && (max - 2.0).abs() < 1e-9);
+
+        // perimeter 12, the gap covers the end of the closing edge
+        let square = [[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0]];
+        let style = StrokeStyle::new(0.5).dash(&[10.0, 100.0], 0.0);
+        let shapes = square.stroke(style, true);
+        // an open dash, not a ring with a hole
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+    }
+
+    #[test]
+    fn test_dash_square_cap() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+
+        // the caps fill the gaps
+        let style = StrokeStyle::new(2.0)
+            .start_cap(LineCap::Square)
+            .end_cap(LineCap::Square)
+            .dash(&[2.0, 2.0], 0.0);
+        let shapes = path.stroke(style, false);
+
+        assert_eq!(shapes.len(), 1);
+        let xs = shapes[0][0].iter().map(|p| p[0]);
+        let (min, max) = xs.fold((f64::MAX, f64::MIN), |(a, b), x| (a.min(x), b.max(x)));
+        assert_eq!((min, max), (-1.0, 11.0));
+    }
+
+    #[test]
+    fn test_dash_corner() {
+        let path = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0]];
+
+        // the first dash turns the corner with a join
+        let style = StrokeStyle::new(1.0).dash(&[6.0, 1.0], 0.0);
+        let shapes = path.stroke(style, false);
+
+        assert_eq!(shapes.len(), 2);
+        let corner = shapes.iter().find(|s| s[0].len() > 4).unwrap();
+        assert_eq!(corner[0].len(), 7);
+    }
+
+    #[test]
+    fn test_dash_closed() {
+        let path = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+
+        // the dash over the start point is not cut there
+        let style = StrokeStyle::new(1.0).dash(&[3.0, 2.0], 0.0);
+        let shapes = path.stroke(style, true);
+        assert_eq!(shapes.len(), 3);
+
+        // a gap never falls on the path
+        let solid = path.stroke(StrokeStyle::new(1.0), true);
+        let dashed = path.stroke(StrokeStyle::new(1.0).dash(&[20.0, 1.0], 0.0), true);
+        assert_eq!(dashed, solid);
+    }
+
+    #[test]
+    fn test_dash_dots() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+
+        let style = StrokeStyle::new(1.0)
+            .start_cap(LineCap::Round(0.25 * PI as f64))
+            .end_cap(LineCap::Round(0.25 * PI as f64))
+            .dash(&[0.0, 2.5], 0.0);
+        let shapes = path.stroke(style, false);
+
+        // dots at 0, 2.5, 5, 7.5 and 10
+        assert_eq!(shapes.len(), 5);
+    }
+
+    #[test]
+    fn test_dash_solid() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+
+        let solid = path.stroke(StrokeStyle::new(2.0), false);
+        for array in [&[][..], &[0.0, 0.0][..], &[1.0, -1.0][..]] {
+            let dashed = path.stroke(StrokeStyle::new(2.0).dash(array, 0.0), false);
+            assert_eq!(dashed, solid);
+        }
+
+        let style = StrokeStyle::new(2.0).dash(&[1.0, f64::NAN], 0.0);
+        assert!(path.try_stroke(style, false).is_err());
+    }
+
+    fn max_x(shapes: &[Vec<Vec<[f64; 2]>>]) -> f64 {
+        shapes
+            .iter()
+            .flatten()
+            .flatten()
+            .fold(f64::MIN, |x, p| x.max(p[0]))
+    }
+
+    #[test]
+    fn test_svg_miter_limit() {
+        // the turn is sharp, the miter ratio is about 20
+        let path = [[-10.0, 0.0], [0.0, 0.0], [-10.0, 1.0]];
+
+        let miter = |join, limit| {
+            let style = StrokeStyle::new(2.0).svg(SvgStroke::new(join).miter_limit(limit));
+            path.stroke(style, false)
+        };
+
+        assert!(max_x(&miter(SvgLineJoin::Miter, 30.0)) > 19.0);
+        assert!(max_x(&miter(SvgLineJoin::Miter, 4.0)) < 0.1);
+        for join in [SvgLineJoin::MiterClip, SvgLineJoin::Arcs] {
+            let x = max_x(&miter(join, 4.0));
+            assert!((x - 4.0).abs() < 0.05, "{}", x);
+        }
+    }
+
+    #[test]
+    fn test_svg_round_tolerance() {
+        let path = [[-10.0, 0.0], [0.0, 0.0], [0.0, 10.0]];
+
+        let round = |tolerance| {
+            let svg = SvgStroke::new(SvgLineJoin::Round).tolerance(tolerance);
+            let style = StrokeStyle::new(4.0).start_cap(LineCap::Round(0.1)).svg(svg);
+            path.stroke(style, false)
+        };
+
+        let coarse = round(0.5);
+        let fine = round(0.01);
+        assert!(fine[0][0].len() > coarse[0][0].len());
+
+        // no tolerance is the finest step, not the coarsest
+        let finest = round(0.0);
+        assert!(finest[0][0].len() > fine[0][0].len());
+        assert_eq!(finest, round(-1.0));
+
+        // every point lies on the circle and every chord within the tolerance
+        let contour = &fine[0][0];
+        let n = contour.len();
+        for i in 0..n {
+            let (a, b) = (contour[i], contour[(i + 1) % n]);
+            let m: [f64; 2] = [0.5 * (a[0] + b[0]), 0.5 * (a[1] + b[1])];
+            if a[0] > 0.0 && a[1] < 0.0 && b[0] > 0.0 && b[1] < 0.0 {
+                assert!(m[0] * m[0] + m[1] * m[1] > 1.989 * 1.989);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svg_style_validation() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let style = StrokeStyle::new(2.0).svg(SvgStroke::new(SvgLineJoin::Miter).miter_limit(f64::NAN));
+        assert!(path.try_stroke(style, false).is_err());
+    }
+
+    fn area(shapes: &[Vec<Vec<[f64; 2]>>]) -> f64 {
+        let mut area = 0.0;
+        for contour in shapes.iter().flatten() {
+            let n = contour.len();
+            for i in 0..n {
+                let (a, b) = (contour[i], contour[(i + 1) % n]);
+                area += a[0] * b[1] - a[1] * b[0];
+            }
+        }
+        0.5 * area
+    }
+
+    fn bounds(shapes: &[Vec<Vec<[f64; 2]>>]) -> [f64; 4] {
+        let mut b = [f64::MAX, f64::MAX, f64::MIN, f64::MIN];
+        for p in shapes.iter().flatten().flatten() {
+            b = [b[0].min(p[0]), b[1].min(p[1]), b[2].max(p[0]), b[3].max(p[1])];
+        }
+        b
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    fn assert_bounds(shapes: &[Vec<Vec<[f64; 2]>>], expected: [f64; 4]) {
+        for (a, b) in bounds(shapes).into_iter().zip(expected) {
+            assert_close(a, b);
+        }
+    }
+
+    fn aligned(width: f64, alignment: StrokeAlignment) -> StrokeStyle<[f64; 2]> {
+        StrokeStyle::new(width)
+            .line_join(LineJoin::Miter(0.1 * PI as f64))
+            .alignment(alignment)
+    }
+
+    #[test]
+    fn test_alignment_square() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+
+        let center = path.stroke(aligned(2.0, StrokeAlignment::Center), true);
+        assert_close(area(&center), 144.0 - 64.0);
+        assert_bounds(&center, [-1.0, -1.0, 11.0, 11.0]);
+
+        let inside = path.stroke(aligned(2.0, StrokeAlignment::Inside), true);
+        assert_eq!(inside.len(), 1);
+        assert_close(area(&inside), 100.0 - 36.0);
+        assert_bounds(&inside, [0.0, 0.0, 10.0, 10.0]);
+
+        let outside = path.stroke(aligned(2.0, StrokeAlignment::Outside), true);
+        assert_eq!(outside.len(), 1);
+        assert_close(area(&outside), 196.0 - 100.0);
+        assert_bounds(&outside, [-2.0, -2.0, 12.0, 12.0]);
+
+        // the orientation of a single contour does not matter
+        let reversed: Vec<[f64; 2]> = path.iter().rev().copied().collect();
+        let reversed_inside = reversed.stroke(aligned(2.0, StrokeAlignment::Inside), true);
+        assert_close(area(&reversed_inside), area(&inside));
+    }
+
+    #[test]
+    fn test_alignment_with_hole() {
+        let shape = vec![
+            vec![[0.0, 0.0], [30.0, 0.0], [30.0, 30.0], [0.0, 30.0]],
+            vec![[10.0, 10.0], [10.0, 20.0], [20.0, 20.0], [20.0, 10.0]],
+        ];
+
+        // a band inside the outer contour and a band around the hole
+        let inside = shape.stroke(aligned(2.0, StrokeAlignment::Inside), true);
+        assert_eq!(inside.len(), 2);
+        assert_close(area(&inside), (900.0 - 676.0) + (196.0 - 100.0));
+        assert_bounds(&inside, [0.0, 0.0, 30.0, 30.0]);
+
+        // a band around the outer contour and a band in the hole
+        let outside = shape.stroke(aligned(2.0, StrokeAlignment::Outside), true);
+        assert_eq!(outside.len(), 2);
+        assert_close(area(&outside), (1156.0 - 900.0) + (100.0 - 36.0));
+        assert_bounds(&outside, [-2.0, -2.0, 32.0, 32.0]);
+    }
+
+    #[test]
+    fn test_alignment_self_touching() {
+        // two squares touching at a corner, traced as one contour
+        let path = [
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [20.0, 10.0],
+            [20.0, 20.0],
+            [10.0, 20.0],
+            [10.0, 10.0],
+            [0.0, 10.0],
+        ];
+
+        let inside = path.stroke(aligned(1.0, StrokeAlignment::Inside), true);
+        assert_close(area(&inside), 2.0 * (100.0 - 64.0));
+        assert_bounds(&inside, [0.0, 0.0, 20.0, 20.0]);
+
+        // the outer bands overlap at the touching corner and each loses a cell to the other square
+        let outside = path.stroke(aligned(1.0, StrokeAlignment::Outside), true);
+        assert_close(area(&outside), 2.0 * (144.0 - 100.0) - 4.0);
+    }
+
+    #[test]
+    fn test_alignment_open_path_is_centered() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let center = path.stroke(aligned(2.0, StrokeAlignment::Center), false);
+        for alignment in [StrokeAlignment::Inside, StrokeAlignment::Outside] {
+            assert_eq!(path.stroke(aligned(2.0, alignment), false), center);
+        }
+    }
+
+    #[test]
+    fn test_alignment_into() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let style = aligned(2.0, StrokeAlignment::Inside);
+        let shapes = path.stroke(style.clone(), true);
+
+        let mut output = FloatFlatContoursBuffer::default();
+        path.stroke_into(style, true, &mut output);
+        assert_eq!(output.ranges.len(), 2);
+        assert_eq!(
+            output.points.len(),
+            shapes.iter().flatten().map(Vec::len).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_side_line() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+
+        let left = path.stroke(StrokeStyle::new(2.0).side(StrokeSide::Left), false);
+        assert_eq!(left.len(), 1);
+        assert_close(area(&left), 20.0);
+        assert_bounds(&left, [0.0, 0.0, 10.0, 2.0]);
+
+        let right = path.stroke(StrokeStyle::new(2.0).side(StrokeSide::Right), false);
+        assert_eq!(right.len(), 1);
+        assert_close(area(&right), 20.0);
+        assert_bounds(&right, [0.0, -2.0, 10.0, 0.0]);
+
+        // the left of a reversed path is the right of the original
+        let reversed = [[10.0, 0.0], [0.0, 0.0]];
+        let reversed_left = reversed.stroke(StrokeStyle::new(2.0).side(StrokeSide::Left), false);
+        assert_bounds(&reversed_left, [0.0, -2.0, 10.0, 0.0]);
+    }
+
+    #[test]
+    fn test_side_corner() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let style = StrokeStyle::new(2.0).line_join(LineJoin::Miter(0.1 * PI as f64));
+
+        // the path turns left, the left side is the inner one
+        let left = path.stroke(style.clone().side(StrokeSide::Left), false);
+        assert_eq!(left.len(), 1);
+        assert_close(area(&left), 40.0 - 4.0);
+        assert_bounds(&left, [0.0, 0.0, 10.0, 10.0]);
+
+        let right = path.stroke(style.side(StrokeSide::Right), false);
+        assert_eq!(right.len(), 1);
+        assert_close(area(&right), 40.0 + 4.0);
+        assert_bounds(&right, [0.0, -2.0, 12.0, 10.0]);
+
+        let bevel = path.stroke(StrokeStyle::new(2.0).side(StrokeSide::Right), false);
+        assert_close(area(&bevel), 40.0 + 2.0);
+
+        // a round join is a quarter circle around the vertex
+        let round_style = StrokeStyle::new(2.0).line_join(LineJoin::Round(0.01));
+        let round = path.stroke(round_style.clone().side(StrokeSide::Right), false);
+        let round_area = area(&round);
+        assert!(round_area < 40.0 + PI as f64 && round_area > 40.0 + PI as f64 - 0.01);
+
+        // the join on the inner side stays on the path
+        let inner = path.stroke(round_style.side(StrokeSide::Left), false);
+        assert_close(area(&inner), 40.0 - 4.0);
+    }
+
+    #[test]
+    fn test_side_caps() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+
+        let square = StrokeStyle::new(2.0)
+            .start_cap(LineCap::Square)
+            .end_cap(LineCap::Square);
+        let left = path.stroke(square.clone().side(StrokeSide::Left), false);
+        assert_close(area(&left), 28.0);
+        assert_bounds(&left, [-2.0, 0.0, 12.0, 2.0]);
+
+        let right = path.stroke(square.side(StrokeSide::Right), false);
+        assert_close(area(&right), 28.0);
+        assert_bounds(&right, [-2.0, -2.0, 12.0, 0.0]);
+
+        // round caps are quarter circles
+        let round = StrokeStyle::new(2.0)
+            .start_cap(LineCap::Round(0.01))
+            .end_cap(LineCap::Round(0.01));
+        for side in [StrokeSide::Left, StrokeSide::Right] {
+            let shapes = path.stroke(round.clone().side(side), false);
+            assert_eq!(shapes.len(), 1);
+            let a = area(&shapes);
+            assert!(a < 20.0 + 2.0 * PI as f64 && a > 20.0 + 2.0 * PI as f64 - 0.01);
+        }
+    }
+
+    #[test]
+    fn test_sides_make_center() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [20.0, 5.0]];
+        let style = StrokeStyle::new(1.0)
+            .start_cap(LineCap::Round(0.1))
+            .end_cap(LineCap::Square)
+            .line_join(LineJoin::Round(0.1));
+
+        let left = path.stroke(style.clone().side(StrokeSide::Left), false);
+        let right = path.stroke(style.clone().side(StrokeSide::Right), false);
+        let both = path.stroke(style.width(2.0), false);
+
+        // the halves overlap past a corner, together they cover the centered stroke
+        let union = left.overlay(&right, OverlayRule::Union, FillRule::NonZero);
+        assert!((area(&union) - area(&both)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_side_closed_path_is_centered() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let center = path.stroke(StrokeStyle::new(2.0), true);
+        for side in [StrokeSide::Left, StrokeSide::Right] {
+            assert_eq!(path.stroke(StrokeStyle::new(2.0).side(side), true), center);
+        }
+    }
+
+    fn edge_sources<F: Fn([f64; 2], [f64; 2]) -> bool>(
+        shapes: &[FloatDataVectorShape<[f64; 2], OffsetEdgeSource>],
+        select: F,
+    ) -> Vec<(usize, OffsetEdgeKind)> {
+        let mut sources: Vec<_> = shapes
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|e| select(e.a, e.b))
+            .map(|e| (e.data.segment_index, e.data.kind))
+            .collect();
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    #[test]
+    fn test_sources_line() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+        let shapes = path.stroke_with_sources(StrokeStyle::new(2.0), false);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0][0].len(), 4);
+        let sides = edge_sources(&shapes, |a, b| a[1] == b[1]);
+        let caps = edge_sources(&shapes, |a, b| a[0] == b[0]);
+        assert_eq!(sides, vec![(0, OffsetEdgeKind::Side)]);
+        assert_eq!(caps, vec![(0, OffsetEdgeKind::Cap)]);
+    }
+
+    #[test]
+    fn test_sources_corner() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let style = StrokeStyle::new(2.0).line_join(LineJoin::Bevel);
+        let shapes = path.stroke_with_sources(style, false);
+
+        let start_cap = edge_sources(&shapes, |a, b| a[0] == 0.0 && b[0] == 0.0);
+        let end_cap = edge_sources(&shapes, |a, b| a[1] == 10.0 && b[1] == 10.0);
+        let bevel = edge_sources(&shapes, |a, b| a[0] != b[0] && a[1] != b[1]);
+        let outer_0 = edge_sources(&shapes, |a, b| a[1] == -1.0 && b[1] == -1.0);
+        let outer_1 = edge_sources(&shapes, |a, b| a[0] == 11.0 && b[0] == 11.0);
+        assert_eq!(start_cap, vec![(0, OffsetEdgeKind::Cap)]);
+        assert_eq!(end_cap, vec![(1, OffsetEdgeKind::Cap)]);
+        assert_eq!(bevel, vec![(1, OffsetEdgeKind::Join)]);
+        assert_eq!(outer_0, vec![(0, OffsetEdgeKind::Side)]);
+        assert_eq!(outer_1, vec![(1, OffsetEdgeKind::Side)]);
+    }
+
+    #[test]
+    fn test_sources_dash() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let style = StrokeStyle::new(2.0)
+            .line_join(LineJoin::Bevel)
+            .dash(&[4.0, 4.0], 0.0);
+        let shapes = path.stroke_with_sources(style, false);
+
+        // dashes over [0, 4], [8, 12] around the corner and [16, 20]
+        assert_eq!(shapes.len(), 3);
+        let horizontal = edge_sources(&shapes, |a, b| a[1] == b[1] && a[1].abs() == 1.0);
+        let vertical = edge_sources(&shapes, |a, b| a[0] == b[0] && a[1].min(b[1]) > 0.0);
+        assert_eq!(horizontal, vec![(0, OffsetEdgeKind::Side)]);
+        assert_eq!(vertical, vec![(1, OffsetEdgeKind::Side)]);
+        let caps = edge_sources(&shapes, |_, _| true)
+            .into_iter()
+            .filter(|s| s.1 == OffsetEdgeKind::Cap)
+            .count();
+        assert_eq!(caps, 2);
+    }
+
+    #[test]
+    fn test_sources_inside() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let style = StrokeStyle::new(2.0).alignment(StrokeAlignment::Inside);
+        let shapes = path.stroke_with_sources_as::<i64>(style, true);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+        for (i, select) in [
+            |a: [f64; 2], b: [f64; 2]| a[1] == 0.0 && b[1] == 0.0,
+            |a: [f64; 2], b: [f64; 2]| a[0] == 10.0 && b[0] == 10.0,
+            |a: [f64; 2], b: [f64; 2]| a[1] == 10.0 && b[1] == 10.0,
+            |a: [f64; 2], b: [f64; 2]| a[0] == 0.0 && b[0] == 0.0,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            assert_eq!(edge_sources(&shapes, select), vec![(i, OffsetEdgeKind::Side)]);
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use crate::mesh::math::Math;
+use crate::mesh::style::StrokeSide;
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::segment::Segment;
 use alloc::vec::Vec;
@@ -39,10 +40,28 @@ impl<P: FloatPointCompatible> Section<P> {
             dir,
         }
     }
+
+    /// A section with the offset only on `side`, the other edge lies on the path.
+    pub(crate) fn with_side(radius: P::Scalar, side: StrokeSide, a: &P, b: &P) -> Self {
+        let mut section = Self::new(radius, a, b);
+        match side {
+            StrokeSide::Both => {}
+            StrokeSide::Left => {
+                section.a_bot = *a;
+                section.b_bot = *b;
+            }
+            StrokeSide::Right => {
+                section.a_top = *a;
+                section.b_top = *b;
+            }
+        }
+        section
+    }
 }
 
 pub(crate) trait SectionToSegment<P: FloatPointCompatible, I: IntNumber> {
     fn add_section(&mut self, section: &Section<P>, adapter: &FloatPointAdapter<P, I>);
+    fn add_side(&mut self, section: &Section<P>, side: StrokeSide, adapter: &FloatPointAdapter<P, I>);
 }
 
 impl<P: FloatPointCompatible, I: IntNumber> SectionToSegment<P, I> for Vec<Segment<ShapeCountBoolean, I>> {
@@ -59,4 +78,16 @@ impl<P: FloatPointCompatible, I: IntNumber> SectionToSegment<P, I> for Vec<Segme
             self.push(Segment::subject(a_bot, b_bot));
         }
     }
+    fn add_side(&mut self, section: &Section<P>, side: StrokeSide, adapter: &FloatPointAdapter<P, I>) {
+        let (a, b) = match side {
+            StrokeSide::Left => (&section.b_top, &section.a_top),
+            StrokeSide::Right => (&section.a_bot, &section.b_bot),
+            StrokeSide::Both => return self.add_section(section, adapter),
+        };
+        let a = adapter.float_to_int(a);
+        let b = adapter.float_to_int(b);
+        if a != b {
+            self.push(Segment::subject(a, b));
+        }
+    }
 }
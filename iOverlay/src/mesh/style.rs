@@ -1,4 +1,6 @@
+use crate::core::error::is_finite_scalar;
 use alloc::rc::Rc;
+use alloc::vec::Vec;
 use core::f64::consts::PI;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -18,7 +20,7 @@ pub enum LineCap<P: FloatPointCompatible> {
 }
 
 /// The join style of a line.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum LineJoin<T: FloatNumber> {
     /// Cuts off the corner where two lines meet. This is the default.
     Bevel,
@@ -32,6 +34,64 @@ pub enum LineJoin<T: FloatNumber> {
     Round(T),
 }
 
+/// The join style of a line with SVG and HTML Canvas semantics, see [`SvgStroke`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SvgLineJoin {
+    /// A sharp corner, replaced with a bevel where the miter exceeds the miter limit. This is the default.
+    #[default]
+    Miter,
+    /// A sharp corner, clipped where the miter exceeds the miter limit.
+    MiterClip,
+    /// SVG 2 `arcs`. The outer edges of a polyline stroke are straight lines, where it is the
+    /// same as `MiterClip`.
+    Arcs,
+    /// A circular arc corner.
+    Round,
+    /// Cuts off the corner where two lines meet.
+    Bevel,
+}
+
+/// Join and round approximation parameters with the semantics of SVG `stroke-linejoin` and
+/// `stroke-miterlimit`, and HTML Canvas `lineJoin` and `miterLimit`.
+///
+/// Set on a [`StrokeStyle`] through [`StrokeStyle::svg`], it replaces [`StrokeStyle::join`] and
+/// the angle of round caps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgStroke<T: FloatNumber> {
+    /// The join style.
+    pub join: SvgLineJoin,
+    /// The largest allowed ratio of the miter length to the stroke width. Values below `1` are
+    /// treated as `1`. The default is `4`, as in SVG.
+    pub miter_limit: T,
+    /// The largest distance between a round join or cap and the polygon approximating it,
+    /// in path units. The default is `0.25`.
+    pub tolerance: T,
+}
+
+/// Where the stroke of a closed path lies relative to the region its contours fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrokeAlignment {
+    /// The stroke is centered on the contour. This is the default.
+    #[default]
+    Center,
+    /// The whole width lies inside the filled region, the stroked shape never grows.
+    Inside,
+    /// The whole width lies outside the filled region.
+    Outside,
+}
+
+/// The side of an open path covered by its stroke, looking along the path direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrokeSide {
+    /// The stroke is centered on the path. This is the default.
+    #[default]
+    Both,
+    /// The whole width lies on the left of the path.
+    Left,
+    /// The whole width lies on the right of the path.
+    Right,
+}
+
 /// Defines the stroke style for outlining paths.
 #[derive(Debug, Clone)]
 pub struct StrokeStyle<P: FloatPointCompatible> {
@@ -43,6 +103,16 @@ pub struct StrokeStyle<P: FloatPointCompatible> {
     pub end_cap: LineCap<P>,
     /// The join style where two lines meet.
     pub join: LineJoin<P::Scalar>,
+    /// Alternating dash and gap lengths, as SVG `stroke-dasharray`. Empty for a solid line.
+    pub dash_array: Vec<P::Scalar>,
+    /// Distance into the dash pattern at which every path starts, as SVG `stroke-dashoffset`.
+    pub dash_offset: P::Scalar,
+    /// SVG compatible join semantics, replacing `join` when set.
+    pub svg: Option<SvgStroke<P::Scalar>>,
+    /// Where the stroke of a closed path lies, open paths are always centered.
+    pub alignment: StrokeAlignment,
+    /// The side of an open path the stroke covers, closed paths follow `alignment`.
+    pub side: StrokeSide,
 }
 
 /// Defines the outline style for offsetting shapes.
@@ -53,6 +123,17 @@ pub struct OutlineStyle<T: FloatNumber> {
     pub join: LineJoin<T>,
 }
 
+/// Defines the style of parallel offset curves of open paths.
+#[derive(Debug, Clone)]
+pub struct OffsetCurveStyle<T: FloatNumber> {
+    /// The distance between a curve and its path.
+    pub distance: T,
+    /// The side of the path the curve runs on, `Both` gives the left and the right curves.
+    pub side: StrokeSide,
+    /// The join style where two offset edges meet on the outer side of a corner.
+    pub join: LineJoin<T>,
+}
+
 impl<P: FloatPointCompatible> LineCap<P> {
     pub(crate) fn normalize(self) -> Self {
         if let LineCap::Round(angle) = self {
@@ -64,6 +145,27 @@ impl<P: FloatPointCompatible> LineCap<P> {
     }
 }
 
+impl<P: FloatPointCompatible> LineCap<P> {
+    pub(crate) fn is_finite(&self) -> bool {
+        match self {
+            LineCap::Round(angle) => is_finite_scalar(*angle),
+            LineCap::Custom(points) => points
+                .iter()
+                .all(|p| is_finite_scalar(p.x()) && is_finite_scalar(p.y())),
+            _ => true,
+        }
+    }
+}
+
+impl<T: FloatNumber> LineJoin<T> {
+    pub(crate) fn is_finite(&self) -> bool {
+        match self {
+            LineJoin::Miter(value) | LineJoin::Round(value) => is_finite_scalar(*value),
+            LineJoin::Bevel => true,
+        }
+    }
+}
+
 impl<T: FloatNumber> LineJoin<T> {
     pub(crate) fn normalize(self) -> Self {
         match self {
@@ -112,6 +214,53 @@ impl<P: FloatPointCompatible> StrokeStyle<P> {
         self.join = join.normalize();
         self
     }
+
+    /// Sets a dash pattern of alternating dash and gap lengths, starting `offset` into the pattern.
+    ///
+    /// The pattern runs continuously along each path, around its corners, and every dash gets the
+    /// start and end caps. An odd list is repeated to make it even, `[2]` is `[2, 2]`. Zero length
+    /// dashes make dots with round or square caps. An empty list, a negative length or a pattern
+    /// with zero total length gives a solid line.
+    pub fn dash(mut self, array: &[P::Scalar], offset: P::Scalar) -> Self {
+        self.dash_array = array.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+
+    /// Switches to SVG compatible joins, see [`SvgStroke`].
+    pub fn svg(mut self, svg: SvgStroke<P::Scalar>) -> Self {
+        self.svg = Some(svg);
+        self
+    }
+
+    /// Sets where the stroke of a closed path lies.
+    ///
+    /// The filled region is the union of all stroked paths with the non-zero fill rule, so a
+    /// clockwise hole inside a counterclockwise contour is not filled. An inside stroke of a hole
+    /// runs around it in the filled region, and an outside stroke runs in the hole.
+    pub fn alignment(mut self, alignment: StrokeAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets the side of an open path the stroke covers.
+    ///
+    /// A one-sided stroke has the whole width on that side, the path itself is its other edge.
+    /// Caps are cut along the path, so a round cap becomes a quarter circle.
+    pub fn side(mut self, side: StrokeSide) -> Self {
+        self.side = side;
+        self
+    }
+
+    pub(crate) fn is_finite(&self) -> bool {
+        is_finite_scalar(self.width)
+            && self.start_cap.is_finite()
+            && self.end_cap.is_finite()
+            && self.join.is_finite()
+            && is_finite_scalar(self.dash_offset)
+            && self.dash_array.iter().all(|v| is_finite_scalar(*v))
+            && self.svg.as_ref().is_none_or(SvgStroke::is_finite)
+    }
 }
 
 impl<P: FloatPointCompatible> Default for StrokeStyle<P> {
@@ -121,6 +270,105 @@ impl<P: FloatPointCompatible> Default for StrokeStyle<P> {
             start_cap: LineCap::Butt,
             end_cap: LineCap::Butt,
             join: LineJoin::Bevel,
+            dash_array: Vec::new(),
+            dash_offset: P::Scalar::from_float(0.0),
+            svg: None,
+            alignment: StrokeAlignment::Center,
+            side: StrokeSide::Both,
+        }
+    }
+}
+
+impl<T: FloatNumber> SvgStroke<T> {
+    /// Creates SVG join parameters with the default miter limit and tolerance.
+    pub fn new(join: SvgLineJoin) -> Self {
+        Self {
+            join,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the miter limit, as SVG `stroke-miterlimit`.
+    pub fn miter_limit(mut self, miter_limit: T) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Sets the round approximation tolerance. A tolerance of zero or less gives the finest step.
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The angle step of round joins and caps of the given radius that keeps every chord within
+    /// `tolerance` of the arc.
+    pub(crate) fn round_angle(&self, radius: T) -> T {
+        let r = radius.to_f64();
+        let tolerance = self.tolerance.to_f64();
+        let angle = if tolerance <= 0.0 {
+            // no deviation allowed, the finest step
+            0.0
+        } else if tolerance < r {
+            2.0 * FloatNumber::acos(1.0 - tolerance / r)
+        } else {
+            0.5 * PI
+        };
+        T::from_float(angle.clamp(0.002 * PI, 0.25 * PI))
+    }
+
+    pub(crate) fn is_finite(&self) -> bool {
+        is_finite_scalar(self.miter_limit) && is_finite_scalar(self.tolerance)
+    }
+}
+
+impl<T: FloatNumber> Default for SvgStroke<T> {
+    fn default() -> Self {
+        Self {
+            join: SvgLineJoin::Miter,
+            miter_limit: T::from_float(4.0),
+            tolerance: T::from_float(0.25),
+        }
+    }
+}
+
+impl<T: FloatNumber> OffsetCurveStyle<T> {
+    /// Creates a new `OffsetCurveStyle` with the specified distance.
+    pub fn new(distance: T) -> Self {
+        Self {
+            distance,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the distance between a curve and its path.
+    pub fn distance(mut self, distance: T) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Sets the side of the path the curve runs on.
+    pub fn side(mut self, side: StrokeSide) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Sets the line join style.
+    pub fn line_join(mut self, join: LineJoin<T>) -> Self {
+        self.join = join.normalize();
+        self
+    }
+
+    pub(crate) fn is_finite(&self) -> bool {
+        is_finite_scalar(self.distance) && self.join.is_finite()
+    }
+}
+
+impl<T: FloatNumber> Default for OffsetCurveStyle<T> {
+    fn default() -> Self {
+        Self {
+            distance: T::from_float(1.0),
+            side: StrokeSide::Both,
+            join: LineJoin::Bevel,
         }
     }
 }
@@ -159,6 +407,10 @@ impl<T: FloatNumber> OutlineStyle<T> {
         self.join = join;
         self
     }
+
+    pub(crate) fn is_finite(&self) -> bool {
+        is_finite_scalar(self.outer_offset) && is_finite_scalar(self.inner_offset) && self.join.is_finite()
+    }
 }
 
 impl<T: FloatNumber> Default for OutlineStyle<T> {
@@ -0,0 +1,90 @@
+use crate::mesh::triangulation::mesh::TriangleMesh;
+use core::ops::Range;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+
+/// The fringe is offset by the miter of the edge normals, but never further than this many
+/// widths, so sharp corners keep a bounded rim.
+const MITER_LIMIT: f64 = 2.0;
+
+/// Adds a rim of triangles along the outer side of every contour. The contour vertices keep
+/// coverage `1`, the new outer vertices get `0`.
+///
+/// The contours must have the filled region on their left.
+pub(super) fn add_fringe<P: FloatPointCompatible>(
+    mesh: &mut TriangleMesh<P>,
+    points: &[P],
+    ranges: &[Range<usize>],
+    point_to_vertex: &[u32],
+    width: P::Scalar,
+) {
+    let one = P::Scalar::from_float(1.0);
+    let zero = P::Scalar::from_float(0.0);
+    mesh.coverage.clear();
+    mesh.coverage.resize(mesh.vertices.len(), one);
+
+    let width = width.to_f64();
+    for range in ranges.iter() {
+        let n = range.len();
+        if n < 3 || point_to_vertex[range.clone()].contains(&u32::MAX) {
+            continue;
+        }
+
+        let first = mesh.vertices.len() as u32;
+        for k in 0..n {
+            let a = &points[range.start + (k + n - 1) % n];
+            let p = &points[range.start + k];
+            let b = &points[range.start + (k + 1) % n];
+            let [mx, my] = miter(a, p, b);
+            let x = p.x().to_f64() + width * mx;
+            let y = p.y().to_f64() + width * my;
+            mesh.vertices
+                .push(P::from_xy(P::Scalar::from_float(x), P::Scalar::from_float(y)));
+            mesh.coverage.push(zero);
+        }
+
+        for k in 0..n {
+            let next = (k + 1) % n;
+            let i = point_to_vertex[range.start + k];
+            let j = point_to_vertex[range.start + next];
+            let oi = first + k as u32;
+            let oj = first + next as u32;
+            mesh.indices.extend_from_slice(&[i, oi, oj, i, oj, j]);
+        }
+    }
+}
+
+/// The outward offset direction at `p`, scaled so the fringe keeps its width along both edges.
+fn miter<P: FloatPointCompatible>(a: &P, p: &P, b: &P) -> [f64; 2] {
+    let n0 = right_normal(a, p);
+    let n1 = right_normal(p, b);
+    let (n0, n1) = match (n0, n1) {
+        (Some(n0), Some(n1)) => (n0, n1),
+        (Some(n), None) | (None, Some(n)) => (n, n),
+        (None, None) => return [0.0, 0.0],
+    };
+
+    let dot = n0[0] * n1[0] + n0[1] * n1[1];
+    let mx = n0[0] + n1[0];
+    let my = n0[1] + n1[1];
+    let len = (mx * mx + my * my).sqrt();
+    if len < 1e-9 {
+        // the contour turns back, push along the edge instead
+        return [-n0[1], n0[0]];
+    }
+
+    // the miter length is 1 / cos(angle / 2)
+    let scale = (2.0 / (1.0 + dot)).min(MITER_LIMIT * MITER_LIMIT).sqrt();
+    [scale * mx / len, scale * my / len]
+}
+
+fn right_normal<P: FloatPointCompatible>(a: &P, b: &P) -> Option<[f64; 2]> {
+    let dx = b.x().to_f64() - a.x().to_f64();
+    let dy = b.y().to_f64() - a.y().to_f64();
+    let len = (dx * dx + dy * dy).sqrt();
+    if len > 0.0 {
+        Some([dy / len, -dx / len])
+    } else {
+        None
+    }
+}
@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+
+/// An indexed triangle mesh, ready to be uploaded to a GPU.
+///
+/// Every three consecutive `indices` form a counterclockwise triangle. The optional per-vertex
+/// attributes are either empty or have one value per vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriangleMesh<P: FloatPointCompatible> {
+    pub vertices: Vec<P>,
+    pub indices: Vec<u32>,
+    /// Edge coverage for antialiasing: `1` on the filled region and `0` on the outer rim of the
+    /// fringe. Empty unless [`MeshOptions::fringe_width`] is positive.
+    pub coverage: Vec<P::Scalar>,
+    /// Distance along the source path to the nearest point of that path. Only filled for
+    /// stroke meshes.
+    pub distance: Vec<P::Scalar>,
+}
+
+/// Options for building a [`TriangleMesh`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshOptions<T: FloatNumber> {
+    /// Width of a fringe of triangles added along the outer side of every contour. Its inner
+    /// vertices have coverage `1` and its outer ones `0`, so interpolating the coverage gives
+    /// smooth edges. `0` disables the fringe.
+    pub fringe_width: T,
+}
+
+impl<P: FloatPointCompatible> TriangleMesh<P> {
+    /// Creates an empty mesh.
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            coverage: Vec::new(),
+            distance: Vec::new(),
+        }
+    }
+
+    /// The number of triangles.
+    #[inline]
+    pub fn triangles_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Whether the mesh has no triangles.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+impl<P: FloatPointCompatible> Default for TriangleMesh<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FloatNumber> MeshOptions<T> {
+    /// Sets the width of the antialiasing fringe.
+    pub fn fringe_width(mut self, width: T) -> Self {
+        self.fringe_width = width;
+        self
+    }
+}
+
+impl<T: FloatNumber> Default for MeshOptions<T> {
+    fn default() -> Self {
+        Self {
+            fringe_width: T::from_float(0.0),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod fringe;
+mod mesh;
+mod sweep;
+pub mod triangulate;
+
+pub use mesh::{MeshOptions, TriangleMesh};
@@ -0,0 +1,380 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+use i_float::int::number::int::IntNumber;
+use i_float::int::number::wide_int::WideIntNumber;
+use i_float::int::point::IntPoint;
+
+/// Triangulation of contours in integer space.
+pub(crate) struct Triangulation {
+    /// For every mesh vertex, the index of the first input point at its position.
+    pub(crate) sources: Vec<usize>,
+    /// For every input point, its mesh vertex or `u32::MAX` if its contour is too short.
+    pub(crate) point_to_vertex: Vec<u32>,
+    pub(crate) indices: Vec<u32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Lower,
+    Upper,
+}
+
+/// A reflex chain of vertices which are not triangulated yet, the classic stack of the monotone
+/// polygon triangulation. The first vertex may lie on the opposite side.
+struct Chain {
+    vertices: Vec<u32>,
+    side: Side,
+}
+
+/// The interior between two active edges, `lower` and `upper` are the edge ids.
+struct Interval {
+    lower: usize,
+    upper: usize,
+    chain: Chain,
+    /// After a merge vertex the region is the lower `chain` and this upper one, until the next
+    /// vertex connects them.
+    pending: Option<Chain>,
+}
+
+struct Vertex<I: IntNumber> {
+    point: IntPoint<I>,
+    source: usize,
+    prev: usize,
+    next: usize,
+}
+
+struct Sweep<'a, I: IntNumber> {
+    vertices: &'a [Vertex<I>],
+    /// The mesh vertex of every input vertex.
+    mesh_index: Vec<u32>,
+    mesh_points: Vec<IntPoint<I>>,
+    indices: Vec<u32>,
+}
+
+/// Triangulates the region to the left of the contour edges, so outer contours must be
+/// counterclockwise and holes clockwise. Contours may touch at vertices but must not cross.
+/// `ranges` are the contours in `points`, without repeated consecutive points.
+pub(crate) fn triangulate<I: IntNumber>(points: &[IntPoint<I>], ranges: &[Range<usize>]) -> Triangulation {
+    let mut vertices = Vec::with_capacity(points.len());
+    for range in ranges.iter() {
+        let n = range.len();
+        if n < 3 {
+            continue;
+        }
+        let first = vertices.len();
+        for i in 0..n {
+            vertices.push(Vertex {
+                point: points[range.start + i],
+                source: range.start + i,
+                prev: first + (i + n - 1) % n,
+                next: first + (i + 1) % n,
+            });
+        }
+    }
+
+    let mut order: Vec<usize> = (0..vertices.len()).collect();
+    order.sort_unstable_by_key(|&i| (vertices[i].point, rank(&vertices, i)));
+
+    // vertices at the same position share a mesh vertex
+    let mut sweep = Sweep {
+        vertices: &vertices,
+        mesh_index: vec![0; vertices.len()],
+        mesh_points: Vec::new(),
+        indices: Vec::with_capacity(3 * vertices.len()),
+    };
+    let mut sources = Vec::new();
+    let mut point_to_vertex = vec![u32::MAX; points.len()];
+    for &i in order.iter() {
+        let v = &vertices[i];
+        if sweep.mesh_points.last() != Some(&v.point) {
+            sweep.mesh_points.push(v.point);
+            sources.push(v.source);
+        }
+        let index = (sweep.mesh_points.len() - 1) as u32;
+        sweep.mesh_index[i] = index;
+        point_to_vertex[v.source] = index;
+    }
+
+    let mut intervals: Vec<Interval> = Vec::new();
+    for &i in order.iter() {
+        sweep.add_vertex(i, &mut intervals);
+    }
+
+    Triangulation {
+        sources,
+        point_to_vertex,
+        indices: sweep.indices,
+    }
+}
+
+/// At the same position, edges are closed before new ones are opened.
+fn rank<I: IntNumber>(vertices: &[Vertex<I>], i: usize) -> u8 {
+    let v = &vertices[i];
+    let is_prev_left = vertices[v.prev].point < v.point;
+    let is_next_left = vertices[v.next].point < v.point;
+    match (is_prev_left, is_next_left) {
+        (true, true) => 0,
+        (false, false) => 2,
+        _ => 1,
+    }
+}
+
+#[inline]
+fn cross<I: IntNumber>(a: IntPoint<I>, b: IntPoint<I>, c: IntPoint<I>) -> I::Wide {
+    (b - a).cross_product(c - b)
+}
+
+impl<I: IntNumber> Sweep<'_, I> {
+    fn add_vertex(&mut self, i: usize, intervals: &mut Vec<Interval>) {
+        let v = &self.vertices[i];
+        let p = v.point;
+        let a = self.vertices[v.prev].point;
+        let b = self.vertices[v.next].point;
+        let w = self.mesh_index[i];
+
+        // the edge from `prev` to this vertex has the id `prev`, the next one the id `i`
+        let zero = I::Wide::ZERO;
+        match (a < p, b < p) {
+            (false, false) => {
+                let is_start = (b - p).cross_product(a - p) > zero;
+                let found = if is_start {
+                    None
+                } else {
+                    intervals.iter().position(|it| self.contains(it, p))
+                };
+                match found {
+                    Some(index) => self.split(intervals, index, w, v.prev, i),
+                    None => intervals.push(Interval {
+                        lower: i,
+                        upper: v.prev,
+                        chain: Chain::new(w),
+                        pending: None,
+                    }),
+                }
+            }
+            (true, true) => {
+                let Some(index) = intervals
+                    .iter()
+                    .position(|it| [v.prev, i].contains(&it.lower) || [v.prev, i].contains(&it.upper))
+                else {
+                    return;
+                };
+                let it = &intervals[index];
+                let is_end = [v.prev, i].contains(&it.lower) && [v.prev, i].contains(&it.upper);
+                if is_end {
+                    let it = intervals.swap_remove(index);
+                    self.close(it, w);
+                } else {
+                    self.merge(intervals, w, v.prev, i);
+                }
+            }
+            (true, false) => {
+                let Some(it) = intervals.iter_mut().find(|it| it.lower == v.prev) else {
+                    return;
+                };
+                it.lower = i;
+                Self::add_to_interval(it, w, Side::Lower, &self.mesh_points, &mut self.indices);
+            }
+            (false, true) => {
+                let Some(it) = intervals.iter_mut().find(|it| it.upper == i) else {
+                    return;
+                };
+                it.upper = v.prev;
+                Self::add_to_interval(it, w, Side::Upper, &self.mesh_points, &mut self.indices);
+            }
+        }
+    }
+
+    /// Whether `p` lies in the interval, or on its lower edge.
+    fn contains(&self, it: &Interval, p: IntPoint<I>) -> bool {
+        let zero = I::Wide::ZERO;
+        let (l0, l1) = self.edge(it.lower);
+        let (u0, u1) = self.edge(it.upper);
+        (l1 - l0).cross_product(p - l0) >= zero && (u1 - u0).cross_product(p - u0) < zero
+    }
+
+    /// The edge with its ends in the sweep order.
+    fn edge(&self, id: usize) -> (IntPoint<I>, IntPoint<I>) {
+        let a = self.vertices[id].point;
+        let b = self.vertices[self.vertices[id].next].point;
+        if a < b { (a, b) } else { (b, a) }
+    }
+
+    fn split(
+        &mut self,
+        intervals: &mut Vec<Interval>,
+        index: usize,
+        w: u32,
+        prev_edge: usize,
+        next_edge: usize,
+    ) {
+        // the edge from `prev` bounds the interval below from above, the edge to `next` the one above
+        let it = intervals.swap_remove(index);
+        let (below, above) = match it.pending {
+            Some(mut upper) => {
+                let mut lower = it.chain;
+                lower.add(w, Side::Upper, &self.mesh_points, &mut self.indices);
+                upper.add(w, Side::Lower, &self.mesh_points, &mut self.indices);
+                (lower, upper)
+            }
+            None => {
+                // the diagonal goes to the last vertex of the interval
+                let mut chain = it.chain;
+                let last = *chain.vertices.last().unwrap();
+                if chain.vertices.len() == 1 {
+                    (
+                        Chain::with_edge(last, w, Side::Upper),
+                        Chain::with_edge(last, w, Side::Lower),
+                    )
+                } else if chain.side == Side::Lower {
+                    chain.add(w, Side::Lower, &self.mesh_points, &mut self.indices);
+                    (Chain::with_edge(last, w, Side::Upper), chain)
+                } else {
+                    chain.add(w, Side::Upper, &self.mesh_points, &mut self.indices);
+                    (chain, Chain::with_edge(last, w, Side::Lower))
+                }
+            }
+        };
+
+        intervals.push(Interval {
+            lower: it.lower,
+            upper: prev_edge,
+            chain: below,
+            pending: None,
+        });
+        intervals.push(Interval {
+            lower: next_edge,
+            upper: it.upper,
+            chain: above,
+            pending: None,
+        });
+    }
+
+    fn merge(&mut self, intervals: &mut Vec<Interval>, w: u32, prev_edge: usize, next_edge: usize) {
+        // the interval below ends with the edge to `next`, the one above with the edge from `prev`
+        let Some(below) = intervals.iter().position(|it| it.upper == next_edge) else {
+            return;
+        };
+        let mut below = intervals.swap_remove(below);
+        let Some(above) = intervals.iter().position(|it| it.lower == prev_edge) else {
+            return;
+        };
+        let mut above = intervals.swap_remove(above);
+
+        Self::add_to_interval(&mut below, w, Side::Upper, &self.mesh_points, &mut self.indices);
+        Self::add_to_interval(&mut above, w, Side::Lower, &self.mesh_points, &mut self.indices);
+
+        intervals.push(Interval {
+            lower: below.lower,
+            upper: above.upper,
+            chain: below.chain,
+            pending: Some(above.chain),
+        });
+    }
+
+    fn close(&mut self, it: Interval, w: u32) {
+        it.chain.close(w, &self.mesh_points, &mut self.indices);
+        if let Some(upper) = it.pending {
+            upper.close(w, &self.mesh_points, &mut self.indices);
+        }
+    }
+
+    fn add_to_interval(
+        it: &mut Interval,
+        w: u32,
+        side: Side,
+        points: &[IntPoint<I>],
+        indices: &mut Vec<u32>,
+    ) {
+        let Some(mut upper) = it.pending.take() else {
+            it.chain.add(w, side, points, indices);
+            return;
+        };
+
+        // the vertex connects to the merge vertex, the part on its side is done
+        it.chain.add(w, side, points, indices);
+        upper.add(w, side, points, indices);
+        if side == Side::Lower {
+            it.chain = upper;
+        }
+    }
+}
+
+impl Chain {
+    fn new(vertex: u32) -> Self {
+        Self {
+            vertices: vec![vertex],
+            side: Side::Lower,
+        }
+    }
+
+    fn with_edge(a: u32, b: u32, side: Side) -> Self {
+        let vertices = if a == b { vec![a] } else { vec![a, b] };
+        Self { vertices, side }
+    }
+
+    fn add<I: IntNumber>(&mut self, w: u32, side: Side, points: &[IntPoint<I>], indices: &mut Vec<u32>) {
+        // the same vertex comes again where contours touch
+        let last = *self.vertices.last().unwrap();
+        if self.vertices.len() == 1 {
+            if last != w {
+                self.vertices.push(w);
+                self.side = side;
+            }
+            return;
+        }
+
+        if side != self.side {
+            // every chain vertex sees the new one
+            for pair in self.vertices.windows(2) {
+                add_triangle(w, pair[0], pair[1], points, indices);
+            }
+            self.vertices.clear();
+            self.vertices.push(last);
+            if last != w {
+                self.vertices.push(w);
+            }
+            self.side = side;
+            return;
+        }
+
+        if last == w {
+            return;
+        }
+
+        let zero = I::Wide::ZERO;
+        let pw = points[w as usize];
+        let mut u = self.vertices.pop().unwrap();
+        while let Some(&t) = self.vertices.last() {
+            let turn = cross(points[t as usize], points[u as usize], pw);
+            let is_convex = match side {
+                Side::Lower => turn > zero,
+                Side::Upper => turn < zero,
+            };
+            if !is_convex {
+                break;
+            }
+            add_triangle(t, u, w, points, indices);
+            u = self.vertices.pop().unwrap();
+        }
+        self.vertices.push(u);
+        self.vertices.push(w);
+    }
+
+    fn close<I: IntNumber>(self, w: u32, points: &[IntPoint<I>], indices: &mut Vec<u32>) {
+        for pair in self.vertices.windows(2) {
+            add_triangle(w, pair[0], pair[1], points, indices);
+        }
+    }
+}
+
+/// Adds a counterclockwise triangle, skipping degenerate ones.
+fn add_triangle<I: IntNumber>(a: u32, b: u32, c: u32, points: &[IntPoint<I>], indices: &mut Vec<u32>) {
+    let area = cross(points[a as usize], points[b as usize], points[c as usize]);
+    if area > I::Wide::ZERO {
+        indices.extend_from_slice(&[a, b, c]);
+    } else if area < I::Wide::ZERO {
+        indices.extend_from_slice(&[a, c, b]);
+    }
+}
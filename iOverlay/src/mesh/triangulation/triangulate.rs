@@ -0,0 +1,337 @@
+use crate::core::integer::OverlayInt;
+use crate::i_shape::source::resource::ShapeResource;
+use crate::mesh::triangulation::fringe::add_fringe;
+use crate::mesh::triangulation::mesh::{MeshOptions, TriangleMesh};
+use crate::mesh::triangulation::sweep;
+use alloc::vec::Vec;
+use core::ops::Range;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+
+/// Trait for building an indexed triangle mesh of filled shapes, such as the results of boolean,
+/// outline, or stroke operations.
+///
+/// The contours must not cross each other and must have consistent orientation: outer
+/// boundaries one way and holes the other, as the overlay outputs them. Either main direction
+/// is accepted.
+///
+/// Default methods use the `i32` integer engine. Use the `*_as::<I>` methods when you need to
+/// select `i16`, `i32`, or `i64` explicitly.
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::float::single::SingleFloatOverlay;
+/// use i_overlay::mesh::triangulation::triangulate::Triangulate;
+/// use i_overlay::mesh::triangulation::MeshOptions;
+///
+/// let square = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+/// let hole = [[1.0, 1.0], [1.0, 3.0], [3.0, 3.0], [3.0, 1.0]];
+///
+/// let shapes = square.overlay(&hole, OverlayRule::Difference, FillRule::EvenOdd);
+/// let mesh = shapes.triangulate(MeshOptions::default());
+///
+/// assert_eq!(mesh.vertices.len(), 8);
+/// assert_eq!(mesh.triangles_count(), 8);
+/// ```
+pub trait Triangulate<P: FloatPointCompatible> {
+    /// Triangulates paths, contours, or shapes.
+    ///
+    /// - `options`: Adjust the mesh, such as the antialiasing fringe.
+    ///
+    /// # Returns
+    /// A `TriangleMesh<P>` whose vertices are the source points, plus the fringe vertices if
+    /// requested.
+    fn triangulate(&self, options: MeshOptions<P::Scalar>) -> TriangleMesh<P>;
+
+    /// Same as [`Self::triangulate`], but with an explicit integer engine.
+    fn triangulate_as<I>(&self, options: MeshOptions<P::Scalar>) -> TriangleMesh<P>
+    where
+        I: OverlayInt + 'static;
+}
+
+impl<S, P> Triangulate<P> for S
+where
+    S: ShapeResource<P>,
+    P: FloatPointCompatible + 'static,
+{
+    fn triangulate(&self, options: MeshOptions<P::Scalar>) -> TriangleMesh<P> {
+        self.triangulate_as::<i32>(options)
+    }
+
+    fn triangulate_as<I>(&self, options: MeshOptions<P::Scalar>) -> TriangleMesh<P>
+    where
+        I: OverlayInt + 'static,
+    {
+        let Some(rect) = FloatRect::with_iter(self.iter_paths().flatten()) else {
+            return TriangleMesh::new();
+        };
+        let adapter = FloatPointAdapter::<P, I>::new(rect);
+
+        // contours without repeated points on the integer grid
+        let mut points = Vec::new();
+        let mut int_points = Vec::new();
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for path in self.iter_paths() {
+            let start = points.len();
+            for p in path.iter() {
+                let ip = adapter.float_to_int(p);
+                if int_points.len() > start && int_points.last() == Some(&ip) {
+                    continue;
+                }
+                points.push(*p);
+                int_points.push(ip);
+            }
+            while int_points.len() > start + 1 && int_points.last() == Some(&int_points[start]) {
+                points.pop();
+                int_points.pop();
+            }
+            if int_points.len() < start + 3 {
+                points.truncate(start);
+                int_points.truncate(start);
+                continue;
+            }
+            ranges.push(start..points.len());
+        }
+
+        if area(&points, &ranges) < 0.0 {
+            // clockwise outer boundaries
+            for range in ranges.iter() {
+                points[range.clone()].reverse();
+                int_points[range.clone()].reverse();
+            }
+        }
+
+        let triangulation = sweep::triangulate(&int_points, &ranges);
+        let mut mesh = TriangleMesh {
+            vertices: triangulation.sources.iter().map(|&i| points[i]).collect(),
+            indices: triangulation.indices,
+            coverage: Vec::new(),
+            distance: Vec::new(),
+        };
+
+        if options.fringe_width > P::Scalar::from_float(0.0) {
+            add_fringe(
+                &mut mesh,
+                &points,
+                &ranges,
+                &triangulation.point_to_vertex,
+                options.fringe_width,
+            );
+        }
+
+        mesh
+    }
+}
+
+/// Twice the signed area of all contours.
+fn area<P: FloatPointCompatible>(points: &[P], ranges: &[Range<usize>]) -> f64 {
+    let mut area = 0.0;
+    for range in ranges.iter() {
+        let contour = &points[range.clone()];
+        let mut a = contour[contour.len() - 1];
+        for &b in contour.iter() {
+            area += a.x().to_f64() * b.y().to_f64() - b.x().to_f64() * a.y().to_f64();
+            a = b;
+        }
+    }
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::float::simplify::SimplifyShape;
+    use crate::mesh::triangulation::triangulate::Triangulate;
+    use crate::mesh::triangulation::{MeshOptions, TriangleMesh};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use rand::RngExt;
+
+    fn triangle_area(mesh: &TriangleMesh<[f64; 2]>, t: &[u32]) -> f64 {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|i| mesh.vertices[i as usize]);
+        0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]))
+    }
+
+    fn shapes_area(shapes: &[Vec<Vec<[f64; 2]>>]) -> f64 {
+        let mut area = 0.0;
+        for contour in shapes.iter().flatten() {
+            let mut a = contour[contour.len() - 1];
+            for &b in contour.iter() {
+                area += 0.5 * (a[0] * b[1] - b[0] * a[1]);
+                a = b;
+            }
+        }
+        area
+    }
+
+    /// Checks the indices and the orientation, returns the mesh area.
+    fn validate(mesh: &TriangleMesh<[f64; 2]>) -> f64 {
+        assert_eq!(mesh.indices.len() % 3, 0);
+        assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertices.len()));
+        let mut area = 0.0;
+        for t in mesh.indices.chunks(3) {
+            let a = triangle_area(mesh, t);
+            assert!(a > 0.0, "triangle {:?} is not counterclockwise", t);
+            area += a;
+        }
+        area
+    }
+
+    #[test]
+    fn test_square() {
+        let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+        let mesh = square.triangulate(MeshOptions::default());
+
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.triangles_count(), 2);
+        assert_eq!(validate(&mesh), 4.0);
+        assert!(mesh.coverage.is_empty());
+        assert!(mesh.distance.is_empty());
+    }
+
+    #[test]
+    fn test_clockwise() {
+        let square = [[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+        let mesh = square.triangulate(MeshOptions::default());
+
+        assert_eq!(mesh.triangles_count(), 2);
+        assert_eq!(validate(&mesh), 4.0);
+    }
+
+    #[test]
+    fn test_hole() {
+        let shape = vec![
+            vec![[0.0, 0.0], [6.0, 0.0], [6.0, 6.0], [0.0, 6.0]],
+            vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]],
+        ];
+        let mesh = shape.triangulate(MeshOptions::default());
+
+        assert_eq!(mesh.vertices.len(), 8);
+        assert_eq!(mesh.triangles_count(), 8);
+        assert_eq!(validate(&mesh), 32.0);
+    }
+
+    #[test]
+    fn test_comb() {
+        // teeth open to both sides, every vertex type appears
+        let comb = [
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 1.0],
+            [2.0, 2.0],
+            [10.0, 3.0],
+            [10.0, 4.0],
+            [3.0, 5.0],
+            [10.0, 6.0],
+            [10.0, 8.0],
+            [0.0, 8.0],
+            [0.0, 7.0],
+            [8.0, 6.0],
+            [0.0, 5.0],
+            [0.0, 4.0],
+            [7.0, 3.0],
+            [0.0, 2.0],
+        ];
+        let mesh = comb.triangulate(MeshOptions::default());
+
+        assert_eq!(mesh.triangles_count(), comb.len() - 2);
+        assert!((validate(&mesh) - shapes_area(&[vec![comb.to_vec()]])).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_touching_contours() {
+        let shapes = vec![
+            vec![vec![[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]]],
+            vec![vec![[2.0, 2.0], [4.0, 2.0], [4.0, 4.0], [2.0, 4.0]]],
+        ];
+        let mesh = shapes.triangulate(MeshOptions::default());
+
+        assert_eq!(mesh.vertices.len(), 7);
+        assert_eq!(mesh.triangles_count(), 4);
+        assert_eq!(validate(&mesh), 8.0);
+    }
+
+    #[test]
+    fn test_touching_holes() {
+        let shape = vec![
+            vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            vec![[2.0, 2.0], [5.0, 5.0], [6.0, 1.0]],
+            vec![[5.0, 5.0], [4.0, 8.0], [7.0, 8.0]],
+        ];
+        let mesh = shape.triangulate(MeshOptions::default());
+
+        assert_eq!(mesh.vertices.len(), 9);
+        assert_eq!(validate(&mesh), 88.0);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let empty: [[f64; 2]; 0] = [];
+        assert!(empty.triangulate(MeshOptions::default()).is_empty());
+
+        let line = [[0.0, 0.0], [2.0, 0.0], [2.0, 0.0]];
+        assert!(line.triangulate(MeshOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_fringe() {
+        let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+        let options = MeshOptions::default().fringe_width(0.5);
+        let mesh = square.triangulate(options);
+
+        assert_eq!(mesh.vertices.len(), 8);
+        assert_eq!(mesh.coverage.len(), 8);
+        assert_eq!(mesh.triangles_count(), 10);
+        assert_eq!(mesh.coverage.iter().filter(|&&c| c == 1.0).count(), 4);
+
+        // the fringe grows the square by its width on every side
+        assert!((validate(&mesh) - 9.0).abs() < 1e-9);
+        let max_x = mesh.vertices.iter().map(|p| p[0]).fold(f64::MIN, f64::max);
+        assert!((max_x - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fringe_hole() {
+        let shape = vec![
+            vec![[0.0, 0.0], [6.0, 0.0], [6.0, 6.0], [0.0, 6.0]],
+            vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]],
+        ];
+        let mesh = shape.triangulate(MeshOptions::default().fringe_width(0.5));
+
+        // the outer rim adds 7 * 7 - 6 * 6, the hole rim 2 * 2 - 1 * 1
+        assert!((validate(&mesh) - 48.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for n in 3..12 {
+            for _ in 0..200 {
+                let paths: Vec<Vec<[f64; 2]>> = (0..3)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| [rng.random_range(0..16) as f64, rng.random_range(0..16) as f64])
+                            .collect()
+                    })
+                    .collect();
+                let shapes = paths.simplify_shape(FillRule::EvenOdd);
+                let mesh = shapes.triangulate(MeshOptions::default());
+
+                let area = validate(&mesh);
+                let expected = shapes_area(&shapes);
+                assert!(
+                    (area - expected).abs() < 1e-6,
+                    "{:?}: {} != {}",
+                    paths,
+                    area,
+                    expected
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,415 @@
+use crate::mesh::math::Math;
+use crate::mesh::rotator::Rotator;
+use crate::mesh::style::LineJoin;
+use crate::mesh::variable_outline::style::{OffsetDistribution, OffsetVertex, VariableOutlineStyle};
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::Segment;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::vector::FloatPointMath;
+use i_float::int::number::int::IntNumber;
+use i_float::int::number::wide_int::WideIntNumber;
+use i_float::int::point::IntPoint;
+
+enum Join<T> {
+    Bevel,
+    /// `max_length = radius * inv_tan` is the longest miter along an offset edge.
+    Miter {
+        inv_tan: T,
+    },
+    Round {
+        inv_ratio: T,
+        limit_dot_product: T,
+        average_count: usize,
+    },
+}
+
+pub(super) struct VariableOutlineBuilder<T> {
+    distribution: OffsetDistribution,
+    join: Join<T>,
+}
+
+/// A contour vertex in integer space with the radii of its incoming and outgoing edges.
+/// A radius is the negated offset, the same sign convention as the constant outline.
+#[derive(Clone, Copy)]
+struct Node<T, I: IntNumber> {
+    point: IntPoint<I>,
+    r_in: T,
+    r_out: T,
+}
+
+#[derive(Clone)]
+struct Section<P: FloatPointCompatible, I: IntNumber> {
+    a: IntPoint<I>,
+    b: IntPoint<I>,
+    a_top: IntPoint<I>,
+    b_top: IntPoint<I>,
+    pa_top: P,
+    pb_top: P,
+    dir: P,
+    top_dir: P,
+    ra: P::Scalar,
+    rb: P::Scalar,
+}
+
+impl<T: FloatNumber> VariableOutlineBuilder<T> {
+    pub(super) fn new(style: &VariableOutlineStyle<T>) -> Self {
+        let join = match style.join.normalize() {
+            LineJoin::Bevel => Join::Bevel,
+            LineJoin::Miter(angle) => {
+                let half_angle = 0.5 * angle.to_f64();
+                Join::Miter {
+                    inv_tan: T::from_float(1.0 / half_angle.tan()),
+                }
+            }
+            LineJoin::Round(ratio) => Join::Round {
+                inv_ratio: T::ONE / ratio,
+                limit_dot_product: ratio.cos(),
+                average_count: (0.6 * PI / ratio.to_f64()) as usize + 2,
+            },
+        };
+
+        Self {
+            distribution: style.distribution,
+            join,
+        }
+    }
+
+    pub(super) fn capacity(&self, points_count: usize) -> usize {
+        let join_count = match &self.join {
+            Join::Bevel => 2,
+            Join::Miter { .. } => 4,
+            Join::Round { average_count, .. } => *average_count,
+        };
+        join_count * points_count
+    }
+
+    pub(super) fn additional_offset(&self, radius: T) -> T {
+        // add extra 10% to avoid problems with floating point precision.
+        let extent = match &self.join {
+            Join::Miter { inv_tan } => radius + radius * *inv_tan,
+            _ => radius,
+        };
+        T::from_float(1.1) * extent
+    }
+
+    pub(super) fn build<P, I>(
+        &self,
+        path: &[OffsetVertex<P>],
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+    ) where
+        P: FloatPointCompatible<Scalar = T>,
+        I: IntNumber,
+    {
+        let nodes = self.nodes(path, adapter);
+        if nodes.len() < 2 {
+            return;
+        }
+
+        let count = nodes.len();
+        let s0 = Section::new(&nodes[0], &nodes[1], adapter);
+        let mut sk = s0.clone();
+        segments.push_some(sk.top_segment());
+
+        for i in 1..count {
+            let si = Section::new(&nodes[i], &nodes[(i + 1) % count], adapter);
+            segments.push_some(si.top_segment());
+            self.feed_join(&sk, &si, adapter, segments);
+            sk = si;
+        }
+        self.feed_join(&sk, &s0, adapter, segments);
+    }
+
+    /// Integer vertices without repeats, each with the radius its incoming and outgoing edges
+    /// have at it. A run of equal points keeps the incoming radius of its first vertex and the
+    /// outgoing radius of its last one.
+    fn nodes<P, I>(&self, path: &[OffsetVertex<P>], adapter: &FloatPointAdapter<P, I>) -> Vec<Node<T, I>>
+    where
+        P: FloatPointCompatible<Scalar = T>,
+        I: IntNumber,
+    {
+        let n = path.len();
+        let mut nodes: Vec<Node<T, I>> = Vec::with_capacity(n);
+        for (i, vertex) in path.iter().enumerate() {
+            let point = adapter.float_to_int(&vertex.point);
+            let r_out = -vertex.offset;
+            let r_in = match self.distribution {
+                OffsetDistribution::PerEdge => -path[(i + n - 1) % n].offset,
+                OffsetDistribution::PerVertex => r_out,
+            };
+            match nodes.last_mut() {
+                Some(last) if last.point == point => last.r_out = r_out,
+                _ => nodes.push(Node { point, r_in, r_out }),
+            }
+        }
+
+        while nodes.len() > 1 {
+            let last = nodes[nodes.len() - 1];
+            if last.point != nodes[0].point {
+                break;
+            }
+            nodes[0].r_in = last.r_in;
+            nodes.pop();
+        }
+
+        nodes
+    }
+
+    fn feed_join<P, I>(
+        &self,
+        s0: &Section<P, I>,
+        s1: &Section<P, I>,
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+    ) where
+        P: FloatPointCompatible<Scalar = T>,
+        I: IntNumber,
+    {
+        if s0.b_top == s1.a_top {
+            return;
+        }
+
+        let (r0, r1) = (s0.rb, s1.ra);
+        let outer_corner = if r0 * r1 < T::ZERO {
+            // the edges move to opposite sides, they meet at the vertex
+            false
+        } else {
+            let extend = r0 + r1 > T::ZERO;
+            let vi = s1.b - s1.a;
+            let vp = s0.b - s0.a;
+            let cross = vi.cross_product(vp);
+            if cross != I::Wide::ZERO {
+                (cross > I::Wide::ZERO) == extend
+            } else {
+                vi.dot_product(vp) < I::Wide::ZERO
+            }
+        };
+
+        if !outer_corner {
+            segments.push_some(s0.b_segment());
+            segments.push_some(s1.a_segment());
+            return;
+        }
+
+        match &self.join {
+            Join::Bevel => segments.push(Segment::subject(s0.b_top, s1.a_top)),
+            Join::Miter { inv_tan } => {
+                let max_length = r0.abs().max(r1.abs()) * *inv_tan;
+                Self::miter_join(s0, s1, max_length, adapter, segments);
+            }
+            Join::Round {
+                inv_ratio,
+                limit_dot_product,
+                ..
+            } => Self::round_join(s0, s1, *inv_ratio, *limit_dot_product, adapter, segments),
+        }
+    }
+
+    fn miter_join<P, I>(
+        s0: &Section<P, I>,
+        s1: &Section<P, I>,
+        max_length: T,
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+    ) where
+        P: FloatPointCompatible<Scalar = T>,
+        I: IntNumber,
+    {
+        let ia = s0.b_top;
+        let ib = s1.a_top;
+        if ia.sqr_distance(ib) < I::Wide::from_usize(4) {
+            segments.push(Segment::subject(ia, ib));
+            return;
+        }
+
+        let (pa, pb) = (s0.pb_top, s1.pa_top);
+        let (ta, tb) = (s0.top_dir, s1.top_dir);
+
+        // pa + t * ta = pb + s * tb
+        let den = FloatPointMath::cross_product(&ta, &tb);
+        let d = FloatPointMath::sub(&pb, &pa);
+        let is_reversal = FloatPointMath::dot_product(&s0.dir, &s1.dir) < T::ZERO;
+        let (t, s) = if den != T::ZERO {
+            (
+                FloatPointMath::cross_product(&d, &tb) / den,
+                FloatPointMath::cross_product(&d, &ta) / den,
+            )
+        } else if is_reversal {
+            (max_length + T::ONE, -max_length - T::ONE)
+        } else {
+            (-T::ONE, T::ZERO)
+        };
+
+        if t < T::ZERO || s > T::ZERO {
+            // the offset edges do not reach each other, cut the corner
+            segments.push(Segment::subject(ia, ib));
+            return;
+        }
+
+        if t <= max_length && -s <= max_length {
+            let c = FloatPointMath::add(&pa, &FloatPointMath::scale(&ta, t));
+            let ic = adapter.float_to_int(&c);
+            if ia == ic || ib == ic {
+                segments.push(Segment::subject(ia, ib));
+            } else {
+                segments.push(Segment::subject(ia, ic));
+                segments.push(Segment::subject(ic, ib));
+            }
+            return;
+        }
+
+        let ac = FloatPointMath::add(&pa, &FloatPointMath::scale(&ta, max_length));
+        let bc = FloatPointMath::sub(&pb, &FloatPointMath::scale(&tb, max_length));
+        let iac = adapter.float_to_int(&ac);
+        let ibc = adapter.float_to_int(&bc);
+
+        if ia != iac {
+            segments.push(Segment::subject(ia, iac));
+        }
+        if iac != ibc {
+            segments.push(Segment::subject(iac, ibc));
+        }
+        if ibc != ib {
+            segments.push(Segment::subject(ibc, ib));
+        }
+    }
+
+    fn round_join<P, I>(
+        s0: &Section<P, I>,
+        s1: &Section<P, I>,
+        inv_ratio: T,
+        limit_dot_product: T,
+        adapter: &FloatPointAdapter<P, I>,
+        segments: &mut Vec<Segment<ShapeCountBoolean, I>>,
+    ) where
+        P: FloatPointCompatible<Scalar = T>,
+        I: IntNumber,
+    {
+        let start = s0.b_top;
+        let end = s1.a_top;
+
+        let (r0, r1) = (s0.rb, s1.ra);
+        let (abs0, abs1) = (r0.abs(), r1.abs());
+
+        let dot_product = FloatPointMath::dot_product(&s0.dir, &s1.dir);
+        if limit_dot_product < dot_product && abs0 == abs1 {
+            segments.push(Segment::subject(start, end));
+            return;
+        }
+
+        // The corner is the boundary of the points within the larger radius of the vertex. That
+        // circle runs past the corner until it crosses the offset edge of the smaller radius.
+        let radius = if abs0 >= abs1 { r0 } else { r1 };
+        let r = radius.abs();
+        let lead = if abs1 > abs0 { (abs0 / r).acos() } else { T::ZERO };
+        let tail = if abs0 > abs1 { (abs1 / r).acos() } else { T::ZERO };
+
+        let angle = dot_product.max(-T::ONE).min(T::ONE).acos();
+        let sweep = lead + angle + tail;
+        let n = (sweep * inv_ratio).to_usize().max(1);
+        let delta_angle = sweep / T::from_usize(n);
+
+        let rot_dir = if r0 + r1 > T::ZERO { -T::ONE } else { T::ONE };
+        let rotator = Rotator::<T>::with_angle(rot_dir * delta_angle);
+
+        let center = adapter.int_to_float(&s0.b);
+        let mut v = P::from_xy(-s0.dir.y(), s0.dir.x());
+        if lead > T::ZERO {
+            v = Rotator::<T>::with_angle(-rot_dir * lead).rotate(&v);
+        }
+
+        let mut a = start;
+        for k in 0..=n {
+            if k > 0 {
+                v = rotator.rotate(&v);
+            }
+            let p = FloatPointMath::add(&center, &FloatPointMath::scale(&v, radius));
+
+            let b = adapter.float_to_int(&p);
+            if a != b {
+                segments.push(Segment::subject(a, b));
+                a = b;
+            }
+        }
+
+        if a != end {
+            segments.push(Segment::subject(a, end));
+        }
+    }
+}
+
+impl<P: FloatPointCompatible, I: IntNumber> Section<P, I> {
+    fn new(a: &Node<P::Scalar, I>, b: &Node<P::Scalar, I>, adapter: &FloatPointAdapter<P, I>) -> Self {
+        let pa = adapter.int_to_float(&a.point);
+        let pb = adapter.int_to_float(&b.point);
+        let dir = FloatPointMath::normalize(&FloatPointMath::sub(&pb, &pa));
+
+        let (ra, rb) = (a.r_out, b.r_in);
+        let pa_top = FloatPointMath::add(&pa, &Math::ortho_and_scale(&dir, ra));
+        let pb_top = FloatPointMath::add(&pb, &Math::ortho_and_scale(&dir, rb));
+
+        let top = FloatPointMath::sub(&pb_top, &pa_top);
+        let top_dir = if FloatPointMath::sqr_length(&top) > P::Scalar::ZERO {
+            FloatPointMath::normalize(&top)
+        } else {
+            dir
+        };
+
+        Self {
+            a: a.point,
+            b: b.point,
+            a_top: adapter.float_to_int(&pa_top),
+            b_top: adapter.float_to_int(&pb_top),
+            pa_top,
+            pb_top,
+            dir,
+            top_dir,
+            ra,
+            rb,
+        }
+    }
+
+    #[inline]
+    fn top_segment(&self) -> Option<Segment<ShapeCountBoolean, I>> {
+        if self.a_top != self.b_top {
+            Some(Segment::subject(self.a_top, self.b_top))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn a_segment(&self) -> Option<Segment<ShapeCountBoolean, I>> {
+        if self.a_top != self.a {
+            Some(Segment::subject(self.a, self.a_top))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn b_segment(&self) -> Option<Segment<ShapeCountBoolean, I>> {
+        if self.b_top != self.b {
+            Some(Segment::subject(self.b_top, self.b))
+        } else {
+            None
+        }
+    }
+}
+
+trait VecPushSome<T> {
+    fn push_some(&mut self, value: Option<T>);
+}
+
+impl<T> VecPushSome<T> for Vec<T> {
+    #[inline]
+    fn push_some(&mut self, value: Option<T>) {
+        if let Some(v) = value {
+            self.push(v);
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod builder;
+pub mod offset;
+mod resource;
+mod style;
+
+pub use resource::VariableOutlineSource;
+pub use style::{OffsetDistribution, OffsetVertex, VariableOutlineStyle};
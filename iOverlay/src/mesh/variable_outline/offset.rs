@@ -0,0 +1,647 @@
+use crate::core::extract::BooleanExtractionBuffer;
+use crate::core::fill_rule::FillRule;
+use crate::core::integer::OverlayInt;
+use crate::core::overlay::ShapeType::Subject;
+use crate::core::overlay::{ContourDirection, Overlay};
+use crate::core::overlay_rule::OverlayRule;
+use crate::float::overlay::OverlayOptions;
+use crate::float::scale::FixedScaleOverlayError;
+use crate::mesh::variable_outline::builder::VariableOutlineBuilder;
+use crate::mesh::variable_outline::resource::VariableOutlineSource;
+use crate::mesh::variable_outline::style::VariableOutlineStyle;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+use i_float::int::number::int::IntNumber;
+use i_float::int::number::uint::UIntNumber;
+use i_float::int::number::wide_int::WideIntNumber;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::Shapes;
+use i_shape::flat::buffer::FlatContoursBuffer;
+use i_shape::flat::float::FloatFlatContoursBuffer;
+use i_shape::float::adapter::ShapesToFloat;
+use i_shape::float::despike::DeSpikeContour;
+use i_shape::float::simple::SimplifyContour;
+use i_shape::int::area::Area;
+
+/// Offsets closed contours and holes by a distance stored at each vertex, with the joins of a
+/// [`VariableOutlineStyle`].
+///
+/// Every path is offset on its own and the results are merged with the overlay engine, the same
+/// way as [`OutlineOffset`](crate::mesh::outline::offset::OutlineOffset) does. Outer boundary
+/// paths have a counterclockwise order, and holes have a clockwise order.
+///
+/// # Example
+///
+/// ```
+/// use i_overlay::mesh::variable_outline::offset::VariableOutlineOffset;
+/// use i_overlay::mesh::variable_outline::{OffsetVertex, VariableOutlineStyle};
+///
+/// // a lot with a 6 m front, 3 m rear and 1.5 m side setbacks
+/// let lot = [
+///     OffsetVertex::new([0.0, 0.0], -6.0),
+///     OffsetVertex::new([20.0, 0.0], -1.5),
+///     OffsetVertex::new([20.0, 30.0], -3.0),
+///     OffsetVertex::new([0.0, 30.0], -1.5),
+/// ];
+///
+/// let buildable = lot.variable_outline(VariableOutlineStyle::new());
+///
+/// assert_eq!(buildable.len(), 1);
+/// ```
+pub trait VariableOutlineOffset<P>: VariableOutlineSource<P>
+where
+    P: FloatPointCompatible + 'static,
+{
+    fn variable_outline(&self, style: VariableOutlineStyle<P::Scalar>) -> Shapes<P> {
+        self.variable_outline_custom(style, Default::default())
+    }
+
+    fn variable_outline_into(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) {
+        self.variable_outline_custom_into(style, Default::default(), output)
+    }
+
+    fn variable_outline_custom(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar>,
+    ) -> Shapes<P> {
+        self.variable_outline_custom_as::<i32>(style, options)
+    }
+
+    fn variable_outline_custom_into(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar>,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) {
+        self.variable_outline_custom_into_as::<i32>(style, options, output)
+    }
+
+    fn variable_outline_fixed_scale(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Shapes<P>, FixedScaleOverlayError> {
+        self.variable_outline_custom_fixed_scale(style, Default::default(), scale)
+    }
+
+    fn variable_outline_fixed_scale_into(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        scale: P::Scalar,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) -> Result<(), FixedScaleOverlayError> {
+        self.variable_outline_custom_fixed_scale_into(style, Default::default(), scale, output)
+    }
+
+    fn variable_outline_custom_fixed_scale(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Shapes<P>, FixedScaleOverlayError> {
+        self.variable_outline_custom_fixed_scale_as::<i32>(style, options, scale)
+    }
+
+    fn variable_outline_custom_fixed_scale_into(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar>,
+        scale: P::Scalar,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) -> Result<(), FixedScaleOverlayError> {
+        self.variable_outline_custom_fixed_scale_into_as::<i32>(style, options, scale, output)
+    }
+
+    fn variable_outline_as<I>(&self, style: VariableOutlineStyle<P::Scalar>) -> Shapes<P>
+    where
+        I: OverlayInt + 'static,
+    {
+        self.variable_outline_custom_as::<I>(style, Default::default())
+    }
+
+    fn variable_outline_into_as<I>(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) where
+        I: OverlayInt + 'static,
+    {
+        self.variable_outline_custom_into_as::<I>(style, Default::default(), output)
+    }
+
+    fn variable_outline_custom_as<I>(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar, I>,
+    ) -> Shapes<P>
+    where
+        I: OverlayInt + 'static,
+    {
+        match VariableOutlineSolver::<P, I>::prepare(self, &style) {
+            Some(solver) => solver.build(self, options),
+            None => vec![],
+        }
+    }
+
+    fn variable_outline_custom_into_as<I>(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar, I>,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) where
+        I: OverlayInt + 'static,
+    {
+        match VariableOutlineSolver::<P, I>::prepare(self, &style) {
+            Some(solver) => solver.build_into(self, options, output),
+            None => output.clear_and_reserve(0, 0),
+        }
+    }
+
+    fn variable_outline_fixed_scale_as<I>(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        scale: P::Scalar,
+    ) -> Result<Shapes<P>, FixedScaleOverlayError>
+    where
+        I: OverlayInt + 'static,
+    {
+        self.variable_outline_custom_fixed_scale_as::<I>(style, Default::default(), scale)
+    }
+
+    fn variable_outline_fixed_scale_into_as<I>(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        scale: P::Scalar,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) -> Result<(), FixedScaleOverlayError>
+    where
+        I: OverlayInt + 'static,
+    {
+        self.variable_outline_custom_fixed_scale_into_as::<I>(style, Default::default(), scale, output)
+    }
+
+    fn variable_outline_custom_fixed_scale_as<I>(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar, I>,
+        scale: P::Scalar,
+    ) -> Result<Shapes<P>, FixedScaleOverlayError>
+    where
+        I: OverlayInt + 'static,
+    {
+        let mut solver = match VariableOutlineSolver::<P, I>::prepare(self, &style) {
+            Some(solver) => solver,
+            None => return Ok(vec![]),
+        };
+        solver.apply_scale(scale)?;
+        Ok(solver.build(self, options))
+    }
+
+    fn variable_outline_custom_fixed_scale_into_as<I>(
+        &self,
+        style: VariableOutlineStyle<P::Scalar>,
+        options: OverlayOptions<P::Scalar, I>,
+        scale: P::Scalar,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) -> Result<(), FixedScaleOverlayError>
+    where
+        I: OverlayInt + 'static,
+    {
+        let mut solver = match VariableOutlineSolver::<P, I>::prepare(self, &style) {
+            Some(solver) => solver,
+            None => {
+                output.clear_and_reserve(0, 0);
+                return Ok(());
+            }
+        };
+        solver.apply_scale(scale)?;
+        solver.build_into(self, options, output);
+        Ok(())
+    }
+}
+
+impl<S, P> VariableOutlineOffset<P> for S
+where
+    S: VariableOutlineSource<P> + ?Sized,
+    P: FloatPointCompatible + 'static,
+{
+}
+
+struct VariableOutlineSolver<P: FloatPointCompatible, I: IntNumber> {
+    builder: VariableOutlineBuilder<P::Scalar>,
+    adapter: FloatPointAdapter<P, I>,
+    points_count: usize,
+}
+
+impl<P, I> VariableOutlineSolver<P, I>
+where
+    P: FloatPointCompatible + 'static,
+    I: OverlayInt + 'static,
+{
+    fn prepare<S: VariableOutlineSource<P> + ?Sized>(
+        source: &S,
+        style: &VariableOutlineStyle<P::Scalar>,
+    ) -> Option<Self> {
+        let mut max_offset = P::Scalar::ZERO;
+        let mut points_count = 0;
+        let mut rect: Option<FloatRect<P::Scalar>> = None;
+
+        for path in source.iter_offset_paths() {
+            points_count += path.len();
+            for vertex in path {
+                max_offset = max_offset.max(vertex.offset.abs());
+                if let Some(rect) = rect.as_mut() {
+                    rect.add_point(&vertex.point);
+                } else {
+                    rect = Some(FloatRect::with_point(vertex.point));
+                }
+            }
+        }
+
+        let builder = VariableOutlineBuilder::new(style);
+        let mut rect = rect?;
+        rect.add_offset(builder.additional_offset(max_offset));
+        let adapter = FloatPointAdapter::<P, I>::new(rect);
+
+        Some(Self {
+            builder,
+            adapter,
+            points_count,
+        })
+    }
+
+    fn apply_scale(&mut self, scale: P::Scalar) -> Result<(), FixedScaleOverlayError> {
+        self.adapter = FloatPointAdapter::try_with_scale(*self.adapter.rect(), scale)?;
+        Ok(())
+    }
+
+    fn build_overlay<S: VariableOutlineSource<P> + ?Sized>(
+        &self,
+        source: &S,
+        options: OverlayOptions<P::Scalar, I>,
+    ) -> Overlay<I> {
+        let mut overlay = Overlay::new_custom(
+            self.builder.capacity(self.points_count),
+            options.int_with_adapter(&self.adapter),
+            Default::default(),
+        );
+
+        let mut offset_overlay = Overlay::new(16);
+        offset_overlay.options = overlay.options;
+
+        let mut int_path = Vec::<IntPoint<I>>::new();
+        let mut segments = Vec::new();
+        let mut bool_buffer = BooleanExtractionBuffer::default();
+        let mut flat_buffer = FlatContoursBuffer::<I>::with_capacity(0);
+
+        for path in source.iter_offset_paths() {
+            if path.len() < 3 {
+                continue;
+            }
+            int_path.clear();
+            int_path.extend(path.iter().map(|v| self.adapter.float_to_int(&v.point)));
+            let area = int_path.area_two();
+            if area.unsigned_abs() <= <I::WideUInt as UIntNumber>::from_u64(1) {
+                // ignore degenerate paths
+                continue;
+            }
+
+            offset_overlay.clear();
+            segments.clear();
+            segments.reserve(self.builder.capacity(path.len()));
+            self.builder.build(path, &self.adapter, &mut segments);
+
+            let contour_fill_rule = if area > I::Wide::ZERO {
+                offset_overlay.options.output_direction = ContourDirection::CounterClockwise;
+                FillRule::Positive
+            } else {
+                offset_overlay.options.output_direction = ContourDirection::Clockwise;
+                FillRule::Negative
+            };
+
+            offset_overlay.add_segments(&segments);
+
+            if let Some(graph) = offset_overlay.build_graph_view(contour_fill_rule) {
+                graph.extract_contours_into(OverlayRule::Subject, &mut bool_buffer, &mut flat_buffer);
+            } else {
+                flat_buffer.clear_and_reserve(0, 0);
+            }
+
+            overlay.add_flat_buffer(&flat_buffer, Subject);
+        }
+
+        overlay
+    }
+
+    fn build<S: VariableOutlineSource<P> + ?Sized>(
+        self,
+        source: &S,
+        options: OverlayOptions<P::Scalar, I>,
+    ) -> Shapes<P> {
+        let preserve_output_collinear = options.preserve_output_collinear;
+        let clean_result = options.clean_result;
+        let mut overlay = self.build_overlay(source, options);
+        let shapes = overlay.overlay(OverlayRule::Subject, FillRule::Positive);
+
+        let mut float = shapes.to_float(&self.adapter);
+        if clean_result {
+            if preserve_output_collinear {
+                float.despike_contour(&self.adapter);
+            } else {
+                float.simplify_contour(&self.adapter);
+            }
+        }
+        float
+    }
+
+    fn build_into<S: VariableOutlineSource<P> + ?Sized>(
+        self,
+        source: &S,
+        options: OverlayOptions<P::Scalar, I>,
+        output: &mut FloatFlatContoursBuffer<P>,
+    ) {
+        let preserve_output_collinear = options.preserve_output_collinear;
+        let clean_result = options.clean_result;
+        let mut overlay = self.build_overlay(source, options);
+
+        let mut int_output = FlatContoursBuffer::<I>::with_capacity(0);
+        overlay.overlay_into(OverlayRule::Subject, FillRule::Positive, &mut int_output);
+        let iter = int_output.points.iter().map(|p| self.adapter.int_to_float(p));
+        output.set_with_iter(iter, &int_output.ranges);
+
+        if clean_result {
+            if preserve_output_collinear {
+                output.despike_contour(&self.adapter);
+            } else {
+                output.simplify_contour(&self.adapter);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VariableOutlineOffset;
+    use crate::core::fill_rule::FillRule;
+    use crate::float::simplify::SimplifyShape;
+    use crate::mesh::outline::offset::OutlineOffset;
+    use crate::mesh::style::{LineJoin, OutlineStyle};
+    use crate::mesh::variable_outline::{OffsetDistribution, OffsetVertex, VariableOutlineStyle};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::f64::consts::PI;
+    use i_shape::flat::float::FloatFlatContoursBuffer;
+    use i_shape::float::area::Area;
+    use rand::RngExt;
+
+    type Vertex = OffsetVertex<[f64; 2]>;
+
+    fn with_offsets(path: &[[f64; 2]], offsets: &[f64]) -> Vec<Vertex> {
+        path.iter()
+            .zip(offsets)
+            .map(|(&point, &offset)| OffsetVertex::new(point, offset))
+            .collect()
+    }
+
+    fn square(x0: f64, y0: f64, size: f64) -> Vec<[f64; 2]> {
+        vec![[x0, y0], [x0 + size, y0], [x0 + size, y0 + size], [x0, y0 + size]]
+    }
+
+    fn bounds(shapes: &[Vec<Vec<[f64; 2]>>]) -> [f64; 4] {
+        let mut b = [f64::MAX, f64::MAX, f64::MIN, f64::MIN];
+        for p in shapes.iter().flatten().flatten() {
+            b = [b[0].min(p[0]), b[1].min(p[1]), b[2].max(p[0]), b[3].max(p[1])];
+        }
+        b
+    }
+
+    fn assert_close(a: f64, b: f64, tolerance: f64) {
+        assert!((a - b).abs() <= tolerance, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn constant_offset_matches_outline() {
+        let outer = square(0.0, 0.0, 30.0);
+        let mut hole = square(10.0, 10.0, 10.0);
+        hole.reverse();
+        let shape = vec![outer.clone(), hole.clone()];
+
+        let joins = [LineJoin::Bevel, LineJoin::Miter(0.1 * PI), LineJoin::Round(0.1)];
+        for offset in [2.0, -2.0] {
+            for join in joins.iter() {
+                let source = vec![
+                    with_offsets(&outer, &[offset; 4]),
+                    with_offsets(&hole, &[offset; 4]),
+                ];
+                let style = VariableOutlineStyle::new().line_join(*join);
+                let shapes = source.variable_outline(style);
+
+                let outline = shape.outline(&OutlineStyle::new(offset).line_join(*join));
+
+                assert_eq!(shapes.len(), 1);
+                assert_eq!(shapes[0].len(), 2);
+                assert_close(shapes.area().abs(), outline.area().abs(), 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn setbacks_shrink_each_lot_line() {
+        let lot = [[0.0, 0.0], [20.0, 0.0], [20.0, 30.0], [0.0, 30.0]];
+        let source = with_offsets(&lot, &[-6.0, -1.5, -3.0, -1.5]);
+
+        for join in [LineJoin::Bevel, LineJoin::Miter(0.1 * PI), LineJoin::Round(0.1)] {
+            let shapes = source.variable_outline(VariableOutlineStyle::new().line_join(join));
+
+            assert_eq!(shapes.len(), 1);
+            assert_eq!(shapes[0].len(), 1);
+            let b = bounds(&shapes);
+            for (value, expected) in b.iter().zip([1.5, 6.0, 18.5, 27.0]) {
+                assert_close(*value, expected, 0.01);
+            }
+            assert_close(shapes.area().abs(), 17.0 * 21.0, 0.1);
+        }
+    }
+
+    #[test]
+    fn single_grown_edge_keeps_square_corners() {
+        let source = with_offsets(&square(0.0, 0.0, 10.0), &[1.0, 0.0, 0.0, 0.0]);
+
+        for join in [LineJoin::Bevel, LineJoin::Miter(0.1 * PI)] {
+            let shapes = source.variable_outline(VariableOutlineStyle::new().line_join(join));
+            assert_eq!(shapes.len(), 1);
+            assert_close(shapes.area().abs(), 110.0, 0.05);
+        }
+
+        // every point within 1 of the bottom edge, with a half disk past each end
+        let style = VariableOutlineStyle::new().line_join(LineJoin::Round(0.1));
+        let shapes = source.variable_outline(style);
+        assert_eq!(shapes.len(), 1);
+        assert_close(shapes.area().abs(), 110.0 + PI, 0.05);
+    }
+
+    #[test]
+    fn different_neighbours_meet_at_miter() {
+        // the bottom edge moves by 2 and the right edge by 1
+        let source = with_offsets(&square(0.0, 0.0, 10.0), &[2.0, 1.0, 0.0, 0.0]);
+        let style = VariableOutlineStyle::new().line_join(LineJoin::Miter(0.1 * PI));
+        let shapes = source.variable_outline(style);
+
+        assert_eq!(shapes.len(), 1);
+        let b = bounds(&shapes);
+        for (value, expected) in b.iter().zip([0.0, -2.0, 11.0, 10.0]) {
+            assert_close(*value, expected, 0.01);
+        }
+        assert_close(shapes.area().abs(), 11.0 * 12.0, 0.05);
+    }
+
+    #[test]
+    fn per_vertex_offsets_taper_along_edges() {
+        let source = with_offsets(&square(0.0, 0.0, 10.0), &[1.0, 1.0, 0.0, 0.0]);
+        let style = VariableOutlineStyle::new()
+            .distribution(OffsetDistribution::PerVertex)
+            .line_join(LineJoin::Miter(0.1 * PI));
+        let shapes = source.variable_outline(style);
+
+        // the tapered sides meet the bottom offset line 0.1 past the corners
+        assert_eq!(shapes.len(), 1);
+        let b = bounds(&shapes);
+        for (value, expected) in b.iter().zip([-1.1, -1.0, 11.1, 10.0]) {
+            assert_close(*value, expected, 0.01);
+        }
+        assert_close(shapes.area().abs(), 0.5 * (12.2 + 10.0) * 11.0, 0.05);
+    }
+
+    #[test]
+    fn hole_edges_move_on_their_own() {
+        let mut hole = square(10.0, 10.0, 10.0);
+        hole.reverse();
+        // the hole runs clockwise from its bottom left corner, the first edge is its left side
+        let source = vec![
+            with_offsets(&square(0.0, 0.0, 30.0), &[0.0; 4]),
+            with_offsets(&hole, &[2.0, 0.0, 0.0, 0.0]),
+        ];
+        let shapes = source.variable_outline(VariableOutlineStyle::new());
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+        assert_close(shapes.area().abs(), 900.0 - 80.0, 0.05);
+    }
+
+    #[test]
+    fn opposite_signs_meet_at_the_vertex() {
+        let source = with_offsets(&square(0.0, 0.0, 10.0), &[1.0, -1.0, 0.0, 0.0]);
+        let style = VariableOutlineStyle::new().line_join(LineJoin::Miter(0.1 * PI));
+        let shapes = source.variable_outline(style);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_eq!(shapes[0][0].len(), 6);
+        assert_close(shapes.area().abs(), 100.0, 0.05);
+    }
+
+    #[test]
+    fn collinear_edges_keep_their_offsets() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [20.0, 0.0], [20.0, 10.0], [0.0, 10.0]];
+        let source = with_offsets(&path, &[2.0, 1.0, 0.0, 0.0, 0.0]);
+        let shapes = source.variable_outline(VariableOutlineStyle::new());
+
+        assert_eq!(shapes.len(), 1);
+        assert_close(shapes.area().abs(), 200.0 + 20.0 + 10.0, 0.05);
+    }
+
+    #[test]
+    fn zero_offsets_keep_the_contour() {
+        let source = with_offsets(&square(0.0, 0.0, 10.0), &[0.0; 4]);
+        let shapes = source.variable_outline(VariableOutlineStyle::new());
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0][0].len(), 4);
+        assert_close(shapes.area().abs(), 100.0, 0.01);
+    }
+
+    #[test]
+    fn degenerate_input_is_empty() {
+        let empty: Vec<Vertex> = vec![];
+        assert!(empty.variable_outline(VariableOutlineStyle::new()).is_empty());
+
+        let line = with_offsets(&[[0.0, 0.0], [10.0, 0.0], [5.0, 0.0]], &[1.0; 3]);
+        assert!(line.variable_outline(VariableOutlineStyle::new()).is_empty());
+    }
+
+    #[test]
+    fn flat_and_fixed_scale_variants_match() {
+        let source = with_offsets(&square(0.0, 0.0, 10.0), &[1.0, 2.0, 3.0, 4.0]);
+        let style = VariableOutlineStyle::new().line_join(LineJoin::Round(0.1));
+        let shapes = source.variable_outline(style.clone());
+
+        let mut output = FloatFlatContoursBuffer::default();
+        source.variable_outline_into(style.clone(), &mut output);
+        assert_eq!(output.ranges.len(), 1);
+        assert_eq!(output.points, shapes[0][0]);
+
+        let as_i64 = source.variable_outline_as::<i64>(style.clone());
+        assert_close(as_i64.area(), shapes.area(), 0.01);
+
+        let fixed = source.variable_outline_fixed_scale(style, 100.0).unwrap();
+        assert_close(fixed.area(), shapes.area(), 0.1);
+    }
+
+    #[test]
+    fn test_random_offsets_grow_and_shrink() {
+        let mut rng = rand::rng();
+        let joins = [LineJoin::Bevel, LineJoin::Miter(0.2 * PI), LineJoin::Round(0.2)];
+        for i in 0..120 {
+            let shapes = random_float(100.0, 50).simplify_shape(FillRule::NonZero);
+            let base_area = shapes.area();
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+
+            let source: Vec<Vec<Vec<Vertex>>> = shapes
+                .iter()
+                .map(|shape| {
+                    shape
+                        .iter()
+                        .map(|path| {
+                            path.iter()
+                                .map(|&p| OffsetVertex::new(p, sign * rng.random_range(0.0..10.0)))
+                                .collect()
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let distribution = if i % 4 < 2 {
+                OffsetDistribution::PerEdge
+            } else {
+                OffsetDistribution::PerVertex
+            };
+            let style = VariableOutlineStyle::new()
+                .distribution(distribution)
+                .line_join(joins[i % 3]);
+            let area = source.variable_outline(style).area();
+            if sign > 0.0 {
+                assert!(base_area <= area);
+            } else {
+                assert!(base_area >= area);
+            }
+        }
+    }
+
+    fn random_float(radius: f64, n: usize) -> Vec<[f64; 2]> {
+        let a = 0.5 * radius;
+        let range = -a..=a;
+        let mut rng = rand::rng();
+        (0..n)
+            .map(|_| [rng.random_range(range.clone()), rng.random_range(range.clone())])
+            .collect()
+    }
+}
@@ -0,0 +1,278 @@
+use crate::mesh::variable_outline::style::OffsetVertex;
+use alloc::vec::Vec;
+use i_float::float::compatible::FloatPointCompatible;
+
+pub trait VariableOutlineSource<P>
+where
+    P: FloatPointCompatible,
+{
+    type ResourceIter<'a>: Iterator<Item = &'a [OffsetVertex<P>]>
+    where
+        P: 'a,
+        Self: 'a;
+
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_>;
+}
+
+pub struct ContourResourceIterator<'a, P: FloatPointCompatible> {
+    slice: &'a [OffsetVertex<P>],
+    finished: bool,
+}
+
+impl<'a, P: FloatPointCompatible> ContourResourceIterator<'a, P> {
+    #[inline]
+    fn with_slice(slice: &'a [OffsetVertex<P>]) -> Self {
+        Self {
+            slice,
+            finished: false,
+        }
+    }
+}
+
+impl<'a, P: FloatPointCompatible> Iterator for ContourResourceIterator<'a, P> {
+    type Item = &'a [OffsetVertex<P>];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        Some(self.slice)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        1
+    }
+}
+
+impl<P: FloatPointCompatible> VariableOutlineSource<P> for [OffsetVertex<P>] {
+    type ResourceIter<'a>
+        = ContourResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ContourResourceIterator::with_slice(self)
+    }
+}
+
+impl<P: FloatPointCompatible, const N: usize> VariableOutlineSource<P> for [OffsetVertex<P>; N] {
+    type ResourceIter<'a>
+        = ContourResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ContourResourceIterator::with_slice(self)
+    }
+}
+
+impl<P: FloatPointCompatible> VariableOutlineSource<P> for Vec<OffsetVertex<P>> {
+    type ResourceIter<'a>
+        = ContourResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ContourResourceIterator::with_slice(self.as_slice())
+    }
+}
+
+impl<'b, P: FloatPointCompatible> VariableOutlineSource<P> for &'b [OffsetVertex<P>] {
+    type ResourceIter<'a>
+        = ContourResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'b> {
+        ContourResourceIterator::with_slice(self)
+    }
+}
+
+pub struct ShapeResourceIterator<'a, P: FloatPointCompatible> {
+    slice: &'a [Vec<OffsetVertex<P>>],
+    index: usize,
+}
+
+impl<'a, P: FloatPointCompatible> Iterator for ShapeResourceIterator<'a, P> {
+    type Item = &'a [OffsetVertex<P>];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.slice.get(self.index)?;
+        self.index += 1;
+        Some(path.as_slice())
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.slice.len() - self.index
+    }
+}
+
+impl<P: FloatPointCompatible> VariableOutlineSource<P> for [Vec<OffsetVertex<P>>] {
+    type ResourceIter<'a>
+        = ShapeResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ShapeResourceIterator {
+            slice: self,
+            index: 0,
+        }
+    }
+}
+
+impl<P: FloatPointCompatible, const N: usize> VariableOutlineSource<P> for [Vec<OffsetVertex<P>>; N] {
+    type ResourceIter<'a>
+        = ShapeResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ShapeResourceIterator {
+            slice: self,
+            index: 0,
+        }
+    }
+}
+
+impl<P: FloatPointCompatible> VariableOutlineSource<P> for Vec<Vec<OffsetVertex<P>>> {
+    type ResourceIter<'a>
+        = ShapeResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ShapeResourceIterator {
+            slice: self.as_slice(),
+            index: 0,
+        }
+    }
+}
+
+impl<'b, P: FloatPointCompatible> VariableOutlineSource<P> for &'b [Vec<OffsetVertex<P>>] {
+    type ResourceIter<'a>
+        = ShapeResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'b> {
+        ShapeResourceIterator {
+            slice: self,
+            index: 0,
+        }
+    }
+}
+
+pub struct ShapesResourceIterator<'a, P: FloatPointCompatible> {
+    slice: &'a [Vec<Vec<OffsetVertex<P>>>],
+    shape_index: usize,
+    path_index: usize,
+}
+
+impl<'a, P: FloatPointCompatible> Iterator for ShapesResourceIterator<'a, P> {
+    type Item = &'a [OffsetVertex<P>];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let shape = self.slice.get(self.shape_index)?;
+            if let Some(path) = shape.get(self.path_index) {
+                self.path_index += 1;
+                return Some(path.as_slice());
+            }
+            self.shape_index += 1;
+            self.path_index = 0;
+        }
+    }
+}
+
+impl<P: FloatPointCompatible> VariableOutlineSource<P> for [Vec<Vec<OffsetVertex<P>>>] {
+    type ResourceIter<'a>
+        = ShapesResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ShapesResourceIterator {
+            slice: self,
+            shape_index: 0,
+            path_index: 0,
+        }
+    }
+}
+
+impl<P: FloatPointCompatible> VariableOutlineSource<P> for Vec<Vec<Vec<OffsetVertex<P>>>> {
+    type ResourceIter<'a>
+        = ShapesResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_offset_paths(&self) -> Self::ResourceIter<'_> {
+        ShapesResourceIterator {
+            slice: self.as_slice(),
+            shape_index: 0,
+            path_index: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VariableOutlineSource;
+    use crate::mesh::variable_outline::OffsetVertex;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    type Vertex = OffsetVertex<[f64; 2]>;
+
+    fn path(x: f64) -> Vec<Vertex> {
+        vec![
+            OffsetVertex::new([x, 0.0], 1.0),
+            OffsetVertex::new([x + 1.0, 0.0], 1.0),
+            OffsetVertex::new([x, 1.0], 1.0),
+        ]
+    }
+
+    #[test]
+    fn contour_and_shape_forms_preserve_path_order() {
+        let contour = path(0.0);
+        assert_eq!(contour.iter_offset_paths().count(), 1);
+
+        let shape = vec![path(0.0), path(1.0)];
+        let mut iter = shape.iter_offset_paths();
+        assert_eq!(iter.next().unwrap()[0].point, [0.0, 0.0]);
+        assert_eq!(iter.count(), 1);
+    }
+
+    #[test]
+    fn shapes_form_skips_empty_shapes() {
+        let shapes = vec![vec![path(0.0), path(1.0)], vec![], vec![path(2.0)]];
+        let starts: Vec<f64> = shapes.iter_offset_paths().map(|path| path[0].point[0]).collect();
+        assert_eq!(starts, vec![0.0, 1.0, 2.0]);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::mesh::style::LineJoin;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+
+/// A contour vertex with its own offset distance.
+///
+/// A positive offset grows the shape, it moves outer contours out and holes in. A negative
+/// offset shrinks it.
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetVertex<P: FloatPointCompatible> {
+    pub point: P,
+    pub offset: P::Scalar,
+}
+
+impl<P: FloatPointCompatible> OffsetVertex<P> {
+    #[inline]
+    pub fn new(point: P, offset: P::Scalar) -> Self {
+        Self { point, offset }
+    }
+}
+
+/// How the vertex offsets are applied to the contour edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OffsetDistribution {
+    /// Every edge is moved by the offset of its start vertex, the offset of the last vertex
+    /// belongs to the closing edge. This is the default.
+    #[default]
+    PerEdge,
+    /// The offset changes linearly along every edge, from its start vertex to its end vertex.
+    PerVertex,
+}
+
+/// Style for outlines whose offset is stored at each contour vertex.
+///
+/// Where two neighbouring edges are moved by different distances, a miter corner is the
+/// intersection of both offset edges, and a round corner keeps the larger distance from the
+/// vertex until it meets the other offset edge. Edges with offsets of opposite signs meet at
+/// the source vertex.
+#[derive(Debug, Clone)]
+pub struct VariableOutlineStyle<T: FloatNumber> {
+    /// How the vertex offsets are applied to the edges.
+    pub distribution: OffsetDistribution,
+    /// The join style where two offset edges meet.
+    pub join: LineJoin<T>,
+}
+
+impl<T: FloatNumber> VariableOutlineStyle<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how the vertex offsets are applied to the edges.
+    #[inline]
+    pub fn distribution(mut self, distribution: OffsetDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Sets the line join style for the offset path.
+    #[inline]
+    pub fn line_join(mut self, join: LineJoin<T>) -> Self {
+        self.join = join;
+        self
+    }
+}
+
+impl<T: FloatNumber> Default for VariableOutlineStyle<T> {
+    fn default() -> Self {
+        Self {
+            distribution: OffsetDistribution::PerEdge,
+            join: LineJoin::Bevel,
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use crate::mesh::rotator::Rotator;
+use crate::mesh::style::LineJoin;
 use crate::mesh::variable_stroke::section::{RadiusTrend, Section};
-use crate::mesh::variable_stroke::style::{StrokeVertex, VariableStrokeStyle};
+use crate::mesh::variable_stroke::style::{StrokeVertex, VariableLineCap, VariableStrokeStyle};
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::segment::Segment;
+use alloc::rc::Rc;
 use alloc::vec::Vec;
 use core::f64::consts::PI;
 use i_float::adapter::FloatPointAdapter;
@@ -27,6 +29,25 @@ enum ArcSweep {
     Major,
 }
 
+/// A cap at the start or the end of a path, set by the style.
+#[derive(Clone, Debug)]
+enum PathCap<T> {
+    Butt,
+    Round(T),
+    /// Template points in the cap frame, in units of the end radius.
+    Points(Rc<[[T; 2]]>),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Join<T> {
+    Bevel,
+    /// The miter is clipped at `max_ratio` times the join radius along each side.
+    Miter {
+        max_ratio: T,
+    },
+    Round(T),
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct SubSegment {
     start: usize,
@@ -37,12 +58,30 @@ struct SubSegment {
 
 pub(super) struct VariableStrokeBuilder<T: FloatNumber> {
     round_angle: T,
+    start_cap: PathCap<T>,
+    end_cap: PathCap<T>,
+    join: Join<T>,
 }
 
 impl<T: FloatNumber> VariableStrokeBuilder<T> {
     pub(super) fn new(style: VariableStrokeStyle<T>) -> Self {
+        let style = style.normalized();
+        let join = match style.join {
+            LineJoin::Bevel => Join::Bevel,
+            LineJoin::Miter(angle) => {
+                let half_angle = T::from_float(0.5) * angle.max(T::from_float(0.01));
+                Join::Miter {
+                    max_ratio: T::ONE / half_angle.tan(),
+                }
+            }
+            LineJoin::Round(angle) => Join::Round(angle),
+        };
+
         Self {
-            round_angle: style.normalized().round_angle,
+            round_angle: style.round_angle,
+            start_cap: PathCap::new(style.start_cap),
+            end_cap: PathCap::new(style.end_cap),
+            join,
         }
     }
 
@@ -112,11 +151,14 @@ impl<T: FloatNumber> VariableStrokeBuilder<T> {
         P: FloatPointCompatible<Scalar = T>,
         I: IntNumber,
     {
+        let is_path_start = subsegment.start == 0;
+        let is_path_end = subsegment.end + 1 == path.len();
+
         if subsegment.start == subsegment.end {
-            if subsegment.start_cap != Cap::Butt || subsegment.end_cap != Cap::Butt {
-                let vertex = &path[subsegment.start];
-                output.add_circle(&vertex.point, vertex.radius(), self.round_angle);
-            }
+            let vertex = &path[subsegment.start];
+            let start_cap = self.resolve_cap(subsegment.start_cap, is_path_start, &self.start_cap);
+            let end_cap = self.resolve_cap(subsegment.end_cap, is_path_end, &self.end_cap);
+            output.add_dot(&vertex.point, vertex.radius(), &start_cap, &end_cap);
             return;
         }
 
@@ -128,15 +170,27 @@ impl<T: FloatNumber> VariableStrokeBuilder<T> {
         };
 
         output.add_section(&previous);
-        output.add_start_cap(&previous, subsegment.start_cap, self.round_angle);
+        let start_cap = self.resolve_cap(subsegment.start_cap, is_path_start, &self.start_cap);
+        output.add_start_cap(&previous, &start_cap);
 
         for section in sections {
             output.add_section(&section);
-            output.add_join(&previous, &section, self.round_angle);
+            output.add_join(&previous, &section, self.join);
             previous = section;
         }
 
-        output.add_end_cap(&previous, subsegment.end_cap, self.round_angle);
+        let end_cap = self.resolve_cap(subsegment.end_cap, is_path_end, &self.end_cap);
+        output.add_end_cap(&previous, &end_cap);
+    }
+
+    /// The style cap replaces the round cap at the ends of the path. Butt caps inside the path
+    /// and at covered ends stay as they are.
+    fn resolve_cap(&self, cap: Cap, is_path_end: bool, style_cap: &PathCap<T>) -> PathCap<T> {
+        match cap {
+            Cap::Round if is_path_end => style_cap.clone(),
+            Cap::Round => PathCap::Round(self.round_angle),
+            Cap::Butt => PathCap::Butt,
+        }
     }
 
     fn find_subsegments<P, I>(path: &[StrokeVertex<P>], adapter: &FloatPointAdapter<P, I>) -> Vec<SubSegment>
@@ -287,7 +341,34 @@ impl<T: FloatNumber> VariableStrokeBuilder<T> {
     }
 
     pub(super) fn additional_offset(&self, max_radius: T) -> T {
-        T::from_float(1.1) * max_radius
+        let mut ratio = T::ONE;
+        if let Join::Miter { max_ratio } = self.join {
+            ratio = ratio + max_ratio;
+        }
+        for cap in [&self.start_cap, &self.end_cap] {
+            if let PathCap::Points(points) = cap {
+                for p in points.iter() {
+                    ratio = ratio.max((p[0] * p[0] + p[1] * p[1]).sqrt());
+                }
+            }
+        }
+        T::from_float(1.1) * ratio * max_radius
+    }
+}
+
+impl<T: FloatNumber> PathCap<T> {
+    fn new(cap: VariableLineCap<T>) -> Self {
+        match cap {
+            VariableLineCap::Butt => PathCap::Butt,
+            VariableLineCap::Round(angle) => PathCap::Round(angle),
+            VariableLineCap::Square => PathCap::Points(Rc::new([[T::ONE, -T::ONE], [T::ONE, T::ONE]])),
+            VariableLineCap::Custom(points) => PathCap::Points(
+                points
+                    .iter()
+                    .map(|p| [T::from_float(p[0]), T::from_float(p[1])])
+                    .collect(),
+            ),
+        }
     }
 }
 
@@ -335,6 +416,51 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
         }
     }
 
+    /// Builds the caps of a zero length subsegment. A round cap on either side gives a circle,
+    /// square and custom caps are laid along the x axis.
+    fn add_dot(
+        &mut self,
+        center: &P,
+        radius: P::Scalar,
+        start_cap: &PathCap<P::Scalar>,
+        end_cap: &PathCap<P::Scalar>,
+    ) {
+        match (start_cap, end_cap) {
+            (PathCap::Round(angle), _) | (_, PathCap::Round(angle)) => {
+                return self.add_circle(center, radius, *angle);
+            }
+            (PathCap::Butt, PathCap::Butt) => return,
+            _ => {}
+        }
+
+        let int_radius = self.adapter.round_len_to_int(radius);
+        if int_radius <= I::ONE {
+            return;
+        }
+
+        let center = self.adapter.int_to_float(&self.adapter.float_to_int(center));
+        let radius = self.adapter.len_to_float(int_radius);
+        let top = P::from_xy(center.x(), center.y() + radius);
+        let bottom = P::from_xy(center.x(), center.y() - radius);
+        let caps = [
+            (start_cap, -P::Scalar::ONE, top, bottom),
+            (end_cap, P::Scalar::ONE, bottom, top),
+        ];
+        for (cap, dir_x, from, to) in caps {
+            if let PathCap::Points(points) = cap {
+                let dir = P::from_xy(dir_x, P::Scalar::ZERO);
+                self.add_cap_points(&center, &dir, &from, &to, radius, points);
+            } else {
+                self.add_edge(
+                    &from,
+                    &to,
+                    #[cfg(feature = "variable_stroke_debug")]
+                    VariableStrokeDebugEdgeKind::CapClosure,
+                );
+            }
+        }
+    }
+
     #[inline]
     fn add_section(&mut self, section: &Section<P>) {
         self.add_edge(
@@ -351,14 +477,14 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
         );
     }
 
-    fn add_join(&mut self, prev: &Section<P>, next: &Section<P>, angle: P::Scalar) -> usize {
+    fn add_join(&mut self, prev: &Section<P>, next: &Section<P>, join: Join<P::Scalar>) -> usize {
         let prev_center = self.adapter.float_to_int(&prev.b);
         let next_center = self.adapter.float_to_int(&next.a);
         if prev_center != next_center {
             // A non-drawable section between these sections was filtered out. They belong to
             // separate chains, so close both chains instead of building an arc between centers.
-            self.add_end_cap(prev, Cap::Butt, angle);
-            self.add_start_cap(next, Cap::Butt, angle);
+            self.add_end_cap(prev, &PathCap::Butt);
+            self.add_start_cap(next, &PathCap::Butt);
             return 0;
         }
 
@@ -395,14 +521,13 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
         let right_arc = right_cross > I::Wide::ZERO || middle_cross >= I::Wide::ZERO;
 
         if left_arc {
-            arc_count += self.add_arc_ccw(
+            // the left boundaries are walked backward
+            arc_count += self.add_outer_join(
                 &prev.b,
-                &next.a_left,
-                &prev.b_left,
-                angle,
+                [&next.b_left, &next.a_left],
+                [&prev.b_left, &prev.a_left],
                 ArcSweep::Minor,
-                #[cfg(feature = "variable_stroke_debug")]
-                VariableStrokeDebugEdgeKind::JoinArc,
+                join,
             ) as usize;
         } else {
             self.add_edge(
@@ -414,14 +539,12 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
         }
 
         if right_arc {
-            arc_count += self.add_arc_ccw(
+            arc_count += self.add_outer_join(
                 &prev.b,
-                &prev.b_right,
-                &next.a_right,
-                angle,
+                [&prev.a_right, &prev.b_right],
+                [&next.a_right, &next.b_right],
                 ArcSweep::Major,
-                #[cfg(feature = "variable_stroke_debug")]
-                VariableStrokeDebugEdgeKind::JoinArc,
+                join,
             ) as usize;
         } else {
             self.add_edge(
@@ -435,15 +558,92 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
         arc_count
     }
 
-    fn add_start_cap(&mut self, section: &Section<P>, cap: Cap, angle: P::Scalar) {
+    /// Joins the outer side of a corner, going CCW around `center` from the end of the `incoming`
+    /// boundary to the start of the `outgoing` one.
+    fn add_outer_join(
+        &mut self,
+        center: &P,
+        incoming: [&P; 2],
+        outgoing: [&P; 2],
+        aligned_sweep: ArcSweep,
+        join: Join<P::Scalar>,
+    ) -> bool {
+        let (from, to) = (incoming[1], outgoing[0]);
+        let max_ratio = match join {
+            Join::Round(angle) => {
+                return self.add_arc_ccw(
+                    center,
+                    from,
+                    to,
+                    angle,
+                    aligned_sweep,
+                    #[cfg(feature = "variable_stroke_debug")]
+                    VariableStrokeDebugEdgeKind::JoinArc,
+                );
+            }
+            Join::Bevel => None,
+            Join::Miter { max_ratio } => Some(max_ratio),
+        };
+
+        if self.adapter.float_to_int(from) == self.adapter.float_to_int(to) {
+            return false;
+        }
+
+        if self.arc_sweep_ccw(center, from, to, aligned_sweep) == ArcSweep::Major {
+            // The contacts wrap around the center, a chord would cut into both sections.
+            // Going through the center adds nothing and leaves the corner to the sections.
+            self.add_corner(&[from, center, to]);
+            return true;
+        }
+
+        let Some(max_ratio) = max_ratio else {
+            self.add_corner(&[from, to]);
+            return true;
+        };
+
+        // the miter point is the intersection of both boundary lines, with per side lengths
+        // `s` and `t` that differ when the width changes through the corner
+        let in_dir = FloatPointMath::normalize(&FloatPointMath::sub(incoming[1], incoming[0]));
+        let out_dir = FloatPointMath::normalize(&FloatPointMath::sub(outgoing[1], outgoing[0]));
+        let cross = FloatPointMath::cross_product(&in_dir, &out_dir);
+        let vector = FloatPointMath::sub(to, from);
+        let s = FloatPointMath::cross_product(&vector, &out_dir) / cross;
+        let t = FloatPointMath::cross_product(&in_dir, &vector) / cross;
+
+        if cross.abs() < P::Scalar::from_float(0.0001) || s < P::Scalar::ZERO || t < P::Scalar::ZERO {
+            self.add_corner(&[from, to]);
+            return true;
+        }
+
+        let radius = FloatPointMath::length(&FloatPointMath::sub(from, center));
+        let max_length = max_ratio * radius;
+        let a = FloatPointMath::add(from, &FloatPointMath::scale(&in_dir, s.min(max_length)));
+        let b = FloatPointMath::sub(to, &FloatPointMath::scale(&out_dir, t.min(max_length)));
+        self.add_corner(&[from, &a, &b, to]);
+
+        true
+    }
+
+    fn add_corner(&mut self, points: &[&P]) {
+        for pair in points.windows(2) {
+            self.add_edge(
+                pair[0],
+                pair[1],
+                #[cfg(feature = "variable_stroke_debug")]
+                VariableStrokeDebugEdgeKind::JoinCorner,
+            );
+        }
+    }
+
+    fn add_start_cap(&mut self, section: &Section<P>, cap: &PathCap<P::Scalar>) {
         match cap {
-            Cap::Butt => self.add_edge(
+            PathCap::Butt => self.add_edge(
                 &section.a_left,
                 &section.a_right,
                 #[cfg(feature = "variable_stroke_debug")]
                 VariableStrokeDebugEdgeKind::CapClosure,
             ),
-            Cap::Round => {
+            PathCap::Round(angle) => {
                 let sweep = if section.radius_trend == RadiusTrend::Decreasing {
                     ArcSweep::Major
                 } else {
@@ -453,24 +653,36 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
                     &section.a,
                     &section.a_left,
                     &section.a_right,
-                    angle,
+                    *angle,
                     sweep,
                     #[cfg(feature = "variable_stroke_debug")]
                     VariableStrokeDebugEdgeKind::CapArc,
                 );
             }
+            PathCap::Points(points) => {
+                let dir = FloatPointMath::normalize(&FloatPointMath::sub(&section.a, &section.b));
+                let radius = FloatPointMath::length(&FloatPointMath::sub(&section.a_left, &section.a));
+                self.add_cap_points(
+                    &section.a,
+                    &dir,
+                    &section.a_left,
+                    &section.a_right,
+                    radius,
+                    points,
+                );
+            }
         }
     }
 
-    fn add_end_cap(&mut self, section: &Section<P>, cap: Cap, angle: P::Scalar) {
+    fn add_end_cap(&mut self, section: &Section<P>, cap: &PathCap<P::Scalar>) {
         match cap {
-            Cap::Butt => self.add_edge(
+            PathCap::Butt => self.add_edge(
                 &section.b_right,
                 &section.b_left,
                 #[cfg(feature = "variable_stroke_debug")]
                 VariableStrokeDebugEdgeKind::CapClosure,
             ),
-            Cap::Round => {
+            PathCap::Round(angle) => {
                 let sweep = if section.radius_trend == RadiusTrend::Increasing {
                     ArcSweep::Major
                 } else {
@@ -480,15 +692,59 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
                     &section.b,
                     &section.b_right,
                     &section.b_left,
-                    angle,
+                    *angle,
                     sweep,
                     #[cfg(feature = "variable_stroke_debug")]
                     VariableStrokeDebugEdgeKind::CapArc,
                 );
             }
+            PathCap::Points(points) => {
+                let dir = FloatPointMath::normalize(&FloatPointMath::sub(&section.b, &section.a));
+                let radius = FloatPointMath::length(&FloatPointMath::sub(&section.b_left, &section.b));
+                self.add_cap_points(
+                    &section.b,
+                    &dir,
+                    &section.b_right,
+                    &section.b_left,
+                    radius,
+                    points,
+                );
+            }
         }
     }
 
+    /// Connects `from` and `to` through template points scaled by `radius`, with the x axis of
+    /// the template along `dir`, pointing out of the stroke.
+    fn add_cap_points(
+        &mut self,
+        center: &P,
+        dir: &P,
+        from: &P,
+        to: &P,
+        radius: P::Scalar,
+        points: &[[P::Scalar; 2]],
+    ) {
+        let rotator = Rotator::with_vector(dir);
+        let mut a = *from;
+        for p in points.iter() {
+            let v = rotator.rotate(&P::from_xy(radius * p[0], radius * p[1]));
+            let b = FloatPointMath::add(center, &v);
+            self.add_edge(
+                &a,
+                &b,
+                #[cfg(feature = "variable_stroke_debug")]
+                VariableStrokeDebugEdgeKind::CapCorner,
+            );
+            a = b;
+        }
+        self.add_edge(
+            &a,
+            to,
+            #[cfg(feature = "variable_stroke_debug")]
+            VariableStrokeDebugEdgeKind::CapCorner,
+        );
+    }
+
     fn arc_sweep_ccw(&self, center: &P, from: &P, to: &P, aligned_sweep: ArcSweep) -> ArcSweep {
         let center = self.adapter.float_to_int(center);
         let from_vector = self.adapter.float_to_int(from) - center;
@@ -584,12 +840,12 @@ impl<P: FloatPointCompatible, I: IntNumber> SegmentBuilder<'_, P, I> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ArcSweep, Cap, SegmentBuilder, SubSegment, VariableStrokeBuilder};
+    use super::{ArcSweep, Cap, Join, SegmentBuilder, SubSegment, VariableStrokeBuilder};
     #[cfg(feature = "variable_stroke_debug")]
     use crate::mesh::variable_stroke::VariableStrokeDebugEdgeKind;
     use crate::mesh::variable_stroke::offset::VariableStrokeOffset;
     use crate::mesh::variable_stroke::section::Section;
-    use crate::mesh::variable_stroke::style::{StrokeVertex, VariableStrokeStyle};
+    use crate::mesh::variable_stroke::style::{StrokeVertex, VariableLineCap, VariableStrokeStyle};
     use crate::segm::boolean::ShapeCountBoolean;
     use crate::segm::segment::Segment;
     use alloc::vec;
@@ -605,7 +861,7 @@ mod tests {
     fn empty_path_does_not_create_subsegments_or_edges() {
         let path: [StrokeVertex<[f64; 2]>; 0] = [];
         let adapter = adapter();
-        let builder = VariableStrokeBuilder::new(VariableStrokeStyle::new());
+        let builder = VariableStrokeBuilder::new(VariableStrokeStyle::<f64>::new());
         let mut segments = Vec::<Segment<ShapeCountBoolean, i32>>::new();
 
         assert!(VariableStrokeBuilder::<f64>::find_subsegments(&path, &adapter).is_empty());
@@ -618,7 +874,7 @@ mod tests {
     fn single_round_vertex_builds_a_circle() {
         let path = [StrokeVertex::new([0.0, 0.0], 4.0)];
         let adapter = adapter();
-        let builder = VariableStrokeBuilder::new(VariableStrokeStyle::new());
+        let builder = VariableStrokeBuilder::new(VariableStrokeStyle::<f64>::new());
         let mut segments = Vec::<Segment<ShapeCountBoolean, i32>>::new();
 
         builder.build(&path, &adapter, &mut segments);
@@ -626,6 +882,28 @@ mod tests {
         assert!(!segments.is_empty());
     }
 
+    #[test]
+    fn single_vertex_follows_style_caps() {
+        let path = [StrokeVertex::new([0.0, 0.0], 4.0)];
+        let adapter = adapter();
+        let build = |style: VariableStrokeStyle<f64>| {
+            let mut segments = Vec::<Segment<ShapeCountBoolean, i32>>::new();
+            VariableStrokeBuilder::new(style).build(&path, &adapter, &mut segments);
+            segments.len()
+        };
+
+        let butt = VariableStrokeStyle::new()
+            .start_cap(VariableLineCap::Butt)
+            .end_cap(VariableLineCap::Butt);
+        let square = VariableStrokeStyle::new()
+            .start_cap(VariableLineCap::Square)
+            .end_cap(VariableLineCap::Square);
+
+        assert_eq!(build(butt), 0);
+        // a square around the vertex, split at the top and the bottom
+        assert_eq!(build(square), 6);
+    }
+
     #[test]
     fn covered_break_uses_butt_on_smaller_side() {
         let path = [
@@ -722,7 +1000,7 @@ mod tests {
             }
         );
 
-        let builder = VariableStrokeBuilder::new(VariableStrokeStyle::new());
+        let builder = VariableStrokeBuilder::new(VariableStrokeStyle::<f64>::new());
         let mut segments = Vec::<Segment<ShapeCountBoolean, i32>>::new();
         let mut output = SegmentBuilder {
             adapter: &adapter,
@@ -759,7 +1037,7 @@ mod tests {
             debug_path_index: 0,
         };
 
-        output.add_join(&previous, &next, core::f64::consts::FRAC_PI_4);
+        output.add_join(&previous, &next, Join::Round(core::f64::consts::FRAC_PI_4));
 
         for contact in contacts {
             let point = adapter.float_to_int(&contact);
@@ -787,7 +1065,7 @@ mod tests {
             debug_path_index: 0,
         };
 
-        output.add_join(&previous, &next, core::f64::consts::FRAC_PI_4)
+        output.add_join(&previous, &next, Join::Round(core::f64::consts::FRAC_PI_4))
     }
 
     #[test]
@@ -922,7 +1200,7 @@ mod tests {
         };
 
         assert_eq!(
-            output.add_join(&previous, &next, 0.17999999_f32),
+            output.add_join(&previous, &next, Join::Round(0.17999999_f32)),
             2,
             "the wide reversal exposes both join arcs"
         );
@@ -960,7 +1238,7 @@ mod tests {
             ArcSweep::Major,
             "the left CCW join crosses the major radial sector"
         );
-        assert_eq!(output.add_join(&first, &second, 0.21_f32), 2);
+        assert_eq!(output.add_join(&first, &second, Join::Round(0.21_f32)), 2);
         assert!(join_segments.len() > 20, "the major join arc was not built");
 
         let style = VariableStrokeStyle::new().round_angle(0.21_f32);
@@ -991,7 +1269,7 @@ mod tests {
             debug_path_index: 0,
         };
 
-        assert_eq!(output.add_join(&first, &second, 0.615_f32), 1);
+        assert_eq!(output.add_join(&first, &second, Join::Round(0.615_f32)), 1);
 
         let style = VariableStrokeStyle::new().round_angle(0.615_f32);
         let result = paths.variable_stroke(style);
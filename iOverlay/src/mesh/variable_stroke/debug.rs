@@ -9,10 +9,14 @@ pub enum VariableStrokeDebugEdgeKind {
     JoinArc,
     /// A straight edge closing the gap between adjacent sections.
     JoinClosure,
+    /// One edge of a miter or bevel join.
+    JoinCorner,
     /// One chord of a round end cap.
     CapArc,
     /// A butt edge closing an end cap.
     CapClosure,
+    /// One edge of a square or custom end cap.
+    CapCorner,
     /// One chord of a circle emitted for an isolated drawable vertex.
     CircleArc,
 }
@@ -4,6 +4,7 @@ mod debug;
 pub mod offset;
 mod resource;
 mod section;
+mod smooth;
 mod style;
 
 #[cfg(feature = "variable_stroke_debug")]
@@ -11,4 +12,4 @@ pub use debug::{VariableStrokeDebugEdge, VariableStrokeDebugEdgeKind, VariableSt
 #[cfg(feature = "variable_stroke_debug")]
 pub use offset::VariableStrokeDebug;
 pub use resource::VariableStrokeSource;
-pub use style::{StrokeVertex, VariableStrokeStyle};
+pub use style::{StrokeInterpolation, StrokeVertex, VariableLineCap, VariableStrokeStyle};
@@ -6,7 +6,8 @@ use crate::float::overlay::OverlayOptions;
 use crate::float::scale::FixedScaleOverlayError;
 use crate::mesh::variable_stroke::builder::VariableStrokeBuilder;
 use crate::mesh::variable_stroke::resource::VariableStrokeSource;
-use crate::mesh::variable_stroke::style::VariableStrokeStyle;
+use crate::mesh::variable_stroke::smooth::PathSmoother;
+use crate::mesh::variable_stroke::style::{StrokeVertex, VariableStrokeStyle};
 use alloc::vec;
 use alloc::vec::Vec;
 use i_float::adapter::FloatPointAdapter;
@@ -26,7 +27,8 @@ use i_shape::float::simple::SimplifyContour;
 #[cfg(feature = "variable_stroke_debug")]
 use crate::mesh::variable_stroke::VariableStrokeDebugResult;
 
-/// Builds round-cap, round-join strokes whose width is stored at each centerline vertex.
+/// Builds strokes whose width is stored at each centerline vertex, with the caps and joins of a
+/// [`VariableStrokeStyle`].
 pub trait VariableStrokeOffset<P>: VariableStrokeSource<P>
 where
     P: FloatPointCompatible + 'static,
@@ -241,6 +243,8 @@ struct VariableStrokeSolver<P: FloatPointCompatible, I: IntNumber> {
     max_radius: P::Scalar,
     builder: VariableStrokeBuilder<P::Scalar>,
     adapter: FloatPointAdapter<P, I>,
+    /// Resampled paths of a smooth or tapered style, stroked instead of the source.
+    smooth_paths: Option<Vec<Vec<StrokeVertex<P>>>>,
     paths_count: usize,
     points_count: usize,
 }
@@ -259,9 +263,16 @@ where
         let mut points_count = 0;
         let mut rect: Option<FloatRect<P::Scalar>> = None;
 
-        for path in source.iter_variable_paths() {
+        let smooth_paths = PathSmoother::new(&style).map(|smoother| {
+            source
+                .iter_variable_paths()
+                .map(|path| smoother.resample(path))
+                .collect::<Vec<_>>()
+        });
+
+        Self::for_each_path(&smooth_paths, source, |_, path| {
             if path.is_empty() {
-                continue;
+                return;
             }
             paths_count += 1;
             points_count += path.len();
@@ -273,7 +284,7 @@ where
                     rect = Some(FloatRect::with_point(vertex.point));
                 }
             }
-        }
+        });
 
         if paths_count == 0 || points_count < 2 || max_radius <= P::Scalar::ZERO {
             return None;
@@ -288,11 +299,26 @@ where
             max_radius,
             builder,
             adapter,
+            smooth_paths,
             paths_count,
             points_count,
         })
     }
 
+    fn for_each_path<S: VariableStrokeSource<P> + ?Sized>(
+        smooth_paths: &Option<Vec<Vec<StrokeVertex<P>>>>,
+        source: &S,
+        mut f: impl FnMut(usize, &[StrokeVertex<P>]),
+    ) {
+        match smooth_paths {
+            Some(paths) => paths.iter().enumerate().for_each(|(index, path)| f(index, path)),
+            None => source
+                .iter_variable_paths()
+                .enumerate()
+                .for_each(|(index, path)| f(index, path)),
+        }
+    }
+
     fn apply_scale(&mut self, scale: P::Scalar) -> Result<(), FixedScaleOverlayError> {
         self.adapter = FloatPointAdapter::try_with_scale(*self.adapter.rect(), scale)?;
         Ok(())
@@ -308,9 +334,9 @@ where
         }
 
         let mut segments = Vec::with_capacity(self.builder.capacity(self.paths_count, self.points_count));
-        for path in source.iter_variable_paths() {
+        Self::for_each_path(&self.smooth_paths, source, |_, path| {
             self.builder.build(path, &self.adapter, &mut segments);
-        }
+        });
 
         let mut overlay = Overlay::with_segments(segments);
         overlay.options = options.int_with_adapter(&self.adapter);
@@ -339,9 +365,9 @@ where
         }
 
         let mut segments = Vec::with_capacity(self.builder.capacity(self.paths_count, self.points_count));
-        for path in source.iter_variable_paths() {
+        Self::for_each_path(&self.smooth_paths, source, |_, path| {
             self.builder.build(path, &self.adapter, &mut segments);
-        }
+        });
 
         let mut overlay = Overlay::with_segments(segments);
         overlay.options = options.int_with_adapter(&self.adapter);
@@ -377,10 +403,10 @@ where
 
         let mut segments = Vec::with_capacity(self.builder.capacity(self.paths_count, self.points_count));
         let mut edges = Vec::with_capacity(segments.capacity());
-        for (path_index, path) in source.iter_variable_paths().enumerate() {
+        Self::for_each_path(&self.smooth_paths, source, |path_index, path| {
             self.builder
                 .build_debug(path, path_index, &self.adapter, &mut segments, &mut edges);
-        }
+        });
 
         let mut overlay = Overlay::with_segments(segments);
         overlay.options = options.int_with_adapter(&self.adapter);
@@ -415,7 +441,9 @@ mod tests {
     use crate::float::overlay::OverlayOptions;
     use crate::mesh::stroke::offset::StrokeOffset;
     use crate::mesh::style::{LineCap, LineJoin, StrokeStyle};
-    use crate::mesh::variable_stroke::{StrokeVertex, VariableStrokeStyle};
+    use crate::mesh::variable_stroke::{
+        StrokeInterpolation, StrokeVertex, VariableLineCap, VariableStrokeStyle,
+    };
     use alloc::vec;
     use alloc::vec::Vec;
     use i_shape::flat::float::FloatFlatContoursBuffer;
@@ -765,4 +793,182 @@ mod tests {
             "round start does not cover the larger circle: projection={min_projection}"
         );
     }
+
+    fn bounds(shapes: &[Vec<Vec<[f64; 2]>>]) -> [f64; 4] {
+        let mut rect = [f64::MAX, f64::MAX, f64::MIN, f64::MIN];
+        for p in shapes.iter().flatten().flatten() {
+            rect = [
+                rect[0].min(p[0]),
+                rect[1].min(p[1]),
+                rect[2].max(p[0]),
+                rect[3].max(p[1]),
+            ];
+        }
+        rect
+    }
+
+    #[test]
+    fn tapered_butt_and_square_caps() {
+        let path = [
+            StrokeVertex::new([0.0, 0.0], 4.0),
+            StrokeVertex::new([10.0, 0.0], 2.0),
+        ];
+        let butt = VariableStrokeStyle::new()
+            .start_cap(VariableLineCap::Butt)
+            .end_cap(VariableLineCap::Butt);
+        let square = VariableStrokeStyle::new()
+            .start_cap(VariableLineCap::Square)
+            .end_cap(VariableLineCap::Square);
+
+        let butt = path.variable_stroke_fixed_scale(butt, 1_000.0).unwrap();
+        let square = path.variable_stroke_fixed_scale(square, 1_000.0).unwrap();
+        let round = path
+            .variable_stroke_fixed_scale(VariableStrokeStyle::new(), 1_000.0)
+            .unwrap();
+
+        // the tangent ends lean toward the narrow side by `r * k`, where `k = (r_a - r_b) / d`
+        let [min_x, min_y, max_x, max_y] = bounds(&butt);
+        assert!((min_x - 0.2).abs() < 0.002 && (max_x - 10.1).abs() < 0.002);
+        let half_width = 2.0 * 0.99f64.sqrt();
+        assert!((min_y + half_width).abs() < 0.002 && (max_y - half_width).abs() < 0.002);
+
+        // square ends extend by the radius at each end
+        let [min_x, min_y, max_x, max_y] = bounds(&square);
+        assert!((min_x + 2.0).abs() < 0.002 && (max_x - 11.0).abs() < 0.002);
+        assert!((min_y + 2.0).abs() < 0.002 && (max_y - 2.0).abs() < 0.002);
+
+        assert!(butt.area() < round.area() && round.area() < square.area());
+    }
+
+    #[test]
+    fn custom_cap_matches_square() {
+        let path = [
+            StrokeVertex::new([0.0, 0.0], 4.0),
+            StrokeVertex::new([10.0, 0.0], 2.0),
+        ];
+        let square = VariableStrokeStyle::new()
+            .start_cap(VariableLineCap::Square)
+            .end_cap(VariableLineCap::Square);
+        // the style is `Copy`, a custom cap is a static template
+        let custom = square
+            .start_cap(VariableLineCap::Custom(&[[1.0, -1.0], [1.0, 1.0]]))
+            .end_cap(VariableLineCap::Custom(&[[1.0, -1.0], [1.0, 1.0]]));
+
+        assert_eq!(
+            path.variable_stroke_fixed_scale(custom, 1_000.0).unwrap(),
+            path.variable_stroke_fixed_scale(square, 1_000.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn constant_width_joins_match_static_stroke_area() {
+        let points = [[0.0f64, 0.0], [10.0, 0.0], [15.0, 8.0], [5.0, 12.0]];
+        let path = points.map(|point| StrokeVertex::new(point, 4.0));
+        for join in [LineJoin::Bevel, LineJoin::Miter(0.5), LineJoin::Miter(2.0)] {
+            let actual = path
+                .variable_stroke_fixed_scale(
+                    VariableStrokeStyle::new()
+                        .start_cap(VariableLineCap::Square)
+                        .end_cap(VariableLineCap::Butt)
+                        .line_join(join),
+                    1_000.0,
+                )
+                .unwrap();
+            let expected = points
+                .stroke_fixed_scale(
+                    StrokeStyle::new(4.0).start_cap(LineCap::Square).line_join(join),
+                    false,
+                    1_000.0,
+                )
+                .unwrap();
+
+            let delta = (actual.area() - expected.area()).abs();
+            assert!(delta < 0.01, "{join:?} area delta: {delta}");
+        }
+    }
+
+    #[test]
+    fn tapered_miter_meets_at_tangent_intersection() {
+        let path = [
+            StrokeVertex::new([0.0, 0.0], 2.0),
+            StrokeVertex::new([10.0, 0.0], 6.0),
+            StrokeVertex::new([10.0, 10.0], 2.0),
+        ];
+        let stroke = |join: LineJoin<f64>| {
+            path.variable_stroke_fixed_scale(VariableStrokeStyle::new().line_join(join), 1_000.0)
+                .unwrap()
+        };
+
+        // the outer tangents of radii 1 and 3 meet at (-5, 0), the second side is their mirror
+        // over x + y = 10, so the miter lies on that line
+        let k = 1.0 / 24.0f64.sqrt();
+        let x = (10.0 + 5.0 * k) / (1.0 - k);
+        let miter = [x, 10.0 - x];
+
+        let sharp = stroke(LineJoin::Miter(0.1));
+        let has_miter = sharp
+            .iter()
+            .flatten()
+            .flatten()
+            .any(|p| (p[0] - miter[0]).abs() < 0.002 && (p[1] - miter[1]).abs() < 0.002);
+        assert!(has_miter, "missing miter point {miter:?}");
+
+        let bevel = stroke(LineJoin::Bevel);
+        let [_, min_y, max_x, _] = bounds(&bevel);
+        assert!(max_x < 13.001 && min_y > -3.001);
+
+        let clipped = stroke(LineJoin::Miter(2.0));
+        assert!(bevel.area() < clipped.area() && clipped.area() < sharp.area());
+    }
+
+    #[test]
+    fn smooth_stroke_follows_the_curve() {
+        // a quarter of a circle with radius 50
+        let path: Vec<_> = (0..=8)
+            .map(|i| {
+                let a = i as f64 * core::f64::consts::PI / 16.0;
+                StrokeVertex::new([50.0 * a.cos(), 50.0 * a.sin()], 2.0)
+            })
+            .collect();
+        let exact = 0.5 * (51.0 * 51.0 - 49.0 * 49.0) * core::f64::consts::FRAC_PI_2 + core::f64::consts::PI;
+
+        let linear = path
+            .variable_stroke_fixed_scale(VariableStrokeStyle::new().round_angle(0.05), 1_000.0)
+            .unwrap();
+        let smooth = path
+            .variable_stroke_fixed_scale(
+                VariableStrokeStyle::new()
+                    .round_angle(0.05)
+                    .interpolation(StrokeInterpolation::CatmullRom)
+                    .tolerance(0.01),
+                1_000.0,
+            )
+            .unwrap();
+
+        let linear_delta = (linear.area() - exact).abs();
+        let smooth_delta = (smooth.area() - exact).abs();
+        assert!(
+            smooth_delta < 0.05 && linear_delta > 4.0 * smooth_delta,
+            "{linear_delta} {smooth_delta}"
+        );
+    }
+
+    #[test]
+    fn tapered_stroke_ends_in_points() {
+        let path = [
+            StrokeVertex::new([0.0, 0.0], 4.0),
+            StrokeVertex::new([100.0, 0.0], 4.0),
+        ];
+        let style = VariableStrokeStyle::new().taper(20.0, 20.0).tolerance(0.01);
+        let shapes = path.variable_stroke_fixed_scale(style, 1_000.0).unwrap();
+
+        let [min_x, min_y, max_x, max_y] = bounds(&shapes);
+        assert!(min_x > -0.01 && max_x < 100.01);
+        assert!((min_y + 2.0).abs() < 0.01 && (max_y - 2.0).abs() < 0.01);
+
+        // the middle and two tapers of `4 * u * (2 - u)`, which averages to 2/3 of the width
+        let expected = 4.0 * 60.0 + 2.0 * 4.0 * 20.0 * 2.0 / 3.0;
+        let delta = (shapes.area() - expected).abs();
+        assert!(delta < 0.5, "area delta: {delta}");
+    }
 }
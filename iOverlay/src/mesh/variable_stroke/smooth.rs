@@ -0,0 +1,427 @@
+use crate::mesh::variable_stroke::style::{StrokeInterpolation, StrokeVertex, VariableStrokeStyle};
+use alloc::vec::Vec;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+
+/// A centerline node: x, y and width.
+type Node = [f64; 3];
+
+/// Resamples paths along their interpolated centerline and width, and applies the tapers.
+pub(super) struct PathSmoother {
+    interpolation: StrokeInterpolation,
+    tolerance: f64,
+    start_taper: f64,
+    end_taper: f64,
+}
+
+impl PathSmoother {
+    /// Returns `None` when the style keeps the paths as they are.
+    pub(super) fn new<T: FloatNumber>(style: &VariableStrokeStyle<T>) -> Option<Self> {
+        let start_taper = style.start_taper.to_f64();
+        let end_taper = style.end_taper.to_f64();
+        let has_taper = start_taper > 0.0 || end_taper > 0.0;
+        if style.interpolation == StrokeInterpolation::Linear && !has_taper {
+            return None;
+        }
+
+        // a tolerance below a millionth of a unit only multiplies vertices
+        let tolerance = style.tolerance.to_f64();
+        let tolerance = if tolerance.is_finite() {
+            tolerance.max(1e-6)
+        } else {
+            0.25
+        };
+
+        Some(Self {
+            interpolation: style.interpolation,
+            tolerance,
+            start_taper: if start_taper.is_finite() { start_taper } else { 0.0 },
+            end_taper: if end_taper.is_finite() { end_taper } else { 0.0 },
+        })
+    }
+
+    pub(super) fn resample<P: FloatPointCompatible>(&self, path: &[StrokeVertex<P>]) -> Vec<StrokeVertex<P>> {
+        let mut nodes: Vec<Node> = path
+            .iter()
+            .map(|v| {
+                [
+                    v.point.x().to_f64(),
+                    v.point.y().to_f64(),
+                    v.width.to_f64().max(0.0),
+                ]
+            })
+            .collect();
+
+        if self.interpolation != StrokeInterpolation::Linear {
+            // the spline needs distinct neighbours
+            nodes.dedup_by(|b, a| a[0] == b[0] && a[1] == b[1]);
+            if nodes.len() > 1 {
+                nodes = self.spline(&nodes);
+            }
+        }
+
+        self.taper(&mut nodes);
+
+        nodes
+            .into_iter()
+            .map(|n| {
+                let point = P::from_xy(P::Scalar::from_float(n[0]), P::Scalar::from_float(n[1]));
+                StrokeVertex::new(point, P::Scalar::from_float(n[2]))
+            })
+            .collect()
+    }
+
+    fn spline(&self, nodes: &[Node]) -> Vec<Node> {
+        let n = nodes.len();
+        let slopes = if self.interpolation == StrokeInterpolation::MonotoneCubic {
+            monotone_slopes(nodes)
+        } else {
+            Vec::new()
+        };
+
+        let mut result = Vec::with_capacity(4 * n);
+        result.push(nodes[0]);
+        for i in 0..n - 1 {
+            let p1 = nodes[i];
+            let p2 = nodes[i + 1];
+            // the ends are continued by reflecting their neighbour
+            let p0 = if i > 0 { nodes[i - 1] } else { lerp(&p2, &p1, 2.0) };
+            let p3 = if i + 2 < n {
+                nodes[i + 2]
+            } else {
+                lerp(&p1, &p2, 2.0)
+            };
+
+            // centripetal knot intervals
+            let d0 = FloatNumber::sqrt(distance(&p0, &p1));
+            let d1 = FloatNumber::sqrt(distance(&p1, &p2));
+            let d2 = FloatNumber::sqrt(distance(&p2, &p3));
+
+            let mut b1 = [0.0; 3];
+            let mut b2 = [0.0; 3];
+            let (a0, a1, a2) = (d0 * d0, 2.0 * d0 * d0 + 3.0 * d0 * d1 + d1 * d1, d1 * d1);
+            let (c3, c2, c1) = (d2 * d2, 2.0 * d2 * d2 + 3.0 * d2 * d1 + d1 * d1, d1 * d1);
+            let k1 = 3.0 * d0 * (d0 + d1);
+            let k2 = 3.0 * d2 * (d2 + d1);
+            for j in 0..3 {
+                b1[j] = (a0 * p2[j] - a2 * p0[j] + a1 * p1[j]) / k1;
+                b2[j] = (c3 * p1[j] - c1 * p3[j] + c2 * p2[j]) / k2;
+            }
+
+            if !slopes.is_empty() {
+                let h = distance(&p1, &p2);
+                b1[2] = p1[2] + slopes[i] * h / 3.0;
+                b2[2] = p2[2] - slopes[i + 1] * h / 3.0;
+            }
+
+            let count = subdivision_count(&[p1, b1, b2, p2], self.tolerance);
+            for k in 1..=count {
+                let mut node = bezier(&p1, &b1, &b2, &p2, k as f64 / count as f64);
+                node[2] = node[2].max(0.0);
+                result.push(node);
+            }
+        }
+
+        result
+    }
+
+    fn taper(&self, nodes: &mut Vec<Node>) {
+        if self.start_taper <= 0.0 && self.end_taper <= 0.0 || nodes.len() < 2 {
+            return;
+        }
+
+        let mut lengths = Vec::with_capacity(nodes.len());
+        let mut total = 0.0;
+        lengths.push(0.0);
+        for pair in nodes.windows(2) {
+            total += distance(&pair[0], &pair[1]);
+            lengths.push(total);
+        }
+        if total <= 0.0 {
+            return;
+        }
+
+        let mut result = Vec::with_capacity(nodes.len());
+        let mut first = nodes[0];
+        first[2] *= self.factor(0.0, total);
+        result.push(first);
+
+        for (i, pair) in nodes.windows(2).enumerate() {
+            let (a, b) = (&pair[0], &pair[1]);
+            let (s0, s1) = (lengths[i], lengths[i + 1]);
+            if s1 <= s0 {
+                let mut node = *b;
+                node[2] *= self.factor(s1, total);
+                result.push(node);
+                continue;
+            }
+
+            // the taper profile is curved, so the parts inside it are split
+            let radius = 0.5 * a[2].max(b[2]);
+            let mut start = s0;
+            for end in [self.start_taper, total - self.end_taper, s1] {
+                if end <= start || end > s1 {
+                    continue;
+                }
+                let count = self.taper_count(start, end, radius, total);
+                for k in 1..=count {
+                    let s = start + (end - start) * k as f64 / count as f64;
+                    let mut node = lerp(a, b, (s - s0) / (s1 - s0));
+                    node[2] *= self.factor(s, total);
+                    result.push(node);
+                }
+                start = end;
+            }
+        }
+
+        *nodes = result;
+    }
+
+    /// The width factor at distance `s` along a path of length `total`.
+    fn factor(&self, s: f64, total: f64) -> f64 {
+        profile(s, self.start_taper) * profile(total - s, self.end_taper)
+    }
+
+    fn taper_count(&self, s0: f64, s1: f64, radius: f64, total: f64) -> usize {
+        let len = s1 - s0;
+        if len <= 0.0 || radius <= self.tolerance {
+            return 1;
+        }
+
+        // with `r * f(s / L)` and `|f''| = 2` a chord of length `ds` is off by `r * ds^2 / (4 * L^2)`,
+        // where overlapping tapers also add the `2 * f' * g'` term of their product
+        let in_start = s0 < self.start_taper;
+        let in_end = s1 > total - self.end_taper;
+        let mut curvature = 0.0;
+        if in_start {
+            curvature += 1.0 / (self.start_taper * self.start_taper);
+        }
+        if in_end {
+            curvature += 1.0 / (self.end_taper * self.end_taper);
+        }
+        if in_start && in_end {
+            curvature += 4.0 / (self.start_taper * self.end_taper);
+        }
+        if curvature == 0.0 {
+            return 1;
+        }
+        let step = 2.0 * FloatNumber::sqrt(self.tolerance / (radius * curvature));
+
+        ceil_count(len / step)
+    }
+}
+
+/// The ease-out taper profile `u * (2 - u)` at distance `s` from the tip of a taper of `length`.
+#[inline]
+fn profile(s: f64, length: f64) -> f64 {
+    if length <= 0.0 || s >= length {
+        return 1.0;
+    }
+    let u = (s / length).max(0.0);
+    u * (2.0 - u)
+}
+
+/// Width slopes over the chord length that keep every span monotone, as in Fritsch and Butland.
+fn monotone_slopes(nodes: &[Node]) -> Vec<f64> {
+    let n = nodes.len();
+    let h: Vec<f64> = nodes.windows(2).map(|p| distance(&p[0], &p[1])).collect();
+    let delta: Vec<f64> = nodes
+        .windows(2)
+        .zip(h.iter())
+        .map(|(p, h)| (p[1][2] - p[0][2]) / h)
+        .collect();
+
+    let mut slopes = Vec::with_capacity(n);
+    slopes.push(delta[0]);
+    for k in 1..n - 1 {
+        let (d0, d1) = (delta[k - 1], delta[k]);
+        if d0 * d1 <= 0.0 {
+            slopes.push(0.0);
+            continue;
+        }
+        let (h0, h1) = (h[k - 1], h[k]);
+        let w0 = 2.0 * h1 + h0;
+        let w1 = h1 + 2.0 * h0;
+        slopes.push((w0 + w1) / (w0 / d0 + w1 / d1));
+    }
+    slopes.push(delta[n - 2]);
+
+    slopes
+}
+
+/// Number of chords that keep a cubic Bezier within `tolerance`, from the bound
+/// `|B''| / 8 / n^2` with `|B''| <= 6 * max(|P0 - 2 P1 + P2|, |P1 - 2 P2 + P3|)`.
+/// The width counts as a radius.
+fn subdivision_count(p: &[Node; 4], tolerance: f64) -> usize {
+    let mut max_sqr: f64 = 0.0;
+    for i in 0..2 {
+        let x = p[i][0] - 2.0 * p[i + 1][0] + p[i + 2][0];
+        let y = p[i][1] - 2.0 * p[i + 1][1] + p[i + 2][1];
+        let r = 0.5 * (p[i][2] - 2.0 * p[i + 1][2] + p[i + 2][2]);
+        max_sqr = max_sqr.max(x * x + y * y + r * r);
+    }
+    ceil_count(FloatNumber::sqrt(0.75 * FloatNumber::sqrt(max_sqr) / tolerance))
+}
+
+/// Rounds a chord count up, within `1..=1024`.
+#[inline]
+fn ceil_count(value: f64) -> usize {
+    // `as` saturates and maps NaN to zero
+    let count = value as usize;
+    let count = if (count as f64) < value { count + 1 } else { count };
+    count.clamp(1, 1024)
+}
+
+#[inline]
+fn bezier(p0: &Node, p1: &Node, p2: &Node, p3: &Node, t: f64) -> Node {
+    let s = 1.0 - t;
+    let (w0, w1, w2, w3) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+    [
+        w0 * p0[0] + w1 * p1[0] + w2 * p2[0] + w3 * p3[0],
+        w0 * p0[1] + w1 * p1[1] + w2 * p2[1] + w3 * p3[1],
+        w0 * p0[2] + w1 * p1[2] + w2 * p2[2] + w3 * p3[2],
+    ]
+}
+
+#[inline]
+fn lerp(a: &Node, b: &Node, t: f64) -> Node {
+    [
+        a[0] + t * (b[0] - a[0]),
+        a[1] + t * (b[1] - a[1]),
+        a[2] + t * (b[2] - a[2]),
+    ]
+}
+
+#[inline]
+fn distance(a: &Node, b: &Node) -> f64 {
+    let dx = b[0] - a[0];
+    let dy = b[1] - a[1];
+    FloatNumber::sqrt(dx * dx + dy * dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PathSmoother;
+    use crate::mesh::variable_stroke::style::{StrokeInterpolation, StrokeVertex, VariableStrokeStyle};
+    use alloc::vec::Vec;
+
+    fn resample(style: VariableStrokeStyle<f64>, path: &[[f64; 3]]) -> Vec<[f64; 3]> {
+        let path: Vec<_> = path
+            .iter()
+            .map(|n| StrokeVertex::new([n[0], n[1]], n[2]))
+            .collect();
+        PathSmoother::new(&style)
+            .unwrap()
+            .resample(&path)
+            .iter()
+            .map(|v| [v.point[0], v.point[1], v.width])
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_is_kept() {
+        assert!(PathSmoother::new(&VariableStrokeStyle::<f64>::new()).is_none());
+    }
+
+    #[test]
+    fn test_spline_passes_through_vertices() {
+        let path = [
+            [0.0, 0.0, 2.0],
+            [10.0, 5.0, 6.0],
+            [20.0, 0.0, 2.0],
+            [30.0, 5.0, 4.0],
+        ];
+        for interpolation in [
+            StrokeInterpolation::CatmullRom,
+            StrokeInterpolation::MonotoneCubic,
+        ] {
+            let style = VariableStrokeStyle::new()
+                .interpolation(interpolation)
+                .tolerance(0.01);
+            let nodes = resample(style, &path);
+            assert!(nodes.len() > 3 * path.len());
+            for vertex in path.iter() {
+                assert!(nodes.iter().any(|n| {
+                    (n[0] - vertex[0]).abs() < 1e-9
+                        && (n[1] - vertex[1]).abs() < 1e-9
+                        && (n[2] - vertex[2]).abs() < 1e-9
+                }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_spline_on_a_line_stays_on_it() {
+        let path = [[0.0, 0.0, 1.0], [10.0, 0.0, 1.0], [20.0, 0.0, 1.0]];
+        let style = VariableStrokeStyle::new().interpolation(StrokeInterpolation::CatmullRom);
+        let nodes = resample(style, &path);
+        assert!(
+            nodes
+                .iter()
+                .all(|n| n[1].abs() < 1e-9 && (n[2] - 1.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn test_monotone_width_does_not_overshoot() {
+        // a pressure jump: Catmull-Rom rings around it, the monotone cubic does not
+        let path = [
+            [0.0, 0.0, 1.0],
+            [10.0, 0.0, 1.0],
+            [11.0, 0.0, 8.0],
+            [21.0, 0.0, 8.0],
+        ];
+        let catmull_rom = resample(
+            VariableStrokeStyle::new().interpolation(StrokeInterpolation::CatmullRom),
+            &path,
+        );
+        let monotone = resample(
+            VariableStrokeStyle::new().interpolation(StrokeInterpolation::MonotoneCubic),
+            &path,
+        );
+
+        assert!(catmull_rom.iter().any(|n| n[2] > 8.0 + 1e-6 || n[2] < 1.0 - 1e-6));
+        assert!(monotone.iter().all(|n| n[2] >= 1.0 - 1e-9 && n[2] <= 8.0 + 1e-9));
+        assert!(monotone.windows(2).all(|p| p[1][2] >= p[0][2] - 1e-9));
+    }
+
+    #[test]
+    fn test_taper() {
+        let path = [[0.0, 0.0, 4.0], [100.0, 0.0, 4.0]];
+        let nodes = resample(
+            VariableStrokeStyle::new().taper(20.0, 10.0).tolerance(0.01),
+            &path,
+        );
+
+        assert_eq!(nodes.first().unwrap()[2], 0.0);
+        assert_eq!(nodes.last().unwrap()[2], 0.0);
+        for n in nodes.iter() {
+            let expected = if n[0] < 20.0 {
+                let u = n[0] / 20.0;
+                4.0 * u * (2.0 - u)
+            } else if n[0] > 90.0 {
+                let u = (100.0 - n[0]) / 10.0;
+                4.0 * u * (2.0 - u)
+            } else {
+                4.0
+            };
+            assert!((n[2] - expected).abs() < 1e-9);
+        }
+        // the curved profile is split, the middle is not
+        assert!(nodes.iter().filter(|n| n[0] < 20.0).count() > 2);
+        assert_eq!(nodes.iter().filter(|n| n[0] > 20.0 && n[0] < 90.0).count(), 0);
+    }
+
+    #[test]
+    fn test_overlapping_tapers() {
+        let path = [[0.0, 0.0, 4.0], [10.0, 0.0, 4.0]];
+        let nodes = resample(
+            VariableStrokeStyle::new().taper(20.0, 20.0).tolerance(0.01),
+            &path,
+        );
+        let max = nodes.iter().map(|n| n[2]).fold(0.0, f64::max);
+        // both factors are 0.4375 in the middle
+        assert!((max - 4.0 * 0.4375 * 0.4375).abs() < 0.02);
+        assert!(max < 4.0 * 0.4375 * 0.4375 + 1e-9);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::mesh::style::LineJoin;
 use core::f64::consts::PI;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -21,11 +22,71 @@ impl<P: FloatPointCompatible> StrokeVertex<P> {
     }
 }
 
-/// Round-only style for variable-width strokes.
+/// How the centerline and the width of a variable-width stroke run between its vertices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrokeInterpolation {
+    /// Straight sections with a linear width change. This is the default.
+    #[default]
+    Linear,
+    /// The centerline and the width follow a centripetal Catmull-Rom spline through the vertices.
+    CatmullRom,
+    /// The centerline follows a centripetal Catmull-Rom spline and the width a monotone cubic,
+    /// which never overshoots the widths of the neighbouring vertices.
+    MonotoneCubic,
+}
+
+/// The endpoint style of a variable-width stroke, the same as [`LineCap`](crate::mesh::style::LineCap)
+/// with the custom template points given as a static slice, so the style stays `Copy`.
+/// The template points are plain ratios and use `f64` for every scalar type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariableLineCap<T: FloatNumber> {
+    /// A line with a squared-off end.
+    Butt,
+    /// A line with a rounded end. Takes a parameter `Angle` in radians.
+    Round(T),
+    /// A line with a squared-off end, extended by half the line width.
+    Square,
+    /// A custom end with template points `[x, y]`, where `x` runs along the path direction and
+    /// `y` across it, both in units of half the line width.
+    Custom(&'static [[f64; 2]]),
+}
+
+/// Style for variable-width strokes.
+///
+/// Joins take the same [`LineJoin`] as [`StrokeStyle`](crate::mesh::style::StrokeStyle) and caps
+/// a [`VariableLineCap`], the default is round everywhere. Miter and bevel corners are built on the
+/// tangent lines of the vertex circles, so the width changes through a corner stay exact.
 #[derive(Debug, Clone, Copy)]
 pub struct VariableStrokeStyle<T: FloatNumber> {
     /// Maximum angular step used to approximate round joins and caps, in radians.
     pub round_angle: T,
+    /// The cap style at the start of every path.
+    pub start_cap: VariableLineCap<T>,
+    /// The cap style at the end of every path.
+    pub end_cap: VariableLineCap<T>,
+    /// The join style where two sections meet.
+    pub join: LineJoin<T>,
+    /// How the centerline and the width run between vertices.
+    pub interpolation: StrokeInterpolation,
+    /// The largest distance between a smooth or tapered stroke and the polyline it is resampled
+    /// to, in path units.
+    pub tolerance: T,
+    /// The length from the start of every path over which the width grows from zero.
+    pub start_taper: T,
+    /// The length before the end of every path over which the width shrinks to zero.
+    pub end_taper: T,
+}
+
+impl<T: FloatNumber> VariableLineCap<T> {
+    #[inline]
+    fn normalize(self) -> Self {
+        if let VariableLineCap::Round(angle) = self {
+            let a = angle.to_f64().clamp(0.01 * PI, 0.25 * PI);
+            VariableLineCap::Round(T::from_float(a))
+        } else {
+            self
+        }
+    }
 }
 
 impl<T: FloatNumber> VariableStrokeStyle<T> {
@@ -34,9 +95,65 @@ impl<T: FloatNumber> VariableStrokeStyle<T> {
         Self::default()
     }
 
+    /// Sets the angular step of every round part, including round caps and joins already set.
     #[inline]
     pub fn round_angle(mut self, angle: T) -> Self {
-        self.round_angle = Self::normalize_angle(angle);
+        let angle = Self::normalize_angle(angle);
+        self.round_angle = angle;
+        if let VariableLineCap::Round(value) = &mut self.start_cap {
+            *value = angle;
+        }
+        if let VariableLineCap::Round(value) = &mut self.end_cap {
+            *value = angle;
+        }
+        if let LineJoin::Round(value) = &mut self.join {
+            *value = angle;
+        }
+        self
+    }
+
+    /// Sets the cap style at the start of every path.
+    #[inline]
+    pub fn start_cap(mut self, cap: VariableLineCap<T>) -> Self {
+        self.start_cap = cap.normalize();
+        self
+    }
+
+    /// Sets the cap style at the end of every path.
+    #[inline]
+    pub fn end_cap(mut self, cap: VariableLineCap<T>) -> Self {
+        self.end_cap = cap.normalize();
+        self
+    }
+
+    /// Sets the join style.
+    #[inline]
+    pub fn line_join(mut self, join: LineJoin<T>) -> Self {
+        self.join = join.normalize();
+        self
+    }
+
+    /// Sets how the centerline and the width run between vertices.
+    #[inline]
+    pub fn interpolation(mut self, interpolation: StrokeInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the resampling tolerance of smooth and tapered strokes.
+    #[inline]
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the lengths over which the width grows from zero at the start of every path and
+    /// shrinks to zero at its end. The width follows an ease-out curve, `u * (2 - u)` of the
+    /// distance from the tip. On a path shorter than both tapers, they overlap.
+    #[inline]
+    pub fn taper(mut self, start: T, end: T) -> Self {
+        self.start_taper = start.max(T::ZERO);
+        self.end_taper = end.max(T::ZERO);
         self
     }
 
@@ -44,6 +161,10 @@ impl<T: FloatNumber> VariableStrokeStyle<T> {
     pub(super) fn normalized(self) -> Self {
         Self {
             round_angle: Self::normalize_angle(self.round_angle),
+            start_cap: self.start_cap.normalize(),
+            end_cap: self.end_cap.normalize(),
+            join: self.join.normalize(),
+            ..self
         }
     }
 
@@ -56,8 +177,16 @@ impl<T: FloatNumber> VariableStrokeStyle<T> {
 
 impl<T: FloatNumber> Default for VariableStrokeStyle<T> {
     fn default() -> Self {
+        let angle = T::from_float(0.1);
         Self {
-            round_angle: T::from_float(0.1),
+            round_angle: angle,
+            start_cap: VariableLineCap::Round(angle),
+            end_cap: VariableLineCap::Round(angle),
+            join: LineJoin::Round(angle),
+            interpolation: StrokeInterpolation::Linear,
+            tolerance: T::from_float(0.25),
+            start_taper: T::ZERO,
+            end_taper: T::ZERO,
         }
     }
 }
@@ -55,6 +55,17 @@ impl<C: WindingCount, I: IntNumber, D: OverlayEdgeData<C>> Segment<C, I, D> {
     }
 }
 
+impl<C: Copy, I: IntNumber, D> Segment<C, I, D> {
+    #[inline(always)]
+    pub(crate) fn without_data(&self) -> Segment<C, I> {
+        Segment {
+            x_segment: self.x_segment,
+            count: self.count,
+            data: (),
+        }
+    }
+}
+
 impl<I: IntNumber, D: OverlayEdgeData<ShapeCountBoolean>> Segment<ShapeCountBoolean, I, D> {
     #[inline]
     pub(crate) fn try_ab_and_data(
@@ -1,13 +1,15 @@
 use crate::segm::segment::Segment;
+use alloc::vec::Vec;
 use i_float::int::number::int::IntNumber;
 use i_key_sort::sort::key::SortKey;
 use i_key_sort::sort::two_keys_cmp::TwoKeysAndCmpSort;
 
-pub(crate) trait ShapeSegmentsSort {
+pub(crate) trait ShapeSegmentsSort<T> {
     fn sort_by_ab(&mut self, parallel: bool);
+    fn sort_by_ab_and_buffer(&mut self, reusable_buffer: &mut Vec<T>);
 }
 
-impl<I: IntNumber + SortKey, C: Send + Sync + Copy, D: Send + Sync + Copy> ShapeSegmentsSort
+impl<I: IntNumber + SortKey, C: Send + Sync + Copy, D: Send + Sync + Copy> ShapeSegmentsSort<Segment<C, I, D>>
     for [Segment<C, I, D>]
 {
     #[inline]
@@ -19,4 +21,15 @@ impl<I: IntNumber + SortKey, C: Send + Sync + Copy, D: Send + Sync + Copy> Shape
             |s0, s1| s0.x_segment.b.cmp(&s1.x_segment.b),
         )
     }
+
+    #[inline]
+    fn sort_by_ab_and_buffer(&mut self, reusable_buffer: &mut Vec<Segment<C, I, D>>) {
+        self.sort_by_two_keys_then_by_and_buffer(
+            false,
+            reusable_buffer,
+            |s| s.x_segment.a.x,
+            |s| s.x_segment.a.y,
+            |s0, s1| s0.x_segment.b.cmp(&s1.x_segment.b),
+        )
+    }
 }
@@ -1,7 +1,7 @@
+use crate::core::memory::MemoryGuard;
 use crate::geom::line_range::LineRange;
 use crate::geom::x_segment::XSegment;
 use crate::split::fragment::Fragment;
-use alloc::vec;
 use alloc::vec::Vec;
 use i_float::int::number::int::IntNumber;
 use i_float::int::number::uint::UIntNumber;
@@ -21,36 +21,57 @@ pub(super) struct FragmentBuffer<I: IntNumber> {
 }
 
 impl<I: IntNumber> FragmentBuffer<I> {
+    /// Creates a buffer with one group per grid column. If `memory` refuses the allocation, the
+    /// buffer has no groups and must not be used.
     #[inline]
-    pub(super) fn new(layout: GridLayout<I>) -> Self {
+    pub(super) fn new(layout: GridLayout<I>, memory: &mut MemoryGuard) -> Self {
         let n = layout.index(layout.max_x) + 1;
+        let mut groups = Vec::new();
+        let mut on_border = Vec::new();
+        if memory.resize(&mut groups, n, Vec::new()) {
+            memory.reserve(&mut on_border, 64);
+        }
         Self {
             layout,
-            groups: vec![Vec::new(); n],
-            on_border: Vec::with_capacity(64),
+            groups,
+            on_border,
         }
     }
 
-    pub(super) fn init_fragment_buffer<It>(&mut self, iter: It)
+    /// Reserves every group for the fragments `iter` will produce. Returns `false` if `memory`
+    /// refuses an allocation.
+    pub(super) fn init_fragment_buffer<It>(&mut self, iter: It, memory: &mut MemoryGuard) -> bool
     where
         It: Iterator<Item = XSegment<I>>,
     {
-        let mut counts = vec![0; self.groups.len()];
+        let mut counts = Vec::new();
+        if !memory.resize(&mut counts, self.groups.len(), 0) {
+            return false;
+        }
+
+        let mut vertical_count = 0;
         for s in iter {
             let i0 = self.layout.index(s.a.x);
             if s.a.x < s.b.x {
+                // the segment puts a fragment into every column from i0 to i1 inclusive; the fallible
+                // path relies on this so that `add_segment` never grows a group outside the budget
                 let i1 = self.layout.index(s.b.x - I::ONE);
-                for count in counts.iter_mut().take(i1).skip(i0) {
+                for count in counts.iter_mut().take(i1 + 1).skip(i0) {
                     *count += 1;
                 }
             } else {
                 counts[i0] += 1;
+                vertical_count += 1;
             }
         }
 
         for (i, group) in self.groups.iter_mut().enumerate() {
-            group.reserve(counts[i]);
+            if !memory.reserve(group, counts[i]) {
+                return false;
+            }
         }
+
+        memory.preallocate(&mut self.on_border, vertical_count)
     }
 
     #[inline]
@@ -300,14 +321,40 @@ impl<I: IntNumber> GridLayout<I> {
 mod tests {
     #![allow(clippy::useless_vec)]
 
+    use crate::core::memory::MemoryGuard;
     use crate::geom::x_segment::XSegment;
-    use crate::split::grid_layout::vec;
     use crate::split::grid_layout::{FragmentBuffer, GridLayout};
+    use alloc::vec;
+    use alloc::vec::Vec;
     use i_float::int::point::IntPoint;
     use i_float::int::rect::IntRect;
     use i_float::triangle::Triangle;
     use rand::RngExt;
 
+    #[test]
+    fn test_reserve_last_column() {
+        let layout = GridLayout {
+            min_x: 0,
+            max_x: 12,
+            power: 1,
+        };
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
+
+        // x from 1 to 6 covers the columns 0, 1 and 2
+        let segment = XSegment {
+            a: IntPoint { x: 1, y: 0 },
+            b: IntPoint { x: 6, y: 3 },
+        };
+        buffer.init_fragment_buffer([segment].into_iter(), &mut MemoryGuard::default());
+
+        assert!(buffer.groups[..3].iter().all(|group| group.capacity() >= 1));
+        assert!(buffer.groups[3..].iter().all(|group| group.capacity() == 0));
+
+        buffer.add_segment(0, segment);
+        let lens: Vec<usize> = buffer.groups.iter().map(|group| group.len()).collect();
+        assert_eq!(lens[..4], [1, 1, 1, 0]);
+    }
+
     #[test]
     fn test_0() {
         let layout = GridLayout {
@@ -315,14 +362,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 0 },
             b: IntPoint { x: 6, y: 3 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -369,14 +416,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 3 },
             b: IntPoint { x: 6, y: 0 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -423,14 +470,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 1 },
             b: IntPoint { x: 6, y: 4 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -477,14 +524,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 4 },
             b: IntPoint { x: 6, y: 1 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -531,14 +578,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: -1 },
             b: IntPoint { x: 6, y: 2 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -585,14 +632,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 2 },
             b: IntPoint { x: 6, y: -1 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -639,14 +686,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 0 },
             b: IntPoint { x: 6, y: 1 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -693,14 +740,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 1 },
             b: IntPoint { x: 6, y: 0 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -747,14 +794,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 0, y: 0 },
             b: IntPoint { x: 5, y: 3 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -801,14 +848,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 0 },
             b: IntPoint { x: 4, y: 5 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -842,14 +889,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 0, y: 0 },
             b: IntPoint { x: 6, y: 6 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -892,14 +939,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 1 },
             b: IntPoint { x: 5, y: 5 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -942,14 +989,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 5 },
             b: IntPoint { x: 5, y: 1 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -992,14 +1039,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 0, y: 0 },
             b: IntPoint { x: 7, y: 0 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1051,14 +1098,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 1 },
             b: IntPoint { x: 1, y: 9 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1083,14 +1130,14 @@ mod tests {
             max_x: 12,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: 1, y: 9 },
             b: IntPoint { x: 1, y: 1 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1116,7 +1163,7 @@ mod tests {
             power: 10,
         };
 
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint {
@@ -1129,7 +1176,7 @@ mod tests {
             },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1154,14 +1201,14 @@ mod tests {
             max_x: 10,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: -6, y: 0 },
             b: IntPoint { x: 4, y: 2 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1228,14 +1275,14 @@ mod tests {
             max_x: 10,
             power: 1,
         };
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: -8, y: -10 },
             b: IntPoint { x: -8, y: -9 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1261,14 +1308,14 @@ mod tests {
             power: 10,
         };
 
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: -83143, y: 65289 },
             b: IntPoint { x: 45253, y: -76778 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1285,14 +1332,14 @@ mod tests {
             power: 10,
         };
 
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let segment = XSegment {
             a: IntPoint { x: -78454, y: -40819 },
             b: IntPoint { x: 47599, y: -57780 },
         };
         let segments = vec![segment];
-        buffer.init_fragment_buffer(segments.iter().copied());
+        buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
         buffer.add_segment(0, segment);
 
@@ -1314,7 +1361,7 @@ mod tests {
             power: 1,
         };
 
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let mut a = IntPoint::new(min_x, min_y - 1);
 
@@ -1327,7 +1374,7 @@ mod tests {
                 let segment = XSegment { a, b };
 
                 let segments = vec![segment];
-                buffer.init_fragment_buffer(segments.iter().copied());
+                buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
                 buffer.add_segment(0, segment);
 
@@ -1353,7 +1400,7 @@ mod tests {
             power: 2,
         };
 
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let mut a = IntPoint::new(min_x, min_y - 1);
 
@@ -1366,7 +1413,7 @@ mod tests {
                 let segment = XSegment { a, b };
 
                 let segments = vec![segment];
-                buffer.init_fragment_buffer(segments.iter().copied());
+                buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
                 buffer.add_segment(0, segment);
 
@@ -1392,7 +1439,7 @@ mod tests {
             power: 3,
         };
 
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let mut a = IntPoint::new(min_x, min_y - 1);
 
@@ -1405,7 +1452,7 @@ mod tests {
                 let segment = XSegment { a, b };
 
                 let segments = vec![segment];
-                buffer.init_fragment_buffer(segments.iter().copied());
+                buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
                 buffer.add_segment(0, segment);
 
@@ -1431,7 +1478,7 @@ mod tests {
             power: 4,
         };
 
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let mut a = IntPoint::new(min_x, min_y - 1);
 
@@ -1444,7 +1491,7 @@ mod tests {
                 let segment = XSegment { a, b };
 
                 let segments = vec![segment];
-                buffer.init_fragment_buffer(segments.iter().copied());
+                buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
                 buffer.add_segment(0, segment);
 
@@ -1466,7 +1513,7 @@ mod tests {
         };
 
         let range = layout.min_x..=layout.max_x;
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let mut rng = rand::rng();
 
@@ -1489,7 +1536,7 @@ mod tests {
             };
 
             let segments = vec![segment];
-            buffer.init_fragment_buffer(segments.iter().copied());
+            buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
             buffer.add_segment(0, segment);
 
@@ -1510,7 +1557,7 @@ mod tests {
         };
 
         let range = layout.min_x..=layout.max_x;
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut MemoryGuard::default());
 
         let mut rng = rand::rng();
 
@@ -1530,7 +1577,7 @@ mod tests {
             };
 
             let segments = vec![segment];
-            buffer.init_fragment_buffer(segments.iter().copied());
+            buffer.init_fragment_buffer(segments.iter().copied(), &mut MemoryGuard::default());
 
             buffer.add_segment(0, segment);
 
@@ -8,3 +8,4 @@ mod snap_radius;
 mod solver_fragment;
 mod solver_list;
 mod solver_tree;
+pub(crate) mod source;
@@ -5,11 +5,18 @@ use i_float::int::number::wide_int::WideIntNumber;
 pub(super) struct SnapRadius {
     current: usize,
     step: usize,
+    escalations: usize,
 }
 
 impl SnapRadius {
     pub(super) fn increment(&mut self) {
         self.current = 60.min(self.current + self.step);
+        self.escalations += 1;
+    }
+
+    /// Number of increments so far.
+    pub(super) fn escalations(&self) -> usize {
+        self.escalations
     }
 
     pub(super) fn radius<I: IntNumber>(&self) -> I::Wide {
@@ -22,6 +29,7 @@ impl Solver {
         SnapRadius {
             current: self.precision.start,
             step: self.precision.progression,
+            escalations: 0,
         }
     }
 }
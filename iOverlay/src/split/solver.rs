@@ -1,6 +1,9 @@
+use crate::core::control::ProgressTracker;
 use crate::core::edge_data::{EdgeDataSplit, OverlayEdgeData};
 use crate::core::integer::OverlayInt;
-use crate::core::solver::Solver;
+use crate::core::memory::MemoryGuard;
+use crate::core::solver::{Solver, Strategy};
+use crate::core::stats::SplitStats;
 use crate::geom::x_segment::XSegment;
 use crate::segm::merge::ShapeSegmentsMerge;
 use crate::segm::segment::Segment;
@@ -8,17 +11,24 @@ use crate::segm::sort::ShapeSegmentsSort;
 use crate::segm::winding::WindingCount;
 use crate::split::cross_solver::{CrossSolver, CrossType, EndMask};
 use crate::split::line_mark::{LineMark, SortMarkByIndexAndPoint};
+use crate::split::snap_radius::SnapRadius;
 use alloc::vec::Vec;
 use i_float::int::number::int::IntNumber;
 
 pub(crate) struct SplitSolver<I: IntNumber> {
     pub(super) marks: Vec<LineMark<I>>,
+    pub(crate) memory: MemoryGuard,
+    pub(crate) stats: SplitStats,
 }
 
 impl<I: IntNumber> SplitSolver<I> {
     #[inline(always)]
     pub(crate) fn new() -> Self {
-        Self { marks: Vec::new() }
+        Self {
+            marks: Vec::new(),
+            memory: MemoryGuard::default(),
+            stats: SplitStats::default(),
+        }
     }
 }
 
@@ -43,17 +53,33 @@ where
         solver: &Solver,
         store: &mut D::Store,
     ) -> bool {
+        self.split_segments_with_progress(segments, solver, store, &mut ProgressTracker::none())
+    }
+
+    /// Same as [`Self::split_segments_with_store`], but polls `progress` in the split loops.
+    /// A cancelled split leaves `segments` valid but not fully split.
+    #[inline]
+    pub(crate) fn split_segments_with_progress<C: WindingCount, D: OverlayEdgeData<C>>(
+        &mut self,
+        segments: &mut Vec<Segment<C, I, D>>,
+        solver: &Solver,
+        store: &mut D::Store,
+        progress: &mut ProgressTracker,
+    ) -> bool {
+        self.stats = SplitStats::default();
         if segments.is_empty() {
             return false;
         }
 
-        segments.sort_by_ab(solver.is_parallel_sort_allowed());
+        if !self.sort_segments(segments, solver) {
+            return false;
+        }
         let any_merged = segments.merge_if_needed_with_store(store);
         if segments.is_empty() {
             return true;
         }
 
-        let any_intersection = self.split(segments, solver, store);
+        let any_intersection = self.split(segments, solver, store, progress);
         any_merged | any_intersection
     }
 
@@ -63,20 +89,41 @@ where
         segments: &mut Vec<Segment<C, I, D>>,
         solver: &Solver,
         store: &mut D::Store,
+        progress: &mut ProgressTracker,
     ) -> bool {
         let is_list = solver.is_list_split(segments);
         let snap_radius = solver.snap_radius();
         if is_list {
-            return self.list_split(snap_radius, segments, solver, store);
+            return self.list_split(snap_radius, segments, solver, store, progress);
         }
 
         let is_fragmentation = solver.is_fragmentation_required(segments);
 
         if is_fragmentation {
-            self.fragment_split(snap_radius, segments, solver, store)
+            self.fragment_split(snap_radius, segments, solver, store, progress)
         } else {
-            self.tree_split(snap_radius, segments, solver, store)
+            self.tree_split(snap_radius, segments, solver, store, progress)
+        }
+    }
+
+    /// Sorts segments by their ends. A fallible guard provides the sort scratch up front.
+    #[inline]
+    fn sort_segments<C: WindingCount, D: OverlayEdgeData<C>>(
+        &mut self,
+        segments: &mut [Segment<C, I, D>],
+        solver: &Solver,
+    ) -> bool {
+        if !self.memory.is_fallible() {
+            segments.sort_by_ab(solver.is_parallel_sort_allowed());
+            return true;
+        }
+
+        let mut buffer = Vec::new();
+        if !self.memory.reserve(&mut buffer, segments.len()) {
+            return false;
         }
+        segments.sort_by_ab_and_buffer(&mut buffer);
+        true
     }
 
     pub(super) fn cross(
@@ -161,12 +208,18 @@ where
         reusable_buffer: &mut Vec<LineMark<I>>,
         solver: &Solver,
         store: &mut D::Store,
-    ) {
+    ) -> bool {
+        if !self.memory.preallocate(reusable_buffer, self.marks.len()) {
+            return false;
+        }
         self.marks
             .sort_by_index_and_point(solver.is_parallel_sort_allowed(), reusable_buffer);
         self.marks.dedup();
+        self.stats.intersections += self.marks.len();
 
-        segments.reserve(self.marks.len());
+        if !self.memory.reserve(segments, self.marks.len()) {
+            return false;
+        }
 
         // split segments
 
@@ -246,8 +299,18 @@ where
             ));
         }
 
-        segments.sort_by_ab(solver.is_parallel_sort_allowed());
+        if !self.sort_segments(segments, solver) {
+            return false;
+        }
         segments.merge_if_needed_with_store(store);
+        true
+    }
+
+    /// Records the strategy and snap radius of a starting split pass.
+    #[inline]
+    pub(super) fn begin_pass(&mut self, strategy: Strategy, snap_radius: &SnapRadius) {
+        self.stats.strategy = Some(strategy);
+        self.stats.snap_escalations = snap_radius.escalations();
     }
 
     #[inline]
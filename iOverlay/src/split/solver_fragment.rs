@@ -1,6 +1,8 @@
+use crate::core::control::{OperationPhase, ProgressTracker};
 use crate::core::edge_data::OverlayEdgeData;
 use crate::core::integer::OverlayInt;
-use crate::core::solver::Solver;
+use crate::core::memory::MemoryGuard;
+use crate::core::solver::{Solver, Strategy};
 use crate::segm::segment::Segment;
 use crate::segm::winding::WindingCount;
 use crate::split::cross_solver::{CrossSolver, CrossType, EndMask};
@@ -21,16 +23,20 @@ where
         segments: &mut Vec<Segment<C, I, D>>,
         solver: &Solver,
         store: &mut D::Store,
+        progress: &mut ProgressTracker,
     ) -> bool {
         let layout =
             if let Some(layout) = GridLayout::new(segments.iter().map(|it| it.x_segment), segments.len()) {
                 layout
             } else {
-                return self.tree_split(snap_radius, segments, solver, store);
+                return self.tree_split(snap_radius, segments, solver, store, progress);
             };
 
         let mut reusable_buffer = Vec::new();
-        let mut buffer = FragmentBuffer::new(layout);
+        let mut buffer = FragmentBuffer::new(layout, &mut self.memory);
+        if self.memory.is_failed() {
+            return false;
+        }
 
         let mut need_to_fix = true;
         let mut any_intersection = false;
@@ -39,13 +45,22 @@ where
 
         while need_to_fix && segments.len() > 2 {
             self.marks.clear();
+            self.begin_pass(Strategy::Frag, &snap_radius);
 
-            buffer.init_fragment_buffer(segments.iter().map(|it| it.x_segment));
+            if !buffer.init_fragment_buffer(segments.iter().map(|it| it.x_segment), &mut self.memory) {
+                return any_intersection;
+            }
             for (i, segment) in segments.iter().enumerate() {
                 buffer.add_segment(i, segment.x_segment);
             }
 
-            need_to_fix = self.process(snap_radius.radius::<I>(), &mut buffer, solver);
+            if !progress.report(OperationPhase::Split, 0, segments.len()) {
+                return any_intersection;
+            }
+            need_to_fix = self.process(snap_radius.radius::<I>(), &mut buffer, solver, progress);
+            if self.memory.is_failed() || progress.is_cancelled() {
+                return any_intersection;
+            }
 
             #[cfg(debug_assertions)]
             debug_assert!(buffer.is_on_border_sorted());
@@ -59,8 +74,10 @@ where
                 }
 
                 let index = buffer.layout.index(x);
-                if let Some(fragments) = buffer.groups.get(index) {
-                    self.on_border_split(x, fragments, &mut buffer.on_border[j0..j]);
+                if let Some(fragments) = buffer.groups.get(index)
+                    && !self.on_border_split(x, fragments, &mut buffer.on_border[j0..j])
+                {
+                    return any_intersection;
                 }
             }
 
@@ -71,7 +88,9 @@ where
             any_intersection = true;
             buffer.clear();
 
-            self.apply(segments, &mut reusable_buffer, solver, store);
+            if !self.apply(segments, &mut reusable_buffer, solver, store) {
+                return true;
+            }
 
             snap_radius.increment();
         }
@@ -80,33 +99,59 @@ where
     }
 
     #[inline]
-    fn process(&mut self, radius: I::Wide, buffer: &mut FragmentBuffer<I>, _solver: &Solver) -> bool {
+    fn process(
+        &mut self,
+        radius: I::Wide,
+        buffer: &mut FragmentBuffer<I>,
+        _solver: &Solver,
+        progress: &mut ProgressTracker,
+    ) -> bool {
         #[cfg(feature = "allow_multithreading")]
         {
             if _solver.multithreading.is_some() {
-                return self.parallel_split(radius, buffer);
+                return self.parallel_split(radius, buffer, progress);
             }
         }
 
-        self.serial_split(radius, buffer)
+        self.serial_split(radius, buffer, progress)
     }
 
     #[inline]
-    fn serial_split(&mut self, radius: I::Wide, buffer: &mut FragmentBuffer<I>) -> bool {
+    fn serial_split(
+        &mut self,
+        radius: I::Wide,
+        buffer: &mut FragmentBuffer<I>,
+        progress: &mut ProgressTracker,
+    ) -> bool {
         let mut is_any_round = false;
+        let total = buffer.groups.iter().map(Vec::len).sum();
+        let mut done = 0;
         for group in buffer.groups.iter_mut() {
             if group.is_empty() {
                 continue;
             }
-            let any_round = Self::bin_split(radius, group, &mut self.marks);
+            if !progress.tick_by(group.len(), OperationPhase::Split, done, total) {
+                break;
+            }
+            done += group.len();
+            let any_round = Self::bin_split(radius, group, &mut self.marks, &mut self.memory);
             is_any_round = is_any_round || any_round;
+            if self.memory.is_failed() {
+                break;
+            }
         }
         is_any_round
     }
 
     #[cfg(feature = "allow_multithreading")]
-    fn parallel_split(&mut self, radius: I::Wide, buffer: &mut FragmentBuffer<I>) -> bool {
+    fn parallel_split(
+        &mut self,
+        radius: I::Wide,
+        buffer: &mut FragmentBuffer<I>,
+        progress: &mut ProgressTracker,
+    ) -> bool {
         use rayon::iter::IntoParallelRefMutIterator;
+        use rayon::iter::ParallelExtend;
         use rayon::iter::ParallelIterator;
 
         struct TaskResult<I: OverlayInt> {
@@ -117,15 +162,37 @@ where
         debug_assert!(!buffer.groups.is_empty(), "groups.len() >= 1");
         let marks_capacity = self.marks.capacity() / buffer.groups.len();
 
-        let results: Vec<TaskResult<I>> = buffer
-            .groups
-            .par_iter_mut()
-            .map(|group| {
+        // The control can't be polled from the workers, so with a control the groups run in
+        // batches and the control is polled between them.
+        let total: usize = buffer.groups.iter().map(Vec::len).sum();
+        let batch = if progress.has_control() {
+            (total / 64).max(ProgressTracker::STRIDE)
+        } else {
+            total
+        };
+
+        let mut results: Vec<TaskResult<I>> = Vec::with_capacity(buffer.groups.len());
+        let mut done = 0;
+        let mut start = 0;
+        while start < buffer.groups.len() {
+            let mut end = start;
+            let mut items = 0;
+            while end < buffer.groups.len() && (end == start || items < batch) {
+                items += buffer.groups[end].len();
+                end += 1;
+            }
+            if !progress.tick_by(items, OperationPhase::Split, done, total) {
+                break;
+            }
+            done += items;
+
+            results.par_extend(buffer.groups[start..end].par_iter_mut().map(|group| {
                 let mut marks = Vec::with_capacity(marks_capacity);
-                let any_round = Self::bin_split(radius, group, &mut marks);
+                let any_round = Self::bin_split(radius, group, &mut marks, &mut MemoryGuard::default());
                 TaskResult { any_round, marks }
-            })
-            .collect();
+            }));
+            start = end;
+        }
 
         let mut is_any_round = false;
         let mut size = 0;
@@ -150,7 +217,12 @@ where
         is_any_round
     }
 
-    fn bin_split(radius: I::Wide, fragments: &mut [Fragment<I>], marks: &mut Vec<LineMark<I>>) -> bool {
+    fn bin_split(
+        radius: I::Wide,
+        fragments: &mut [Fragment<I>],
+        marks: &mut Vec<LineMark<I>>,
+        memory: &mut MemoryGuard,
+    ) -> bool {
         if fragments.len() < 2 {
             return false;
         }
@@ -168,6 +240,10 @@ where
                     continue;
                 }
 
+                if !memory.ensure_spare(marks, 4) {
+                    return any_round;
+                }
+
                 // MARK: the intersection, ensuring the right order for deterministic results
 
                 let is_round = if fi.x_segment < fj.x_segment {
@@ -188,16 +264,19 @@ where
         border_x: I,
         fragments: &[Fragment<I>],
         vertical_segments: &mut [BorderVSegment<I>],
-    ) {
+    ) -> bool {
         let mut points = Vec::new();
         for fragment in fragments.iter() {
             if fragment.x_segment.b.x == border_x {
+                if !self.memory.ensure_spare(&mut points, 1) {
+                    return false;
+                }
                 points.push(fragment.x_segment.b)
             }
         }
 
         if points.is_empty() {
-            return;
+            return true;
         }
 
         points.sort_unstable_by_key(|p0| p0.y);
@@ -210,6 +289,9 @@ where
             }
             let mut j = i;
             while j < points.len() && points[j].y < s.y_range.max {
+                if !self.memory.ensure_spare(&mut self.marks, 1) {
+                    return false;
+                }
                 self.marks.push(LineMark {
                     index: s.id,
                     point: points[j],
@@ -217,6 +299,8 @@ where
                 j += 1;
             }
         }
+
+        true
     }
 
     fn cross_fragments(
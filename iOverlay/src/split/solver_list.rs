@@ -1,6 +1,7 @@
+use crate::core::control::{OperationPhase, ProgressTracker};
 use crate::core::edge_data::OverlayEdgeData;
 use crate::core::integer::OverlayInt;
-use crate::core::solver::Solver;
+use crate::core::solver::{Solver, Strategy};
 use crate::segm::segment::Segment;
 use crate::segm::winding::WindingCount;
 use crate::split::snap_radius::SnapRadius;
@@ -17,6 +18,7 @@ where
         segments: &mut Vec<Segment<C, I, D>>,
         solver: &Solver,
         store: &mut D::Store,
+        progress: &mut ProgressTracker,
     ) -> bool {
         let mut need_to_fix = true;
 
@@ -28,9 +30,18 @@ where
             need_to_fix = false;
             self.marks.clear();
 
+            self.begin_pass(Strategy::List, &snap_radius);
             let radius = snap_radius.radius::<I>();
 
+            let n = segments.len();
+            if !progress.report(OperationPhase::Split, 0, n) {
+                return any_intersection;
+            }
+
             for (i, si) in segments.iter().enumerate() {
+                if !progress.tick(OperationPhase::Split, i, n) {
+                    return any_intersection;
+                }
                 let xsi = &si.x_segment;
                 let ri = xsi.y_range();
                 for (j, sj) in segments.iter().enumerate().skip(i + 1) {
@@ -43,6 +54,9 @@ where
                         continue;
                     }
 
+                    if !self.memory.ensure_spare(&mut self.marks, 4) {
+                        return any_intersection;
+                    }
                     let is_round = Self::cross(i, j, xsi, xsj, &mut self.marks, radius);
                     need_to_fix = need_to_fix || is_round
                 }
@@ -52,13 +66,15 @@ where
                 return any_intersection;
             }
             any_intersection = true;
-            self.apply(segments, &mut reusable_buffer, solver, store);
+            if !self.apply(segments, &mut reusable_buffer, solver, store) {
+                return true;
+            }
 
             snap_radius.increment();
 
             if need_to_fix && !solver.is_list_split(segments) {
                 // finish with tree solver if edges is become large
-                self.tree_split(snap_radius, segments, solver, store);
+                self.tree_split(snap_radius, segments, solver, store, progress);
                 return true;
             }
         }
@@ -1,6 +1,7 @@
+use crate::core::control::{OperationPhase, ProgressTracker};
 use crate::core::edge_data::OverlayEdgeData;
 use crate::core::integer::OverlayInt;
-use crate::core::solver::Solver;
+use crate::core::solver::{Solver, Strategy};
 use crate::geom::line_range::LineRange;
 use crate::geom::x_segment::XSegment;
 use crate::segm::segment::Segment;
@@ -37,6 +38,7 @@ where
         segments: &mut Vec<Segment<C, I, D>>,
         solver: &Solver,
         store: &mut D::Store,
+        progress: &mut ProgressTracker,
     ) -> bool {
         let range: SegRange<I> = if let Some(range) = segments.ver_range() {
             range.into()
@@ -46,7 +48,7 @@ where
         let mut tree: SegExpTree<I, I, IdSegment<I>> = if let Some(tree) = SegExpTree::new(range) {
             tree
         } else {
-            return self.list_split(snap_radius, segments, solver, store);
+            return self.list_split(snap_radius, segments, solver, store, progress);
         };
 
         let mut reusable_buffer = Vec::new();
@@ -60,9 +62,18 @@ where
             need_to_fix = false;
             self.marks.clear();
 
+            self.begin_pass(Strategy::Tree, &snap_radius);
             let radius = snap_radius.radius::<I>();
 
+            let n = segments.len();
+            if !progress.report(OperationPhase::Split, 0, n) {
+                return any_intersection;
+            }
+
             for (i, si) in segments.iter().enumerate() {
+                if !progress.tick(OperationPhase::Split, i, n) {
+                    return any_intersection;
+                }
                 let time = si.x_segment.a.x;
                 let si_range = si.x_segment.y_range().into();
                 for sj in tree.iter_by_range(si_range, time) {
@@ -72,6 +83,9 @@ where
                         (sj.id, i, &sj.x_segment, &si.x_segment)
                     };
 
+                    if !self.memory.ensure_spare(&mut self.marks, 4) {
+                        return any_intersection;
+                    }
                     let is_round = Self::cross(this_index, scan_index, this, scan, &mut self.marks, radius);
 
                     need_to_fix = is_round || need_to_fix;
@@ -87,7 +101,9 @@ where
             any_intersection = true;
             tree.clear();
 
-            self.apply(segments, &mut reusable_buffer, solver, store);
+            if !self.apply(segments, &mut reusable_buffer, solver, store) {
+                return true;
+            }
 
             snap_radius.increment();
         }
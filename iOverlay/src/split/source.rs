@@ -0,0 +1,93 @@
+use crate::core::edge_data::{EdgeDataMerge, EdgeDataSplit, OverlayEdgeData};
+use crate::segm::segment::Segment;
+use alloc::vec::Vec;
+use i_float::int::number::int::IntNumber;
+use i_float::int::point::IntPoint;
+
+/// A point of any supported integer engine, widened to `i64`.
+pub(crate) type GridPoint = [i64; 2];
+
+/// Edge data holding the index of the input segment a split segment comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SourceEdge(usize);
+
+/// Records every split point together with the input segment it splits.
+#[derive(Default)]
+pub(crate) struct SplitSources {
+    /// Input segments, `a < b`, indexed by [`SourceEdge`].
+    pub(crate) sources: Vec<[GridPoint; 2]>,
+    /// Split points and the index of the input segment they split, in split order.
+    pub(crate) splits: Vec<(GridPoint, usize)>,
+}
+
+impl<C: Send + Sync> OverlayEdgeData<C> for SourceEdge {
+    type Store = SplitSources;
+
+    #[inline]
+    fn split<J: IntNumber>(self, ctx: EdgeDataSplit<J>, store: &mut Self::Store) -> (Self, Self) {
+        store.splits.push((grid_point(ctx.p), self.0));
+        (self, self)
+    }
+
+    #[inline(always)]
+    fn merge(ctx: EdgeDataMerge<C, Self>, _: &mut Self::Store) -> Self {
+        // both halves are already recorded at their split points
+        ctx.lhs_data
+    }
+}
+
+impl SplitSources {
+    /// Tags every segment with its own input segment.
+    pub(crate) fn track<C: Copy, I: IntNumber>(
+        &mut self,
+        segments: &[Segment<C, I>],
+    ) -> Vec<Segment<C, I, SourceEdge>> {
+        self.sources.clear();
+        self.splits.clear();
+        self.sources.reserve(segments.len());
+        segments
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                self.sources
+                    .push([grid_point(s.x_segment.a), grid_point(s.x_segment.b)]);
+                Segment {
+                    x_segment: s.x_segment,
+                    count: s.count,
+                    data: SourceEdge(i),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Exact `i64` value of any supported integer.
+#[inline]
+pub(crate) fn grid_value<J: IntNumber>(value: J) -> i64 {
+    if J::BITS <= 32 {
+        return value.to_f64() as i64;
+    }
+    // split into two exactly representable halves
+    let hi = value >> 32;
+    let lo = value - (hi << 32);
+    ((hi.to_f64() as i64) << 32) + lo.to_f64() as i64
+}
+
+#[inline]
+pub(crate) fn grid_point<J: IntNumber>(p: IntPoint<J>) -> GridPoint {
+    [grid_value(p.x), grid_value(p.y)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_value() {
+        assert_eq!(grid_value(-7i16), -7);
+        assert_eq!(grid_value(i32::MIN), i32::MIN as i64);
+        assert_eq!(grid_value(i64::MAX), i64::MAX);
+        assert_eq!(grid_value(i64::MIN + 3), i64::MIN + 3);
+        assert_eq!(grid_value((1i64 << 60) + 1), (1i64 << 60) + 1);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::core::control::{OperationPhase, ProgressTracker};
+use crate::core::error::{InputRole, OverlayError, validate_int_paths};
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
 use crate::core::link::OverlayLink;
@@ -25,10 +27,24 @@ pub struct ClipRule {
 impl<I: IntNumber> StringGraph<'_, I> {
     #[inline]
     pub(super) fn into_clip_string_lines(self) -> Vec<IntPath<I>> {
+        // never cancelled without a control
+        self.into_clip_string_lines_with_progress(&mut ProgressTracker::none())
+            .unwrap_or_default()
+    }
+
+    /// Same as [`Self::into_clip_string_lines`], but polls `progress` for every extracted path.
+    /// Returns `None` if the operation is cancelled.
+    pub(super) fn into_clip_string_lines_with_progress(
+        self,
+        progress: &mut ProgressTracker,
+    ) -> Option<Vec<IntPath<I>>> {
         let mut paths = Vec::new();
 
         let links = self.links;
         let nodes = self.nodes;
+        if !progress.report(OperationPhase::Extract, 0, links.len()) {
+            return None;
+        }
 
         let mut link_index = 0;
         let mut sub_path = Vec::new();
@@ -56,8 +72,11 @@ impl<I: IntNumber> StringGraph<'_, I> {
 
             paths.push(sub_path.clone());
             sub_path.clear();
+            if !progress.tick(OperationPhase::Extract, link_index, links.len()) {
+                return None;
+            }
         }
-        paths
+        Some(paths)
     }
 
     #[inline]
@@ -157,6 +176,18 @@ pub trait IntClip<I: IntNumber> {
     /// # Returns
     /// A vector of `IntPath<I>` instances containing the clipped portions of the input paths.
     fn clip_paths(&self, paths: &[IntPath<I>], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<IntPath<I>>;
+
+    /// Same as [`Self::clip_paths`], but checks the input first.
+    ///
+    /// Returns [`OverlayError::CoordinateOutOfRange`] naming the first point whose coordinates are
+    /// outside the safe range of the integer engine. Clipping shape contours are reported as
+    /// [`InputRole::Subject`] and counted across all shapes.
+    fn try_clip_paths(
+        &self,
+        paths: &[IntPath<I>],
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Vec<IntPath<I>>, OverlayError>;
 }
 
 impl<I> IntClip<I> for IntShapes<I>
@@ -190,6 +221,18 @@ where
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn try_clip_paths(
+        &self,
+        paths: &[IntPath<I>],
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Vec<IntPath<I>>, OverlayError> {
+        validate_int_paths(InputRole::Subject, self.iter().flatten().map(|c| c.as_slice()))?;
+        validate_int_paths(InputRole::String, paths.iter().map(|p| p.as_slice()))?;
+        Ok(self.clip_paths(paths, fill_rule, clip_rule))
+    }
 }
 
 impl<I> IntClip<I> for IntShape<I>
@@ -223,6 +266,18 @@ where
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn try_clip_paths(
+        &self,
+        paths: &[IntPath<I>],
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Vec<IntPath<I>>, OverlayError> {
+        validate_int_paths(InputRole::Subject, self.iter().map(|c| c.as_slice()))?;
+        validate_int_paths(InputRole::String, paths.iter().map(|p| p.as_slice()))?;
+        Ok(self.clip_paths(paths, fill_rule, clip_rule))
+    }
 }
 
 impl<I> IntClip<I> for [IntPoint<I>]
@@ -256,6 +311,18 @@ where
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn try_clip_paths(
+        &self,
+        paths: &[IntPath<I>],
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+    ) -> Result<Vec<IntPath<I>>, OverlayError> {
+        validate_int_paths(InputRole::Subject, core::iter::once(self))?;
+        validate_int_paths(InputRole::String, paths.iter().map(|p| p.as_slice()))?;
+        Ok(self.clip_paths(paths, fill_rule, clip_rule))
+    }
 }
 
 #[cfg(test)]
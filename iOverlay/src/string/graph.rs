@@ -1,5 +1,6 @@
 use crate::build::builder::GraphNode;
 use crate::core::link::OverlayLink;
+use crate::core::memory::MemoryGuard;
 use alloc::vec::Vec;
 use i_float::int::number::int::IntNumber;
 
@@ -10,7 +11,7 @@ pub struct StringGraph<'a, I: IntNumber> {
 
 impl GraphNode for Vec<usize> {
     #[inline(always)]
-    fn with_indices(indices: &[usize]) -> Self {
-        indices.to_vec()
+    fn with_indices(indices: &[usize], memory: &mut MemoryGuard) -> Option<Self> {
+        memory.copy_slice(indices)
     }
 }
@@ -1,15 +1,21 @@
 use crate::build::builder::GraphBuilder;
+use crate::core::control::{OperationControl, ProgressTracker};
+use crate::core::error::{InputRole, OverlayError, validate_int_paths};
 use crate::core::fill_rule::FillRule;
 use crate::core::integer::OverlayInt;
 use crate::core::overlay::{IntOverlayOptions, ShapeType};
+use crate::core::provenance::InputLog;
 use crate::core::solver::Solver;
+use crate::core::stats::{OverlayStats, PhaseTimer, StatsClock};
 use crate::geom::x_segment::XSegment;
+use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::build::BuildSegments;
 use crate::segm::segment::Segment;
 use crate::segm::string::STRING_BACK_CLIP;
 use crate::segm::string::STRING_FORWARD_CLIP;
 use crate::segm::string::ShapeCountString;
 use crate::split::solver::SplitSolver;
+use crate::split::source::SplitSources;
 use crate::string::clip::ClipRule;
 use crate::string::graph::StringGraph;
 use crate::string::line::IntLine;
@@ -25,6 +31,7 @@ pub struct StringOverlay<I: OverlayInt> {
     pub(super) segments: Vec<Segment<ShapeCountString, I>>,
     pub(crate) split_solver: SplitSolver<I>,
     pub(crate) graph_builder: GraphBuilder<ShapeCountString, Vec<usize>, I>,
+    pub(crate) input_log: Option<InputLog>,
 }
 
 impl<I> StringOverlay<I>
@@ -41,6 +48,7 @@ where
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
             graph_builder: GraphBuilder::<ShapeCountString, Vec<usize>, I>::new(),
+            input_log: None,
         }
     }
 
@@ -54,6 +62,7 @@ where
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
             graph_builder: GraphBuilder::<ShapeCountString, Vec<usize>, I>::new(),
+            input_log: None,
         }
     }
 
@@ -93,6 +102,25 @@ where
         overlay
     }
 
+    /// Creates a new `StringOverlay` with shapes and string paths, checking the input first.
+    ///
+    /// Returns [`OverlayError::CoordinateOutOfRange`] naming the first point whose coordinates are
+    /// outside the safe range of the integer engine. Shape contours are reported as
+    /// [`InputRole::Subject`] and counted across all shapes.
+    /// - `shapes`: An array of `IntShape<I>` instances to be added as shapes.
+    /// - `paths`: A collection of paths, each representing a string line.
+    pub fn try_with_shapes_and_paths(
+        shapes: &[IntShape<I>],
+        paths: &[IntPath<I>],
+    ) -> Result<Self, OverlayError> {
+        validate_int_paths(InputRole::Subject, shapes.iter().flatten().map(|c| c.as_slice()))?;
+        validate_int_paths(InputRole::String, paths.iter().map(|p| p.as_slice()))?;
+        let mut overlay = Self::new(shapes.points_count() + paths.points_count());
+        overlay.add_shapes(shapes);
+        overlay.add_string_paths(paths);
+        Ok(overlay)
+    }
+
     /// Adds a path to the overlay using an iterator, allowing for more flexible path input.
     /// This function is particularly useful when working with dynamically generated paths or
     /// when paths are not directly stored in a collection.
@@ -130,6 +158,12 @@ where
     /// - `line`: An `IntLine` representing the open line (defined by two points).
     #[inline]
     pub fn add_string_line(&mut self, line: IntLine<I>) {
+        self.log_string_path(&line, false);
+        self.push_string_line(line);
+    }
+
+    #[inline]
+    pub(crate) fn push_string_line(&mut self, line: IntLine<I>) {
         let a = line[0];
         let b = line[1];
         let segment = match a.cmp(&b) {
@@ -155,6 +189,37 @@ where
         self.segments.push(segment);
     }
 
+    /// Adds shape edges built as boolean segments, such as the edges of a stroke.
+    #[inline]
+    pub(crate) fn push_shape_segments(&mut self, segments: &[Segment<ShapeCountBoolean, I>]) {
+        self.segments.extend(segments.iter().map(|s| Segment {
+            x_segment: s.x_segment,
+            count: ShapeCountString {
+                subj: s.count.subj,
+                clip: 0,
+            },
+            data: (),
+        }));
+    }
+
+    /// Same as [`Self::new`], but the overlay logs the added string lines, so their vertices can be
+    /// traced through a clip. Shape contours are not logged: clipped lines only run along string
+    /// edges.
+    #[inline]
+    pub(crate) fn new_tracked(capacity: usize) -> Self {
+        let mut overlay = Self::new(capacity);
+        overlay.input_log = Some(InputLog::default());
+        overlay
+    }
+
+    /// Logs a string path about to be added.
+    #[inline]
+    pub(crate) fn log_string_path(&mut self, path: &[IntPoint<I>], closed: bool) {
+        if let Some(log) = self.input_log.as_mut() {
+            log.add_path(InputRole::String, path, closed);
+        }
+    }
+
     /// Adds multiple lines (open paths) to the overlay.
     /// - `lines`: An array of `IntLine` instances to be added.
     #[inline]
@@ -168,6 +233,7 @@ where
     /// - `path`: A path representing a string line.
     #[inline]
     pub fn add_string_path(&mut self, path: &[IntPoint<I>]) {
+        self.log_string_path(path, false);
         if path.len() < 2 {
             return;
         }
@@ -177,7 +243,7 @@ where
             return;
         };
         for &b in path.iter().skip(1) {
-            self.add_string_line([a, b]);
+            self.push_string_line([a, b]);
             a = b;
         }
     }
@@ -186,6 +252,7 @@ where
     /// - `contour`: A contour representing a string line closed path. This path is interpreted as closed, so it doesn’t require the start and endpoint to be the same for processing.
     #[inline]
     pub fn add_string_contour(&mut self, contour: &[IntPoint<I>]) {
+        self.log_string_path(contour, true);
         if contour.len() < 2 {
             return;
         }
@@ -195,7 +262,7 @@ where
             return;
         };
         for &b in contour.iter() {
-            self.add_string_line([a, b]);
+            self.push_string_line([a, b]);
             a = b;
         }
     }
@@ -251,6 +318,103 @@ where
             .into_clip_string_lines()
     }
 
+    /// Same as [`Self::clip_string_lines_with_solver`], but reports progress to `control` and lets it
+    /// cancel the operation with [`OverlayError::Cancelled`]. `control` is polled as in
+    /// [`Overlay::try_overlay_with_control`](crate::core::overlay::Overlay::try_overlay_with_control).
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of line segments.
+    /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
+    /// - `solver`: A solver type to be used for advanced control over the graph building process.
+    /// - `control`: Receives progress updates and may cancel.
+    pub fn try_clip_string_lines_with_control(
+        mut self,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+        control: &mut dyn OperationControl,
+    ) -> Result<Vec<IntPath<I>>, OverlayError> {
+        let mut progress = ProgressTracker::new(control);
+        self.split_solver
+            .split_segments_with_progress(&mut self.segments, &solver, &mut (), &mut progress);
+        if progress.is_cancelled() {
+            return Err(OverlayError::Cancelled);
+        }
+        if self.segments.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.graph_builder
+            .build_string_clip_with_progress(fill_rule, clip_rule, &solver, &self.segments, &mut progress)
+            .and_then(|graph| graph.into_clip_string_lines_with_progress(&mut progress))
+            .ok_or(OverlayError::Cancelled)
+    }
+
+    /// Runs [`Self::clip_string_lines_with_solver`] while recording every split point and the input
+    /// segment it splits. Also returns the input log, if tracking was enabled.
+    pub(crate) fn clip_string_lines_tracking_splits(
+        mut self,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+    ) -> (Vec<IntPath<I>>, SplitSources, Option<InputLog>) {
+        let mut store = SplitSources::default();
+        let mut segments = store.track(&self.segments);
+        self.split_solver
+            .split_segments_with_store(&mut segments, &solver, &mut store);
+
+        self.segments.clear();
+        self.segments.extend(segments.iter().map(Segment::without_data));
+
+        if self.segments.is_empty() {
+            return (Vec::new(), store, self.input_log);
+        }
+        let paths = self
+            .graph_builder
+            .build_string_clip(fill_rule, clip_rule, &solver, &self.segments)
+            .into_clip_string_lines();
+        (paths, store, self.input_log)
+    }
+
+    /// Same as [`Self::clip_string_lines_with_solver`], but also returns [`OverlayStats`] of the
+    /// operation. See [`Overlay::overlay_with_stats`](crate::core::overlay::Overlay::overlay_with_stats);
+    /// `input_edges` counts shape and string edges together and `output_edges` counts the edges of
+    /// the clipped paths.
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of line segments.
+    /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
+    /// - `solver`: A solver type to be used for advanced control over the graph building process.
+    /// - `clock`: Time source for the phase timings.
+    pub fn clip_string_lines_with_stats(
+        mut self,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+        clock: &mut dyn StatsClock,
+    ) -> (Vec<IntPath<I>>, OverlayStats) {
+        let mut stats = OverlayStats {
+            input_edges: self.segments.len(),
+            ..Default::default()
+        };
+        let mut timer = PhaseTimer::start(clock);
+
+        self.split_solver.split_segments(&mut self.segments, &solver);
+        stats.set_split(self.split_solver.stats, self.segments.len());
+        stats.split_time = timer.lap();
+        if self.segments.is_empty() {
+            return (Vec::new(), stats);
+        }
+
+        let graph = self
+            .graph_builder
+            .build_string_clip(fill_rule, clip_rule, &solver, &self.segments);
+        stats.nodes = graph.nodes.len();
+        stats.links = graph.links.len();
+        stats.build_time = timer.lap();
+
+        let paths = graph.into_clip_string_lines();
+        stats.output_edges = paths.iter().map(|path| path.len().saturating_sub(1)).sum();
+        stats.extract_time = timer.lap();
+
+        (paths, stats)
+    }
+
     /// Builds and returns a lightweight, borrowed view of the overlay graph.
     /// This graph is used for string operations, enabling analysis and manipulation of geometric data.
     /// - `fill_rule`: The rule that defines how to build shapes (e.g., non-zero, even-odd).
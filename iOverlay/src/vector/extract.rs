@@ -46,10 +46,21 @@ where
         overlay_rule: OverlayRule,
         buffer: &mut BooleanExtractionBuffer<I>,
         store: &mut D::Store,
+    ) -> Vec<DataVectorShape<I, D>> {
+        self.with_windings_for(overlay_rule, |graph| {
+            graph.extract_vector_shapes_by_windings(overlay_rule, buffer, store)
+        })
+    }
+
+    fn extract_vector_shapes_by_windings(
+        &self,
+        overlay_rule: OverlayRule,
+        buffer: &mut BooleanExtractionBuffer<I>,
+        store: &mut D::Store,
     ) -> Vec<DataVectorShape<I, D>> {
         let clockwise = self.options.output_direction == ContourDirection::Clockwise;
         self.links
-            .filter_by_overlay_into(overlay_rule, &mut buffer.visited);
+            .filter_by_overlay_into(overlay_rule, self.windings, &mut buffer.visited);
 
         let mut holes = Vec::new();
         let mut shapes = Vec::new();
@@ -74,7 +85,7 @@ where
                 self.links.get_unchecked(left_top_link)
             };
 
-            let is_hole = overlay_rule.is_fill_top(link.fill);
+            let is_hole = overlay_rule.is_fill_top(link.fill, self.top_winding(left_top_link));
             let visited_state = [VisitState::HullVisited, VisitState::HoleVisited][is_hole as usize];
 
             let direction = is_hole == clockwise;
@@ -0,0 +1,292 @@
+#[cfg(test)]
+mod tests {
+    use core::ops::ControlFlow;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::control::{OperationPhase, OperationProgress};
+    use i_overlay::core::error::OverlayError;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{IntOverlayOptions, Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::solver::Solver;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::float::string_overlay::FloatStringOverlay;
+    use i_overlay::mesh::outline::offset::OutlineOffset;
+    use i_overlay::mesh::stroke::offset::StrokeOffset;
+    use i_overlay::mesh::style::{OutlineStyle, StrokeAlignment, StrokeStyle};
+    use i_overlay::string::clip::ClipRule;
+    use i_overlay::string::overlay::StringOverlay;
+
+    fn star(n: usize, r0: f64, r1: f64, dx: f64) -> Vec<IntPoint<i32>> {
+        let count = 2 * n;
+        (0..count)
+            .map(|i| {
+                let r = if i % 2 == 0 { r0 } else { r1 };
+                let a = core::f64::consts::TAU * i as f64 / count as f64;
+                IntPoint::new((dx + r * a.cos()) as i32, (r * a.sin()) as i32)
+            })
+            .collect()
+    }
+
+    fn overlay(solver: Solver) -> Overlay<i32> {
+        let mut overlay = Overlay::new_custom(2400, IntOverlayOptions::default(), solver);
+        overlay.add_contour(&star(700, 100_000.0, 60_000.0, 0.0), ShapeType::Subject);
+        overlay.add_contour(&star(500, 100_000.0, 70_000.0, 30_000.0), ShapeType::Clip);
+        overlay
+    }
+
+    fn float_star(n: usize, r0: f64, r1: f64) -> Vec<[f64; 2]> {
+        let count = 2 * n;
+        (0..count)
+            .map(|i| {
+                let r = if i % 2 == 0 { r0 } else { r1 };
+                let a = core::f64::consts::TAU * i as f64 / count as f64;
+                [r * a.cos(), r * a.sin()]
+            })
+            .collect()
+    }
+
+    fn string_overlay() -> StringOverlay<i32> {
+        let mut overlay = StringOverlay::new(2100);
+        overlay.add_shape_contour(&star(20, 100_000.0, 60_000.0, 0.0));
+        for i in 0..2000 {
+            let y = 80 * i - 80_000;
+            overlay.add_string_line([IntPoint::new(-120_000, y), IntPoint::new(120_000, y + 7)]);
+        }
+        overlay
+    }
+
+    const CLIP_RULE: ClipRule = ClipRule {
+        invert: false,
+        boundary_included: false,
+    };
+
+    fn cancel_in(solver: Solver, phase: OperationPhase) {
+        let mut calls = 0;
+        let mut control = |progress: OperationProgress| {
+            if progress.phase == phase && progress.done > 0 {
+                calls += 1;
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+
+        let mut overlay = overlay(solver);
+        let result = overlay.try_overlay_with_control(OverlayRule::Xor, FillRule::EvenOdd, &mut control);
+        assert_eq!(result, Err(OverlayError::Cancelled));
+        assert_eq!(calls, 1);
+
+        // the overlay stays usable after a cancellation
+        let mut never = |_: OperationProgress| ControlFlow::Continue(());
+        let shapes = overlay
+            .try_overlay_with_control(OverlayRule::Xor, FillRule::EvenOdd, &mut never)
+            .unwrap();
+        let expected = self::overlay(solver).overlay(OverlayRule::Xor, FillRule::EvenOdd);
+        assert_eq!(shapes, expected);
+    }
+
+    #[test]
+    fn test_cancel_split_list() {
+        cancel_in(Solver::LIST, OperationPhase::Split);
+    }
+
+    #[test]
+    fn test_cancel_split_tree() {
+        cancel_in(Solver::TREE, OperationPhase::Split);
+    }
+
+    #[test]
+    fn test_cancel_split_frag() {
+        cancel_in(Solver::FRAG, OperationPhase::Split);
+    }
+
+    #[test]
+    fn test_cancel_build() {
+        cancel_in(Solver::AUTO, OperationPhase::Build);
+    }
+
+    #[test]
+    fn test_cancel_extract() {
+        cancel_in(Solver::AUTO, OperationPhase::Extract);
+    }
+
+    #[test]
+    fn test_progress_order() {
+        let mut reports = Vec::new();
+        let mut control = |progress: OperationProgress| {
+            reports.push(progress);
+            ControlFlow::Continue(())
+        };
+
+        overlay(Solver::AUTO)
+            .try_overlay_with_control(OverlayRule::Union, FillRule::NonZero, &mut control)
+            .unwrap();
+
+        let phases: Vec<_> = reports.iter().map(|p| p.phase as usize).collect();
+        assert!(phases.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(reports.first().unwrap().phase, OperationPhase::Split);
+        assert_eq!(reports.last().unwrap().phase, OperationPhase::Extract);
+        assert!(reports.iter().all(|p| p.done <= p.total && p.percent() <= 100));
+    }
+
+    #[test]
+    fn test_float_cancel() {
+        let subj = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let clip = [[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]];
+
+        let mut cancel = |_: OperationProgress| ControlFlow::Break(());
+        let result = FloatOverlay::with_subj_and_clip(&subj, &clip).try_overlay_with_control(
+            OverlayRule::Union,
+            FillRule::NonZero,
+            &mut cancel,
+        );
+        assert_eq!(result, Err(OverlayError::Cancelled));
+
+        let mut never = |_: OperationProgress| ControlFlow::Continue(());
+        let shapes = FloatOverlay::with_subj_and_clip(&subj, &clip)
+            .try_overlay_with_control(OverlayRule::Union, FillRule::NonZero, &mut never)
+            .unwrap();
+        assert_eq!(shapes.len(), 1);
+    }
+
+    #[test]
+    fn test_string_cancel() {
+        for phase in [
+            OperationPhase::Split,
+            OperationPhase::Build,
+            OperationPhase::Extract,
+        ] {
+            let mut cancel = |progress: OperationProgress| {
+                if progress.phase == phase {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            };
+            let result = string_overlay().try_clip_string_lines_with_control(
+                FillRule::NonZero,
+                CLIP_RULE,
+                Solver::AUTO,
+                &mut cancel,
+            );
+            assert_eq!(result, Err(OverlayError::Cancelled), "{:?}", phase);
+        }
+
+        let mut reports = Vec::new();
+        let mut record = |progress: OperationProgress| {
+            reports.push(progress);
+            ControlFlow::Continue(())
+        };
+        let lines = string_overlay()
+            .try_clip_string_lines_with_control(FillRule::NonZero, CLIP_RULE, Solver::AUTO, &mut record)
+            .unwrap();
+        assert_eq!(
+            lines,
+            string_overlay().clip_string_lines(FillRule::NonZero, CLIP_RULE)
+        );
+
+        let phases: Vec<_> = reports.iter().map(|p| p.phase as usize).collect();
+        assert!(phases.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(reports.last().unwrap().phase, OperationPhase::Extract);
+        assert!(
+            reports
+                .iter()
+                .any(|p| p.phase == OperationPhase::Extract && p.done > 0)
+        );
+    }
+
+    #[test]
+    fn test_float_string_cancel() {
+        let shape = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let line = [[-1.0, 0.5], [2.0, 0.5]];
+
+        let mut cancel = |_: OperationProgress| ControlFlow::Break(());
+        let result = FloatStringOverlay::with_shape_and_string(&shape, &line)
+            .try_clip_string_lines_with_control(FillRule::NonZero, CLIP_RULE, Solver::AUTO, &mut cancel);
+        assert_eq!(result, Err(OverlayError::Cancelled));
+
+        let mut never = |_: OperationProgress| ControlFlow::Continue(());
+        let lines = FloatStringOverlay::with_shape_and_string(&shape, &line)
+            .try_clip_string_lines_with_control(FillRule::NonZero, CLIP_RULE, Solver::AUTO, &mut never)
+            .unwrap();
+        assert_eq!(lines, vec![vec![[0.0, 0.5], [1.0, 0.5]]]);
+    }
+
+    #[test]
+    fn test_stroke_cancel() {
+        let path = float_star(500, 100.0, 60.0);
+        for alignment in [StrokeAlignment::Center, StrokeAlignment::Inside] {
+            let style = StrokeStyle::new(4.0).alignment(alignment);
+
+            // an aligned stroke runs two overlays, the second split comes after the first extract
+            let mut splits = 0;
+            let mut extracted = false;
+            let mut cancel = |progress: OperationProgress| match progress.phase {
+                OperationPhase::Extract => {
+                    extracted = true;
+                    ControlFlow::Continue(())
+                }
+                OperationPhase::Split if extracted => {
+                    splits += 1;
+                    ControlFlow::Break(())
+                }
+                _ => ControlFlow::Continue(()),
+            };
+            let result = path.try_stroke_with_control(style.clone(), true, &mut cancel);
+            match alignment {
+                StrokeAlignment::Center => {
+                    assert_eq!(splits, 0);
+                    assert_eq!(result.unwrap(), path.stroke(style.clone(), true));
+                }
+                _ => {
+                    assert_eq!(splits, 1);
+                    assert_eq!(result, Err(OverlayError::Cancelled));
+                }
+            }
+
+            let mut never = |_: OperationProgress| ControlFlow::Continue(());
+            let shapes = path
+                .try_stroke_with_control(style.clone(), true, &mut never)
+                .unwrap();
+            assert_eq!(shapes, path.stroke(style, true));
+        }
+    }
+
+    #[test]
+    fn test_outline_cancel() {
+        let shape = vec![
+            float_star(500, 100.0, 60.0),
+            float_star(400, 40.0, 20.0).into_iter().rev().collect(),
+        ];
+        let style = OutlineStyle::new(2.0);
+
+        let mut cancel = |progress: OperationProgress| {
+            if progress.phase == OperationPhase::Build {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+        assert_eq!(
+            shape.try_outline_with_control(&style, &mut cancel),
+            Err(OverlayError::Cancelled)
+        );
+
+        let mut reports = Vec::new();
+        let mut record = |progress: OperationProgress| {
+            reports.push(progress);
+            ControlFlow::Continue(())
+        };
+        let shapes = shape.try_outline_with_control(&style, &mut record).unwrap();
+        assert_eq!(shapes, shape.outline(&style));
+
+        // the contour offsets come first, then the union of them
+        assert_eq!(reports.first().unwrap().phase, OperationPhase::Build);
+        assert!(
+            reports
+                .iter()
+                .any(|p| p.phase == OperationPhase::Build && p.done > 0)
+        );
+        assert_eq!(reports.last().unwrap().phase, OperationPhase::Extract);
+    }
+}
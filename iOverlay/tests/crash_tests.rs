@@ -51,6 +51,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn test_01() {
         let subj = [
             [-117.04171489206965, 1820.3621519926919],
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::extract::BooleanExtractionBuffer;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{IntOverlayOptions, Overlay};
+    use i_overlay::core::overlay_rule::{CustomRule, OverlayRule, RegionFill};
+    use i_shape::int::shape::IntContour;
+
+    type Predicate = fn(RegionFill) -> bool;
+
+    const RULES: [(OverlayRule, Predicate); 7] = [
+        (OverlayRule::Subject, |f| f.subj),
+        (OverlayRule::Clip, |f| f.clip),
+        (OverlayRule::Intersect, |f| f.subj && f.clip),
+        (OverlayRule::Union, |f| f.subj || f.clip),
+        (OverlayRule::Difference, |f| f.subj && !f.clip),
+        (OverlayRule::InverseDifference, |f| !f.subj && f.clip),
+        (OverlayRule::Xor, |f| f.subj != f.clip),
+    ];
+
+    fn rect(x0: i32, y0: i32, x1: i32, y1: i32) -> IntContour<i32> {
+        vec![
+            IntPoint::new(x0, y0),
+            IntPoint::new(x1, y0),
+            IntPoint::new(x1, y1),
+            IntPoint::new(x0, y1),
+        ]
+    }
+
+    fn input() -> (Vec<IntContour<i32>>, Vec<IntContour<i32>>) {
+        // a subject with a hole and a clip crossing both
+        let subj = vec![rect(0, 0, 20, 20), rect(5, 5, 15, 15).into_iter().rev().collect()];
+        let clip = vec![rect(10, -5, 30, 10), rect(-5, 12, 8, 25)];
+        (subj, clip)
+    }
+
+    #[test]
+    fn test_builtin_equivalents() {
+        let (subj, clip) = input();
+        for options in [IntOverlayOptions::default(), IntOverlayOptions::ogc()] {
+            for (rule, predicate) in RULES {
+                let custom = OverlayRule::Custom(CustomRule::new(predicate).unwrap());
+                for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+                    let expected = Overlay::with_contours_custom(&subj, &clip, options, Default::default())
+                        .overlay(rule, fill_rule);
+                    let result = Overlay::with_contours_custom(&subj, &clip, options, Default::default())
+                        .overlay(custom, fill_rule);
+                    assert_eq!(result, expected, "{} {:?}", rule, fill_rule);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_graph_extractors() {
+        let (subj, clip) = input();
+        let mut overlay = Overlay::with_contours(&subj, &clip);
+        let graph = overlay.build_graph_view(FillRule::NonZero).unwrap();
+        let mut buffer = BooleanExtractionBuffer::default();
+
+        for (rule, predicate) in RULES {
+            let custom = OverlayRule::Custom(CustomRule::new(predicate).unwrap());
+            assert_eq!(
+                graph.extract_shapes(custom, &mut buffer),
+                graph.extract_shapes(rule, &mut buffer)
+            );
+            assert_eq!(
+                graph.extract_vector_shapes(custom, &mut buffer),
+                graph.extract_vector_shapes(rule, &mut buffer)
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_builtin_rule() {
+        // subject minus clip, but a negative-wound clip does not cut
+        let rule = CustomRule::new(|f: RegionFill| f.subj && f.clip_winding <= 0).unwrap();
+        let subj = [rect(0, 0, 20, 10)];
+        let positive = rect(4, -5, 8, 15);
+        let negative: IntContour<i32> = rect(12, -5, 16, 15).into_iter().rev().collect();
+        let clip = [positive.clone(), negative];
+
+        let shapes =
+            Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Custom(rule), FillRule::NonZero);
+        let expected =
+            Overlay::with_contours(&subj, &[positive]).overlay(OverlayRule::Difference, FillRule::NonZero);
+        assert_eq!(shapes, expected);
+
+        assert_eq!(shapes.len(), 2);
+        for shape in shapes.iter() {
+            assert_eq!(shape.len(), 1);
+            // outer contours are counterclockwise
+            assert!(area(&shape[0]) > 0);
+        }
+        let mut areas: Vec<_> = shapes.iter().map(|shape| area(&shape[0])).collect();
+        areas.sort();
+        assert_eq!(areas, [40, 120]);
+
+        // the graph view sweeps the windings for the same result
+        let mut overlay = Overlay::with_contours(&subj, &clip);
+        let graph = overlay.build_graph_view(FillRule::NonZero).unwrap();
+        let from_graph =
+            graph.extract_shapes(OverlayRule::Custom(rule), &mut BooleanExtractionBuffer::default());
+        assert_eq!(from_graph, shapes);
+    }
+
+    #[test]
+    fn test_outside_rejected() {
+        assert!(CustomRule::new(|f: RegionFill| !f.subj && !f.clip).is_none());
+        assert!(CustomRule::new(|f: RegionFill| f.subj_winding == 0).is_none());
+        assert!(CustomRule::new(|f: RegionFill| f.subj_winding < 0).is_some());
+    }
+
+    #[test]
+    fn test_rule_equality() {
+        let custom = CustomRule::new(|f: RegionFill| f.subj).unwrap();
+        let rule = OverlayRule::Custom(custom);
+        assert_eq!(rule, OverlayRule::Custom(custom));
+        assert_ne!(rule, OverlayRule::Subject);
+        assert_eq!(rule.to_string(), "Custom");
+    }
+
+    fn area(contour: &IntContour<i32>) -> i64 {
+        let mut sum = 0;
+        let mut a = contour[contour.len() - 1];
+        for &b in contour.iter() {
+            sum += a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64;
+            a = b;
+        }
+        sum / 2
+    }
+}
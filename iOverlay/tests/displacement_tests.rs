@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{IntOverlayOptions, Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::solver::{Precision, Solver};
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::i_float::adapter::FloatPointAdapter;
+    use i_overlay::i_float::float::rect::FloatRect;
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint<i32>> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]
+    }
+
+    #[test]
+    fn test_exact_intersections() {
+        let mut overlay = Overlay::with_contour(&square(0, 0, 10), &square(5, 5, 10));
+        let (shapes, report) = overlay.overlay_with_displacement(OverlayRule::Union, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(report.max_distance, 0.0);
+        assert!(report.vertices.is_empty());
+    }
+
+    #[test]
+    fn test_rounded_intersection() {
+        let subj = [IntPoint::new(0, 0), IntPoint::new(3, 3), IntPoint::new(3, 0)];
+        let clip = [IntPoint::new(0, 3), IntPoint::new(3, 0), IntPoint::new(0, 0)];
+
+        let expected = Overlay::with_contour(&subj, &clip).overlay(OverlayRule::Xor, FillRule::NonZero);
+        let (shapes, report) = Overlay::with_contour(&subj, &clip)
+            .overlay_with_displacement(OverlayRule::Xor, FillRule::NonZero);
+        assert_eq!(shapes, expected);
+
+        assert!(!report.vertices.is_empty());
+        assert!(report.max_distance > 0.0 && report.max_distance < 1.0);
+        assert_eq!(report.max_distance, report.max_grid_distance);
+        for v in report.vertices.iter() {
+            assert_eq!(shapes[v.shape][v.contour][v.vertex], v.point);
+            assert!(!v.moved_input);
+        }
+        assert!(!report.exceeds(1.0));
+        assert_eq!(report.exceeding(0.0).count(), report.vertices.len());
+    }
+
+    #[test]
+    fn test_snap_to_input_vertex() {
+        // the clip edge crosses the bottom of the square at x = 98.5, close to its corner
+        let subj = square(0, 0, 100);
+        let clip = [
+            IntPoint::new(97, -50),
+            IntPoint::new(100, 50),
+            IntPoint::new(200, 0),
+        ];
+        let solver = Solver {
+            precision: Precision::LOW,
+            ..Default::default()
+        };
+
+        let mut overlay = Overlay::new_custom(8, IntOverlayOptions::default(), solver);
+        overlay.add_contour(&subj, ShapeType::Subject);
+        overlay.add_contour(&clip, ShapeType::Clip);
+        let (shapes, report) = overlay.overlay_with_displacement(OverlayRule::Union, FillRule::NonZero);
+
+        let corner = report
+            .vertices
+            .iter()
+            .find(|v| v.point == IntPoint::new(100, 0))
+            .unwrap();
+        // the corner stays in place, the clip edge is bent onto it
+        assert!(!corner.moved_input);
+        assert!(corner.distance > 0.9);
+        assert!(report.exceeds(0.9));
+        assert_eq!(shapes[corner.shape][corner.contour][corner.vertex], corner.point);
+    }
+
+    #[test]
+    fn test_i64() {
+        let subj = [IntPoint::new(0i64, 0), IntPoint::new(3, 3), IntPoint::new(3, 0)];
+        let clip = [IntPoint::new(0i64, 3), IntPoint::new(3, 0), IntPoint::new(0, 0)];
+
+        let (_, report) = Overlay::with_contour(&subj, &clip)
+            .overlay_with_displacement(OverlayRule::Xor, FillRule::NonZero);
+        assert!(report.max_distance > 0.0 && report.max_distance < 1.0);
+    }
+
+    #[test]
+    fn test_float() {
+        let subj = [[0.0, 0.0], [1.0, 1.0], [1.0, 0.0]];
+        let clip = [[0.0, 1.0], [1.0, 0.0], [0.0, 0.0], [0.3, 0.0]];
+
+        let (shapes, report) = FloatOverlay::with_subj_and_clip(&subj, &clip)
+            .overlay_with_displacement(OverlayRule::Union, FillRule::NonZero);
+        let expected =
+            FloatOverlay::with_subj_and_clip(&subj, &clip).overlay(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(shapes, expected);
+
+        for v in report.vertices.iter() {
+            assert_eq!(shapes[v.shape][v.contour][v.vertex], v.point);
+            assert!(v.distance <= v.grid_distance);
+        }
+        assert!(report.max_distance < 1e-6);
+    }
+
+    #[test]
+    fn test_float_rounding() {
+        // with a grid step of 1, (4.25, 4) is rounded to (4, 4)
+        let subj = [[0.0, 0.0], [4.0, 0.0], [4.25, 4.0], [0.0, 4.0]];
+        let clip = [[2.0, 2.0], [6.0, 2.0], [6.0, 3.0], [2.0, 3.0]];
+        let overlay = |adapter| {
+            FloatOverlay::with_adapter_tracked(adapter, 8)
+                .unsafe_add_contour(&subj, ShapeType::Subject)
+                .unsafe_add_contour(&clip, ShapeType::Clip)
+        };
+        let adapter =
+            FloatPointAdapter::<[f64; 2], i32>::with_scale(FloatRect::new(0.0, 10.0, 0.0, 10.0), 1.0);
+
+        let (shapes, report) =
+            overlay(adapter.clone()).overlay_with_displacement(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(shapes[0][0].len(), 8);
+        assert_eq!(report.max_distance, 0.25);
+
+        let mut vertices: Vec<_> = report
+            .vertices
+            .iter()
+            .map(|v| (v.point, v.distance, v.moved_input))
+            .collect();
+        vertices.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        // the crossings lie on the rounded subject edge, which is off its exact position
+        assert_eq!(
+            vertices,
+            [
+                ([4.0, 2.0], 0.25, false),
+                ([4.0, 3.0], 0.25, false),
+                ([4.0, 4.0], 0.25, true),
+            ]
+        );
+
+        // an untracked overlay does not know the exact input
+        let (_, report) = FloatOverlay::with_adapter(adapter, 8)
+            .unsafe_add_contour(&subj, ShapeType::Subject)
+            .unsafe_add_contour(&clip, ShapeType::Clip)
+            .overlay_with_displacement(OverlayRule::Union, FillRule::NonZero);
+        assert!(report.vertices.is_empty());
+    }
+}
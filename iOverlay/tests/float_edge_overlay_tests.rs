@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use i_overlay::core::edge_data::{EdgeDataMerge, EdgeDataSplit, OverlayEdgeData};
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::ShapeType;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::float::edge_overlay::{FloatEdgeOverlay, FloatInputEdge};
+    use i_overlay::i_float::adapter::FloatPointAdapter;
+    use i_overlay::i_float::float::rect::FloatRect;
+    use i_overlay::i_float::int::number::int::IntNumber;
+    use i_overlay::segm::boolean::ShapeCountBoolean;
+    use i_overlay::vector::edge::{CLIP_LEFT, CLIP_RIGHT, SUBJ_LEFT, SUBJ_RIGHT};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Source(u32);
+
+    impl OverlayEdgeData for Source {
+        type Store = ();
+
+        fn merge(ctx: EdgeDataMerge<ShapeCountBoolean, Self>, _: &mut Self::Store) -> Self {
+            Source(ctx.lhs_data.0.min(ctx.rhs_data.0))
+        }
+    }
+
+    /// Counts how many times an edge was split.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Splits(u32);
+
+    impl OverlayEdgeData for Splits {
+        type Store = usize;
+
+        fn split<I: IntNumber>(self, _: EdgeDataSplit<I>, store: &mut usize) -> (Self, Self) {
+            *store += 1;
+            (Splits(self.0 + 1), Splits(self.0 + 1))
+        }
+
+        fn merge(ctx: EdgeDataMerge<ShapeCountBoolean, Self>, _: &mut usize) -> Self {
+            Splits(ctx.lhs_data.0.max(ctx.rhs_data.0))
+        }
+    }
+
+    fn rect<D: Copy>(x0: f64, y0: f64, x1: f64, y1: f64, data: D) -> Vec<FloatInputEdge<[f64; 2], D>> {
+        let p = [[x0, y0], [x1, y0], [x1, y1], [x0, y1]];
+        (0..4)
+            .map(|i| FloatInputEdge {
+                a: p[i],
+                b: p[(i + 1) % 4],
+                data,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn union_keeps_data() {
+        let subj = rect(0.0, 0.0, 0.25, 0.25, Source(1));
+        let clip = rect(0.25, 0.0, 0.5, 0.25, Source(2));
+
+        let mut overlay = FloatEdgeOverlay::with_edges(&subj, &clip);
+        let shapes = overlay.build_vector_shapes(OverlayRule::Union, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        let contour = &shapes[0][0];
+        assert_eq!(contour.len(), 6);
+
+        for edge in contour.iter() {
+            let x = 0.5 * (edge.a[0] + edge.b[0]);
+            if x < 0.25 {
+                assert_eq!(edge.data, Source(1));
+                assert_eq!(edge.fill & (CLIP_LEFT | CLIP_RIGHT), 0);
+            } else {
+                assert_eq!(edge.data, Source(2));
+                assert_eq!(edge.fill & (SUBJ_LEFT | SUBJ_RIGHT), 0);
+            }
+        }
+
+        let corners = [[0.0, 0.0], [0.5, 0.0], [0.5, 0.25], [0.0, 0.25]];
+        for corner in corners {
+            assert!(contour.iter().any(|e| e.a == corner));
+        }
+    }
+
+    #[test]
+    fn vectors_with_fixed_scale() {
+        let subj = rect(0.0, 0.0, 4.0, 4.0, Source(1));
+        let clip = rect(2.0, 2.0, 6.0, 6.0, Source(2));
+
+        let mut overlay = FloatEdgeOverlay::with_edges_fixed_scale(&subj, &clip, 1000.0).unwrap();
+        let vectors = overlay.build_vectors(OverlayRule::Intersect, FillRule::NonZero);
+
+        assert_eq!(vectors.len(), 4);
+        for edge in vectors.iter() {
+            assert_eq!(edge.fill.count_ones(), 3);
+            for p in [edge.a, edge.b] {
+                assert!((2.0..=4.0).contains(&p[0]));
+                assert!((2.0..=4.0).contains(&p[1]));
+            }
+        }
+
+        assert!(FloatEdgeOverlay::with_edges_fixed_scale(&subj, &clip, 0.0).is_err());
+        assert!(FloatEdgeOverlay::with_edges_fixed_scale(&subj, &clip, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn split_uses_store() {
+        let rect_bounds = FloatRect::new(-1.0, 11.0, -1.0, 11.0);
+        let adapter = FloatPointAdapter::<[f64; 2], i32>::new(rect_bounds);
+        let mut overlay = FloatEdgeOverlay::with_adapter(adapter, 8);
+        overlay.add_edges(rect(0.0, 0.0, 10.0, 10.0, Splits(0)), ShapeType::Subject);
+        overlay.add_edges(rect(5.0, -1.0, 6.0, 11.0, Splits(0)), ShapeType::Clip);
+
+        let vectors = overlay.build_vectors(OverlayRule::Difference, FillRule::NonZero);
+
+        assert!(*overlay.data_store() > 0);
+        assert!(vectors.iter().any(|e| e.data.0 > 0));
+        assert!(overlay.edges().all(|[a, b]| a != b));
+    }
+}
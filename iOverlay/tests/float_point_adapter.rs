@@ -58,7 +58,7 @@ mod tests {
             rect.max_y + 0.1,
         );
 
-        let adapter_100 = FloatPointAdapter::<_, i32>::with_scale(buffer_rect.clone(), 100.0);
+        let adapter_100 = FloatPointAdapter::<_, i32>::with_scale(buffer_rect, 100.0);
         let adapter_1000 = FloatPointAdapter::<_, i32>::with_scale(buffer_rect, 1000.0);
 
         let subj_100 = FloatOverlay::with_adapter(adapter_100, shape.len())
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::error::OverlayError;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::memory::MemoryBudget;
+    use i_overlay::core::overlay::{IntOverlayOptions, Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::solver::Solver;
+    use i_overlay::i_shape::flat::buffer::FlatContoursBuffer;
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint<i32>> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]
+    }
+
+    fn star(n: usize, r0: f64, r1: f64) -> Vec<IntPoint<i32>> {
+        let count = 2 * n;
+        (0..count)
+            .map(|i| {
+                let r = if i % 2 == 0 { r0 } else { r1 };
+                let a = core::f64::consts::TAU * i as f64 / count as f64;
+                IntPoint::new((r * a.cos()) as i32, (r * a.sin()) as i32)
+            })
+            .collect()
+    }
+
+    fn overlay(solver: Solver) -> Overlay<i32> {
+        let mut overlay = Overlay::new_custom(64, IntOverlayOptions::default(), solver);
+        overlay.add_contour(&star(64, 1000.0, 400.0), ShapeType::Subject);
+        for i in 0..8 {
+            overlay.add_contour(&square(-900 + 200 * i, -100, 300), ShapeType::Clip);
+        }
+        overlay
+    }
+
+    fn check(solver: Solver) {
+        let mut expected = FlatContoursBuffer::default();
+        overlay(solver).overlay_into(OverlayRule::Xor, FillRule::NonZero, &mut expected);
+        assert!(!expected.ranges.is_empty());
+
+        let mut output = FlatContoursBuffer::default();
+        overlay(solver)
+            .try_overlay_into(
+                OverlayRule::Xor,
+                FillRule::NonZero,
+                MemoryBudget::UNLIMITED,
+                &mut output,
+            )
+            .unwrap();
+        assert_eq!(output.points, expected.points);
+        assert_eq!(output.ranges, expected.ranges);
+
+        // every budget below the need fails cleanly, the first sufficient one matches
+        let mut max_bytes = 0;
+        loop {
+            let result = overlay(solver).try_overlay_into(
+                OverlayRule::Xor,
+                FillRule::NonZero,
+                MemoryBudget::with_max_bytes(max_bytes),
+                &mut output,
+            );
+            match result {
+                Ok(()) => break,
+                Err(error) => assert_eq!(error, OverlayError::MemoryBudgetExceeded),
+            }
+            max_bytes += 256;
+        }
+        assert!(max_bytes > 0);
+        assert_eq!(output.points, expected.points);
+        assert_eq!(output.ranges, expected.ranges);
+    }
+
+    #[test]
+    fn test_list() {
+        check(Solver::LIST);
+    }
+
+    #[test]
+    fn test_tree() {
+        check(Solver::TREE);
+    }
+
+    #[test]
+    fn test_frag() {
+        check(Solver::FRAG);
+    }
+
+    #[test]
+    fn test_retry_after_failure() {
+        let mut overlay = overlay(Solver::AUTO);
+        let mut output = FlatContoursBuffer::default();
+
+        let result = overlay.try_overlay_into(
+            OverlayRule::Union,
+            FillRule::NonZero,
+            MemoryBudget::with_max_bytes(1024),
+            &mut output,
+        );
+        assert_eq!(result, Err(OverlayError::MemoryBudgetExceeded));
+
+        overlay
+            .try_overlay_into(
+                OverlayRule::Union,
+                FillRule::NonZero,
+                MemoryBudget::UNLIMITED,
+                &mut output,
+            )
+            .unwrap();
+        assert_eq!(output.ranges.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        // input is validated by the try_with_* constructors, before any budgeted work
+        let subj = [square(0, 0, 10)];
+        let clip = [square(i32::MAX - 5, 0, 1)];
+        let result = Overlay::try_with_contours(&subj, &clip);
+        assert!(matches!(result, Err(OverlayError::CoordinateOutOfRange(_))));
+    }
+}
@@ -122,6 +122,7 @@ mod tests {
             OverlayRule::Xor => {
                 assert_eq!(true, overlay::is_group_of_shapes_one_of(&result, &test.xor))
             }
+            OverlayRule::Custom(_) => {}
         }
     }
 
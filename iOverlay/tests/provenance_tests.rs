@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::error::{InputRole, PointIndex};
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::provenance::VertexSource;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::float::string_overlay::FloatStringOverlay;
+    use i_overlay::i_float::adapter::FloatPointAdapter;
+    use i_overlay::i_float::float::rect::FloatRect;
+    use i_overlay::string::clip::ClipRule;
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint<i32>> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]
+    }
+
+    fn index(role: InputRole, contour: usize, point: usize) -> PointIndex {
+        PointIndex { role, contour, point }
+    }
+
+    fn tracked(subj: &[Vec<IntPoint<i32>>], clip: &[Vec<IntPoint<i32>>]) -> Overlay<i32> {
+        let mut overlay = Overlay::new_tracked(16);
+        for contour in subj {
+            overlay.add_contour(contour, ShapeType::Subject);
+        }
+        for contour in clip {
+            overlay.add_contour(contour, ShapeType::Clip);
+        }
+        overlay
+    }
+
+    #[test]
+    fn test_input_vertices() {
+        let subj = [square(0, 0, 10), square(20, 0, 10)];
+        let clip = [square(5, 5, 10)];
+        let mut overlay = tracked(&subj, &clip);
+        let (shapes, sources) = overlay.overlay_with_vertex_sources(OverlayRule::Union, FillRule::NonZero);
+        let sources = sources.unwrap();
+
+        assert_eq!(
+            shapes,
+            tracked(&subj, &clip).overlay(OverlayRule::Union, FillRule::NonZero)
+        );
+        for (shape, shape_sources) in shapes.iter().zip(sources.iter()) {
+            for (contour, contour_sources) in shape.iter().zip(shape_sources.iter()) {
+                assert_eq!(contour.len(), contour_sources.len());
+                for (p, source) in contour.iter().zip(contour_sources.iter()) {
+                    if let VertexSource::Input(index) = source {
+                        let input = match index.role {
+                            InputRole::Subject => &subj[index.contour],
+                            _ => &clip[index.contour],
+                        };
+                        assert_eq!(input[index.point], *p);
+                    }
+                }
+            }
+        }
+
+        let far = shapes
+            .iter()
+            .zip(sources.iter())
+            .find(|(shape, _)| shape[0].contains(&IntPoint::new(30, 10)))
+            .unwrap();
+        let vertex = far.0[0].iter().position(|p| *p == IntPoint::new(30, 10)).unwrap();
+        assert_eq!(
+            far.1[0][vertex],
+            VertexSource::Input(index(InputRole::Subject, 1, 2))
+        );
+    }
+
+    #[test]
+    fn test_intersections() {
+        let mut overlay = tracked(&[square(0, 0, 10)], &[square(5, 5, 10)]);
+        let (shapes, sources) = overlay.overlay_with_vertex_sources(OverlayRule::Union, FillRule::NonZero);
+        let sources = sources.unwrap();
+
+        let contour = &shapes[0][0];
+        let at = |x, y| &sources[0][0][contour.iter().position(|p| *p == IntPoint::new(x, y)).unwrap()];
+
+        assert_eq!(
+            at(10, 5),
+            &VertexSource::Intersection(vec![
+                index(InputRole::Subject, 0, 1),
+                index(InputRole::Clip, 0, 0)
+            ])
+        );
+        assert_eq!(
+            at(5, 10),
+            &VertexSource::Intersection(vec![
+                index(InputRole::Subject, 0, 2),
+                index(InputRole::Clip, 0, 3)
+            ])
+        );
+        assert_eq!(at(0, 0), &VertexSource::Input(index(InputRole::Subject, 0, 0)));
+        assert_eq!(at(15, 15), &VertexSource::Input(index(InputRole::Clip, 0, 2)));
+    }
+
+    #[test]
+    fn test_collinear_input() {
+        // the subject has a collinear vertex on its bottom edge, dropped when added
+        let subj = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(4, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+        let clip = vec![
+            IntPoint::new(7, -5),
+            IntPoint::new(9, -5),
+            IntPoint::new(9, 5),
+            IntPoint::new(7, 5),
+        ];
+        let mut overlay = tracked(&[subj], &[clip]);
+        let (shapes, sources) =
+            overlay.overlay_with_vertex_sources(OverlayRule::Difference, FillRule::NonZero);
+        let sources = sources.unwrap();
+
+        let contour = &shapes[0][0];
+        let vertex = contour.iter().position(|p| *p == IntPoint::new(7, 0)).unwrap();
+        assert_eq!(
+            sources[0][0][vertex],
+            VertexSource::Intersection(vec![
+                index(InputRole::Subject, 0, 1),
+                index(InputRole::Clip, 0, 3)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_not_tracked() {
+        let mut overlay = Overlay::with_contour(&square(0, 0, 10), &square(5, 5, 10));
+        let (shapes, sources) = overlay.overlay_with_vertex_sources(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(shapes.len(), 1);
+        assert!(sources.is_none());
+    }
+
+    #[test]
+    fn test_tracked_constructors() {
+        let subj = [square(0, 0, 10)];
+        let clip = [square(5, 5, 10)];
+
+        let mut overlay = Overlay::with_contours_tracked(&subj, &clip);
+        let (_, sources) = overlay.overlay_with_vertex_sources(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(sources.unwrap()[0][0].len(), 8);
+
+        let mut overlay = Overlay::with_shapes_tracked(&[subj.to_vec()], &[clip.to_vec()]);
+        let (_, sources) = overlay.overlay_with_vertex_sources(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(sources.unwrap()[0][0].len(), 8);
+
+        // the log survives a reinit, with the contour indices restarting
+        let float_subj = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let mut overlay = FloatOverlay::with_subj_and_clip_tracked(&float_subj, &float_subj);
+        overlay.reinit_with_subj_and_clip(&float_subj, &[[2.0, 0.0], [3.0, 0.0], [3.0, 1.0]]);
+        let (_, sources) = overlay.overlay_with_vertex_sources(OverlayRule::Union, FillRule::NonZero);
+        let sources = sources.unwrap();
+        assert_eq!(sources.len(), 2);
+        let clip_source = sources.iter().flatten().flatten().find(|source| match source {
+            VertexSource::Input(index) => index.role == InputRole::Clip,
+            VertexSource::Intersection(_) => false,
+        });
+        assert!(matches!(clip_source, Some(VertexSource::Input(index)) if index.contour == 0));
+
+        let line = [[-1.0, 0.5], [2.0, 0.5]];
+        let (_, attributes) = FloatStringOverlay::with_shape_and_string_tracked(&float_subj, &line)
+            .clip_string_lines_with_attributes(
+                FillRule::NonZero,
+                ClipRule {
+                    invert: false,
+                    boundary_included: false,
+                },
+                Default::default(),
+                |index: PointIndex| [index.point as f64],
+            );
+        assert_eq!(attributes.unwrap(), [[[1.0 / 3.0], [2.0 / 3.0]]]);
+    }
+
+    #[test]
+    fn test_float() {
+        let subj = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let clip = [[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]];
+
+        let mut overlay = FloatOverlay::with_subj_and_clip_tracked(&subj, &clip);
+        let (shapes, sources) =
+            overlay.overlay_with_vertex_sources(OverlayRule::Intersect, FillRule::NonZero);
+        let sources = sources.unwrap();
+
+        let contour = &shapes[0][0];
+        assert_eq!(contour.len(), 4);
+        for (p, source) in contour.iter().zip(sources[0][0].iter()) {
+            match source {
+                VertexSource::Input(index) => {
+                    let input = match index.role {
+                        InputRole::Subject => &subj,
+                        _ => &clip,
+                    };
+                    assert_eq!(input[index.point], *p);
+                }
+                VertexSource::Intersection(edges) => {
+                    assert_eq!(edges.len(), 2);
+                    assert_eq!(edges[0].role, InputRole::Subject);
+                    assert_eq!(edges[1].role, InputRole::Clip);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_attributes() {
+        let subj = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+        // shares the edge (4, 0) - (4, 4) with the subject
+        let clip = [[4.0, 0.0], [8.0, 2.0], [4.0, 4.0]];
+        let subj_z = [0.0, 4.0, 8.0, 4.0];
+        let clip_z = [2.0, 2.0, 2.0];
+        let z = |index: PointIndex| match index.role {
+            InputRole::Subject => [subj_z[index.point], 1.0],
+            _ => [clip_z[index.point], 1.0],
+        };
+
+        let mut overlay = FloatOverlay::with_subj_and_clip(&subj, &clip);
+        let (_, attributes) = overlay.overlay_with_attributes(OverlayRule::Union, FillRule::NonZero, z);
+        assert!(attributes.is_none());
+
+        let mut overlay = FloatOverlay::with_subj_and_clip_tracked(&subj, &clip);
+        let (shapes, attributes) = overlay.overlay_with_attributes(OverlayRule::Union, FillRule::NonZero, z);
+        let attributes = attributes.unwrap();
+
+        let contour = &shapes[0][0];
+        assert_eq!(contour.len(), 5);
+        let at = |x: f64, y: f64| attributes[0][0][contour.iter().position(|p| *p == [x, y]).unwrap()];
+
+        assert_eq!(at(0.0, 0.0), [0.0, 1.0]);
+        assert_eq!(at(8.0, 2.0), [2.0, 1.0]);
+        // input vertices of both shapes share the position
+        assert_eq!(at(4.0, 0.0), [3.0, 1.0]);
+        assert_eq!(at(4.0, 4.0), [5.0, 1.0]);
+    }
+
+    #[test]
+    fn test_string_attributes() {
+        let square = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+        let line = [[-2.0, 1.0], [2.0, 1.0], [2.0, 6.0]];
+        let m = [0.0, 4.0, 9.0];
+
+        let adapter = FloatPointAdapter::<[f64; 2], i32>::new(FloatRect::new(-2.0, 4.0, 0.0, 6.0));
+        let (paths, attributes) = FloatStringOverlay::with_adapter_tracked(adapter, 8)
+            .unsafe_add_shape_contour(&square)
+            .unsafe_add_string_line(&line)
+            .clip_string_lines_with_attributes(
+                FillRule::NonZero,
+                ClipRule {
+                    invert: false,
+                    boundary_included: false,
+                },
+                Default::default(),
+                |index: PointIndex| {
+                    assert_eq!(index.role, InputRole::String);
+                    [m[index.point]]
+                },
+            );
+        let attributes = attributes.unwrap();
+
+        assert_eq!(paths.len(), 1);
+        let path = &paths[0];
+        assert_eq!(path.len(), 3);
+        for (p, a) in path.iter().zip(attributes[0].iter()) {
+            let expected = match *p {
+                [0.0, 1.0] => 2.0,
+                [2.0, 1.0] => 4.0,
+                [2.0, 4.0] => 7.0,
+                _ => panic!("unexpected vertex {:?}", p),
+            };
+            assert_eq!(a[0], expected);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{IntOverlayOptions, Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::solver::{Solver, Strategy};
+    use i_overlay::core::stats::NoClock;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::string::clip::ClipRule;
+    use i_overlay::string::overlay::StringOverlay;
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint<i32>> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]
+    }
+
+    fn star(n: usize, r0: f64, r1: f64, dx: f64) -> Vec<IntPoint<i32>> {
+        let count = 2 * n;
+        (0..count)
+            .map(|i| {
+                let r = if i % 2 == 0 { r0 } else { r1 };
+                let a = core::f64::consts::TAU * i as f64 / count as f64;
+                IntPoint::new((dx + r * a.cos()) as i32, (r * a.sin()) as i32)
+            })
+            .collect()
+    }
+
+    fn stars(solver: Solver) -> Overlay<i32> {
+        let mut overlay = Overlay::new_custom(2400, IntOverlayOptions::default(), solver);
+        overlay.add_contour(&star(700, 100_000.0, 60_000.0, 0.0), ShapeType::Subject);
+        overlay.add_contour(&star(500, 100_000.0, 70_000.0, 30_000.0), ShapeType::Clip);
+        overlay
+    }
+
+    #[test]
+    fn test_squares() {
+        let mut overlay = Overlay::with_contour(&square(0, 0, 10), &square(5, 5, 10));
+        let (shapes, stats) = overlay.overlay_with_stats(OverlayRule::Xor, FillRule::EvenOdd, &mut NoClock);
+
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(stats.input_edges, 8);
+        assert_eq!(stats.split_edges, 12);
+        assert_eq!(stats.intersections, 4);
+        assert_eq!(stats.snap_escalations, 0);
+        assert_eq!(stats.split_strategy, Some(Strategy::List));
+        assert_eq!(stats.nodes, 10);
+        assert_eq!(stats.links, 12);
+        assert_eq!(stats.output_edges, 12);
+        assert_eq!(stats.total_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_no_intersections() {
+        let mut overlay = Overlay::with_contour(&square(0, 0, 10), &square(20, 0, 10));
+        let (_, stats) = overlay.overlay_with_stats(OverlayRule::Union, FillRule::NonZero, &mut NoClock);
+
+        assert_eq!(stats.split_edges, 8);
+        assert_eq!(stats.intersections, 0);
+        assert_eq!(stats.output_edges, 8);
+    }
+
+    #[test]
+    fn test_strategy() {
+        for (solver, strategy) in [
+            (Solver::LIST, Strategy::List),
+            (Solver::TREE, Strategy::Tree),
+            (Solver::FRAG, Strategy::Frag),
+        ] {
+            let (shapes, stats) =
+                stars(solver).overlay_with_stats(OverlayRule::Union, FillRule::NonZero, &mut NoClock);
+            let expected = stars(solver).overlay(OverlayRule::Union, FillRule::NonZero);
+
+            assert_eq!(shapes, expected);
+            assert_eq!(stats.split_strategy, Some(strategy));
+            assert!(stats.intersections > 0);
+            assert!(stats.split_edges > stats.input_edges);
+            assert!(stats.links >= stats.output_edges);
+        }
+    }
+
+    #[test]
+    fn test_clock() {
+        let mut reads = 0;
+        let mut clock = || {
+            reads += 1;
+            Duration::from_micros(10 * reads)
+        };
+
+        let (_, stats) =
+            stars(Solver::AUTO).overlay_with_stats(OverlayRule::Xor, FillRule::EvenOdd, &mut clock);
+
+        assert_eq!(stats.split_time, Duration::from_micros(10));
+        assert_eq!(stats.build_time, Duration::from_micros(10));
+        assert_eq!(stats.extract_time, Duration::from_micros(10));
+        assert_eq!(reads, 4);
+    }
+
+    #[test]
+    fn test_float() {
+        let subj = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let clip = [[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]];
+
+        let (shapes, stats) = FloatOverlay::with_subj_and_clip(&subj, &clip).overlay_with_stats(
+            OverlayRule::Union,
+            FillRule::NonZero,
+            &mut NoClock,
+        );
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(stats.intersections, 4);
+        assert_eq!(stats.output_edges, 8);
+    }
+
+    #[test]
+    fn test_string() {
+        let mut overlay = StringOverlay::with_shape_contour(&square(0, 0, 10));
+        overlay.add_string_path(&[IntPoint::new(-5, 5), IntPoint::new(15, 5)]);
+
+        let clip_rule = ClipRule {
+            invert: false,
+            boundary_included: false,
+        };
+        let (paths, stats) = overlay.clip_string_lines_with_stats(
+            FillRule::NonZero,
+            clip_rule,
+            Solver::default(),
+            &mut NoClock,
+        );
+
+        assert_eq!(paths, vec![vec![IntPoint::new(0, 5), IntPoint::new(10, 5)]]);
+        assert_eq!(stats.input_edges, 5);
+        assert_eq!(stats.intersections, 4);
+        assert_eq!(stats.split_edges, 9);
+        assert_eq!(stats.output_edges, 1);
+    }
+}
@@ -0,0 +1,39 @@
+use i_overlay::mesh::stroke::mesh::StrokeMesh;
+use i_overlay::mesh::stroke::offset::StrokeOffset;
+use i_overlay::mesh::style::StrokeStyle;
+use i_overlay::mesh::triangulation::MeshOptions;
+use std::time::{Duration, Instant};
+
+fn wave(count: usize) -> Vec<[f64; 2]> {
+    (0..count)
+        .map(|i| {
+            let x = i as f64;
+            [x, 20.0 * (0.05 * x).sin()]
+        })
+        .collect()
+}
+
+fn time<R>(f: impl FnOnce() -> R) -> (R, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+#[test]
+fn stroke_mesh_distance_scales_with_stroke() {
+    let path = wave(32_000);
+    let style = StrokeStyle::new(2.0);
+
+    let (shapes, stroke_time) = time(|| path.stroke(style.clone(), false));
+    let (mesh, mesh_time) = time(|| path.stroke_mesh(style.clone(), false, MeshOptions::default()));
+
+    assert!(!shapes.is_empty());
+    assert_eq!(mesh.distance.len(), mesh.vertices.len());
+
+    // the mesh adds triangulation and distances on top of the stroke, both near linear
+    eprintln!("stroke: {stroke_time:?}, stroke_mesh: {mesh_time:?}");
+    assert!(
+        mesh_time < 5 * stroke_time + Duration::from_millis(500),
+        "stroke: {stroke_time:?}, stroke_mesh: {mesh_time:?}"
+    );
+}
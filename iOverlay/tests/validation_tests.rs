@@ -0,0 +1,300 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::error::{InputRole, OverlayError, PointIndex};
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::float::clip::FloatClip;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::float::single::SingleFloatOverlay;
+    use i_overlay::float::string_overlay::FloatStringOverlay;
+    use i_overlay::mesh::outline::offset::OutlineOffset;
+    use i_overlay::mesh::stroke::offset::StrokeOffset;
+    use i_overlay::mesh::style::{OutlineStyle, StrokeStyle};
+    use i_overlay::string::clip::{ClipRule, IntClip};
+    use i_overlay::string::overlay::StringOverlay;
+
+    const CLIP_RULE: ClipRule = ClipRule {
+        invert: false,
+        boundary_included: false,
+    };
+
+    fn int_square(x: i32, y: i32, size: i32) -> Vec<IntPoint<i32>> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<[f64; 2]> {
+        vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]
+    }
+
+    #[test]
+    fn test_overlay_valid() {
+        let overlay = Overlay::try_with_contours(&[int_square(0, 0, 10)], &[int_square(5, 5, 10)]);
+        let result = overlay.unwrap().overlay(OverlayRule::Union, FillRule::NonZero);
+
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_overlay_out_of_range() {
+        let mut clip = int_square(5, 5, 10);
+        clip[2] = IntPoint::new(1 << 30, 0);
+
+        let subj = [int_square(0, 0, 10)];
+        let result = Overlay::try_with_contours(&subj, &[int_square(0, 0, 10), clip]);
+
+        let expected = PointIndex {
+            role: InputRole::Clip,
+            contour: 1,
+            point: 2,
+        };
+        assert_eq!(result.err(), Some(OverlayError::CoordinateOutOfRange(expected)));
+    }
+
+    #[test]
+    fn test_overlay_shapes_out_of_range() {
+        let mut hole = int_square(2, 2, 2);
+        hole[3] = IntPoint::new(0, -(1 << 30));
+        let subj = vec![vec![int_square(20, 0, 5)], vec![int_square(0, 0, 10), hole]];
+
+        let expected = PointIndex {
+            role: InputRole::Subject,
+            contour: 2,
+            point: 3,
+        };
+        assert_eq!(
+            Overlay::try_with_shapes(&subj, &[]).err(),
+            Some(OverlayError::CoordinateOutOfRange(expected))
+        );
+    }
+
+    #[test]
+    fn test_i16_range() {
+        let subj = vec![vec![
+            IntPoint::<i16>::new(0, 0),
+            IntPoint::new(20_000, 0),
+            IntPoint::new(20_000, 20_000),
+        ]];
+
+        assert_eq!(
+            Overlay::try_with_contours(&subj, &[]).err(),
+            Some(OverlayError::CoordinateOutOfRange(PointIndex {
+                role: InputRole::Subject,
+                contour: 0,
+                point: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_add_paths_unchecked() {
+        // the infallible add paths take the input as is and leave validation to the try_* constructors
+        let mut overlay = Overlay::<i32>::new(16);
+        overlay.add_contour(&int_square(0, 0, 10), ShapeType::Subject);
+        overlay.add_path_iter(int_square(5, 5, 10).into_iter(), ShapeType::Clip);
+
+        assert_eq!(overlay.overlay(OverlayRule::Union, FillRule::NonZero).len(), 1);
+    }
+
+    #[test]
+    fn test_string_overlay_out_of_range() {
+        let paths = vec![
+            vec![IntPoint::new(-5, 5), IntPoint::new(15, 5)],
+            vec![IntPoint::new(-5, 6), IntPoint::new(i32::MIN, 6)],
+        ];
+        let result = StringOverlay::try_with_shapes_and_paths(&[vec![int_square(0, 0, 10)]], &paths);
+
+        let expected = PointIndex {
+            role: InputRole::String,
+            contour: 1,
+            point: 1,
+        };
+        assert_eq!(result.err(), Some(OverlayError::CoordinateOutOfRange(expected)));
+    }
+
+    #[test]
+    fn test_string_overlay_valid() {
+        let paths = vec![vec![IntPoint::new(-5, 5), IntPoint::new(15, 5)]];
+        let overlay = StringOverlay::try_with_shapes_and_paths(&[vec![int_square(0, 0, 10)]], &paths);
+
+        let result = overlay.unwrap().clip_string_lines(FillRule::NonZero, CLIP_RULE);
+        assert_eq!(result, vec![vec![IntPoint::new(0, 5), IntPoint::new(10, 5)]]);
+    }
+
+    #[test]
+    fn test_float_overlay_non_finite() {
+        let subj = vec![square(0.0, 0.0, 1.0), square(2.0, 0.0, 1.0)];
+        let mut clip = square(0.5, 0.5, 1.0);
+        clip[3][1] = f64::NAN;
+
+        let result: Result<FloatOverlay<[f64; 2]>, _> = FloatOverlay::try_with_subj_and_clip(&subj, &clip);
+
+        let expected = PointIndex {
+            role: InputRole::Clip,
+            contour: 0,
+            point: 3,
+        };
+        assert_eq!(result.err(), Some(OverlayError::NonFiniteCoordinate(expected)));
+    }
+
+    #[test]
+    fn test_float_overlay_valid() {
+        let overlay = FloatOverlay::try_with_subj_and_clip(&square(0.0, 0.0, 1.0), &square(0.5, 0.5, 1.0));
+        let result = overlay.unwrap().overlay(OverlayRule::Union, FillRule::NonZero);
+
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_float_string_overlay_non_finite() {
+        let shape = square(0.0, 0.0, 1.0);
+        let string = vec![
+            vec![[-1.0, 0.5], [2.0, 0.5]],
+            vec![[-1.0, 0.7], [f64::INFINITY, 0.7]],
+        ];
+
+        let result: Result<FloatStringOverlay<[f64; 2]>, _> =
+            FloatStringOverlay::try_with_shape_and_string(&shape, &string);
+
+        let expected = PointIndex {
+            role: InputRole::String,
+            contour: 1,
+            point: 1,
+        };
+        assert_eq!(result.err(), Some(OverlayError::NonFiniteCoordinate(expected)));
+    }
+
+    #[test]
+    fn test_float_engines() {
+        let subj = square(0.0, 0.0, 1.0);
+        let mut clip = square(0.5, 0.5, 1.0);
+
+        let overlay = FloatOverlay::<[f64; 2], i64>::try_from_subj_and_clip(&subj, &clip);
+        assert_eq!(
+            overlay
+                .unwrap()
+                .overlay(OverlayRule::Union, FillRule::NonZero)
+                .len(),
+            1
+        );
+        assert_eq!(
+            subj.try_overlay_as::<i16>(&clip, OverlayRule::Union, FillRule::NonZero)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        clip[1][0] = f64::NAN;
+        let expected = OverlayError::NonFiniteCoordinate(PointIndex {
+            role: InputRole::Clip,
+            contour: 0,
+            point: 1,
+        });
+        assert_eq!(
+            subj.try_overlay(&clip, OverlayRule::Union, FillRule::NonZero),
+            Err(expected)
+        );
+        assert_eq!(
+            subj.try_overlay_as::<i64>(&clip, OverlayRule::Union, FillRule::NonZero),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn test_float_clip() {
+        let shape = square(0.0, 0.0, 1.0);
+        let line = vec![[-1.0, 0.5], [2.0, 0.5]];
+
+        let string = FloatStringOverlay::<[f64; 2], i64>::try_from_shape_and_string(&shape, &line);
+        assert_eq!(
+            string
+                .unwrap()
+                .clip_string_lines_with_solver(FillRule::NonZero, CLIP_RULE, Default::default()),
+            vec![vec![[0.0, 0.5], [1.0, 0.5]]]
+        );
+        assert_eq!(
+            line.try_clip_by_as::<i64>(&shape, FillRule::NonZero, CLIP_RULE),
+            Ok(vec![vec![[0.0, 0.5], [1.0, 0.5]]])
+        );
+
+        let bad = vec![[-1.0, 0.5], [f64::NAN, 0.5]];
+        assert_eq!(
+            bad.try_clip_by(&shape, FillRule::NonZero, CLIP_RULE),
+            Err(OverlayError::NonFiniteCoordinate(PointIndex {
+                role: InputRole::String,
+                contour: 0,
+                point: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_int_clip() {
+        let shape = int_square(0, 0, 10);
+        let paths = vec![vec![IntPoint::new(-5, 5), IntPoint::new(15, 5)]];
+
+        assert_eq!(
+            shape.try_clip_paths(&paths, FillRule::NonZero, CLIP_RULE),
+            Ok(vec![vec![IntPoint::new(0, 5), IntPoint::new(10, 5)]])
+        );
+
+        let mut bad = int_square(0, 0, 10);
+        bad[1] = IntPoint::new(i32::MAX, 0);
+        assert_eq!(
+            vec![vec![bad]].try_clip_paths(&paths, FillRule::NonZero, CLIP_RULE),
+            Err(OverlayError::CoordinateOutOfRange(PointIndex {
+                role: InputRole::Subject,
+                contour: 0,
+                point: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_stroke() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, f64::NAN]];
+        let valid = [[0.0, 0.0], [10.0, 0.0]];
+
+        assert_eq!(
+            path.try_stroke(StrokeStyle::new(1.0), false),
+            Err(OverlayError::NonFiniteCoordinate(PointIndex {
+                role: InputRole::Subject,
+                contour: 0,
+                point: 2,
+            }))
+        );
+        assert_eq!(
+            valid.try_stroke(StrokeStyle::new(f64::NAN), false),
+            Err(OverlayError::NonFiniteStyle)
+        );
+        assert_eq!(valid.try_stroke(StrokeStyle::new(1.0), false).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_outline() {
+        let shape = vec![
+            square(0.0, 0.0, 10.0),
+            vec![[2.0, 2.0], [2.0, f64::NEG_INFINITY], [4.0, 4.0]],
+        ];
+
+        assert_eq!(
+            shape.try_outline(&OutlineStyle::new(1.0)),
+            Err(OverlayError::NonFiniteCoordinate(PointIndex {
+                role: InputRole::Subject,
+                contour: 1,
+                point: 1,
+            }))
+        );
+        assert_eq!(
+            shape[0].try_outline(&OutlineStyle::new(f64::INFINITY)),
+            Err(OverlayError::NonFiniteStyle)
+        );
+        assert_eq!(shape[0].try_outline(&OutlineStyle::new(1.0)).unwrap().len(), 1);
+    }
+}
@@ -1,5 +1,8 @@
+use i_overlay::mesh::style::LineJoin;
 use i_overlay::mesh::variable_stroke::offset::VariableStrokeOffset;
-use i_overlay::mesh::variable_stroke::{StrokeVertex, VariableStrokeStyle};
+use i_overlay::mesh::variable_stroke::{
+    StrokeInterpolation, StrokeVertex, VariableLineCap, VariableStrokeStyle,
+};
 use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::time::{Duration, Instant};
 
@@ -7,14 +10,30 @@ use std::time::{Duration, Instant};
 fn randomized_variable_stroke_does_not_panic() {
     for iteration in 0..512 {
         let seed = next_stress_seed(iteration as u64);
-        run_variable_stroke_stress_case(seed, iteration);
+        run_variable_stroke_stress_case(seed, iteration, StressStyle::Round);
+    }
+}
+
+#[test]
+fn randomized_variable_stroke_caps_and_joins_do_not_panic() {
+    for iteration in 0..512 {
+        let seed = next_stress_seed(iteration as u64);
+        run_variable_stroke_stress_case(seed, iteration, StressStyle::CapsAndJoins);
+    }
+}
+
+#[test]
+fn randomized_smooth_variable_stroke_does_not_panic() {
+    for iteration in 0..256 {
+        let seed = next_stress_seed(iteration as u64);
+        run_variable_stroke_stress_case(seed, iteration, StressStyle::Smooth);
     }
 }
 
 #[test]
 fn disconnected_drawable_sections_do_not_build_join_between_centers() {
     let seed = 11_958_792_495_002_733_140;
-    run_variable_stroke_stress_case(seed, 163);
+    run_variable_stroke_stress_case(seed, 163, StressStyle::Round);
 }
 
 #[test]
@@ -26,7 +45,7 @@ fn randomized_variable_stroke_stress() {
     let mut iteration = 0usize;
 
     while Instant::now() < deadline {
-        run_variable_stroke_stress_case(seed, iteration);
+        run_variable_stroke_stress_case(seed, iteration, STRESS_STYLES[iteration % 3]);
         seed = next_stress_seed(seed);
         iteration += 1;
     }
@@ -34,11 +53,40 @@ fn randomized_variable_stroke_stress() {
     eprintln!("variable-stroke stress completed: iterations={iteration} seconds={seconds}");
 }
 
-fn run_variable_stroke_stress_case(seed: u64, iteration: usize) {
+#[derive(Debug, Clone, Copy)]
+enum StressStyle {
+    Round,
+    CapsAndJoins,
+    Smooth,
+}
+
+const STRESS_STYLES: [StressStyle; 3] = [StressStyle::Round, StressStyle::CapsAndJoins, StressStyle::Smooth];
+
+fn run_variable_stroke_stress_case(seed: u64, iteration: usize, kind: StressStyle) {
     let mut rng = StressRng::new(seed);
     let path = random_variable_stroke_path(&mut rng);
     let round_angle = rng.range_f32(0.01, 0.8);
-    let style = VariableStrokeStyle::new().round_angle(round_angle);
+    let mut style = VariableStrokeStyle::new().round_angle(round_angle);
+    match kind {
+        StressStyle::Round => {}
+        StressStyle::CapsAndJoins => {
+            style = style
+                .start_cap(random_cap(&mut rng))
+                .end_cap(random_cap(&mut rng))
+                .line_join(random_join(&mut rng));
+        }
+        StressStyle::Smooth => {
+            let interpolation = match rng.next_u32() % 3 {
+                0 => StrokeInterpolation::Linear,
+                1 => StrokeInterpolation::CatmullRom,
+                _ => StrokeInterpolation::MonotoneCubic,
+            };
+            style = style
+                .interpolation(interpolation)
+                .tolerance(rng.range_f32(0.01, 2.0))
+                .taper(rng.range_f32(0.0, 200.0), rng.range_f32(0.0, 200.0));
+        }
+    }
 
     let result = catch_unwind(AssertUnwindSafe(|| {
         let shapes = path.variable_stroke(style);
@@ -57,7 +105,7 @@ fn run_variable_stroke_stress_case(seed: u64, iteration: usize) {
 
     if let Err(payload) = result {
         panic!(
-            "variable-stroke stress failed: iteration={iteration} seed={seed} path={path:?} panic={}",
+            "variable-stroke stress failed: iteration={iteration} seed={seed} style={kind:?} path={path:?} panic={}",
             panic_message(payload)
         );
     }
@@ -78,7 +126,7 @@ fn random_variable_stroke_path(rng: &mut StressRng) -> Vec<StrokeVertex<[f32; 2]
         };
         path.push(StrokeVertex::new(point, width));
 
-        if index + 1 == count || rng.next_u32() % 20 == 0 {
+        if index + 1 == count || rng.next_u32().is_multiple_of(20) {
             continue;
         }
 
@@ -101,6 +149,23 @@ fn random_variable_stroke_path(rng: &mut StressRng) -> Vec<StrokeVertex<[f32; 2]
     path
 }
 
+fn random_cap(rng: &mut StressRng) -> VariableLineCap<f32> {
+    match rng.next_u32() % 4 {
+        0 => VariableLineCap::Butt,
+        1 => VariableLineCap::Square,
+        2 => VariableLineCap::Custom(&[[0.5, -1.0], [1.5, 0.0], [0.5, 1.0]]),
+        _ => VariableLineCap::Round(rng.range_f32(0.01, 0.8)),
+    }
+}
+
+fn random_join(rng: &mut StressRng) -> LineJoin<f32> {
+    match rng.next_u32() % 3 {
+        0 => LineJoin::Bevel,
+        1 => LineJoin::Miter(rng.range_f32(0.0, 3.2)),
+        _ => LineJoin::Round(rng.range_f32(0.01, 0.8)),
+    }
+}
+
 fn assert_valid_shapes(shapes: &[Vec<Vec<[f32; 2]>>], seed: u64) {
     for contour in shapes.iter().flatten() {
         assert!(contour.len() >= 3, "seed={seed} contour={contour:?}");
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::Overlay;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::float::single::SingleFloatOverlay;
+    use i_shape::int::shape::IntContour;
+
+    fn rect(x0: i32, y0: i32, x1: i32, y1: i32) -> IntContour<i32> {
+        vec![
+            IntPoint::new(x0, y0),
+            IntPoint::new(x1, y0),
+            IntPoint::new(x1, y1),
+            IntPoint::new(x0, y1),
+        ]
+    }
+
+    /// A rectangle the way overlay output starts it, from the top left corner.
+    fn out_rect(x0: i32, y0: i32, x1: i32, y1: i32) -> IntContour<i32> {
+        let mut contour = rect(x0, y0, x1, y1);
+        contour.rotate_right(1);
+        contour
+    }
+
+    fn footprints() -> Vec<IntContour<i32>> {
+        vec![
+            rect(0, 0, 20, 20),
+            rect(10, 5, 30, 15),
+            rect(5, 10, 25, 30),
+            rect(12, 8, 18, 12),
+            // clockwise, removes one level
+            rect(2, 2, 4, 4).into_iter().rev().collect(),
+        ]
+    }
+
+    #[test]
+    fn test_levels_match_threshold_rule() {
+        let subj = footprints();
+        let levels = Overlay::with_contours(&subj, &[]).extract_winding_levels();
+
+        assert_eq!(levels.len(), 4);
+        for (i, level) in levels.iter().enumerate() {
+            let rule = FillRule::WindingAtLeast(i as u32 + 1);
+            let expected = Overlay::with_contours(&subj, &[]).overlay(OverlayRule::Subject, rule);
+            assert!(!level.is_empty());
+            assert_eq!(level, &expected, "{}", rule);
+        }
+
+        let above =
+            Overlay::with_contours(&subj, &[]).overlay(OverlayRule::Subject, FillRule::WindingAtLeast(5));
+        assert!(above.is_empty());
+    }
+
+    #[test]
+    fn test_threshold_rule() {
+        let subj = footprints();
+
+        let positive = Overlay::with_contours(&subj, &[]).overlay(OverlayRule::Subject, FillRule::Positive);
+        for k in [0, 1] {
+            let shapes =
+                Overlay::with_contours(&subj, &[]).overlay(OverlayRule::Subject, FillRule::WindingAtLeast(k));
+            assert_eq!(shapes, positive);
+        }
+
+        let shapes =
+            Overlay::with_contours(&subj, &[]).overlay(OverlayRule::Subject, FillRule::WindingAtLeast(4));
+        assert_eq!(shapes, vec![vec![out_rect(12, 10, 18, 12)]]);
+    }
+
+    #[test]
+    fn test_threshold_rule_with_clip() {
+        let subj = vec![rect(0, 0, 10, 10), rect(5, 0, 15, 10)];
+        let clip = vec![rect(0, 0, 20, 5), rect(0, 0, 20, 5)];
+
+        let shapes =
+            Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Intersect, FillRule::WindingAtLeast(2));
+        assert_eq!(shapes, vec![vec![out_rect(5, 0, 10, 5)]]);
+    }
+
+    #[test]
+    fn test_single_contour() {
+        let contour = rect(0, 0, 10, 10);
+        let mut overlay = Overlay::new(4);
+        // already correct
+        assert_eq!(
+            overlay.simplify_contour(&contour, FillRule::WindingAtLeast(1)),
+            None
+        );
+        assert_eq!(
+            overlay.simplify_contour(&contour, FillRule::WindingAtLeast(2)),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut overlay = Overlay::<i32>::new(0);
+        assert!(overlay.extract_winding_levels().is_empty());
+
+        let clip_only = Overlay::with_contours(&[], &[rect(0, 0, 10, 10)]).extract_winding_levels();
+        assert!(clip_only.is_empty());
+    }
+
+    #[test]
+    fn test_float_levels() {
+        let squares = [0.0, 0.5, 1.0].map(|x: f64| vec![[x, 0.0], [x + 1.0, 0.0], [x + 1.0, 1.0], [x, 1.0]]);
+        let levels = FloatOverlay::with_subj(&squares.to_vec()).extract_winding_levels();
+
+        assert_eq!(levels.len(), 2);
+        for (i, level) in levels.iter().enumerate() {
+            let rule = FillRule::WindingAtLeast(i as u32 + 1);
+            let expected = squares
+                .to_vec()
+                .overlay(&Vec::<Vec<[f64; 2]>>::new(), OverlayRule::Subject, rule);
+            assert_eq!(level, &expected);
+        }
+        assert_eq!(levels[1].len(), 1);
+        assert_eq!(levels[1][0].len(), 1);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(FillRule::WindingAtLeast(3).to_string(), "WindingAtLeast(3)");
+    }
+}